@@ -24,7 +24,13 @@ pub fn process_dir_entries_v1(dir_entry_results: &mut JwalkResults, min_size: u6
                 if file_size >= min_size && file_size <= max_size {
                     let key = Key::new(file_size, None);
                     let path = dir_entry.path();
-                    dir_entry.client_state = Some(FileInfo { key, path });
+                    dir_entry.client_state = Some(FileInfo {
+                        key,
+                        path,
+                        inode: None,
+                        modified: None,
+                        allocated: None,
+                    });
                 }
             }
         });
@@ -42,7 +48,13 @@ pub fn process_dir_entries_v2(dir_entry_results: &mut JwalkResults, min_size: u6
                     if file_size >= min_size && file_size <= max_size {
                         let key = Key::new(file_size, None);
                         let path = dir_entry.path();
-                        dir_entry.client_state = Some(FileInfo { key, path });
+                        dir_entry.client_state = Some(FileInfo {
+                            key,
+                            path,
+                            inode: None,
+                            modified: None,
+                            allocated: None,
+                        });
                     }
                 }
             }
@@ -69,7 +81,13 @@ pub fn process_dir_entries_v3(dir_entry_results: &mut JwalkResults, min_size: u6
         .for_each(|(dir_entry, file_size)| {
             let key = Key::new(file_size, None);
             let path = dir_entry.path();
-            dir_entry.client_state = Some(FileInfo { key, path });
+            dir_entry.client_state = Some(FileInfo {
+                key,
+                path,
+                inode: None,
+                modified: None,
+                allocated: None,
+            });
         });
 }
 
@@ -1,20 +1,1542 @@
-complete -c find-identical-files -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash	'',blake3	'',fxhash	'',sha256	'',sha512	''}"
-complete -c find-identical-files -s b -l min_size -d 'Set a minimum file size (in bytes) to search for identical files' -r
-complete -c find-identical-files -s B -l max_size -d 'Set a maximum file size (in bytes) to search for identical files' -r
-complete -c find-identical-files -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
-complete -c find-identical-files -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
-complete -c find-identical-files -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
-complete -c find-identical-files -s f -l min_frequency -d 'Minimum frequency (number of identical files) to be filtered' -r
-complete -c find-identical-files -s F -l max_frequency -d 'Maximum frequency (number of identical files) to be filtered' -r
-complete -c find-identical-files -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash	'',elvish	'',fish	'',powershell	'',zsh	''}"
-complete -c find-identical-files -s i -l input_dir -d 'Set the input directory where to search for identical files [default: current directory]' -r -F
-complete -c find-identical-files -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json	'',yaml	'',personal	''}"
-complete -c find-identical-files -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
-complete -c find-identical-files -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
-complete -c find-identical-files -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
-complete -c find-identical-files -s s -l sort -d 'Sort result by number of identical files, otherwise sort by file size'
-complete -c find-identical-files -s t -l time -d 'Show total execution time'
-complete -c find-identical-files -s v -l verbose -d 'Show intermediate runtime messages'
-complete -c find-identical-files -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
-complete -c find-identical-files -s h -l help -d 'Print help (see more with \'--help\')'
-complete -c find-identical-files -s V -l version -d 'Print version'
+# Print an optspec for argparse to handle cmd's options that are independent of any subcommand.
+function __fish_find_identical_files_global_optspecs
+	string join \n a/algorithm= audio b/min_size= B/max_size= min_allocated= bench buffer_size= by_content content_only c/csv_dir= color= compare= compare_metadata= dry_run verify_before_action diff= dirs max_distance= min_similarity= max_files= max_group_memory= max_open_files= limit= d/min_depth= D/max_depth= delete deterministic exclude= no_default_excludes export_index= export_sqlite= sqlite_upsert e/extended_path path_style= f/min_frequency= F/max_frequency= min_wasted= follow_links files_from= 0/null g/generate= hardlink heuristic= include= include_empty file_type= scan_archives s3= remote= include_regex= ignore_case idle ignore_hardlinks= import_index= newer_than= older_than= i/input_dir= interactive io_threads= io_profile= j/jobs= lang= log_level= log_file= log_json avoid= keep= metadata_diff mmap mmap_min_size= move_to= trash no_config one_file_system o/omit_hidden owner= group= writable_only O/output= compress= report_dir= reflink symlink relative_symlink paranoid perceptual pipeline prefilter_bytes= prefilter_tail prefer= print0 progress r/result_format= same_name_only script= serve= stats_by_dir= skip= skip_busy selftest journal= undo= size_only s/sort_by= order= summary= strict symlink_files timeout= throttle= t/time top= find_copies_of= find_digest= resume= unique verify_subset= verify_manifest= v/verbose watch w/wipe_terminal write_manifest= x/xlsx_dir= h/help V/version
+end
+
+function __fish_find_identical_files_needs_command
+	# Figure out if the current invocation already has a command.
+	set -l cmd (commandline -opc)
+	set -e cmd[1]
+	argparse -s (__fish_find_identical_files_global_optspecs) -- $cmd 2>/dev/null
+	or return
+	if set -q argv[1]
+		# Also print the command, so this can be used to figure out what it is.
+		echo $argv[1]
+		return 1
+	end
+	return 0
+end
+
+function __fish_find_identical_files_using_subcommand
+	set -l cmd (__fish_find_identical_files_needs_command)
+	test -z "$cmd"
+	and return 1
+	contains -- $cmd[1] $argv
+end
+
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -s V -l version -d 'Print version'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "scan" -d 'Search and report the identical files: no file is changed'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "clean" -d 'Remove or replace the identical files, keeping one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "verify" -d 'Verify a backup or a checksum manifest'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "diff" -d 'Compare two JSON reports (see `--diff`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "cache" -d 'Hash all files and save the digest index, without reporting the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "bench" -d 'Measure the throughput of each hash algorithm on this machine (see `--bench`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "selftest" -d 'Check that the search finds the identical files of a synthetic tree (see `--selftest`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "undo" -d 'Undo the actions recorded in a journal, in reverse order (see `--undo`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "completions" -d 'Print the shell completion file (see `--generate`)'
+complete -c find-identical-files -n "__fish_find_identical_files_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand scan" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand clean" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -f -a "subset" -d 'Report every file of SOURCE whose content has no identical file in BACKUP (see `--verify_subset`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -f -a "manifest" -d 'Report the files changed or missing since the manifest was written (see `--verify_manifest`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and not __fish_seen_subcommand_from subset manifest help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from subset" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from manifest" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from help" -f -a "subset" -d 'Report every file of SOURCE whose content has no identical file in BACKUP (see `--verify_subset`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from help" -f -a "manifest" -d 'Report the files changed or missing since the manifest was written (see `--verify_manifest`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand verify; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand diff" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand cache" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand bench" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand selftest" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand undo" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s a -l algorithm -d 'Choose the hash algorithm' -r -f -a "{ahash\t'',blake3\t'',fxhash\t'',sha256\t'',sha512\t'',sha3-256\t'',sha3-512\t'',xxh3-128\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s b -l min_size -d 'Set a minimum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s B -l max_size -d 'Set a maximum file size to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l min_allocated -d 'Set a minimum size allocated on disk to search for identical files (unix only)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l buffer_size -d 'Set the size (in bytes) of the buffer used to read each file' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s c -l csv_dir -d 'Set the output directory for the CSV file (fif.csv)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l color -d 'Color the Personal format: paths of consecutive groups alternate between two colors' -r -f -a "{auto\t'Color only if stdout is a terminal and `NO_COLOR` is not set',always\t'Always color',never\t'Never color'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l compare -d 'Compare the input directories (targets) with a reference directory' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l compare_metadata -d 'Require identical files to also have the same metadata' -r -f -a "{mtime\t'Last modification time (in seconds)',owner\t'User and group ids (unix only)',perms\t'Permission bits (unix only)',xattr\t'Extended attributes: names and values (unix only)'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l diff -d 'Compare two JSON reports (`-r json`) and report the groups of identical files that appeared, disappeared or whose number of files changed' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l max_distance -d 'Maximum number of different bits between the perceptual hashes of visually identical images (`--perceptual`), from 0 to 64' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l min_similarity -d 'Minimum similarity between the audio fingerprints of the same song in different encodings (`--audio`), from 0.0 to 1.0' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l max_files -d 'Stop the search after finding N files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l max_group_memory -d 'Limit the memory of the index used to group the files by size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l max_open_files -d 'Limit the number of files opened at the same time to read them' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l limit -d 'Print at most N groups of identical files (a page of the result)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s d -l min_depth -d 'Set the minimum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s D -l max_depth -d 'Set the maximum depth to search for identical files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l exclude -d 'Skip directories and files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l export_index -d 'Save the digests of all files (path, size, digest) to an index file' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l export_sqlite -d 'Export the files, digests and groups of identical files to a SQLite database' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l path_style -d 'How the paths of identical files are displayed in all output formats' -r -f -a "{relative\t'Paths relative to the input directories, as given on the command line',absolute\t'Absolute paths, without resolving symbolic links',canonical\t'Absolute paths with symbolic links resolved (same as `-e`)',home\t'Absolute paths with the home directory abbreviated to `~`'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s f -l min_frequency -l min_copies -d 'Minimum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s F -l max_frequency -l max_copies -d 'Maximum frequency (number of identical files) to be filtered' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l min_wasted -d 'Hide groups of identical files that waste less than the size' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l files_from -d 'Read the list of candidate files from FILE instead of walking directories' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s g -l generate -d 'If provided, outputs the completion file for given shell' -r -f -a "{bash\t'',elvish\t'',fish\t'',powershell\t'',zsh\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l heuristic -d 'Group the files with the same name and size before hashing' -r -f -a "{name+size\t'Files with the same name and the same size'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l include -d 'Search only files whose name or path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l file_type -d 'Search only files of these types, detected from their first bytes (magic numbers) instead of their extensions' -r -f -a "{image\t'JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF',video\t'MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV',audio\t'MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI',document\t'PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office',archive\t'ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l s3 -d 'Also search the objects of an S3 bucket: `--s3 bucket/prefix`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l remote -d 'Also search the files of a remote server: `--remote sftp://user@host/path`' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l include_regex -d 'Search only files whose path matches the regular expression' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l ignore_hardlinks -d 'Do not report hard links to the same file as identical files' -r -f -a "{true\t'',false\t''}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l import_index -d 'Load digest indexes saved with `--export_index` (ex.: on another machine)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l newer_than -d 'Search only files modified after the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l older_than -d 'Search only files modified before the date or duration' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s i -l input_dir -d 'Set the input directories where to search for identical files [default: current directory]' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l io_threads -d 'Set the number of threads used to read and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l io_profile -d 'Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd)' -r -f -a "{auto\t'Detect if an input directory is on a spinning disk (Linux only)',hdd\t'Spinning disk: one reader, files hashed in physical order (inode)',ssd\t'Solid-state drive: as many readers as possible'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s j -l jobs -d 'Set the maximum number of threads used to walk the directories and hash the files' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l lang -d 'Language of the messages, of the summary and of the Personal format' -r -f -a "{en\t'English',pt-BR\t'Brazilian Portuguese'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l log_level -d 'Log the procedures of the search and their timings, up to the chosen level' -r -f -a "{error\t'Errors only',warn\t'Errors and warnings',info\t'Procedures of the search, with their timings',debug\t'Details of each procedure',trace\t'Everything'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l log_file -d 'Append the log to a file, so long scheduled scans can be audited' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l avoid -d 'Avoid keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l keep -d 'Choose which file to keep in each group of identical files' -r -f -a "{newest\t'Keep the most recently modified file',oldest\t'Keep the least recently modified file',first-alphabetical\t'Keep the first file in alphabetical order',shortest-path\t'Keep the file with the shortest path'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l mmap_min_size -d 'Set the minimum file size to use memory-mapped I/O' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l move_to -d 'Move identical files to a quarantine directory, keeping one file per group' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l owner -d 'Only search files owned by the user (name or numeric uid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l group -d 'Only search files of the group (name or numeric gid)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s O -l output -d 'Write the result (identical files and summary) to a file instead of stdout' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l compress -d 'Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written' -r -f -a "{gzip\t'gzip (`.gz`): readable everywhere',zstd\t'Zstandard (`.zst`): faster and smaller than gzip'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l report_dir -d 'Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l prefilter_bytes -d 'Set the number of bytes read from the beginning of each file to quickly discard files with identical size but different contents' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l prefer -d 'Prefer keeping files whose path matches the glob pattern' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s r -l result_format -d 'Print the result in the chosen format' -r -f -a "{json\t'',yaml\t'',personal\t'',html\t'Standalone HTML report (ex.: `-r html -O report.html`)',markdown\t'Markdown headings and tables (ex.: for issues, wikis or pull requests)',xml\t'XML (schema: `schema/identical_files.xsd`)',cbor\t'Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)',msgpack\t'Compact binary MessagePack, for large results read by programs'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l script -d 'Write a script of the proposed actions instead of applying them' -r -f -a "{sh\t'Bash script (`$\'...\'` quoting)',bat\t'Windows batch file',ps1\t'PowerShell script'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l serve -d 'After the search, answer queries of other processes about the files found' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l stats_by_dir -d 'Aggregate the wasted size by directory, at DEPTH levels below the input directories (default: 1, the top-level directories)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l skip -d 'Skip the first N groups of identical files (see `--limit`)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l journal -d 'Write the applied actions to an undo journal (JSON Lines), in append mode' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l undo -d 'Undo the actions recorded in a journal (`--journal`), in reverse order' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s s -l sort_by -d 'Sort the groups of identical files by one or more keys' -r -f -a "{size\t'File size',count\t'Number of identical files',wasted\t'Wasted size: size × (number of identical files − 1)',path\t'Path of the first file of the group',name\t'File name of the first file of the group'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l order -d 'Sort the groups of identical files in ascending or descending order' -r -f -a "{asc\t'Ascending order',desc\t'Descending order'}"
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l summary -d 'Write a machine-readable JSON summary to the file or, by default, to stderr' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l timeout -d 'Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h)' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l throttle -d 'Limit the read bandwidth to the given number of megabytes (MiB) per second' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l top -d 'Print only the N groups wasting the most space, sorted by wasted size in descending order' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l find_copies_of -d 'Report only the files with the same content as FILE' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l find_digest -d 'Report only the files whose hash is the given digest' -r
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l resume -d 'Continue an interrupted scan: the digests already calculated are not calculated again' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l verify_subset -d 'Report every file of SOURCE whose content has no identical file in BACKUP' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l verify_manifest -d 'Hash again the files of a checksum manifest and report the changed and missing files' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l write_manifest -d 'Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s x -l xlsx_dir -d 'Set the output directory for the XLSX file (fif.xlsx)' -r -F
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l audio -d 'Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l bench -d 'Measure the throughput of each hash algorithm on this machine and recommend one'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l by_content -d 'Group files by the hash of the entire file only, ignoring the size reported by the filesystem'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l content_only -d 'Ignore embedded metadata when hashing files of known formats'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l dry_run -d 'Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`, `--move_to`, `--interactive`) and the space that would be reclaimed, without changing any file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l verify_before_action -d 'Check each file again immediately before applying the action to it'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l dirs -d 'Report identical directories instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l delete -d 'Remove identical files, keeping only one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l deterministic -d 'Sort the paths of each group and the groups canonically, so two runs over the same data produce byte-identical reports'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l no_default_excludes -d 'Search the well-known system and junk directories excluded by default: `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`, `System Volume Information` and `$RECYCLE.BIN` (depending on the platform)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l sqlite_upsert -d 'Add the scan to the SQLite database instead of replacing the previous scans'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s e -l extended_path -d 'Prints extended path of identical files, otherwise relative path'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l follow_links -d 'Follow symbolic links to directories'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s 0 -l null -d 'The list of `--files_from` is NUL-delimited (ex.: `find -print0`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l hardlink -d 'Replace identical files with hard links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l include_empty -d 'Include empty files (zero bytes) in the search'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l scan_archives -d 'Also search the files inside zip, tar and tar.gz archives'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l ignore_case -d 'Compare file names case-insensitively with `--same_name_only`'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l idle -d 'Run with the lowest CPU and I/O priorities (idle class)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l interactive -d 'Walk through each group of identical files and choose what to do with each file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l log_json -d 'Write the log in JSON format (one object per line)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l metadata_diff -d 'Report the metadata of `--compare_metadata` that differ within each group of identical files, instead of splitting the groups'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l mmap -d 'Memory-map files instead of using buffered reads to calculate the hash'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l trash -d 'With `--delete` (or `d` in `--interactive`), send the files to the trash instead of removing them, so mistakes can be recovered'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l no_config -d 'Do not load the configuration file'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l one_file_system -d 'Stay on the filesystem of each input directory'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s o -l omit_hidden -d 'Omit hidden files (starts with \'.\'), otherwise search all files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l writable_only -d 'Only search files that the current user can write to'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l reflink -d 'Replace identical files with copy-on-write clones (reflinks) of a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l symlink -d 'Replace identical files with symbolic links to a single file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l relative_symlink -d 'Same as `--symlink`, with links relative to their directory: the links remain valid if the whole tree is moved'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l paranoid -d 'Compare identical files byte by byte after hash matching'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l perceptual -d 'Report visually identical images (jpg, png, webp) instead of identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l pipeline -d 'Hash the files while the directories are still being walked'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l prefilter_tail -d 'Also hash the last bytes of each file in the prefilter'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l print0 -d 'Print only the paths of the identical files, each one terminated by NUL'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l progress -d 'Show a progress bar with files discovered, bytes hashed, throughput and ETA'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l same_name_only -d 'Report only identical files that also have the same file name'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l skip_busy -d 'Skip the files that appear to be actively written: modified in the last few seconds, locked by another process (`flock` on unix) or open for writing (Windows)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l selftest -d 'Check that the search finds exactly the identical files of a synthetic tree'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l size_only -d 'Report the files of identical size, without reading their contents'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l strict -d 'Abort the search with exit code 3 if a file or directory cannot be read'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l symlink_files -d 'Treat symbolic links to files as regular files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s t -l time -d 'Show total execution time'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l unique -d 'Report the files that have no identical file, instead of the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s v -l verbose -d 'Show intermediate runtime messages'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -l watch -d 'After the search, watch the input directories and report new identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s w -l wipe_terminal -d 'Wipe (Clear) the terminal screen before listing the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand completions" -s h -l help -d 'Print help (see more with \'--help\')'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "scan" -d 'Search and report the identical files: no file is changed'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "clean" -d 'Remove or replace the identical files, keeping one file per group'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "verify" -d 'Verify a backup or a checksum manifest'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "diff" -d 'Compare two JSON reports (see `--diff`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "cache" -d 'Hash all files and save the digest index, without reporting the identical files'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "bench" -d 'Measure the throughput of each hash algorithm on this machine (see `--bench`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "selftest" -d 'Check that the search finds the identical files of a synthetic tree (see `--selftest`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "undo" -d 'Undo the actions recorded in a journal, in reverse order (see `--undo`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "completions" -d 'Print the shell completion file (see `--generate`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and not __fish_seen_subcommand_from scan clean verify diff cache bench selftest undo completions help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and __fish_seen_subcommand_from verify" -f -a "subset" -d 'Report every file of SOURCE whose content has no identical file in BACKUP (see `--verify_subset`)'
+complete -c find-identical-files -n "__fish_find_identical_files_using_subcommand help; and __fish_seen_subcommand_from verify" -f -a "manifest" -d 'Report the files changed or missing since the manifest was written (see `--verify_manifest`)'
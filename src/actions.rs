@@ -0,0 +1,1016 @@
+use crate::{
+    create_temp_file, is_virtual_file,
+    journal::{is_journaling, record},
+    ActionInfo, Arguments, GroupInfo, KeepRules, MyResult, PathBufExtension, ScriptFormat,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Component, Path, PathBuf},
+    sync::{LazyLock, Mutex},
+    time::SystemTime,
+};
+
+/// Manifest of the files moved to the quarantine directory.
+pub const MANIFEST_FILENAME: &str = "fif-manifest.jsonl";
+
+/// Script of the proposed actions (without extension).
+pub const SCRIPT_FILENAME: &str = "fif-actions";
+
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+
+/// Action applied to a file identical to the kept file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    /// Remove the file
+    Delete,
+    /// Send the file to the trash (`--trash`)
+    Trash,
+    /// Replace the file with a hard link to the kept file
+    Hardlink,
+    /// Replace the file with a copy-on-write clone of the kept file
+    Reflink,
+    /// Replace the file with a symbolic link to the kept file
+    Symlink,
+    /// Replace the file with a relative symbolic link to the kept file
+    RelativeSymlink,
+    /// Move the file to a quarantine directory
+    Move,
+}
+
+/// Planned action on one file of a group of identical files.
+#[derive(Debug, Clone)]
+pub struct Task<'a> {
+    /// File kept in the group
+    pub keeper: &'a Path,
+    /// File to which the action is applied
+    pub path: &'a Path,
+    /// File size (in bytes)
+    pub size: usize,
+    /// Action applied to the file
+    pub action: Action,
+    /// Quarantine directory (`--move_to`)
+    pub move_to: Option<&'a Path>,
+    /// Only print what would be done (`--dry_run`)
+    pub dry_run: bool,
+    /// Check the file again before the action (`--verify_before_action`)
+    pub verify: bool,
+}
+
+impl Task<'_> {
+    /// Apply the action to the file.
+    ///
+    /// With `--dry_run`, the same checks are performed and the action is
+    /// printed, but the filesystem is not changed.
+    ///
+    /// Returns the size of the reclaimed space (in bytes).
+    ///
+    /// With `--journal`, the applied action is written to the undo journal.
+    ///
+    /// A file that is the same file as the kept file (same device and inode)
+    /// is skipped with a warning: removing it would remove the kept file.
+    pub fn execute(&self) -> MyResult<usize> {
+        if is_same_file(self.keeper, self.path)? {
+            eprintln!(
+                "Same file as the kept file {:?}, skipped: {:?}",
+                self.keeper, self.path
+            );
+            return Ok(0);
+        }
+
+        // Modification time before the action, restored by `--undo`.
+        let modified: Option<SystemTime> = is_journaling()
+            .then(|| fs::symlink_metadata(self.path).and_then(|metadata| metadata.modified()))
+            .and_then(Result::ok);
+        let mut moved_to: Option<PathBuf> = None;
+
+        let reclaimed_size: usize = match self.action {
+            Action::Delete => {
+                remove_file(self.path, self.dry_run)?;
+                self.size
+            }
+            Action::Trash => {
+                move_to_trash(self.path, self.dry_run)?;
+                self.size
+            }
+            Action::Hardlink => {
+                let replaced = replace_with_hardlink(self.keeper, self.path, self.dry_run)?;
+                if replaced {
+                    self.size
+                } else {
+                    0
+                }
+            }
+            Action::Reflink => {
+                replace_with_reflink(self.keeper, self.path, self.dry_run)?;
+                self.size
+            }
+            Action::Symlink | Action::RelativeSymlink => {
+                let relative: bool = self.action == Action::RelativeSymlink;
+                let replaced =
+                    replace_with_symlink(self.keeper, self.path, relative, self.dry_run)?;
+                if replaced {
+                    self.size
+                } else {
+                    0
+                }
+            }
+            Action::Move => {
+                let Some(dir) = self.move_to else {
+                    return Err("quarantine directory not defined".into());
+                };
+                moved_to = Some(move_to_quarantine(self, dir, self.dry_run)?);
+                self.size
+            }
+        };
+
+        // Files already linked were not changed.
+        if !self.dry_run && reclaimed_size > 0 {
+            record(self, moved_to, modified)?;
+        }
+
+        Ok(reclaimed_size)
+    }
+
+    /**
+    Check that the file and the kept file were not changed since the search:
+    both must still have the size of the group and identical contents.
+
+    A file that no longer exists is considered changed.
+    */
+    pub fn is_unchanged(&self) -> MyResult<bool> {
+        for path in [self.keeper, self.path] {
+            match fs::metadata(path) {
+                Ok(metadata) if metadata.len() == self.size as u64 => {}
+                Ok(_) => return Ok(false),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+                Err(error) => return Err(error.into()),
+            }
+        }
+
+        self.path
+            .to_path_buf()
+            .is_identical_to(self.keeper, VERIFY_BUFFER_SIZE)
+    }
+}
+
+pub trait ActionExtension {
+    /**
+    Apply the chosen action to the identical files of each group.
+
+    In each group, one file is kept according to the `--keep` strategy.
+
+    The other files are removed (`--delete`), sent to the trash (`--trash`),
+    moved to a quarantine directory (`--move_to`) or replaced with hard links
+    (`--hardlink`), copy-on-write clones (`--reflink`) or symbolic links
+    (`--symlink`) of the kept file.
+    */
+    fn apply_actions(&self, arguments: &Arguments) -> MyResult<ActionInfo>;
+
+    /// Get the planned actions according to the `--prefer`, `--avoid` and `--keep` rules.
+    fn get_tasks<'a>(&'a self, arguments: &'a Arguments) -> MyResult<Vec<Task<'a>>>;
+
+    /**
+    Write a script with the commands of the planned actions (`--script`),
+    without changing any file.
+
+    Returns the path of the script.
+    */
+    fn write_script(&self, arguments: &Arguments, format: ScriptFormat) -> MyResult<PathBuf>;
+}
+
+impl ActionExtension for [GroupInfo] {
+    fn apply_actions(&self, arguments: &Arguments) -> MyResult<ActionInfo> {
+        let tasks: Vec<Task> = self.get_tasks(arguments)?;
+        Ok(execute_tasks(&tasks))
+    }
+
+    fn get_tasks<'a>(&'a self, arguments: &'a Arguments) -> MyResult<Vec<Task<'a>>> {
+        let mut tasks: Vec<Task> = Vec::new();
+
+        let Some(action) = arguments.get_action() else {
+            return Ok(tasks);
+        };
+
+        let rules = KeepRules::new(arguments)?;
+
+        for group_info in self {
+            // Only files with the same hash are identical.
+            if group_info.key.hash.is_none() {
+                continue;
+            }
+
+            // With `--compare`, the kept file is chosen among the reference files
+            // and the action is only applied to the target files.
+            // Virtual files (archive members and S3 objects) are never kept nor changed.
+            let candidates: Vec<PathBuf> = group_info
+                .paths
+                .iter()
+                .filter(|path| arguments.compare.is_none() || arguments.is_reference(path))
+                .filter(|path| !is_virtual_file(path))
+                .cloned()
+                .collect();
+
+            let Some(index) = rules.select(&candidates) else {
+                continue;
+            };
+
+            let Some(keeper) = group_info
+                .paths
+                .iter()
+                .find(|&path| *path == candidates[index])
+            else {
+                continue;
+            };
+
+            for path in group_info.paths.iter().filter(|&path| {
+                path != keeper && !arguments.is_reference(path) && !is_virtual_file(path)
+            }) {
+                // Another path of the kept file (ex.: a symbolic link to it).
+                if is_same_file(keeper, path).unwrap_or(false) {
+                    eprintln!("Same file as the kept file {keeper:?}, skipped: {path:?}");
+                    continue;
+                }
+
+                tasks.push(Task {
+                    keeper,
+                    path,
+                    size: group_info.key.size,
+                    action,
+                    move_to: arguments.move_to.as_deref(),
+                    dry_run: arguments.dry_run,
+                    verify: arguments.verify_before_action,
+                });
+            }
+        }
+
+        Ok(tasks)
+    }
+
+    fn write_script(&self, arguments: &Arguments, format: ScriptFormat) -> MyResult<PathBuf> {
+        let path = PathBuf::from(format!("{SCRIPT_FILENAME}.{}", format.extension()));
+        let mut script: String = format.header();
+        let mut previous_keeper: Option<&Path> = None;
+
+        for task in self.get_tasks(arguments)? {
+            if previous_keeper != Some(task.keeper) {
+                let keep = format!("keep {}", format.quote(task.keeper)?);
+                script.push_str(&format!("\n{}\n", format.comment(&keep)));
+                previous_keeper = Some(task.keeper);
+            }
+
+            let destination: Option<PathBuf> = match task.move_to {
+                Some(dir) => Some(get_quarantine_path(dir, task.path)?),
+                None => None,
+            };
+
+            for command in format.commands(&task, destination.as_deref())? {
+                script.push_str(&command);
+                script.push('\n');
+            }
+        }
+
+        fs::write(&path, script).inspect_err(|error| {
+            eprintln!("fn write_script()");
+            eprintln!("Couldn't create {path:?}");
+            eprintln!("Error: {error}");
+        })?;
+        eprintln!("Write script file: {path:?}");
+
+        Ok(path)
+    }
+}
+
+/// Execute the planned actions and summarize the results.
+///
+/// A failed action is reported and does not stop the others.
+///
+/// With `--verify_before_action`, files changed since the search are skipped.
+pub fn execute_tasks(tasks: &[Task]) -> ActionInfo {
+    let mut action_info = ActionInfo {
+        dry_run: tasks.iter().any(|task| task.dry_run),
+        ..Default::default()
+    };
+
+    for task in tasks {
+        if task.verify {
+            match task.is_unchanged() {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("File changed since the search, skipped: {:?}", task.path);
+                    action_info.changed.push(task.path.to_path_buf());
+                    continue;
+                }
+                Err(error) => {
+                    eprintln!("fn execute_tasks()");
+                    eprintln!("Failed to verify file {:?}", task.path);
+                    eprintln!("Error: {error}");
+                    action_info.num_failed += 1;
+                    continue;
+                }
+            }
+        }
+
+        match task.execute() {
+            Ok(reclaimed_size) => {
+                action_info.num_files += 1;
+                action_info.reclaimed_size += reclaimed_size;
+            }
+            Err(error) => {
+                eprintln!("fn execute_tasks()");
+                eprintln!("Failed to apply {:?} to file {:?}", task.action, task.path);
+                eprintln!("Error: {error}");
+                action_info.num_failed += 1;
+            }
+        }
+    }
+
+    action_info
+}
+
+/// Prefix of the messages printed with `--dry_run`.
+fn get_prefix(dry_run: bool) -> &'static str {
+    if dry_run {
+        "[dry run] "
+    } else {
+        ""
+    }
+}
+
+/// Remove file from the filesystem.
+fn remove_file(path: &Path, dry_run: bool) -> MyResult<()> {
+    eprintln!("{}Remove file: {path:?}", get_prefix(dry_run));
+
+    if dry_run {
+        // Check that the file can be accessed.
+        fs::symlink_metadata(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+
+    Ok(())
+}
+
+/**
+Send the file to the trash of the platform, so it can be recovered:
+the freedesktop.org trash on Linux, the Recycle Bin on Windows
+and the Trash on macOS.
+*/
+fn move_to_trash(path: &Path, dry_run: bool) -> MyResult<()> {
+    eprintln!("{}Move file to trash: {path:?}", get_prefix(dry_run));
+
+    if dry_run {
+        // Check that the file can be accessed.
+        fs::symlink_metadata(path)?;
+    } else {
+        trash::delete(path).map_err(|error| error.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Entry of the quarantine manifest, used to restore the moved files.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// Original path of the file
+    pub original: PathBuf,
+    /// Path of the file in the quarantine directory
+    pub moved_to: PathBuf,
+    /// File kept in the group
+    pub kept: PathBuf,
+    /// File size (in bytes)
+    pub size: usize,
+}
+
+/**
+Move the file to the quarantine directory.
+
+The directory structure is preserved: `/home/user/a.txt` is moved to
+`<DIR>/home/user/a.txt`. If this path already exists, a numeric suffix is added.
+
+Across filesystems, the file is copied with its permissions and
+modification time, then removed.
+
+Each moved file is recorded in the manifest of the quarantine directory.
+
+Returns the path of the file in the quarantine directory.
+*/
+fn move_to_quarantine(task: &Task, dir: &Path, dry_run: bool) -> MyResult<PathBuf> {
+    let original: PathBuf = fs::canonicalize(task.path)?;
+    let moved_to: PathBuf = get_quarantine_path(dir, &original)?;
+
+    eprintln!(
+        "{}Move file: {original:?} -> {moved_to:?}",
+        get_prefix(dry_run)
+    );
+
+    if dry_run {
+        return Ok(moved_to);
+    }
+
+    if let Some(parent) = moved_to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    match fs::rename(&original, &moved_to) {
+        Ok(()) => {}
+        // Files cannot be renamed across filesystems: copy and remove.
+        Err(error) if is_cross_device(&error) => copy_and_remove(&original, &moved_to)?,
+        Err(error) => return Err(error.into()),
+    }
+
+    let entry = ManifestEntry {
+        original,
+        moved_to,
+        kept: task.keeper.to_path_buf(),
+        size: task.size,
+    };
+
+    let mut manifest = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(MANIFEST_FILENAME))?;
+    writeln!(manifest, "{}", serde_json::to_string(&entry)?)?;
+
+    Ok(entry.moved_to)
+}
+
+/// Check if the rename failed because the paths are on different filesystems
+/// (`EXDEV` on unix, `ERROR_NOT_SAME_DEVICE` on Windows).
+pub(crate) fn is_cross_device(error: &io::Error) -> bool {
+    #[cfg(unix)]
+    const CROSS_DEVICE: i32 = 18;
+    #[cfg(windows)]
+    const CROSS_DEVICE: i32 = 17;
+    #[cfg(not(any(unix, windows)))]
+    const CROSS_DEVICE: i32 = -1;
+
+    error.raw_os_error() == Some(CROSS_DEVICE)
+}
+
+/**
+Copy the file to another filesystem, with its permissions and
+modification time, then remove the original file.
+
+If the copy fails, the original file is kept.
+*/
+pub(crate) fn copy_and_remove(original: &Path, destination: &Path) -> MyResult<()> {
+    let metadata = fs::metadata(original)?;
+
+    let copy = || -> io::Result<()> {
+        fs::copy(original, destination)?;
+        File::options()
+            .write(true)
+            .open(destination)?
+            .set_modified(metadata.modified()?)?;
+        fs::set_permissions(destination, metadata.permissions())
+    };
+
+    if let Err(error) = copy() {
+        let _ = fs::remove_file(destination);
+        return Err(error.into());
+    }
+
+    fs::remove_file(original)?;
+
+    Ok(())
+}
+
+/// Get the path of the file in the quarantine directory.
+fn get_quarantine_path(dir: &Path, path: &Path) -> MyResult<PathBuf> {
+    let original: PathBuf = fs::canonicalize(path)?;
+    let relative: PathBuf = original
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+
+    Ok(get_available_path(&dir.join(relative)))
+}
+
+/// Add a numeric suffix to the path until it does not exist.
+///
+/// Example: "a.txt" -> "a.txt.1" -> "a.txt.2"
+fn get_available_path(path: &Path) -> PathBuf {
+    let mut available: PathBuf = path.to_path_buf();
+    let mut number: usize = 0;
+
+    while available.exists() {
+        number += 1;
+        let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(format!(".{number}"));
+        available = path.with_file_name(file_name);
+    }
+
+    available
+}
+
+/**
+Replace the file with a hard link to the kept file.
+
+The hard link is created with a temporary name in the same directory
+and then renamed over the file, so the file is replaced atomically.
+
+Hard links cannot cross filesystem boundaries: in this case, the file
+is left untouched and an error is returned.
+
+Returns false if both paths were already hard links to the same file.
+*/
+fn replace_with_hardlink(keeper: &Path, path: &Path, dry_run: bool) -> MyResult<bool> {
+    #[cfg(unix)]
+    {
+        let metadata_keeper = fs::metadata(keeper)?;
+        let metadata_path = fs::metadata(path)?;
+
+        if metadata_keeper.dev() != metadata_path.dev() {
+            return Err(format!("{keeper:?} and {path:?} are on different filesystems").into());
+        }
+
+        if metadata_keeper.ino() == metadata_path.ino() {
+            return Ok(false);
+        }
+    }
+
+    eprintln!(
+        "{}Hard link file: {path:?} -> {keeper:?}",
+        get_prefix(dry_run)
+    );
+
+    if dry_run {
+        return Ok(true);
+    }
+
+    // On failure, the temporary link is removed: the original file is kept.
+    create_temp_file(path, |temp_path| fs::hard_link(keeper, temp_path))?
+        .persist(path)
+        .map_err(|error| error.error)?;
+
+    Ok(true)
+}
+
+/// Buffer size used to compare the files immediately before an action.
+const VERIFY_BUFFER_SIZE: usize = 1024 * 1024;
+
+/**
+Replace the file with a symbolic link to the kept file.
+
+The contents are compared byte by byte immediately before the replacement:
+a file changed since the search is left untouched and an error is returned.
+
+The link is created with a temporary name in the same directory and then
+renamed over the file, so the file is replaced atomically. With `relative`,
+the target of the link is relative to the directory of the file.
+
+The kept file is resolved first: if it is the file itself (ex.: the kept file
+is a symbolic link to it), the link would point to itself and an error is returned.
+
+Returns false if the file is already a symbolic link.
+*/
+fn replace_with_symlink(
+    keeper: &Path,
+    path: &Path,
+    relative: bool,
+    dry_run: bool,
+) -> MyResult<bool> {
+    if fs::symlink_metadata(path)?.is_symlink() {
+        return Ok(false);
+    }
+
+    let keeper: PathBuf = fs::canonicalize(keeper)?;
+    if keeper == fs::canonicalize(path)? {
+        return Err(format!("the kept file {keeper:?} resolves to {path:?}").into());
+    }
+
+    if !path
+        .to_path_buf()
+        .is_identical_to(&keeper, VERIFY_BUFFER_SIZE)?
+    {
+        return Err(format!("{path:?} and {keeper:?} are no longer identical").into());
+    }
+
+    let target: PathBuf = get_symlink_target(&keeper, path, relative)?;
+
+    eprintln!(
+        "{}Symbolic link file: {path:?} -> {target:?}",
+        get_prefix(dry_run)
+    );
+
+    if dry_run {
+        return Ok(true);
+    }
+
+    // On failure, the temporary link is removed: the original file is kept.
+    create_temp_file(path, |temp_path| symlink_file(&target, temp_path))?
+        .persist(path)
+        .map_err(|error| error.error)?;
+
+    Ok(true)
+}
+
+/**
+Get the target of the symbolic link `path` to the kept file:
+the canonical path of the kept file or, with `relative`, its path
+relative to the directory of the link.
+
+A target relative to the current directory would dangle.
+*/
+pub(crate) fn get_symlink_target(keeper: &Path, path: &Path, relative: bool) -> MyResult<PathBuf> {
+    let keeper: PathBuf = fs::canonicalize(keeper)?;
+
+    if !relative {
+        return Ok(keeper);
+    }
+
+    let dir: PathBuf = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::canonicalize(parent)?,
+        _ => std::env::current_dir()?,
+    };
+
+    Ok(get_relative_path(&dir, &keeper))
+}
+
+/// Create a symbolic link to a file.
+fn symlink_file(target: &Path, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+
+    #[cfg(windows)]
+    {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+/// Get the path of `target` relative to the directory `dir` (both absolute).
+///
+/// Example: dir `/a/b/c`, target `/a/d/e.txt` -> `../../d/e.txt`
+fn get_relative_path(dir: &Path, target: &Path) -> PathBuf {
+    let dir_components: Vec<Component> = dir.components().collect();
+    let target_components: Vec<Component> = target.components().collect();
+
+    let common: usize = dir_components
+        .iter()
+        .zip(&target_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..dir_components.len() {
+        relative.push("..");
+    }
+    for component in &target_components[common..] {
+        relative.push(component);
+    }
+
+    relative
+}
+
+/// Reflink support for each filesystem (device id) already probed.
+static REFLINK_SUPPORT: LazyLock<Mutex<HashMap<u64, bool>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/**
+Replace the file with a copy-on-write clone (reflink) of the kept file.
+
+Supported filesystems: Btrfs, XFS, ZFS (Linux), APFS (macOS) and ReFS (Windows).
+
+Unlike hard links, the clone keeps its own metadata (permissions and
+modification time are preserved) while sharing the data blocks.
+
+The first reflink on each filesystem probes whether it is supported.
+If not, the other files on the same filesystem are skipped.
+*/
+fn replace_with_reflink(keeper: &Path, path: &Path, dry_run: bool) -> MyResult<()> {
+    let metadata = fs::metadata(path)?;
+    let device: Option<u64> = get_device(&metadata);
+
+    if let Some(dev) = device {
+        if REFLINK_SUPPORT.lock().unwrap().get(&dev) == Some(&false) {
+            return Err(format!("reflink is not supported on the filesystem of {path:?}").into());
+        }
+    }
+
+    eprintln!(
+        "{}Reflink file: {path:?} -> {keeper:?}",
+        get_prefix(dry_run)
+    );
+
+    if dry_run {
+        return Ok(());
+    }
+
+    let result = create_temp_file(path, |temp_path| reflink_copy::reflink(keeper, temp_path));
+
+    if let Some(dev) = device {
+        REFLINK_SUPPORT.lock().unwrap().insert(dev, result.is_ok());
+    }
+    let temp_file = result?;
+
+    // Preserve the metadata of the replaced file.
+    let temp_path: &Path = temp_file.path();
+    fs::set_permissions(temp_path, metadata.permissions())?;
+    File::options()
+        .write(true)
+        .open(temp_path)?
+        .set_modified(metadata.modified()?)?;
+
+    // On failure, the clone is removed: the original file is kept.
+    temp_file.persist(path).map_err(|error| error.error)?;
+
+    Ok(())
+}
+
+/**
+Check if both paths are the same file: same device and inode on unix,
+same canonical path on other platforms.
+
+Symbolic links are followed.
+*/
+fn is_same_file(keeper: &Path, path: &Path) -> io::Result<bool> {
+    #[cfg(unix)]
+    {
+        let metadata_keeper = fs::metadata(keeper)?;
+        let metadata_path = fs::metadata(path)?;
+
+        Ok(metadata_keeper.dev() == metadata_path.dev()
+            && metadata_keeper.ino() == metadata_path.ino())
+    }
+
+    #[cfg(not(unix))]
+    {
+        Ok(fs::canonicalize(keeper)? == fs::canonicalize(path)?)
+    }
+}
+
+/// Get the device id of the filesystem containing the file.
+fn get_device(metadata: &fs::Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        Some(metadata.dev())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+#[cfg(test)]
+mod test_actions {
+    use super::*;
+
+    /// Size of the identical files.
+    const SIZE: usize = 9;
+
+    /// Write the kept file and an identical file in the directory.
+    fn identical_files(dir: &Path) -> MyResult<(PathBuf, PathBuf)> {
+        let keeper = dir.join("keeper.txt");
+        let path = dir.join("copy.txt");
+        fs::write(&keeper, "identical")?;
+        fs::write(&path, "identical")?;
+        Ok((keeper, path))
+    }
+
+    fn task<'a>(keeper: &'a Path, path: &'a Path, action: Action, dry_run: bool) -> Task<'a> {
+        Task {
+            keeper,
+            path,
+            size: SIZE,
+            action,
+            move_to: None,
+            dry_run,
+            verify: false,
+        }
+    }
+
+    /// cargo test -- --show-output test_execute_delete
+    #[test]
+    fn test_execute_delete() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+
+        // With `--dry_run`, the filesystem is not changed.
+        assert_eq!(task(&keeper, &path, Action::Delete, true).execute()?, SIZE);
+        assert!(path.exists());
+
+        assert_eq!(task(&keeper, &path, Action::Delete, false).execute()?, SIZE);
+        assert!(!path.exists());
+        assert!(keeper.exists());
+
+        // The file no longer exists.
+        assert!(task(&keeper, &path, Action::Delete, false)
+            .execute()
+            .is_err());
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_same_file
+    #[test]
+    fn test_execute_same_file() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, _path) = identical_files(temp_dir.path())?;
+
+        // Another path of the kept file, ex.: `./dir/keeper.txt` and `dir/keeper.txt`.
+        let same_file = temp_dir.path().join(".").join("keeper.txt");
+        let mut other_paths = vec![same_file];
+
+        #[cfg(unix)]
+        {
+            let link = temp_dir.path().join("link.txt");
+            std::os::unix::fs::symlink(&keeper, &link)?;
+            other_paths.push(link);
+        }
+
+        for path in &other_paths {
+            for action in [Action::Delete, Action::Move, Action::Symlink] {
+                assert_eq!(task(&keeper, path, action, false).execute()?, 0);
+                assert_eq!(fs::read_to_string(&keeper)?, "identical");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_dry_run
+    #[test]
+    fn test_execute_dry_run() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+
+        for action in [Action::Trash, Action::Reflink] {
+            assert_eq!(task(&keeper, &path, action, true).execute()?, SIZE);
+            assert_eq!(fs::read_to_string(&path)?, "identical");
+        }
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_hardlink
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_hardlink() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+        let inode = |path: &Path| -> MyResult<u64> { Ok(fs::metadata(path)?.ino()) };
+
+        assert_eq!(
+            task(&keeper, &path, Action::Hardlink, true).execute()?,
+            SIZE
+        );
+        assert_ne!(inode(&path)?, inode(&keeper)?);
+
+        assert_eq!(
+            task(&keeper, &path, Action::Hardlink, false).execute()?,
+            SIZE
+        );
+        assert_eq!(inode(&path)?, inode(&keeper)?);
+
+        // Already linked: nothing reclaimed.
+        assert_eq!(task(&keeper, &path, Action::Hardlink, false).execute()?, 0);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_symlink
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_symlink() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+
+        assert_eq!(task(&keeper, &path, Action::Symlink, true).execute()?, SIZE);
+        assert!(!fs::symlink_metadata(&path)?.is_symlink());
+
+        assert_eq!(
+            task(&keeper, &path, Action::Symlink, false).execute()?,
+            SIZE
+        );
+        assert_eq!(fs::read_link(&path)?, fs::canonicalize(&keeper)?);
+        assert_eq!(fs::read_to_string(&path)?, "identical");
+
+        // Already a link: nothing reclaimed.
+        assert_eq!(task(&keeper, &path, Action::Symlink, false).execute()?, 0);
+
+        // Relative link from a subdirectory.
+        let sub_path = temp_dir.path().join("sub/copy.txt");
+        fs::create_dir_all(temp_dir.path().join("sub"))?;
+        fs::write(&sub_path, "identical")?;
+
+        let relative = task(&keeper, &sub_path, Action::RelativeSymlink, false);
+        assert_eq!(relative.execute()?, SIZE);
+        assert_eq!(fs::read_link(&sub_path)?, Path::new("../keeper.txt"));
+
+        // The kept file is a link to the file: the file is not replaced.
+        #[cfg(unix)]
+        {
+            let other_path = temp_dir.path().join("other.txt");
+            let link = temp_dir.path().join("link.txt");
+            fs::write(&other_path, "identical")?;
+            std::os::unix::fs::symlink(&other_path, &link)?;
+
+            let result = replace_with_symlink(&link, &other_path, false, false);
+            assert!(result.is_err_and(|error| error.to_string().contains("resolves to")));
+            assert!(!fs::symlink_metadata(&other_path)?.is_symlink());
+            assert_eq!(fs::read_to_string(&other_path)?, "identical");
+        }
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_move
+    #[test]
+    fn test_execute_move() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+        let quarantine = temp_dir.path().join("quarantine");
+
+        let mut move_task = task(&keeper, &path, Action::Move, true);
+        move_task.move_to = Some(&quarantine);
+
+        assert_eq!(move_task.execute()?, SIZE);
+        assert!(path.exists());
+        assert!(!quarantine.exists());
+
+        move_task.dry_run = false;
+        let original: PathBuf = fs::canonicalize(&path)?;
+        assert_eq!(move_task.execute()?, SIZE);
+        assert!(!path.exists());
+
+        let moved_to: PathBuf = quarantine.join(
+            original
+                .components()
+                .filter(|component| matches!(component, Component::Normal(_)))
+                .collect::<PathBuf>(),
+        );
+        assert_eq!(fs::read_to_string(moved_to)?, "identical");
+        assert!(quarantine.join(MANIFEST_FILENAME).is_file());
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_copy_and_remove
+    #[cfg(unix)]
+    #[test]
+    fn test_copy_and_remove() -> MyResult<()> {
+        use std::{os::unix::fs::PermissionsExt, time::Duration};
+
+        let temp_dir = tempfile::tempdir()?;
+        let (_keeper, path) = identical_files(temp_dir.path())?;
+        let destination = temp_dir.path().join("moved.txt");
+
+        let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(modified)?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o440))?;
+
+        copy_and_remove(&path, &destination)?;
+
+        let metadata = fs::metadata(&destination)?;
+        assert!(!path.exists());
+        assert_eq!(metadata.modified()?, modified);
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o440);
+
+        // Only a rename across filesystems falls back to copy and remove.
+        assert!(is_cross_device(&io::Error::from_raw_os_error(18)));
+        assert!(!is_cross_device(&io::Error::from(io::ErrorKind::NotFound)));
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_execute_changed_files
+    #[test]
+    fn test_execute_changed_files() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let (keeper, path) = identical_files(temp_dir.path())?;
+
+        // Same size, different contents.
+        fs::write(&path, "different")?;
+
+        // Symbolic links are only created for identical files.
+        #[cfg(unix)]
+        {
+            let result = task(&keeper, &path, Action::Symlink, false).execute();
+            assert!(result.is_err_and(|error| error.to_string().contains("no longer identical")));
+            assert!(!fs::symlink_metadata(&path)?.is_symlink());
+        }
+
+        // With `--verify_before_action`, changed files are skipped.
+        let mut delete_task = task(&keeper, &path, Action::Delete, false);
+        delete_task.verify = true;
+
+        let action_info = execute_tasks(&[delete_task]);
+        assert_eq!(action_info.changed, vec![path.clone()]);
+        assert_eq!(action_info.num_files, 0);
+        assert_eq!(fs::read_to_string(&path)?, "different");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,520 @@
+use crate::{
+    open_file, skipped::add_skipped, source::find_source, Arguments, FileFilter, FileInfo, Key,
+    MyResult, OpenFile,
+};
+use flate2::read::{DeflateDecoder, GzDecoder};
+use std::{
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// Separator between the path of an archive and the path of a member:
+/// `photos.zip!/2024/a.jpg`.
+pub const ARCHIVE_SEPARATOR: &str = "!/";
+
+/// Size of the blocks of a tar archive.
+const TAR_BLOCK_SIZE: u64 = 512;
+
+/// Maximum size of the end of central directory record of a zip archive (with comment).
+const ZIP_EOCD_MAX_SIZE: u64 = 22 + 65_535;
+
+/// Archive formats supported by `--scan_archives`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// Get the format of the archive from its file name.
+    fn from_path(path: &Path) -> Option<Self> {
+        let name: String = path.file_name()?.to_string_lossy().to_lowercase();
+
+        if name.ends_with(".zip") {
+            Some(ArchiveFormat::Zip)
+        } else if name.ends_with(".tar") {
+            Some(ArchiveFormat::Tar)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveFormat::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Member of an archive: regular files only.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Member {
+    /// Path inside the archive
+    name: String,
+    /// Uncompressed size
+    size: u64,
+}
+
+/// Entry of the central directory of a zip archive.
+#[derive(Debug, Clone)]
+struct ZipEntry {
+    member: Member,
+    /// Compression method: 0 (stored) or 8 (deflate)
+    method: u16,
+    compressed_size: u64,
+    /// Offset of the local file header
+    offset: u64,
+}
+
+/**
+Get the members of an archive as virtual files (`--scan_archives`).
+
+The path of a member is `archive.zip!/inner/file`. Members are filtered
+like regular files (`--include`, `--exclude`, `--min_size`, ...).
+
+Nested archives are not opened. Unreadable archives are reported as skipped files.
+*/
+pub fn get_archive_files(
+    file_info: &FileInfo,
+    arguments: &Arguments,
+    filter: &FileFilter,
+) -> Vec<FileInfo> {
+    let Some(format) = ArchiveFormat::from_path(&file_info.path) else {
+        return Vec::new();
+    };
+
+    let members: Vec<Member> = match list_members(&file_info.path, format) {
+        Ok(members) => members,
+        Err(error) => {
+            add_skipped(&file_info.path, &error);
+            return Vec::new();
+        }
+    };
+
+    members
+        .into_iter()
+        .filter(|member| arguments.size_is_included(member.size))
+        .map(|member| (get_member_path(&file_info.path, &member.name), member.size))
+        .filter(|(path, _size)| filter.is_included(path) && !filter.is_excluded(path))
+        .map(|(path, size)| FileInfo {
+            key: Key::new(size, None),
+            path,
+            inode: None,
+            modified: file_info.modified,
+            allocated: None,
+        })
+        .collect()
+}
+
+/// Get the virtual path of a member of an archive.
+fn get_member_path(archive: &Path, name: &str) -> PathBuf {
+    let mut path = archive.as_os_str().to_owned();
+    path.push(ARCHIVE_SEPARATOR);
+    path.push(name);
+    PathBuf::from(path)
+}
+
+/**
+Split a virtual path into the path of the archive and the path of the member.
+
+Returns None for regular files.
+*/
+pub fn split_member_path(path: &Path) -> Option<(PathBuf, String)> {
+    let path_str = path.to_str()?;
+
+    path_str
+        .match_indices(ARCHIVE_SEPARATOR)
+        .find_map(|(index, _)| {
+            let archive = Path::new(&path_str[..index]);
+            let name = &path_str[index + ARCHIVE_SEPARATOR.len()..];
+            ArchiveFormat::from_path(archive)
+                .filter(|_| archive.is_file())
+                .map(|_| (archive.to_path_buf(), name.to_string()))
+        })
+}
+
+/// Check if the path is a member of an archive (`--scan_archives`).
+pub fn is_archive_member(path: &Path) -> bool {
+    path.as_os_str()
+        .to_str()
+        .is_some_and(|path_str| path_str.contains(ARCHIVE_SEPARATOR))
+        && split_member_path(path).is_some()
+}
+
+/**
+Open a regular file, a member of an archive or a file of a source (`--s3`, `--remote`).
+
+Members are decompressed while they are read (streaming).
+*/
+pub fn open_reader(path: &Path) -> MyResult<Box<dyn Read + Send>> {
+    if let Some(source) = find_source(path) {
+        return source.open(path);
+    }
+
+    let Some((archive, name)) = split_member_path(path) else {
+        return Ok(Box::new(open_file(&path)?));
+    };
+
+    let reader: Box<dyn Read + Send> = match ArchiveFormat::from_path(&archive) {
+        Some(ArchiveFormat::Zip) => open_zip_member(&archive, &name)?,
+        Some(ArchiveFormat::Tar) => open_tar_member(Box::new(open_file(&archive)?), &name)?,
+        Some(ArchiveFormat::TarGz) => {
+            let decoder = GzDecoder::new(BufReader::new(open_file(&archive)?));
+            open_tar_member(Box::new(decoder), &name)?
+        }
+        None => return Ok(Box::new(open_file(&path)?)),
+    };
+
+    Ok(reader)
+}
+
+/// Get the regular files of the archive.
+fn list_members(path: &Path, format: ArchiveFormat) -> io::Result<Vec<Member>> {
+    let file = OpenFile::open(path)?;
+
+    match format {
+        ArchiveFormat::Zip => Ok(read_zip_entries(file)?
+            .into_iter()
+            .map(|entry| entry.member)
+            .collect()),
+        ArchiveFormat::Tar => list_tar_members(BufReader::new(file)),
+        ArchiveFormat::TarGz => list_tar_members(GzDecoder::new(BufReader::new(file))),
+    }
+}
+
+/// Read a little-endian integer of 2 bytes.
+fn read_u16(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+/// Read a little-endian integer of 4 bytes.
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Get an error for a corrupted or unsupported archive.
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+/**
+Read the central directory of a zip archive.
+
+Directories, encrypted members and ZIP64 members are skipped.
+*/
+fn read_zip_entries(mut file: OpenFile) -> io::Result<Vec<ZipEntry>> {
+    let size: u64 = file.metadata()?.len();
+    let start: u64 = size.saturating_sub(ZIP_EOCD_MAX_SIZE);
+
+    let mut tail: Vec<u8> = Vec::new();
+    file.seek(SeekFrom::Start(start))?;
+    file.read_to_end(&mut tail)?;
+
+    // End of central directory record: the last signature of the file.
+    let eocd: usize = (0..tail.len().saturating_sub(21))
+        .rev()
+        .find(|&index| tail[index..].starts_with(b"PK\x05\x06"))
+        .ok_or_else(|| invalid_data("zip: end of central directory not found"))?;
+
+    let num_entries: usize = read_u16(&tail, eocd + 10) as usize;
+    let directory_size: u64 = read_u32(&tail, eocd + 12) as u64;
+    let directory_offset: u64 = read_u32(&tail, eocd + 16) as u64;
+
+    let mut directory: Vec<u8> = Vec::new();
+    file.seek(SeekFrom::Start(directory_offset))?;
+    file.take(directory_size).read_to_end(&mut directory)?;
+
+    let mut entries: Vec<ZipEntry> = Vec::with_capacity(num_entries);
+    let mut position: usize = 0;
+
+    while position + 46 <= directory.len() && directory[position..].starts_with(b"PK\x01\x02") {
+        let header: &[u8] = &directory[position..];
+        let flags: u16 = read_u16(header, 8);
+        let method: u16 = read_u16(header, 10);
+        let compressed_size: u32 = read_u32(header, 20);
+        let uncompressed_size: u32 = read_u32(header, 24);
+        let name_len: usize = read_u16(header, 28) as usize;
+        let extra_len: usize = read_u16(header, 30) as usize;
+        let comment_len: usize = read_u16(header, 32) as usize;
+        let offset: u32 = read_u32(header, 42);
+
+        let name_bytes: &[u8] = header
+            .get(46..46 + name_len)
+            .ok_or_else(|| invalid_data("zip: truncated central directory"))?;
+        let name: String = String::from_utf8_lossy(name_bytes).into_owned();
+
+        let is_dir: bool = name.ends_with('/');
+        let is_encrypted: bool = flags & 1 == 1;
+        let is_zip64: bool = [compressed_size, uncompressed_size, offset].contains(&u32::MAX);
+
+        if !is_dir && !is_encrypted && !is_zip64 {
+            entries.push(ZipEntry {
+                member: Member {
+                    name,
+                    size: uncompressed_size as u64,
+                },
+                method,
+                compressed_size: compressed_size as u64,
+                offset: offset as u64,
+            });
+        }
+
+        position += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(entries)
+}
+
+/// Open a member of a zip archive: stored or compressed with deflate.
+fn open_zip_member(archive: &Path, name: &str) -> MyResult<Box<dyn Read + Send>> {
+    let entry: ZipEntry = read_zip_entries(open_file(&archive)?)?
+        .into_iter()
+        .find(|entry| entry.member.name == name)
+        .ok_or_else(|| invalid_data("zip: member not found"))?;
+
+    let mut file: OpenFile = open_file(&archive)?;
+
+    // The data follows the local header, whose extra field may differ from the central directory.
+    let mut header = [0_u8; 30];
+    file.seek(SeekFrom::Start(entry.offset))?;
+    file.read_exact(&mut header)?;
+
+    if !header.starts_with(b"PK\x03\x04") {
+        return Err(invalid_data("zip: invalid local header").into());
+    }
+
+    let data_offset: u64 =
+        entry.offset + 30 + read_u16(&header, 26) as u64 + read_u16(&header, 28) as u64;
+    file.seek(SeekFrom::Start(data_offset))?;
+
+    let data = BufReader::new(file).take(entry.compressed_size);
+
+    match entry.method {
+        0 => Ok(Box::new(data)),
+        8 => Ok(Box::new(DeflateDecoder::new(data))),
+        method => Err(format!("zip: unsupported compression method {method}").into()),
+    }
+}
+
+/// Header of a member of a tar archive.
+struct TarHeader {
+    name: String,
+    size: u64,
+    type_flag: u8,
+}
+
+/// Read the next header of a tar archive: None at the end of the archive.
+fn read_tar_header(reader: &mut impl Read) -> io::Result<Option<TarHeader>> {
+    let mut block = [0_u8; TAR_BLOCK_SIZE as usize];
+
+    match reader.read_exact(&mut block) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error),
+    }
+
+    // The archive ends with zero blocks.
+    if block.iter().all(|&byte| byte == 0) {
+        return Ok(None);
+    }
+
+    let field = |range: std::ops::Range<usize>| -> String {
+        let bytes: &[u8] = &block[range];
+        let end: usize = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[..end]).into_owned()
+    };
+
+    let name: String = field(0..100);
+    let prefix: String = if &block[257..262] == b"ustar" {
+        field(345..500)
+    } else {
+        String::new()
+    };
+
+    Ok(Some(TarHeader {
+        name: if prefix.is_empty() {
+            name
+        } else {
+            format!("{prefix}/{name}")
+        },
+        size: parse_tar_size(&block[124..136])?,
+        type_flag: block[156],
+    }))
+}
+
+/// Parse the size of a tar member: octal digits or base-256 (large files).
+fn parse_tar_size(bytes: &[u8]) -> io::Result<u64> {
+    if bytes[0] & 0x80 != 0 {
+        let size = bytes[1..]
+            .iter()
+            .fold(0_u64, |size, &byte| (size << 8) | byte as u64);
+        return Ok(size);
+    }
+
+    let digits: String = bytes
+        .iter()
+        .filter(|byte| byte.is_ascii_digit())
+        .map(|&byte| byte as char)
+        .collect();
+
+    if digits.is_empty() {
+        return Ok(0);
+    }
+
+    u64::from_str_radix(&digits, 8).map_err(|_| invalid_data("tar: invalid size"))
+}
+
+/// Skip the data of a tar member (padded to 512 bytes).
+fn skip_tar_data(reader: &mut impl Read, size: u64) -> io::Result<()> {
+    let padded: u64 = size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE;
+    io::copy(&mut reader.by_ref().take(padded), &mut io::sink())?;
+    Ok(())
+}
+
+/**
+Visit the regular files of a tar archive.
+
+The closure gets the name and the size of each member and returns
+true to stop at this member: its data is the next content of the reader.
+
+GNU long names (type `L`) are supported.
+*/
+fn visit_tar_members<R, F>(reader: &mut R, mut stop: F) -> io::Result<bool>
+where
+    R: Read,
+    F: FnMut(&Member) -> bool,
+{
+    let mut long_name: Option<String> = None;
+
+    while let Some(header) = read_tar_header(reader)? {
+        match header.type_flag {
+            // GNU long name: the name of the next member is the data.
+            b'L' => {
+                let mut name: Vec<u8> = Vec::new();
+                reader.by_ref().take(header.size).read_to_end(&mut name)?;
+                let padding: u64 = header.size.div_ceil(TAR_BLOCK_SIZE) * TAR_BLOCK_SIZE;
+                skip_tar_data(reader, padding - header.size)?;
+                let end: usize = name
+                    .iter()
+                    .position(|&byte| byte == 0)
+                    .unwrap_or(name.len());
+                long_name = Some(String::from_utf8_lossy(&name[..end]).into_owned());
+            }
+            // Regular files
+            b'0' | 0 => {
+                let member = Member {
+                    name: long_name.take().unwrap_or(header.name),
+                    size: header.size,
+                };
+                if stop(&member) {
+                    return Ok(true);
+                }
+                skip_tar_data(reader, header.size)?;
+            }
+            // Directories, links, extended headers, ...
+            _ => {
+                long_name = None;
+                skip_tar_data(reader, header.size)?;
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+/// Get the regular files of a tar archive.
+fn list_tar_members(mut reader: impl Read) -> io::Result<Vec<Member>> {
+    let mut members: Vec<Member> = Vec::new();
+
+    visit_tar_members(&mut reader, |member| {
+        members.push(member.clone());
+        false
+    })?;
+
+    Ok(members)
+}
+
+/// Open a member of a tar archive: the archive is read up to the member.
+fn open_tar_member(mut reader: Box<dyn Read + Send>, name: &str) -> MyResult<Box<dyn Read + Send>> {
+    let mut size: u64 = 0;
+
+    let found: bool = visit_tar_members(&mut reader, |member| {
+        size = member.size;
+        member.name == name
+    })?;
+
+    if !found {
+        return Err(invalid_data("tar: member not found").into());
+    }
+
+    Ok(Box::new(reader.take(size)))
+}
+
+#[cfg(test)]
+mod test_archive {
+    use super::*;
+
+    /// Build a tar archive with one file.
+    fn tar_archive(name: &str, content: &[u8]) -> Vec<u8> {
+        let mut header = [0_u8; 512];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[124..135].copy_from_slice(format!("{:011o}", content.len()).as_bytes());
+        header[156] = b'0';
+        header[257..262].copy_from_slice(b"ustar");
+
+        let mut archive: Vec<u8> = header.to_vec();
+        archive.extend(content);
+        archive.resize(archive.len().div_ceil(512) * 512, 0);
+        archive.extend([0; 1024]);
+        archive
+    }
+
+    /// cargo test -- --show-output test_tar_members
+    #[test]
+    fn test_tar_members() -> MyResult<()> {
+        let archive: Vec<u8> = tar_archive("dir/a.txt", b"hello");
+
+        let members = list_tar_members(&archive[..])?;
+        assert_eq!(
+            members,
+            [Member {
+                name: "dir/a.txt".to_string(),
+                size: 5
+            }]
+        );
+
+        let mut content = String::new();
+        open_tar_member(Box::new(io::Cursor::new(archive)), "dir/a.txt")?
+            .read_to_string(&mut content)?;
+        assert_eq!(content, "hello");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_split_member_path
+    #[test]
+    fn test_split_member_path() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let archive = temp_dir.path().join("archive.tar");
+        std::fs::write(&archive, tar_archive("a.txt", b"hello"))?;
+
+        let path = get_member_path(&archive, "a.txt");
+        assert_eq!(
+            split_member_path(&path),
+            Some((archive.clone(), "a.txt".to_string()))
+        );
+        assert!(is_archive_member(&path));
+        assert!(!is_archive_member(&archive));
+
+        let mut content = String::new();
+        open_reader(&path)?.read_to_string(&mut content)?;
+        assert_eq!(content, "hello");
+
+        Ok(())
+    }
+}
@@ -1,8 +1,33 @@
-use crate::{clear_terminal_screen, Algorithm, MyResult};
-use clap::{CommandFactory, Parser, ValueEnum};
+use crate::{
+    actions::Action, clear_terminal_screen, Algorithm, ColorChoice, Compression, Config, FileKind,
+    Heuristic, IoProfile, KeepStrategy, Lang, LogLevel, MetadataKey, MyResult, PathStyle,
+    ScriptFormat, SortKey, SortOrder,
+};
+use clap::{
+    builder::TypedValueParser, error::ErrorKind, parser::ValueSource, ArgAction, ArgMatches,
+    CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum,
+};
 use clap_complete::{generate, Generator, Shell};
 use serde::Serialize;
-use std::{fs, io, path::PathBuf, process};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    time::{Duration, SystemTime},
+};
+
+/// Options that change files: only accepted by `clean` or without subcommand.
+const ACTION_OPTIONS: [&str; 9] = [
+    "delete",
+    "trash",
+    "hardlink",
+    "reflink",
+    "symlink",
+    "relative_symlink",
+    "move_to",
+    "interactive",
+    "script",
+];
 
 #[derive(Debug, Default, Clone, ValueEnum, Serialize)]
 pub enum ResultFormat {
@@ -10,6 +35,86 @@ pub enum ResultFormat {
     Yaml,
     #[default]
     Personal,
+    /// Standalone HTML report (ex.: `-r html -O report.html`)
+    Html,
+    /// Markdown headings and tables (ex.: for issues, wikis or pull requests)
+    Markdown,
+    /// XML (schema: `schema/identical_files.xsd`)
+    Xml,
+    /// Compact binary CBOR, for large results read by programs (ex.: `-r cbor -O result.cbor`)
+    Cbor,
+    /// Compact binary MessagePack, for large results read by programs
+    #[value(name = "msgpack")]
+    MsgPack,
+}
+
+/**
+Subcommands: the actions that change files are separated from the search.
+
+Without subcommand, all options are accepted (`scan` and `clean` together).
+
+The options are shared by all subcommands (ex.: `find-identical-files scan -i ~/Photos`),
+except the actions that change files (`--delete`, `--move_to`...): only `clean` accepts them.
+*/
+#[derive(Debug, Clone, Subcommand)]
+pub enum Commands {
+    /// Search and report the identical files: no file is changed
+    Scan,
+    /// Remove or replace the identical files, keeping one file per group
+    ///
+    /// Requires `--delete`, `--hardlink`, `--reflink`, `--symlink`, `--move_to`, `--interactive` or `--script`.
+    Clean,
+    /// Verify a backup or a checksum manifest
+    Verify {
+        #[command(subcommand)]
+        target: VerifyTarget,
+    },
+    /// Compare two JSON reports (see `--diff`)
+    Diff {
+        /// Previous report
+        old: PathBuf,
+        /// Current report
+        new: PathBuf,
+    },
+    /// Hash all files and save the digest index, without reporting the identical files
+    ///
+    /// Use the index later with `--import_index` (see `--export_index`).
+    Cache {
+        /// Digest index file
+        file: PathBuf,
+    },
+    /// Measure the throughput of each hash algorithm on this machine (see `--bench`)
+    Bench,
+    /// Check that the search finds the identical files of a synthetic tree (see `--selftest`)
+    Selftest,
+    /// Undo the actions recorded in a journal, in reverse order (see `--undo`)
+    Undo {
+        /// Journal written with `--journal`
+        journal: PathBuf,
+    },
+    /// Print the shell completion file (see `--generate`)
+    Completions {
+        /// Shell
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+/// What `verify` checks.
+#[derive(Debug, Clone, Subcommand)]
+pub enum VerifyTarget {
+    /// Report every file of SOURCE whose content has no identical file in BACKUP (see `--verify_subset`)
+    Subset {
+        /// Source directory
+        source: PathBuf,
+        /// Backup directory
+        backup: PathBuf,
+    },
+    /// Report the files changed or missing since the manifest was written (see `--verify_manifest`)
+    Manifest {
+        /// Manifest file
+        file: PathBuf,
+    },
 }
 
 // https://stackoverflow.com/questions/74068168/clap-rs-not-printing-colors-during-help
@@ -40,7 +145,7 @@ const APPLET_TEMPLATE: &str = "\
 {after-help}";
 
 /// Command Line Arguments
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(
     // Read from `Cargo.toml`
     author, version, about,
@@ -54,27 +159,111 @@ pub struct Arguments {
     #[arg(short('a'), long("algorithm"), value_enum, default_value_t = Algorithm::default())]
     pub algorithm: Algorithm,
 
-    /// Set a minimum file size (in bytes) to search for identical files.
+    /// Report the same songs in different encodings (ex.: mp3 and flac) instead of identical files.
+    ///
+    /// Songs are compared by an audio fingerprint (chromaprint):
+    /// use `--min_similarity` to set the tolerance. Other files are skipped.
+    ///
+    /// Similar songs are not identical: the actions that remove files are not allowed.
+    ///
+    /// Requires the `audio` feature.
+    #[cfg(feature = "audio")]
+    #[arg(
+        long("audio"),
+        default_value_t = false,
+        conflicts_with_all = ["by_content", "content_only", "dirs", "paranoid", "same_name_only", "unique", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "trash", "interactive", "script"]
+    )]
+    pub audio: bool,
+
+    /// Set a minimum file size to search for identical files.
     ///
     /// keep files whose size is greater than or equal to a minimum value.
     ///
     /// size >= min_size
-    #[arg(short('b'), long("min_size"), required = false, default_value_t = 0)]
+    ///
+    /// Accepts bytes or units: 10K, 10M, 2G, 1T (binary), 10KiB, 2GiB (binary), 10KB, 2GB (decimal).
+    #[arg(
+        short('b'), long("min_size"),
+        required = false,
+        default_value_t = 0,
+        value_parser = parse_size,
+    )]
     pub min_size: u64,
 
-    /// Set a maximum file size (in bytes) to search for identical files.
+    /// Set a maximum file size to search for identical files.
     ///
     /// keep files whose size is less than or equal to a maximum value.
     ///
     /// size <= max_size
+    ///
+    /// Accepts bytes or units, like `--min_size`.
     #[arg(
         short('B'), long("max_size"),
         required = false,
         default_value_t = u64::MAX,
         hide_default_value = true,
+        value_parser = parse_size,
     )]
     pub max_size: u64,
 
+    /// Set a minimum size allocated on disk to search for identical files (unix only).
+    ///
+    /// Skip sparse or compressed files that use less disk space than the size.
+    ///
+    /// Accepts bytes or units, like `--min_size`.
+    #[arg(
+        long("min_allocated"),
+        required = false,
+        default_value_t = 0,
+        value_name = "SIZE",
+        value_parser = parse_size,
+    )]
+    pub min_allocated: u64,
+
+    /// Measure the throughput of each hash algorithm on this machine and recommend one.
+    ///
+    /// A temporary file of 256 MiB is hashed with several buffer sizes and with `--mmap`.
+    ///
+    /// No directory is searched.
+    #[arg(
+        long("bench"),
+        default_value_t = false,
+        conflicts_with_all = ["input_dir", "compare", "files_from", "dirs", "unique", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub bench: bool,
+
+    /// Set the size (in bytes) of the buffer used to read each file.
+    ///
+    /// Larger buffers reduce the number of reads on spinning disks.
+    #[arg(long("buffer_size"), required = false, default_value_t = 64 * 1024)]
+    pub buffer_size: usize,
+
+    /// Group files by the hash of the entire file only, ignoring the size reported by the filesystem.
+    ///
+    /// Useful when sizes differ from content length (ex.: files of `/proc` or `/sys`).
+    /// The size of each group is the number of bytes hashed.
+    ///
+    /// The apparent size and the size allocated on disk of each file are also reported.
+    ///
+    /// All files are entirely hashed: slower than the default search.
+    /// Files reported as empty are skipped, unless `--include_empty` is chosen.
+    #[arg(long("by_content"), default_value_t = false)]
+    pub by_content: bool,
+
+    /// Ignore embedded metadata when hashing files of known formats.
+    ///
+    /// Only the payload is hashed: MP3 without ID3 tags, FLAC without
+    /// metadata blocks (except STREAMINFO) and JPEG without APPn (EXIF, XMP, ...)
+    /// and COM segments. Files that differ only in tags are reported as identical.
+    ///
+    /// All files are entirely hashed, as with `--by_content`.
+    #[arg(
+        long("content_only"),
+        default_value_t = false,
+        conflicts_with_all = ["dirs", "paranoid", "hardlink", "reflink", "symlink", "relative_symlink"]
+    )]
+    pub content_only: bool,
+
     /// Set the output directory for the CSV file (fif.csv).
     ///
     /// By default, use the current directory.
@@ -87,6 +276,158 @@ pub struct Arguments {
     #[arg(short('c'), long("csv_dir"), required = false)]
     pub csv_dir: Option<PathBuf>,
 
+    /// Color the Personal format: paths of consecutive groups alternate between two colors.
+    ///
+    /// With `auto`, the result is plain when piped or when `NO_COLOR` is set.
+    #[arg(long("color"), value_enum, default_value_t = ColorChoice::default())]
+    pub color: ColorChoice,
+
+    /// Compare the input directories (targets) with a reference directory.
+    ///
+    /// Only report target files that are identical to files in the reference directory.
+    ///
+    /// Identical files found only within the reference directory are not reported.
+    ///
+    /// With `--delete`, `--hardlink` or `--reflink`, the kept file is a reference file
+    /// and the action is only applied to the target files.
+    #[arg(long("compare"), required = false, value_name = "DIR")]
+    pub compare: Option<PathBuf>,
+
+    /// Require identical files to also have the same metadata.
+    ///
+    /// Ex.: `--compare_metadata mtime,perms` splits the groups of identical files
+    /// by modification time and permissions.
+    ///
+    /// owner, perms and xattr (extended attributes) are only compared on unix.
+    ///
+    /// With `--metadata_diff`, the groups are not split: the metadata
+    /// that differ within each group are reported in the summary.
+    #[arg(
+        long("compare_metadata"),
+        required = false,
+        value_name = "KEYS",
+        value_delimiter = ',',
+        conflicts_with = "dirs"
+    )]
+    pub compare_metadata: Vec<MetadataKey>,
+
+    /// Print the actions that would be applied (`--delete`, `--hardlink`, `--reflink`,
+    /// `--move_to`, `--interactive`) and the space that would be reclaimed,
+    /// without changing any file.
+    #[arg(long("dry_run"), default_value_t = false)]
+    pub dry_run: bool,
+
+    /// Check each file again immediately before applying the action to it.
+    ///
+    /// The file and the kept file must still have the size found by the search
+    /// and identical contents (compared byte by byte). Files changed since the
+    /// search are skipped and reported.
+    #[arg(
+        long("verify_before_action"),
+        default_value_t = false,
+        conflicts_with_all = ["by_content", "content_only", "dirs"]
+    )]
+    pub verify_before_action: bool,
+
+    /// Compare two JSON reports (`-r json`) and report the groups of identical files
+    /// that appeared, disappeared or whose number of files changed.
+    ///
+    /// Groups are identified by (size, hash). Ex.: with `--report_dir`:
+    ///
+    /// `find-identical-files --diff reports/fif-2024-01-30_020000.json reports/latest`
+    #[arg(
+        long("diff"),
+        required = false,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["input_dir", "compare", "files_from", "dirs", "unique", "verify_subset", "verify_manifest", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub diff: Vec<PathBuf>,
+
+    /// Report identical directories instead of identical files.
+    ///
+    /// The digest of each directory is computed from the names and digests
+    /// of its files and subdirectories: two directories are identical only
+    /// if their entire trees are identical.
+    ///
+    /// Only the top-most identical directories are reported.
+    ///
+    /// All files are entirely hashed, including the files skipped by the filters
+    /// (hidden, excluded, empty or out of range files). Symbolic links and
+    /// empty subdirectories are also compared.
+    #[arg(
+        long("dirs"),
+        default_value_t = false,
+        conflicts_with_all = ["by_content", "same_name_only", "paranoid", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub dirs: bool,
+
+    /// Maximum number of different bits between the perceptual hashes
+    /// of visually identical images (`--perceptual`), from 0 to 64.
+    #[cfg(feature = "perceptual")]
+    #[arg(
+        long("max_distance"),
+        default_value_t = 4,
+        value_parser = clap::value_parser!(u32).range(0..=64),
+        requires = "perceptual"
+    )]
+    pub max_distance: u32,
+
+    /// Minimum similarity between the audio fingerprints of
+    /// the same song in different encodings (`--audio`), from 0.0 to 1.0.
+    ///
+    /// Unrelated songs have a similarity of about 0.5.
+    #[cfg(feature = "audio")]
+    #[arg(
+        long("min_similarity"),
+        default_value_t = 0.85,
+        value_parser = parse_similarity,
+        requires = "audio"
+    )]
+    pub min_similarity: f64,
+
+    /// Stop the search after finding N files.
+    ///
+    /// The files found so far are compared and the result is marked as an incomplete scan.
+    #[arg(long("max_files"), required = false, value_name = "N")]
+    pub max_files: Option<usize>,
+
+    /// Limit the memory of the index used to group the files by size.
+    ///
+    /// Accepts bytes or units, like `--min_size` (ex.: 512M, 2GiB).
+    ///
+    /// On trees with hundreds of millions of files, the files are grouped
+    /// on disk instead: sorted runs are written to the temporary directory and merged.
+    ///
+    /// The list of files found is still kept in memory:
+    /// this is not a limit on the total memory of the search.
+    #[arg(
+        long("max_group_memory"),
+        required = false,
+        value_name = "SIZE",
+        value_parser = parse_size
+    )]
+    pub max_group_memory: Option<u64>,
+
+    /// Limit the number of files opened at the same time to read them.
+    ///
+    /// Wide parallel scans may exceed the limit of file descriptors of the
+    /// process (`ulimit -n`). The limit is at least two files per thread:
+    /// a lower value is raised, with a warning.
+    ///
+    /// Without this option, there is no limit, but a file that cannot be
+    /// opened for lack of descriptors (`EMFILE`) is retried after other files are closed.
+    #[arg(long("max_open_files"), required = false, value_name = "N")]
+    pub max_open_files: Option<usize>,
+
+    /// Print at most N groups of identical files (a page of the result).
+    ///
+    /// With `--skip`, the pages can be consumed one at a time: --skip 100 --limit 100
+    ///
+    /// The summary and the actions only take these groups into account.
+    #[arg(long("limit"), required = false, value_name = "N")]
+    pub limit: Option<usize>,
+
     /// Set the minimum depth to search for identical files.
     ///
     /// depth >= min_depth
@@ -104,17 +445,100 @@ pub struct Arguments {
     )]
     pub max_depth: usize,
 
+    /// Remove identical files, keeping only one file per group.
+    ///
+    /// The file to be kept is chosen with the `--keep` option.
+    ///
+    /// Warning: the removed files cannot be recovered!
+    /// Use `--trash` to send them to the trash instead.
+    #[arg(long("delete"), default_value_t = false)]
+    pub delete: bool,
+
+    /// Sort the paths of each group and the groups canonically,
+    /// so two runs over the same data produce byte-identical reports.
+    ///
+    /// Parallel directory walking may find the files in a different order on each run.
+    #[arg(long("deterministic"), default_value_t = false)]
+    pub deterministic: bool,
+
+    /// Skip directories and files whose name or path matches the glob pattern.
+    ///
+    /// This option can be specified multiple times:
+    ///
+    /// --exclude node_modules --exclude .git --exclude '*.tmp'
+    ///
+    /// Excluded directories are not traversed.
+    ///
+    /// Pseudo filesystems (`/proc`, `/sys`, `/dev`), trash and snapshot
+    /// directories are also excluded, unless `--no_default_excludes` is chosen.
+    #[arg(long("exclude"), required = false)]
+    pub exclude: Vec<String>,
+
+    /// Search the well-known system and junk directories excluded by default:
+    /// `/proc`, `/sys`, `/dev`, `.Trash*`, `.snapshots`,
+    /// `System Volume Information` and `$RECYCLE.BIN` (depending on the platform).
+    #[arg(long("no_default_excludes"), default_value_t = false)]
+    pub no_default_excludes: bool,
+
+    /// Save the digests of all files (path, size, digest) to an index file.
+    ///
+    /// If the filename ends in `.json`, the index is written in JSON format,
+    /// otherwise in a compact binary format.
+    #[arg(long("export_index"), required = false, value_name = "FILE")]
+    pub export_index: Option<PathBuf>,
+
+    /// Export the files, digests and groups of identical files to a SQLite database.
+    ///
+    /// Tables: `scans`, `files`, `duplicate_groups` and `group_files`.
+    ///
+    /// The results of the previous scans are replaced, unless `--sqlite_upsert` is chosen.
+    #[cfg(feature = "sqlite")]
+    #[arg(long("export_sqlite"), required = false, value_name = "FILE")]
+    pub export_sqlite: Option<PathBuf>,
+
+    /// Add the scan to the SQLite database instead of replacing the previous scans.
+    ///
+    /// Files are inserted or updated: `first_scan` and `last_scan` track their history.
+    #[cfg(feature = "sqlite")]
+    #[arg(
+        long("sqlite_upsert"),
+        default_value_t = false,
+        requires = "export_sqlite"
+    )]
+    pub sqlite_upsert: bool,
+
     /// Prints extended path of identical files, otherwise relative path.
+    ///
+    /// Same as `--path_style canonical`.
     #[arg(short('e'), long("extended_path"), default_value_t = false)]
     pub extended_path: bool,
 
+    /// How the paths of identical files are displayed in all output formats.
+    ///
+    /// relative: as found from the input directories;
+    /// absolute: full paths;
+    /// canonical: full paths with symbolic links resolved;
+    /// home: full paths with the home directory abbreviated to `~`.
+    ///
+    /// Actions and scripts always use the paths of the files found.
+    #[arg(
+        long("path_style"),
+        value_enum,
+        default_value_t = PathStyle::default(),
+        conflicts_with = "extended_path"
+    )]
+    pub path_style: PathStyle,
+
     /// Minimum frequency (number of identical files) to be filtered.
     ///
     /// If n = 1, all files will be reported.
     ///
+    /// Ex.: `--min_copies 5` shows the files duplicated 5 or more times.
+    ///
     /// Default value = 2
     #[arg(
         short('f'), long("min_frequency"), 
+        visible_alias = "min_copies",
         required = false,
         default_value_t = 2,
         value_parser = clap::value_parser!(u64).range(1..)
@@ -124,6 +548,7 @@ pub struct Arguments {
     /// Maximum frequency (number of identical files) to be filtered.
     #[arg(
         short('F'), long("max_frequency"), 
+        visible_alias = "max_copies",
         required = false,
         default_value_t = u64::MAX,
         hide_default_value = true,
@@ -131,6 +556,51 @@ pub struct Arguments {
     )]
     pub max_frequency: u64,
 
+    /// Hide groups of identical files that waste less than the size.
+    ///
+    /// The wasted size of a group is: size × (number of identical files − 1)
+    ///
+    /// Accepts bytes or units, like `--min_size`.
+    #[arg(
+        long("min_wasted"),
+        required = false,
+        default_value_t = 0,
+        value_name = "SIZE",
+        value_parser = parse_size,
+    )]
+    pub min_wasted: u64,
+
+    /// Follow symbolic links to directories.
+    ///
+    /// Each directory is traversed only once, even if reached through several links,
+    /// so that symbolic link loops are never followed.
+    #[arg(long("follow_links"), default_value_t = false)]
+    pub follow_links: bool,
+
+    /// Read the list of candidate files from FILE instead of walking directories.
+    ///
+    /// One path per line, or NUL-delimited with `--null`. Use `-` to read from stdin.
+    ///
+    /// Ex.: `git ls-files -z | find-identical-files --files_from - --null`
+    ///
+    /// The size, time, include and exclude filters are still applied.
+    #[arg(
+        long("files_from"),
+        required = false,
+        value_name = "FILE",
+        conflicts_with_all = ["input_dir", "compare", "dirs", "watch"]
+    )]
+    pub files_from: Option<PathBuf>,
+
+    /// The list of `--files_from` is NUL-delimited (ex.: `find -print0`).
+    #[arg(
+        short('0'),
+        long("null"),
+        default_value_t = false,
+        requires = "files_from"
+    )]
+    pub null: bool,
+
     /**
     If provided, outputs the completion file for given shell.
 
@@ -207,120 +677,1191 @@ pub struct Arguments {
     #[arg(short('g'), long("generate"), value_enum)]
     pub generator: Option<Shell>,
 
-    /// Set the input directory where to search for identical files
-    /// [default: current directory].
-    #[arg(short('i'), long("input_dir"), required = false)]
-    pub input_dir: Option<PathBuf>,
+    /// Replace identical files with hard links to a single file per group.
+    ///
+    /// The file to be kept is chosen with the `--keep` option.
+    ///
+    /// Hard links only work within the same filesystem:
+    /// files on other filesystems are left untouched.
+    #[arg(long("hardlink"), default_value_t = false, conflicts_with = "delete")]
+    pub hardlink: bool,
 
-    /// Omit hidden files (starts with '.'), otherwise search all files.
-    #[arg(short('o'), long("omit_hidden"), default_value_t = false)]
-    pub omit_hidden: bool,
+    /// Group the files with the same name and size before hashing.
+    ///
+    /// Files with the same size but different names are never hashed.
+    ///
+    /// Only these groups are then confirmed by hashing, or not at all with `--size_only`.
+    ///
+    /// Copies with different names are not found (see also `--same_name_only`).
+    #[arg(
+        long("heuristic"),
+        required = false,
+        value_enum,
+        value_name = "HEURISTIC",
+        conflicts_with_all = ["by_content", "content_only", "dirs", "unique", "find_copies_of", "find_digest"]
+    )]
+    pub heuristic: Option<Heuristic>,
 
-    /// Print the result in the chosen format.
-    #[arg(short('r'), long("result_format"), value_enum, default_value_t = ResultFormat::default())]
-    pub result_format: ResultFormat,
+    /// Search only files whose name or path matches the glob pattern.
+    ///
+    /// This option can be specified multiple times or with a comma-separated list:
+    ///
+    /// --include '*.jpg,*.png,*.raw'
+    ///
+    /// Files must also satisfy the other filters (size, depth, ...).
+    #[arg(long("include"), required = false, value_delimiter = ',')]
+    pub include: Vec<String>,
 
-    /// Sort result by number of identical files, otherwise sort by file size.
-    #[arg(short('s'), long("sort"), default_value_t = false)]
-    pub sort: bool,
+    /// Include empty files (zero bytes) in the search.
+    ///
+    /// By default, empty files are skipped: they are all trivially identical.
+    #[arg(long("include_empty"), default_value_t = false)]
+    pub include_empty: bool,
 
-    /// Show total execution time.
-    #[arg(short('t'), long("time"), default_value_t = false)]
-    pub time: bool,
+    /// Search only files of these types, detected from their first bytes
+    /// (magic numbers) instead of their extensions.
+    ///
+    /// Renamed files are still included: --file_type image,video
+    ///
+    /// Only the files with the same size as another file are read (262 bytes).
+    #[arg(
+        long("file_type"),
+        required = false,
+        value_enum,
+        value_delimiter = ',',
+        conflicts_with = "dirs"
+    )]
+    pub file_type: Vec<FileKind>,
 
-    /// Show intermediate runtime messages.
-    #[arg(short('v'), long("verbose"), default_value_t = false)]
-    pub verbose: bool,
+    /// Also search the files inside zip, tar and tar.gz archives.
+    ///
+    /// Members are reported as virtual files: archive.zip!/inner/file
+    ///
+    /// They are compared with regular files and with members of other archives,
+    /// but are never changed by `--delete`, `--hardlink`, `--reflink` or `--move_to`.
+    ///
+    /// Nested archives, encrypted and ZIP64 members are not searched.
+    #[arg(
+        long("scan_archives"),
+        default_value_t = false,
+        conflicts_with_all = ["dirs", "interactive"]
+    )]
+    pub scan_archives: bool,
 
-    /// Wipe (Clear) the terminal screen before listing the identical files.
+    /// Also search the objects of an S3 bucket: `--s3 bucket/prefix`.
     ///
-    /// On Linux, to clear use the command:
+    /// Objects are reported as virtual files: s3://bucket/key
     ///
-    /// tput reset
+    /// The credentials are read from AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY,
+    /// AWS_SESSION_TOKEN and AWS_REGION. Set AWS_ENDPOINT_URL for S3-compatible
+    /// services (MinIO, Ceph, ...).
     ///
-    /// Unlike the clear command, the reset command does more than just clear the terminal screen.
+    /// Without `--input_dir`, only the bucket is searched and the ETags of objects
+    /// uploaded in a single part are used as digests: these objects are not downloaded.
+    /// The other objects are streamed and hashed with the chosen algorithm.
     ///
-    /// It also resets the terminal to its default settings.
-    #[arg(short('w'), long("wipe_terminal"), default_value_t = false)]
-    // action = ArgAction::SetTrue
-    pub wipe_terminal: bool,
+    /// Objects are never changed by `--delete`, `--hardlink`, `--reflink` or `--move_to`.
+    #[cfg(feature = "s3")]
+    #[arg(
+        long("s3"),
+        value_name = "BUCKET/PREFIX",
+        conflicts_with_all = ["dirs", "interactive"]
+    )]
+    pub s3: Option<String>,
 
-    /// Set the output directory for the XLSX file (fif.xlsx).
+    /// Also search the files of a remote server: `--remote sftp://user@host/path`.
     ///
-    /// By default, use the current directory.
+    /// Files are reported as virtual files: sftp://user@host/path/file
     ///
-    /// That is, export identical file information to XLSX format.
+    /// Commands are run with the ssh client of the system (key authentication).
+    /// The server needs a POSIX shell and GNU find. Files are hashed remotely
+    /// by sha256sum, sha512sum or b3sum when available for the chosen algorithm,
+    /// otherwise they are streamed and hashed locally.
     ///
-    /// XLSX: Excel file
-    #[arg(short('x'), long("xlsx_dir"), required = false)]
-    pub xlsx_dir: Option<PathBuf>,
-}
+    /// With `--compare`, the remote files are target files.
+    ///
+    /// Remote files are never changed by `--delete`, `--hardlink`, `--reflink` or `--move_to`.
+    #[arg(
+        long("remote"),
+        value_name = "URL",
+        conflicts_with_all = ["dirs", "interactive"]
+    )]
+    pub remote: Option<String>,
 
-impl Arguments {
-    /// Build Arguments struct
-    pub fn build() -> MyResult<Arguments> {
-        let args: Arguments = Arguments::parse();
+    /// Search only files whose path matches the regular expression.
+    ///
+    /// This option can be specified multiple times:
+    ///
+    /// --include_regex '(?i)\.jpe?g$'
+    #[arg(long("include_regex"), required = false)]
+    pub include_regex: Vec<String>,
 
-        if let Some(generator) = args.generator {
-            args.print_completions(generator);
-        }
+    /// Compare file names case-insensitively with `--same_name_only`.
+    ///
+    /// Ex.: IMG_0001.JPG and img_0001.jpg are considered the same name.
+    #[arg(
+        long("ignore_case"),
+        default_value_t = false,
+        requires = "same_name_only"
+    )]
+    pub ignore_case: bool,
 
-        if args.wipe_terminal {
-            clear_terminal_screen();
-        }
+    /// Run with the lowest CPU and I/O priorities (idle class).
+    ///
+    /// Linux: `renice` and `ionice -c 3`. macOS: `renice` and `taskpolicy -b`.
+    #[arg(long("idle"), default_value_t = false)]
+    pub idle: bool,
 
-        args.validate_range_size()?;
-        args.validate_range_depth()?;
-        args.validate_range_frequency()?;
-        args.validate_dir_path()?;
+    /// Do not report hard links to the same file as identical files.
+    ///
+    /// Hard links share the same data and do not waste space:
+    /// only one path per (device, inode) is searched.
+    ///
+    /// Use `--ignore_hardlinks false` to report them.
+    #[arg(long("ignore_hardlinks"), default_value_t = true, action = ArgAction::Set)]
+    pub ignore_hardlinks: bool,
 
-        /*
-        // validate simultaneously.
-        std::thread::scope(|s| {
-            s.spawn(|| -> MyResult<()> {
-                args.validate_range_size()?;
-                Ok(())
-            });
+    /// Load digest indexes saved with `--export_index` (ex.: on another machine).
+    ///
+    /// Indexed files are compared with the files of the input directories
+    /// without being read again. They are reported with the path of the index:
+    /// `/tmp/pictures.idx::/home/user/Pictures/a.jpg`.
+    ///
+    /// The indexes must be created with the same hash algorithm.
+    #[arg(long("import_index"), required = false, value_name = "FILE")]
+    pub import_index: Vec<PathBuf>,
 
-            s.spawn(|| -> MyResult<()> {
-                args.validate_range_depth()?;
-                Ok(())
-            });
+    /// Search only files modified after the date or duration.
+    ///
+    /// Accepts a date (UTC) or a duration before now (s, m, h, d, w, y):
+    ///
+    /// --newer_than 2024-01-31 or --newer_than '2024-01-31 18:30:00' or --newer_than 30d
+    #[arg(long("newer_than"), required = false, value_name = "TIME", value_parser = parse_time)]
+    pub newer_than: Option<SystemTime>,
 
-            s.spawn(|| -> MyResult<()> {
-                args.validate_range_frequency()?;
-                Ok(())
-            });
+    /// Search only files modified before the date or duration.
+    ///
+    /// Accepts a date (UTC) or a duration before now (s, m, h, d, w, y):
+    ///
+    /// --older_than 2024-01-31 or --older_than 1y
+    #[arg(long("older_than"), required = false, value_name = "TIME", value_parser = parse_time)]
+    pub older_than: Option<SystemTime>,
 
-            s.spawn(|| -> MyResult<()> {
-                args.validate_dir_path()?;
-                Ok(())
-            });
-        });
-        */
+    /// Set the input directories where to search for identical files
+    /// [default: current directory].
+    ///
+    /// This option can be specified multiple times to search several directories:
+    ///
+    /// -i ~/Pictures -i /mnt/backup/Pictures
+    ///
+    /// or
+    ///
+    /// -i ~/Pictures /mnt/backup/Pictures
+    #[arg(short('i'), long("input_dir"), required = false, num_args = 1..)]
+    pub input_dir: Vec<PathBuf>,
 
-        Ok(args)
-    }
+    /// Walk through each group of identical files and choose what to do with each file.
+    ///
+    /// Answer (k)eep, (d)elete, (h)ardlink, (r)eflink, (s)kip group or (q)uit for each file.
+    ///
+    /// Nothing is changed until the planned actions are confirmed.
+    #[arg(
+        long("interactive"),
+        default_value_t = false,
+        conflicts_with = "delete"
+    )]
+    pub interactive: bool,
 
-    /// Print shell completions to standard output
-    fn print_completions<G>(&self, gen: G)
-    where
-        G: Generator + std::fmt::Debug,
-    {
-        let mut cmd = Arguments::command();
-        let cmd_name = cmd.get_name().to_string();
-        let mut stdout = io::stdout();
+    /// Set the number of threads used to read and hash the files.
+    ///
+    /// Use a small value on spinning disks to avoid seek thrashing,
+    /// or a large value on NVMe drives.
+    ///
+    /// If 0, the number of threads is chosen by `--io_profile`.
+    #[arg(long("io_threads"), required = false, default_value_t = 0)]
+    pub io_threads: usize,
 
-        eprintln!("Generating completion file for {gen:?}...");
+    /// Schedule the reading of the files for spinning disks (hdd) or solid-state drives (ssd).
+    ///
+    /// hdd: one reader and the files are hashed in physical order (inode) to reduce seeks.
+    ///
+    /// ssd: twice as many readers as CPUs.
+    ///
+    /// auto: hdd if an input directory is on a rotational device (Linux only), otherwise ssd.
+    #[arg(long("io_profile"), value_enum, default_value_t = IoProfile::default())]
+    pub io_profile: IoProfile,
+
+    /// Set the maximum number of threads used to walk the directories and hash the files.
+    ///
+    /// By default, the number of CPUs.
+    ///
+    /// With `-j 1`, the search is fully serial (useful for debugging).
+    #[arg(
+        short('j'),
+        long("jobs"),
+        required = false,
+        value_name = "N",
+        value_parser = clap::value_parser!(u64).range(1..).map(|jobs| jobs as usize)
+    )]
+    pub jobs: Option<usize>,
+
+    /// Language of the messages, of the summary and of the Personal format.
+    ///
+    /// By default, the language of `LC_ALL`, `LC_MESSAGES` or `LANG` (English if unsupported).
+    ///
+    /// The keys of the JSON, YAML, XML and CSV reports are not translated.
+    #[arg(long("lang"), value_enum, required = false)]
+    pub lang: Option<Lang>,
+
+    /// Log the procedures of the search and their timings, up to the chosen level.
+    ///
+    /// The log is written to stderr or to `--log_file` (default level with a file: info).
+    #[arg(long("log_level"), value_enum, required = false)]
+    pub log_level: Option<LogLevel>,
+
+    /// Append the log to a file, so long scheduled scans can be audited.
+    #[arg(long("log_file"), required = false, value_name = "FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Write the log in JSON format (one object per line).
+    #[arg(long("log_json"), default_value_t = false)]
+    pub log_json: bool,
+
+    /// Avoid keeping files whose path matches the glob pattern.
+    ///
+    /// Files matching `--avoid` are kept only if all identical files match.
+    ///
+    /// This option can be specified multiple times:
+    ///
+    /// --avoid '**/Downloads/**' --avoid '*.bak'
+    #[arg(long("avoid"), required = false)]
+    pub avoid: Vec<String>,
+
+    /// Choose which file to keep in each group of identical files.
+    ///
+    /// Used with the `--delete`, `--hardlink` or `--reflink` options.
+    ///
+    /// Applied among the files with the same `--prefer` and `--avoid` priority.
+    #[arg(long("keep"), value_enum, default_value_t = KeepStrategy::default())]
+    pub keep: KeepStrategy,
+
+    /// Report the metadata of `--compare_metadata` that differ within each group
+    /// of identical files, instead of splitting the groups.
+    #[arg(
+        long("metadata_diff"),
+        default_value_t = false,
+        requires = "compare_metadata"
+    )]
+    pub metadata_diff: bool,
+
+    /// Memory-map files instead of using buffered reads to calculate the hash.
+    ///
+    /// Only files with at least `mmap_min_size` bytes are memory-mapped.
+    ///
+    /// Only supported by the blake3 algorithm: other algorithms are rejected.
+    ///
+    /// Falls back to buffered reads if the file cannot be memory-mapped.
+    #[arg(long("mmap"), default_value_t = false)]
+    pub mmap: bool,
+
+    /// Set the minimum file size to use memory-mapped I/O.
+    ///
+    /// Accepts bytes or units, like `--min_size`.
+    #[arg(
+        long("mmap_min_size"),
+        required = false,
+        default_value_t = 16 * 1024 * 1024,
+        value_parser = parse_size,
+    )]
+    pub mmap_min_size: u64,
+
+    /// Move identical files to a quarantine directory, keeping one file per group.
+    ///
+    /// The file to be kept is chosen with the `--keep` option.
+    ///
+    /// The directory structure of the moved files is preserved and a manifest
+    /// (fif-manifest.jsonl) is written in the quarantine directory,
+    /// so the files can be restored later.
+    #[arg(
+        long("move_to"),
+        required = false,
+        value_name = "DIR",
+        conflicts_with_all = ["delete", "hardlink", "reflink", "symlink", "relative_symlink", "interactive"]
+    )]
+    pub move_to: Option<PathBuf>,
+
+    /// With `--delete` (or `d` in `--interactive`), send the files to the trash
+    /// instead of removing them, so mistakes can be recovered.
+    ///
+    /// Linux: freedesktop.org trash. Windows: Recycle Bin. macOS: Trash.
+    #[arg(
+        long("trash"),
+        default_value_t = false,
+        conflicts_with_all = ["hardlink", "reflink", "symlink", "relative_symlink", "move_to"]
+    )]
+    pub trash: bool,
+
+    /// Do not load the configuration file.
+    ///
+    /// By default, `~/.config/find_duplicate_files/config.toml` stores default values
+    /// of some options (algorithm, exclude, result_format, min_size and max_size).
+    ///
+    /// Options given on the command line take precedence over the configuration file.
+    #[arg(long("no_config"), default_value_t = false)]
+    pub no_config: bool,
+
+    /// Stay on the filesystem of each input directory.
+    ///
+    /// Mount points (network mounts, backup drives, ...) are not traversed.
+    #[arg(long("one_file_system"), default_value_t = false)]
+    pub one_file_system: bool,
+
+    /// Omit hidden files (starts with '.'), otherwise search all files.
+    #[arg(short('o'), long("omit_hidden"), default_value_t = false)]
+    pub omit_hidden: bool,
+
+    /// Only search files owned by the user (name or numeric uid).
+    ///
+    /// Unix only. Files of remote sources are not filtered.
+    #[arg(long("owner"), required = false, value_name = "USER")]
+    pub owner: Option<String>,
+
+    /// Only search files of the group (name or numeric gid).
+    ///
+    /// Unix only. Files of remote sources are not filtered.
+    #[arg(long("group"), required = false, value_name = "GROUP")]
+    pub group: Option<String>,
+
+    /// Only search files that the current user can write to.
+    ///
+    /// On unix, the permission bits are checked against the effective user
+    /// and its groups. On Windows, read-only files are skipped.
+    #[arg(long("writable_only"), default_value_t = false)]
+    pub writable_only: bool,
+
+    /// Write the result (identical files and summary) to a file instead of stdout.
+    ///
+    /// The file is written atomically (temporary file + rename).
+    ///
+    /// If the filename ends in `.gz` or `.zst`, the result is compressed with gzip or zstd.
+    ///
+    /// The summary is still shown on the terminal.
+    #[arg(short('O'), long("output"), required = false)]
+    pub output: Option<PathBuf>,
+
+    /// Compress the result files (`--output`, `--report_dir` and `--csv_dir`) as they are written.
+    ///
+    /// The extension of the compression (`.gz` or `.zst`) is added to the filenames.
+    /// The result written to stdout is not compressed.
+    #[arg(long("compress"), value_enum, required = false)]
+    pub compress: Option<Compression>,
+
+    /// Save the result to a new timestamped file of DIR at each run (ex.: `fif-2024-01-31_183000.json`).
+    ///
+    /// The `latest` symbolic link of DIR points to the last report,
+    /// so scheduled scans accumulate a history.
+    ///
+    /// The extension is chosen by `--result_format`. The summary is still shown on the terminal.
+    #[arg(
+        long("report_dir"),
+        required = false,
+        value_name = "DIR",
+        conflicts_with = "output"
+    )]
+    pub report_dir: Option<PathBuf>,
+
+    /// Replace identical files with copy-on-write clones (reflinks) of a single file per group.
+    ///
+    /// The file to be kept is chosen with the `--keep` option.
+    ///
+    /// Unlike hard links, each clone keeps its own metadata.
+    ///
+    /// Supported filesystems: Btrfs, XFS, ZFS, APFS and ReFS.
+    #[arg(long("reflink"), default_value_t = false, conflicts_with_all = ["delete", "hardlink"])]
+    pub reflink: bool,
+
+    /// Replace identical files with symbolic links to a single file per group.
+    ///
+    /// The file to be kept is chosen with the `--keep` option.
+    ///
+    /// Unlike hard links, symbolic links can span filesystems. The contents are
+    /// compared byte by byte immediately before each replacement.
+    ///
+    /// Warning: removing the kept file breaks the links.
+    #[arg(
+        long("symlink"),
+        default_value_t = false,
+        conflicts_with_all = ["delete", "hardlink", "reflink", "move_to", "trash", "relative_symlink"]
+    )]
+    pub symlink: bool,
+
+    /// Same as `--symlink`, with links relative to their directory:
+    /// the links remain valid if the whole tree is moved.
+    #[arg(
+        long("relative_symlink"),
+        default_value_t = false,
+        conflicts_with_all = ["delete", "hardlink", "reflink", "move_to", "trash"]
+    )]
+    pub relative_symlink: bool,
+
+    /// Compare identical files byte by byte after hash matching.
+    ///
+    /// Hashes can theoretically collide: this option guarantees that
+    /// the reported files have exactly the same content, at the cost
+    /// of reading the files once more.
+    #[arg(long("paranoid"), default_value_t = false)]
+    pub paranoid: bool,
+
+    /// Report visually identical images (jpg, png, webp) instead of identical files.
+    ///
+    /// Images are compared by a perceptual hash (dHash): copies that differ
+    /// in metadata, recompression or resolution are grouped.
+    ///
+    /// Use `--max_distance` to set the tolerance. Other files are skipped.
+    ///
+    /// Similar images are not identical: the actions that remove files are not allowed.
+    ///
+    /// Requires the `perceptual` feature.
+    #[cfg(feature = "perceptual")]
+    #[arg(
+        long("perceptual"),
+        default_value_t = false,
+        conflicts_with_all = ["by_content", "dirs", "paranoid", "same_name_only", "unique", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "trash", "interactive", "script"]
+    )]
+    pub perceptual: bool,
+
+    /// Hash the files while the directories are still being walked.
+    ///
+    /// As soon as two files have the same size, they are hashed in background threads,
+    /// reducing the total time on deep directory trees with large files.
+    ///
+    /// Files that the prefilter would skip may also be hashed.
+    #[arg(long("pipeline"), default_value_t = false)]
+    pub pipeline: bool,
+
+    /// Set the number of bytes read from the beginning of each file to
+    /// quickly discard files with identical size but different contents.
+    ///
+    /// Only files whose first bytes have the same hash are entirely hashed.
+    ///
+    /// If 0, this prefilter is disabled and the files are entirely hashed.
+    #[arg(long("prefilter_bytes"), required = false, default_value_t = 1024)]
+    pub prefilter_bytes: usize,
+
+    /// Also hash the last bytes of each file in the prefilter.
+    ///
+    /// Useful for files that share a common header (ex.: media files).
+    #[arg(long("prefilter_tail"), default_value_t = false)]
+    pub prefilter_tail: bool,
+
+    /// Prefer keeping files whose path matches the glob pattern.
+    ///
+    /// This option can be specified multiple times:
+    ///
+    /// --prefer '/home/user/originals/**' --prefer '**/Photos/**'
+    #[arg(long("prefer"), required = false)]
+    pub prefer: Vec<String>,
+
+    /// Print only the paths of the identical files, each one terminated by NUL.
+    ///
+    /// Groups are separated by an empty path (two consecutive NULs).
+    ///
+    /// Filenames with newlines are safe: `find-identical-files --print0 | xargs -0 ls -l`
+    ///
+    /// The summary information is not printed (see `--summary`).
+    #[arg(
+        long("print0"),
+        default_value_t = false,
+        conflicts_with = "result_format"
+    )]
+    pub print0: bool,
+
+    /// Show a progress bar with files discovered, bytes hashed, throughput and ETA.
+    ///
+    /// Suppressed automatically when stdout is not a terminal.
+    #[arg(long("progress"), default_value_t = false)]
+    pub progress: bool,
+
+    /// Print the result in the chosen format.
+    #[arg(short('r'), long("result_format"), value_enum, default_value_t = ResultFormat::default())]
+    pub result_format: ResultFormat,
+
+    /// Report only identical files that also have the same file name.
+    ///
+    /// Useful for photo libraries, where copies with different names are intentional.
+    ///
+    /// Each group of identical files is split by file name (without the directory).
+    #[arg(long("same_name_only"), default_value_t = false)]
+    pub same_name_only: bool,
+
+    /// Write a script of the proposed actions instead of applying them.
+    ///
+    /// The script contains the commands (rm, ln, mv, ...) of the chosen action
+    /// (`--delete` by default) for each group of identical files,
+    /// so it can be reviewed and run manually.
+    ///
+    /// The script is written to the current directory (fif-actions.sh, .bat or .ps1).
+    ///
+    /// The sh script is run by bash. Paths that are not valid UTF-8 are quoted
+    /// byte by byte (`$'...'`); the bat and ps1 scripts refuse them.
+    ///
+    /// With `--reflink`, the sh script uses `cp --reflink=always` (GNU coreutils).
+    #[arg(
+        long("script"),
+        required = false,
+        value_enum,
+        conflicts_with = "interactive"
+    )]
+    pub script: Option<ScriptFormat>,
+
+    /// After the search, answer queries of other processes about the files found.
+    ///
+    /// Listen on a TCP port (on localhost) or on a unix socket:
+    ///
+    /// --serve 7878 or --serve /tmp/fif.sock
+    ///
+    /// Queries and answers are JSON objects, one per line:
+    ///
+    /// {"digest": "<hash>", "size": 1024} -> {"found": true, "algorithm": "Blake3", "paths": [...]}
+    #[arg(
+        long("serve"),
+        required = false,
+        value_name = "UNIX_SOCKET|PORT",
+        conflicts_with_all = ["delete", "move_to", "interactive", "watch", "summary"]
+    )]
+    pub serve: Option<String>,
+
+    /// Aggregate the wasted size by directory, at DEPTH levels below
+    /// the input directories (default: 1, the top-level directories).
+    ///
+    /// Ex.: 90% of the wasted size is under ~/Downloads
+    #[arg(
+        long("stats_by_dir"),
+        required = false,
+        num_args = 0..=1,
+        default_missing_value = "1",
+        value_name = "DEPTH",
+        value_parser = clap::value_parser!(u64).range(1..).map(|depth| depth as usize)
+    )]
+    pub stats_by_dir: Option<usize>,
+
+    /// Skip the first N groups of identical files (see `--limit`).
+    #[arg(long("skip"), required = false, default_value_t = 0, value_name = "N")]
+    pub skip: usize,
+
+    /// Skip the files that appear to be actively written: modified in the last
+    /// few seconds, locked by another process (`flock` on unix) or open for
+    /// writing (Windows).
+    ///
+    /// Busy files are not hashed nor changed by the actions, and are
+    /// reported in the "Skipped files (busy)" section.
+    #[arg(long("skip_busy"), default_value_t = false)]
+    pub skip_busy: bool,
+
+    /// Check that the search finds exactly the identical files of a synthetic tree.
+    ///
+    /// The tree is created in the temporary directory with known identical files
+    /// and edge cases: empty files, large sparse files, unicode names and deep nesting.
+    ///
+    /// Only `--algorithm` is used. Exit code 6 if the groups found are not the expected ones.
+    #[arg(
+        long("selftest"),
+        default_value_t = false,
+        conflicts_with_all = ["input_dir", "compare", "files_from", "bench", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub selftest: bool,
+
+    /// Write the applied actions to an undo journal (JSON Lines), in append mode.
+    ///
+    /// Each `--delete`, `--trash`, `--move_to`, `--hardlink`, `--reflink` or `--symlink` action
+    /// is recorded with the kept file, so it can be undone later with `--undo`.
+    #[arg(long("journal"), value_name = "FILE")]
+    pub journal: Option<PathBuf>,
+
+    /// Undo the actions recorded in a journal (`--journal`), in reverse order.
+    ///
+    /// Moved files are restored from the quarantine directory, hard and symbolic links
+    /// are replaced with copies of the kept file, deleted files are restored as copies
+    /// of the kept file and trashed files are restored from the trash (Linux, Windows).
+    /// Existing files are never overwritten.
+    ///
+    /// Exit code 7 if some actions could not be undone. Use `--dry_run` to preview.
+    #[arg(
+        long("undo"),
+        value_name = "JOURNAL",
+        conflicts_with_all = ["input_dir", "compare", "files_from", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "journal", "watch"]
+    )]
+    pub undo: Option<PathBuf>,
+
+    /// Report the files of identical size, without reading their contents.
+    ///
+    /// Quick triage on slow media: the groups are unverified candidates,
+    /// files of the same size may have different contents.
+    ///
+    /// With `--same_name_only`, the files must also have the same name.
+    ///
+    /// No action can be applied to these groups.
+    #[arg(
+        long("size_only"),
+        default_value_t = false,
+        conflicts_with_all = ["by_content", "content_only", "dirs", "unique", "find_copies_of", "find_digest", "paranoid", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script"]
+    )]
+    pub size_only: bool,
+
+    /// Sort the groups of identical files by one or more keys.
+    ///
+    /// Ties are broken by the next key: --sort_by count,size
+    #[arg(
+        short('s'),
+        long("sort_by"),
+        value_enum,
+        value_delimiter = ',',
+        default_value = "size"
+    )]
+    pub sort_by: Vec<SortKey>,
+
+    /// Sort the groups of identical files in ascending or descending order.
+    #[arg(long("order"), value_enum, default_value_t = SortOrder::default())]
+    pub order: SortOrder,
+
+    /// Write a machine-readable JSON summary to the file or, by default, to stderr.
+    ///
+    /// The summary contains: files scanned, groups found,
+    /// duplicate files, duplicate bytes and elapsed time.
+    ///
+    /// With this option, the process exits with code 2 if identical files are found,
+    /// so it can be used in scripts and CI checks.
+    #[arg(long("summary"), required = false, num_args = 0..=1, value_name = "FILE")]
+    pub summary: Option<Option<PathBuf>>,
+
+    /// Abort the search with exit code 3 if a file or directory cannot be read.
+    ///
+    /// By default, unreadable files (permission denied, broken symbolic links,
+    /// files removed during the search) are skipped and reported as skipped files.
+    #[arg(long("strict"), default_value_t = false)]
+    pub strict: bool,
+
+    /// Treat symbolic links to files as regular files.
+    ///
+    /// By default, symbolic links to files are ignored.
+    ///
+    /// Cannot be used with the actions (`--delete`, `--hardlink`, `--symlink`,
+    /// `--move_to`...): a link and its target are the same file, removing one
+    /// of them as a duplicate of the other would lose the data.
+    #[arg(
+        long("symlink_files"),
+        default_value_t = false,
+        conflicts_with_all = ["delete", "trash", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script"]
+    )]
+    pub symlink_files: bool,
+
+    /// Stop walking directories and hashing files after the duration (ex.: 90s, 10m, 1h).
+    ///
+    /// The files already hashed are compared and the result is marked as an incomplete scan.
+    #[arg(long("timeout"), required = false, value_name = "DURATION", value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    /// Limit the read bandwidth to the given number of megabytes (MiB) per second.
+    ///
+    /// Ex.: `--throttle 20` or `--throttle 0.5`, so that scheduled scans
+    /// do not interfere with interactive use. Combine with `--idle`.
+    #[arg(
+        long("throttle"),
+        required = false,
+        value_name = "MBps",
+        value_parser = parse_rate,
+        conflicts_with = "mmap"
+    )]
+    pub throttle: Option<f64>,
+
+    /// Show total execution time.
+    ///
+    /// Also show the time spent in each stage (walk, size grouping, partial hash,
+    /// full hash, output) and the number and size of the files it handled.
+    #[arg(short('t'), long("time"), default_value_t = false)]
+    pub time: bool,
+
+    /// Print only the N groups wasting the most space,
+    /// sorted by wasted size in descending order.
+    ///
+    /// The summary and the actions only take these groups into account.
+    #[arg(
+        long("top"),
+        required = false,
+        value_name = "N",
+        conflicts_with_all = ["sort_by", "order"]
+    )]
+    pub top: Option<usize>,
+
+    /// Report only the files with the same content as FILE.
+    ///
+    /// Only the files with the same size as FILE are hashed.
+    ///
+    /// FILE does not need to be inside the input directories.
+    #[arg(
+        long("find_copies_of"),
+        required = false,
+        value_name = "FILE",
+        conflicts_with_all = ["unique", "dirs", "compare", "by_content", "content_only"]
+    )]
+    pub find_copies_of: Option<PathBuf>,
+
+    /// Report only the files whose hash is the given digest.
+    ///
+    /// The digest must be calculated with `--algorithm`: its length is checked.
+    ///
+    /// With `--import_index`, the digests of the index are used without reading the files.
+    ///
+    /// Ex.: find the copies of a known file (incident response, provenance checks).
+    #[arg(
+        long("find_digest"),
+        required = false,
+        value_name = "HEX",
+        conflicts_with_all = ["find_copies_of", "unique", "dirs", "compare", "by_content", "content_only"]
+    )]
+    pub find_digest: Option<String>,
+
+    /// Continue an interrupted scan: the digests already calculated are not calculated again.
+    ///
+    /// The digests of the hashed files are saved to STATE_FILE every 30 seconds
+    /// and when the scan is interrupted (Ctrl+C, `--timeout`, `--max_files`).
+    /// After a crash, run the same command again to continue from the last checkpoint.
+    ///
+    /// A digest is reused only if the size and modification time of the file have not changed.
+    ///
+    /// The state file is removed when the scan is complete.
+    #[arg(long("resume"), required = false, value_name = "STATE_FILE")]
+    pub resume: Option<PathBuf>,
+
+    /// Report the files that have no identical file, instead of the identical files.
+    ///
+    /// Ex.: verify that a backup contains everything before wiping the source.
+    ///
+    /// Files of unique size are not hashed: their hash is not reported.
+    #[arg(
+        long("unique"),
+        default_value_t = false,
+        conflicts_with_all = ["dirs", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub unique: bool,
+
+    /// Report every file of SOURCE whose content has no identical file in BACKUP.
+    ///
+    /// Names and locations are not compared: only the size and the hash.
+    ///
+    /// Ex.: `find-identical-files --verify_subset ~/Photos /mnt/backup`
+    ///
+    /// Exit code 4 if files are missing.
+    #[arg(
+        long("verify_subset"),
+        required = false,
+        num_args = 2,
+        value_names = ["SOURCE", "BACKUP"],
+        conflicts_with_all = ["input_dir", "compare", "files_from", "dirs", "unique", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub verify_subset: Vec<PathBuf>,
+
+    /// Hash again the files of a checksum manifest and report the changed and missing files.
+    ///
+    /// Accepts the manifests of `--write_manifest`, `sha256sum` and `b3sum`,
+    /// hashed with the same `--algorithm`.
+    ///
+    /// Exit code 5 if files were changed or are missing.
+    #[arg(
+        long("verify_manifest"),
+        required = false,
+        value_name = "FILE",
+        conflicts_with_all = ["input_dir", "compare", "files_from", "dirs", "unique", "verify_subset", "write_manifest", "import_index", "delete", "hardlink", "reflink", "symlink", "relative_symlink", "move_to", "interactive", "script", "watch"]
+    )]
+    pub verify_manifest: Option<PathBuf>,
+
+    /// Show intermediate runtime messages.
+    #[arg(short('v'), long("verbose"), default_value_t = false)]
+    pub verbose: bool,
+
+    /// After the search, watch the input directories and report new identical files.
+    ///
+    /// Files created or modified are compared with the known files
+    /// until the process is interrupted (Ctrl+C).
+    ///
+    /// With `--export_index`, the digest index is updated incrementally.
+    #[arg(
+        long("watch"),
+        default_value_t = false,
+        conflicts_with_all = ["interactive", "summary"]
+    )]
+    pub watch: bool,
+
+    /// Wipe (Clear) the terminal screen before listing the identical files.
+    ///
+    /// On Linux, to clear use the command:
+    ///
+    /// tput reset
+    ///
+    /// Unlike the clear command, the reset command does more than just clear the terminal screen.
+    ///
+    /// It also resets the terminal to its default settings.
+    #[arg(short('w'), long("wipe_terminal"), default_value_t = false)]
+    // action = ArgAction::SetTrue
+    pub wipe_terminal: bool,
+
+    /// Write a checksum manifest of all files, compatible with `sha256sum -c` and `b3sum -c`.
+    ///
+    /// One line per file: `<digest>  <path>`, with the digest of `--algorithm`.
+    ///
+    /// Check the files later with `--verify_manifest`.
+    #[arg(long("write_manifest"), required = false, value_name = "FILE")]
+    pub write_manifest: Option<PathBuf>,
+
+    /// Set the output directory for the XLSX file (fif.xlsx).
+    ///
+    /// By default, use the current directory.
+    ///
+    /// That is, export identical file information to XLSX format.
+    ///
+    /// XLSX: Excel file
+    #[arg(short('x'), long("xlsx_dir"), required = false)]
+    pub xlsx_dir: Option<PathBuf>,
+
+    /// Subcommand (optional)
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+impl Arguments {
+    /// Build Arguments struct
+    pub fn build() -> MyResult<Arguments> {
+        let matches = Arguments::get_command().get_matches();
+        Arguments::check_action_options(&matches).unwrap_or_else(|error| error.exit());
+
+        let mut args: Arguments =
+            Arguments::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+
+        // Options not given on the command line are read from the configuration file.
+        if !args.no_config {
+            Config::load()?.apply(&mut args, &matches);
+        }
+
+        args.apply_command()?;
+
+        // `-e` is a shortcut for `--path_style canonical`.
+        if args.extended_path {
+            args.path_style = PathStyle::Canonical;
+        }
+
+        if let Some(generator) = args.generator {
+            args.print_completions(generator);
+        }
+
+        if args.wipe_terminal {
+            clear_terminal_screen();
+        }
+
+        args.validate()?;
+
+        /*
+        // validate simultaneously.
+        std::thread::scope(|s| {
+            s.spawn(|| -> MyResult<()> {
+                args.validate_range_size()?;
+                Ok(())
+            });
+
+            s.spawn(|| -> MyResult<()> {
+                args.validate_range_depth()?;
+                Ok(())
+            });
+
+            s.spawn(|| -> MyResult<()> {
+                args.validate_range_frequency()?;
+                Ok(())
+            });
+
+            s.spawn(|| -> MyResult<()> {
+                args.validate_dir_path()?;
+                Ok(())
+            });
+        });
+        */
+
+        Ok(args)
+    }
+
+    /// Get the command line parser: the options are shared by all subcommands.
+    fn get_command() -> clap::Command {
+        Arguments::command().mut_args(|arg| arg.global(true))
+    }
+
+    /**
+    Reject the actions that change files (`--delete`, `--move_to`...)
+    with the subcommands other than `clean` (ex.: `scan --delete`).
+
+    The options are global: the parser accepts them with any subcommand.
+    */
+    fn check_action_options(matches: &ArgMatches) -> Result<(), clap::Error> {
+        let Some(subcommand) = matches.subcommand_name().filter(|&name| name != "clean") else {
+            return Ok(());
+        };
+
+        let given = ACTION_OPTIONS
+            .iter()
+            .find(|&&id| matches.value_source(id) == Some(ValueSource::CommandLine));
+
+        match given {
+            Some(id) => Err(Arguments::get_command().error(
+                ErrorKind::ArgumentConflict,
+                format!("--{id} changes files: use `clean` instead of `{subcommand}`"),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /**
+    Apply the subcommand to the options.
+
+    `clean` requires an action that changes files.
+    The other subcommands are shortcuts for the equivalent options.
+    */
+    fn apply_command(&mut self) -> MyResult<()> {
+        let has_action = self.get_action().is_some() || self.interactive || self.script.is_some();
+
+        match self.command.clone() {
+            Some(Commands::Clean) if !has_action => {
+                eprintln!("fn apply_command()");
+                eprintln!("`clean` requires --delete, --hardlink, --reflink, --symlink, --move_to, --interactive or --script.");
+                return Err("no action chosen with `clean`".into());
+            }
+            None | Some(Commands::Scan | Commands::Clean) => {}
+            Some(Commands::Verify { target }) => match target {
+                VerifyTarget::Subset { source, backup } => {
+                    self.verify_subset = vec![source, backup]
+                }
+                VerifyTarget::Manifest { file } => self.verify_manifest = Some(file),
+            },
+            Some(Commands::Diff { old, new }) => self.diff = vec![old, new],
+            Some(Commands::Cache { file }) => self.export_index = Some(file),
+            Some(Commands::Bench) => self.bench = true,
+            Some(Commands::Selftest) => self.selftest = true,
+            Some(Commands::Undo { journal }) => self.undo = Some(journal),
+            Some(Commands::Completions { shell }) => self.generator = Some(shell),
+        }
+
+        Ok(())
+    }
+
+    /// Check if only the digest index is saved (`cache` subcommand).
+    pub fn is_cache_only(&self) -> bool {
+        matches!(self.command, Some(Commands::Cache { .. }))
+    }
+
+    /// Print shell completions to standard output
+    fn print_completions<G>(&self, gen: G)
+    where
+        G: Generator + std::fmt::Debug,
+    {
+        let mut cmd = Arguments::get_command();
+        let cmd_name = cmd.get_name().to_string();
+        let mut stdout = io::stdout();
+
+        eprintln!("Generating completion file for {gen:?}...");
         generate(gen, &mut cmd, cmd_name, &mut stdout);
         process::exit(1);
     }
 
+    /// Get the action to be applied to identical files, if any.
+    pub fn get_action(&self) -> Option<Action> {
+        if self.delete && self.trash {
+            Some(Action::Trash)
+        } else if self.delete {
+            Some(Action::Delete)
+        } else if self.hardlink {
+            Some(Action::Hardlink)
+        } else if self.reflink {
+            Some(Action::Reflink)
+        } else if self.symlink {
+            Some(Action::Symlink)
+        } else if self.relative_symlink {
+            Some(Action::RelativeSymlink)
+        } else if self.move_to.is_some() {
+            Some(Action::Move)
+        } else if self.script.is_some() {
+            Some(Action::Delete)
+        } else {
+            None
+        }
+    }
+
+    /// Check if procedure 2 (prefilter) is enabled.
+    ///
+    /// The first bytes of the files of imported indexes are not available.
+    pub fn prefilter_is_enabled(&self) -> bool {
+        self.prefilter_bytes > 0 && self.import_index.is_empty()
+    }
+
+    /// Get the minimum file size.
+    ///
+    /// Empty files are skipped, unless `--include_empty` is chosen.
+    pub fn get_min_size(&self) -> u64 {
+        if self.include_empty {
+            self.min_size
+        } else {
+            self.min_size.max(1)
+        }
+    }
+
     /// Get the size range (inclusive)
     ///
     /// min_size <= size <= max_size
     pub fn size_is_included(&self, size: u64) -> bool {
-        size >= self.min_size && size <= self.max_size
+        size >= self.get_min_size() && size <= self.max_size
+    }
+
+    /// Check if the size allocated on disk reaches `--min_allocated`.
+    ///
+    /// Files of unknown allocated size (not unix) are included.
+    pub fn allocated_is_included(&self, allocated: Option<u64>) -> bool {
+        allocated.map_or(true, |allocated| allocated >= self.min_allocated)
+    }
+
+    /// Check if the modification time is in the time window
+    /// (`--newer_than` and `--older_than`).
+    ///
+    /// newer_than < modified < older_than
+    pub fn time_is_included(&self, modified: Option<SystemTime>) -> bool {
+        if self.newer_than.is_none() && self.older_than.is_none() {
+            return true;
+        }
+
+        let Some(modified) = modified else {
+            return false;
+        };
+
+        self.newer_than.map_or(true, |time| modified > time)
+            && self.older_than.map_or(true, |time| modified < time)
+    }
+
+    /// Check if the file is in the reference directory (`--compare`).
+    pub fn is_reference(&self, path: &Path) -> bool {
+        self.compare
+            .as_ref()
+            .is_some_and(|reference| path.starts_with(reference))
+    }
+
+    /// With `--compare`, a group must contain files of the reference directory
+    /// and files of the target directories.
+    pub fn compare_is_included(&self, paths: &[PathBuf]) -> bool {
+        if self.compare.is_none() {
+            return true;
+        }
+
+        let num_reference: usize = paths.iter().filter(|path| self.is_reference(path)).count();
+
+        num_reference > 0 && num_reference < paths.len()
+    }
+
+    /// Check if the wasted size of a group reaches `--min_wasted`.
+    pub fn wasted_is_included(&self, wasted_size: usize) -> bool {
+        wasted_size as u64 >= self.min_wasted
+    }
+
+    /// Get the frequency range (inclusive)
+    ///
+    /// min_frequency <= frequency <= max_frequency
+    pub fn frequency_is_included(&self, frequency: usize) -> bool {
+        frequency as u64 >= self.min_frequency && frequency as u64 <= self.max_frequency
+    }
+
+    /// Get the kind of search (ex.: identical files, identical directories).
+    pub fn get_search_mode(&self) -> &'static str {
+        #[cfg(feature = "perceptual")]
+        if self.perceptual {
+            return "visually identical images (--perceptual)";
+        }
+
+        #[cfg(feature = "audio")]
+        if self.audio {
+            return "same songs (--audio)";
+        }
+
+        if self.dirs {
+            "identical directories (--dirs)"
+        } else if self.content_only {
+            "identical payloads (--content_only)"
+        } else if self.by_content {
+            "identical files (--by_content)"
+        } else {
+            "identical files"
+        }
+    }
+
+    /// Get the scan parameters shown in the reports (name, value).
+    ///
+    /// Options with default values are omitted.
+    pub fn get_scan_options(&self) -> Vec<(&'static str, String)> {
+        let mut options: Vec<(&'static str, String)> = Vec::new();
+
+        let input: String = match &self.files_from {
+            Some(source) => format!("list of files: {}", source.display()),
+            None if self.input_dir.is_empty() => ".".to_string(),
+            None => self
+                .input_dir
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        options.push(("Input", input));
+
+        if let Some(reference) = &self.compare {
+            options.push(("Reference directory", reference.display().to_string()));
+        }
+
+        options.push(("Search", self.get_search_mode().to_string()));
+        options.push(("Hashing algorithm", self.algorithm.to_string()));
+        options.push(("Minimum size", format!("{} bytes", self.get_min_size())));
+
+        if self.max_size != u64::MAX {
+            options.push(("Maximum size", format!("{} bytes", self.max_size)));
+        }
+
+        options.push(("Minimum frequency", self.min_frequency.to_string()));
+
+        if self.max_frequency != u64::MAX {
+            options.push(("Maximum frequency", self.max_frequency.to_string()));
+        }
+
+        if self.min_wasted > 0 {
+            options.push(("Minimum wasted size", format!("{} bytes", self.min_wasted)));
+        }
+
+        if !self.include.is_empty() {
+            options.push(("Include", self.include.join(", ")));
+        }
+
+        if !self.exclude.is_empty() {
+            options.push(("Exclude", self.exclude.join(", ")));
+        }
+
+        options
+    }
+
+    /// Validate the options: used by the command line and by [`DuplicateFinder`](crate::DuplicateFinder).
+    pub(crate) fn validate(&mut self) -> MyResult<()> {
+        self.validate_range_size()?;
+        self.validate_range_depth()?;
+        self.validate_range_frequency()?;
+        self.validate_range_time()?;
+        self.validate_dir_path()?;
+        self.validate_file_path()?;
+        self.validate_digest()?;
+        self.validate_mmap()?;
+
+        // Paths are compared with the full path of the reference directory.
+        if let Some(reference) = &self.compare {
+            self.compare = Some(fs::canonicalize(reference)?);
+        }
+
+        Ok(())
     }
 
     fn validate_range_size(&self) -> MyResult<()> {
@@ -329,7 +1870,21 @@ impl Arguments {
             eprintln!("max_size cannot be less than min_size");
             eprintln!("min_size: {}", self.min_size);
             eprintln!("max_size: {}", self.max_size);
-            process::exit(1);
+            return Err("max_size cannot be less than min_size".into());
+        }
+
+        Ok(())
+    }
+
+    fn validate_range_time(&self) -> MyResult<()> {
+        if let (Some(newer_than), Some(older_than)) = (self.newer_than, self.older_than) {
+            if newer_than >= older_than {
+                eprintln!("fn validate_range_time()");
+                eprintln!("older_than must be later than newer_than");
+                eprintln!("newer_than: {newer_than:?}");
+                eprintln!("older_than: {older_than:?}");
+                return Err("older_than must be later than newer_than".into());
+            }
         }
 
         Ok(())
@@ -341,7 +1896,7 @@ impl Arguments {
             eprintln!("max_depth cannot be less than min_depth");
             eprintln!("min_depth: {}", self.min_depth);
             eprintln!("max_depth: {}", self.max_depth);
-            process::exit(1);
+            return Err("max_depth cannot be less than min_depth".into());
         }
 
         Ok(())
@@ -353,7 +1908,7 @@ impl Arguments {
             eprintln!("max_frequency cannot be less than min_frequency");
             eprintln!("min_frequency: {}", self.min_frequency);
             eprintln!("max_frequency: {}", self.max_frequency);
-            process::exit(1);
+            return Err("max_frequency cannot be less than min_frequency".into());
         }
 
         Ok(())
@@ -361,19 +1916,24 @@ impl Arguments {
 
     /// Validate directory paths
     fn validate_dir_path(&self) -> MyResult<()> {
-        let paths = [&self.input_dir, &self.csv_dir, &self.xlsx_dir];
-
-        for dir_path in paths.into_iter().flatten() {
+        let paths = self
+            .input_dir
+            .iter()
+            .chain(self.compare.iter())
+            .chain(self.csv_dir.iter())
+            .chain(self.xlsx_dir.iter());
+
+        for dir_path in paths {
             if !std::path::Path::new(&dir_path).try_exists()? {
                 eprintln!("fn validate_dir_path()");
                 eprintln!("The path {dir_path:?} was not found!");
-                process::exit(1);
+                return Err(format!("the path {dir_path:?} was not found").into());
             };
 
             if !dir_path.is_dir() {
                 eprintln!("fn validate_dir_path()");
                 eprintln!("{dir_path:?} is not a directory!");
-                process::exit(1);
+                return Err(format!("{dir_path:?} is not a directory").into());
             }
 
             // Check if able to write inside directory
@@ -383,10 +1943,398 @@ impl Arguments {
                 eprintln!("fn validate_dir_path()");
                 eprintln!("No write permission");
                 eprintln!("{dir_path:?} is readonly!");
-                process::exit(1);
+                return Err(format!("{dir_path:?} is readonly").into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_file_path(&self) -> MyResult<()> {
+        if let Some(file_path) = &self.find_copies_of {
+            if !file_path.is_file() {
+                eprintln!("fn validate_file_path()");
+                eprintln!("{file_path:?} is not a file!");
+                return Err(format!("{file_path:?} is not a file").into());
             }
         }
 
         Ok(())
     }
+
+    fn validate_mmap(&self) -> MyResult<()> {
+        if self.mmap && self.algorithm != Algorithm::Blake3 {
+            eprintln!("fn validate_mmap()");
+            eprintln!("mmap is only supported by the blake3 algorithm");
+            eprintln!("algorithm: {}", self.algorithm);
+            return Err(
+                format!("mmap is not supported by the {} algorithm", self.algorithm).into(),
+            );
+        }
+
+        Ok(())
+    }
+
+    fn validate_digest(&mut self) -> MyResult<()> {
+        if let Some(digest) = &self.find_digest {
+            match self.algorithm.parse_digest(digest) {
+                Ok(digest) => self.find_digest = Some(digest),
+                Err(why) => {
+                    eprintln!("fn validate_digest()");
+                    eprintln!("Invalid digest: {digest:?}");
+                    eprintln!("Error: {why}");
+                    return Err(format!("invalid digest: {why}").into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Parse a size in bytes with an optional unit (case insensitive).
+
+Binary units: `K`, `M`, `G`, `T`, `KiB`, `MiB`, `GiB` and `TiB` (powers of 1024).
+
+Decimal units: `KB`, `MB`, `GB` and `TB` (powers of 1000).
+
+Ex.: `4096`, `10M`, `2GiB`, `1.5GB`, `100 KB`.
+*/
+pub(crate) fn parse_size(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+
+    let error = || {
+        format!("invalid size {value:?}: expected bytes or a number with a unit (ex.: 10M, 2GiB)")
+    };
+
+    let index: usize = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(index);
+
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kib" => 1 << 10,
+        "m" | "mib" => 1 << 20,
+        "g" | "gib" => 1 << 30,
+        "t" | "tib" => 1 << 40,
+        "kb" => 1_000,
+        "mb" => 1_000_000,
+        "gb" => 1_000_000_000,
+        "tb" => 1_000_000_000_000,
+        _ => return Err(error()),
+    };
+
+    // Integers are parsed exactly, fractions (ex.: 1.5G) are rounded.
+    if let Ok(integer) = number.parse::<u64>() {
+        return integer.checked_mul(multiplier).ok_or_else(error);
+    }
+
+    let size: f64 = number.parse::<f64>().map_err(|_| error())? * multiplier as f64;
+
+    if size.is_finite() && size < u64::MAX as f64 {
+        Ok(size.round() as u64)
+    } else {
+        Err(error())
+    }
+}
+
+/// Parse a similarity between 0.0 and 1.0 (`--min_similarity`).
+#[cfg(feature = "audio")]
+fn parse_similarity(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(similarity) if (0.0..=1.0).contains(&similarity) => Ok(similarity),
+        _ => Err(format!(
+            "invalid similarity {value:?}: expected a number from 0.0 to 1.0"
+        )),
+    }
+}
+
+/// Parse a positive number of megabytes per second (`--throttle`).
+fn parse_rate(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!(
+            "invalid rate {value:?}: expected a positive number of megabytes per second"
+        )),
+    }
+}
+
+/**
+Parse a date or a duration before now.
+
+Dates (UTC): `2024-01-31`, `2024-01-31 18:30:00` or `2024-01-31T18:30:00`.
+
+Durations: a number followed by a unit: `90s`, `45m`, `12h`, `30d`, `2w` or `1y` (365 days).
+*/
+fn parse_time(value: &str) -> Result<SystemTime, String> {
+    let value = value.trim();
+
+    if let Some(time) = parse_date(value) {
+        return Ok(time);
+    }
+
+    let error =
+        || format!("invalid time {value:?}: expected a date (YYYY-MM-DD) or a duration (ex.: 30d)");
+
+    let duration: Duration = parse_duration(value).map_err(|_| error())?;
+
+    SystemTime::now().checked_sub(duration).ok_or_else(error)
+}
+
+/**
+Parse a duration: a number followed by a unit.
+
+Units: `s`, `m`, `h`, `d`, `w` or `y` (365 days). Ex.: `90s`, `45m`, `12h`.
+*/
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+
+    let error =
+        || format!("invalid duration {value:?}: expected a number and a unit (ex.: 90s, 45m)");
+
+    let index: usize = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(error)?;
+    let (number, unit) = value.split_at(index);
+    let number: u64 = number.parse().map_err(|_| error())?;
+
+    let seconds: u64 = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        "y" => 365 * 24 * 60 * 60,
+        _ => return Err(error()),
+    };
+
+    number
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(error)
+}
+
+/// Parse a date (UTC) with an optional time: `YYYY-MM-DD[( |T)HH:MM[:SS]]`.
+fn parse_date(value: &str) -> Option<SystemTime> {
+    let (date, time) = match value.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+
+    let mut date = date.split('-').map(|field| field.parse::<u32>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    if date.next().is_some()
+        || !(1..=12).contains(&month)
+        || !(1..=days_in_month(year, month)).contains(&day)
+    {
+        return None;
+    }
+
+    let mut seconds: u64 = 0;
+
+    if let Some(time) = time {
+        let fields: Vec<u64> = time
+            .split(':')
+            .map(|field| field.parse::<u64>().ok())
+            .collect::<Option<Vec<u64>>>()?;
+
+        let (hour, minute, second) = match fields[..] {
+            [hour, minute] => (hour, minute, 0),
+            [hour, minute, second] => (hour, minute, second),
+            _ => return None,
+        };
+
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+
+        seconds = hour * 3600 + minute * 60 + second;
+    }
+
+    let days: u64 = days_since_unix_epoch(year, month, day)?;
+
+    SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(days * 24 * 60 * 60 + seconds))
+}
+
+/// Number of days of the month (February has 29 days in leap years).
+fn days_in_month(year: u32, month: u32) -> u32 {
+    let is_leap_year: bool = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+
+    match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days from 1970-01-01 to the date (proleptic Gregorian calendar).
+///
+/// <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+fn days_since_unix_epoch(year: u32, month: u32, day: u32) -> Option<u64> {
+    let year: i64 = i64::from(year) - i64::from(month <= 2);
+    let month: i64 = i64::from(month);
+    let day: i64 = i64::from(day);
+
+    let era: i64 = year.div_euclid(400);
+    let year_of_era: i64 = year - era * 400;
+    let day_of_year: i64 = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era: i64 = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    u64::try_from(era * 146097 + day_of_era - 719468).ok()
+}
+
+#[cfg(test)]
+mod test_args {
+    use super::*;
+
+    /// cargo test -- --show-output test_parse_size
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("0"), Ok(0));
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("10K"), Ok(10 * 1024));
+        assert_eq!(parse_size("10m"), Ok(10 * 1024 * 1024));
+        assert_eq!(parse_size("2GiB"), Ok(2 * 1024 * 1024 * 1024));
+        assert_eq!(parse_size("1T"), Ok(1 << 40));
+        assert_eq!(parse_size("10KB"), Ok(10_000));
+        assert_eq!(parse_size("100 mb"), Ok(100_000_000));
+        assert_eq!(parse_size("1.5GB"), Ok(1_500_000_000));
+        assert_eq!(parse_size("0.5K"), Ok(512));
+        assert_eq!(parse_size(&u64::MAX.to_string()), Ok(u64::MAX));
+
+        for invalid in ["", "K", "10X", "-1", "1.2.3M", "20000000T"] {
+            assert!(parse_size(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    /// cargo test -- --show-output test_parse_duration
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(parse_duration(" 1h "), Ok(Duration::from_secs(3600)));
+
+        for invalid in ["", "10", "m", "10x", "-5s"] {
+            assert!(parse_duration(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    /// cargo test -- --show-output test_parse_time
+    #[test]
+    fn test_parse_time() {
+        let from_secs = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+
+        assert_eq!(parse_time("1970-01-01"), Ok(from_secs(0)));
+        assert_eq!(parse_time("2000-03-01"), Ok(from_secs(951_868_800)));
+        assert_eq!(parse_time("2024-02-29 12:30"), Ok(from_secs(1_709_209_800)));
+        assert_eq!(
+            parse_time("2024-02-29T12:30:15"),
+            Ok(from_secs(1_709_209_815))
+        );
+
+        let time = parse_time("30d").unwrap();
+        let now = SystemTime::now();
+        let elapsed = now.duration_since(time).unwrap().as_secs();
+        assert!((30 * 24 * 60 * 60..30 * 24 * 60 * 60 + 60).contains(&elapsed));
+
+        for invalid in [
+            "",
+            "30",
+            "d",
+            "30x",
+            "2024-13-01",
+            "2024-02-31",
+            "2023-02-29",
+            "2100-02-29",
+            "2024-04-31",
+            "2024-01-01 25:00",
+            "1969-12-31",
+        ] {
+            assert!(parse_time(invalid).is_err(), "{invalid:?}");
+        }
+    }
+
+    /// cargo test -- --show-output test_subcommands
+    #[test]
+    fn test_subcommands() -> MyResult<()> {
+        let parse = |args: &[&str]| -> MyResult<Arguments> {
+            let matches = Arguments::get_command().try_get_matches_from(args)?;
+            Arguments::check_action_options(&matches)?;
+            let mut arguments = Arguments::from_arg_matches(&matches)?;
+            arguments.apply_command()?;
+            Ok(arguments)
+        };
+
+        // Options are accepted after the subcommand.
+        let arguments = parse(&["fif", "scan", "-i", "/tmp", "--min_size", "1K"])?;
+        assert!(matches!(arguments.command, Some(Commands::Scan)));
+        assert_eq!(arguments.input_dir, [PathBuf::from("/tmp")]);
+        assert_eq!(arguments.min_size, 1024);
+
+        let arguments = parse(&["fif", "verify", "subset", "src", "backup"])?;
+        assert_eq!(
+            arguments.verify_subset,
+            [PathBuf::from("src"), PathBuf::from("backup")]
+        );
+
+        // Only `clean` changes files.
+        assert!(parse(&["fif", "scan", "--delete"]).is_err());
+        assert!(parse(&["fif", "--trash", "scan"]).is_err());
+        assert!(parse(&["fif", "scan", "--move_to", "/tmp"]).is_err());
+        assert!(parse(&["fif", "verify", "subset", "src", "backup", "--delete"]).is_err());
+        assert!(parse(&["fif", "clean"]).is_err());
+        assert!(parse(&["fif", "clean", "--delete"]).is_ok());
+        assert!(parse(&["fif", "--delete"]).is_ok());
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_symlink_files_actions
+    #[test]
+    fn test_symlink_files_actions() {
+        // Symbolic links are reported, never used as kept files nor changed.
+        for action in [
+            "--delete",
+            "--trash",
+            "--hardlink",
+            "--symlink",
+            "--interactive",
+        ] {
+            let result = Arguments::try_parse_from(["fif", "--symlink_files", action]);
+            assert!(result.is_err(), "{action}");
+        }
+        let result = Arguments::try_parse_from(["fif", "--symlink_files", "--move_to", "/tmp"]);
+        assert!(result.is_err());
+        assert!(Arguments::try_parse_from(["fif", "--symlink_files"]).is_ok());
+    }
+
+    /// cargo test --features perceptual -- --show-output test_perceptual_actions
+    #[cfg(feature = "perceptual")]
+    #[test]
+    fn test_perceptual_actions() {
+        // Similar images are reported, never removed.
+        for action in ["--delete", "--trash", "--interactive"] {
+            let result = Arguments::try_parse_from(["fif", "--perceptual", action]);
+            assert!(result.is_err(), "{action}");
+        }
+        let result = Arguments::try_parse_from(["fif", "--perceptual", "--move_to", "/tmp"]);
+        assert!(result.is_err());
+    }
+
+    /// cargo test --features audio -- --show-output test_audio_actions
+    #[cfg(feature = "audio")]
+    #[test]
+    fn test_audio_actions() {
+        // Similar songs are reported, never removed.
+        for action in ["--delete", "--trash", "--interactive"] {
+            let result = Arguments::try_parse_from(["fif", "--audio", action]);
+            assert!(result.is_err(), "{action}");
+        }
+        let result = Arguments::try_parse_from(["fif", "--audio", "--move_to", "/tmp"]);
+        assert!(result.is_err());
+    }
 }
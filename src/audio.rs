@@ -0,0 +1,301 @@
+use crate::{
+    cluster::{get_clusters, get_similar_group},
+    log_procedure, run_io, Arguments, FileInfo, GroupInfo, MyResult,
+};
+use rayon::prelude::*;
+use rusty_chromaprint::{Configuration, Fingerprinter};
+use std::{fs::File, io, path::Path, time::Instant};
+use symphonia::core::{
+    audio::SampleBuffer, codecs::DecoderOptions, errors::Error as AudioError,
+    formats::FormatOptions, io::MediaSourceStream, meta::MetadataOptions, probe::Hint,
+};
+
+/// Extensions of the audio files compared with `--audio`.
+const AUDIO_EXTENSIONS: [&str; 7] = ["mp3", "flac", "ogg", "wav", "m4a", "aac", "opus"];
+
+/// Only the beginning of each song is fingerprinted (in seconds), as `fpcalc` does.
+const MAX_DURATION: u64 = 120;
+
+/// Songs whose durations differ by more than this (in seconds) are not compared.
+const MAX_DURATION_DIFFERENCE: f64 = 2.0;
+
+/// Maximum shift between two fingerprints (in fingerprint items, about 1 second).
+const MAX_OFFSET: usize = 8;
+
+/// Audio fingerprint of a song.
+#[derive(Debug, Clone)]
+pub struct AudioFingerprint {
+    /// Chromaprint items (32 bits each)
+    pub items: Vec<u32>,
+    /// Duration of the song (in seconds)
+    pub duration: f64,
+}
+
+/// Check if the file is an audio file supported by `--audio`.
+pub fn is_audio(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            AUDIO_EXTENSIONS
+                .iter()
+                .any(|audio| extension.eq_ignore_ascii_case(audio))
+        })
+}
+
+/**
+Decode the song and compute its chromaprint-style fingerprint.
+
+The same song in different encodings (ex.: mp3 and flac)
+has similar fingerprints.
+
+<https://oxygene.sk/2011/01/how-does-chromaprint-work/>
+*/
+pub fn get_fingerprint(path: &Path) -> MyResult<AudioFingerprint> {
+    let file = File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|extension| extension.to_str()) {
+        hint.with_extension(extension);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = format.default_track().ok_or("no audio track")?;
+    let track_id = track.id;
+    let sample_rate: u32 = track
+        .codec_params
+        .sample_rate
+        .ok_or("unknown sample rate")?;
+    let channels: u32 = track
+        .codec_params
+        .channels
+        .ok_or("unknown number of channels")?
+        .count() as u32;
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let config = Configuration::preset_test2();
+    let mut printer = Fingerprinter::new(&config);
+    printer
+        .start(sample_rate, channels)
+        .map_err(|error| format!("{error:?}"))?;
+
+    let max_samples: u64 = MAX_DURATION * u64::from(sample_rate) * u64::from(channels);
+    let mut num_samples: u64 = 0;
+    let mut buffer: Option<SampleBuffer<i16>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(AudioError::IoError(error)) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                break
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        match decoder.decode(&packet) {
+            Ok(decoded) => {
+                let buffer = buffer.get_or_insert_with(|| {
+                    SampleBuffer::<i16>::new(decoded.capacity() as u64, *decoded.spec())
+                });
+                buffer.copy_interleaved_ref(decoded);
+
+                // Only the beginning of the song is fingerprinted.
+                if num_samples < max_samples {
+                    printer.consume(buffer.samples());
+                }
+                num_samples += buffer.samples().len() as u64;
+            }
+            // Corrupted packets are skipped.
+            Err(AudioError::DecodeError(_)) => continue,
+            Err(error) => return Err(error.into()),
+        }
+    }
+
+    printer.finish();
+
+    Ok(AudioFingerprint {
+        items: printer.fingerprint().to_vec(),
+        duration: num_samples as f64 / f64::from(sample_rate * channels),
+    })
+}
+
+/**
+Get the similarity of two fingerprints, from 0.0 to 1.0.
+
+The fingerprints are compared bit by bit, with small shifts
+(encoder delays): the best alignment is chosen.
+
+Unrelated songs have a similarity of about 0.5.
+*/
+pub fn get_similarity(a: &[u32], b: &[u32]) -> f64 {
+    let min_items: usize = a.len().min(b.len()) / 2;
+    let mut best: f64 = 0.0;
+
+    for offset in 0..=MAX_OFFSET {
+        for (x, y) in [(a, b), (b, a)] {
+            let pairs: Vec<(&u32, &u32)> = x.iter().zip(y.iter().skip(offset)).collect();
+
+            if pairs.is_empty() || pairs.len() < min_items {
+                continue;
+            }
+
+            let errors: u32 = pairs.iter().map(|(x, y)| (*x ^ *y).count_ones()).sum();
+            let similarity: f64 = 1.0 - f64::from(errors) / (32.0 * pairs.len() as f64);
+            best = best.max(similarity);
+        }
+    }
+
+    best
+}
+
+/**
+Group the same songs in different encodings (`--audio`).
+
+Songs with similar durations whose fingerprints have a similarity
+of at least `--min_similarity` are grouped (transitively).
+
+The largest file is assumed to be kept (usually the lossless encoding).
+*/
+pub fn get_audio_groups(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    let songs: Vec<(&FileInfo, AudioFingerprint)> = run_io(arguments, || {
+        all_files
+            .par_iter() // rayon parallel iterator
+            .filter(|file_info| is_audio(&file_info.path))
+            .filter_map(|file_info| match get_fingerprint(&file_info.path) {
+                Ok(fingerprint) if !fingerprint.items.is_empty() => Some((file_info, fingerprint)),
+                Ok(_) => None,
+                Err(error) => {
+                    eprintln!("fn get_audio_groups()");
+                    eprintln!("path: {:?}", file_info.path);
+                    eprintln!("Error: {error}");
+                    None
+                }
+            })
+            .collect()
+    });
+
+    log_procedure(arguments, 1, "Number of songs", songs.len(), time);
+
+    let clusters: Vec<Vec<usize>> = get_clusters(songs.len(), |i, j| {
+        let (a, b) = (&songs[i].1, &songs[j].1);
+        (a.duration - b.duration).abs() <= MAX_DURATION_DIFFERENCE
+            && get_similarity(&a.items, &b.items) >= arguments.min_similarity
+    });
+
+    let identical_songs: Vec<GroupInfo> = clusters
+        .into_iter()
+        .filter_map(|cluster| {
+            let files: Vec<&FileInfo> = cluster.iter().map(|&index| songs[index].0).collect();
+            let duration: f64 = songs[cluster[0]].1.duration;
+            get_similar_group(files, format!("audio: {duration:.1} s"), arguments)
+        })
+        .collect();
+
+    log_procedure(
+        arguments,
+        2,
+        "Number of identical songs",
+        identical_songs.len(),
+        time,
+    );
+
+    identical_songs
+}
+
+#[cfg(test)]
+mod test_audio {
+    use super::*;
+    use std::{f64::consts::PI, fs};
+
+    /// Write a mono 16-bit PCM WAV file.
+    fn write_wav(path: &Path, sample_rate: u32, samples: &[i16]) -> MyResult<()> {
+        let data_len = (samples.len() * 2) as u32;
+        let mut wav: Vec<u8> = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+        wav.extend_from_slice(b"WAVEfmt ");
+        wav.extend_from_slice(&16_u32.to_le_bytes());
+        wav.extend_from_slice(&1_u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1_u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(sample_rate * 2).to_le_bytes());
+        wav.extend_from_slice(&2_u16.to_le_bytes());
+        wav.extend_from_slice(&16_u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&data_len.to_le_bytes());
+        samples
+            .iter()
+            .for_each(|sample| wav.extend_from_slice(&sample.to_le_bytes()));
+        fs::write(path, wav)?;
+        Ok(())
+    }
+
+    /// A melody: a different note every quarter of a second.
+    fn melody(sample_rate: u32, notes: &[f64], volume: f64) -> Vec<i16> {
+        let samples_per_note = sample_rate as usize / 4;
+        notes
+            .iter()
+            .flat_map(|frequency| {
+                (0..samples_per_note).map(move |n| {
+                    let t = n as f64 / f64::from(sample_rate);
+                    (volume * (2.0 * PI * frequency * t).sin()) as i16
+                })
+            })
+            .collect()
+    }
+
+    /// cargo test --features audio -- --show-output test_audio_fingerprint
+    #[test]
+    fn test_audio_fingerprint() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        let notes: Vec<f64> = (0..40)
+            .map(|n| 220.0 * (1.0 + (n * 7 % 12) as f64 / 12.0))
+            .collect();
+        let other: Vec<f64> = notes
+            .iter()
+            .rev()
+            .map(|frequency| frequency * 1.5)
+            .collect();
+
+        // The same melody, louder and at another sample rate.
+        let song = dir.join("song.wav");
+        let copy = dir.join("copy.wav");
+        let different = dir.join("different.wav");
+        write_wav(&song, 44100, &melody(44100, &notes, 8000.0))?;
+        write_wav(&copy, 22050, &melody(22050, &notes, 16000.0))?;
+        write_wav(&different, 44100, &melody(44100, &other, 8000.0))?;
+
+        let fingerprint = get_fingerprint(&song)?;
+        assert!(!fingerprint.items.is_empty());
+        assert!((fingerprint.duration - 10.0).abs() < 0.1);
+
+        let similar = get_similarity(&fingerprint.items, &get_fingerprint(&copy)?.items);
+        let unrelated = get_similarity(&fingerprint.items, &get_fingerprint(&different)?.items);
+        assert!(similar > unrelated);
+        assert_eq!(get_similarity(&fingerprint.items, &fingerprint.items), 1.0);
+
+        assert!(is_audio(Path::new("song.FLAC")));
+        assert!(!is_audio(Path::new("notes.txt")));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,216 @@
+use crate::{
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    human_readable_size,
+    limits::hash_is_stopped,
+    Algorithm, MyResult, OpenFile,
+};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::{
+    fs,
+    io::{BufWriter, Write},
+    path::Path,
+    time::Instant,
+};
+use tempfile::NamedTempFile;
+
+/// Size of the file hashed by each algorithm.
+const BENCH_SIZE: usize = 256 * 1024 * 1024;
+
+/// Buffer sizes of the buffered reads.
+const BUFFER_SIZES: [usize; 3] = [64 * 1024, 1024 * 1024, 8 * 1024 * 1024];
+
+/// Throughput of an algorithm with a read method
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchResult {
+    /// Hash algorithm
+    pub algorithm: Algorithm,
+    /// Read method: buffer size or memory-mapped I/O
+    pub method: String,
+    /// Time to hash the file (in seconds)
+    pub seconds: f64,
+    /// Bytes hashed per second
+    pub throughput: f64,
+}
+
+/// Throughput of the hash algorithms on this machine (`--bench`)
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchInfo {
+    /// Size of the hashed file (in bytes)
+    pub size: usize,
+    /// Results, in the order of the algorithms
+    pub results: Vec<BenchResult>,
+    /// Fastest algorithm with a digest of at least 128 bits
+    pub recommended: Option<Algorithm>,
+}
+
+/// Temporary file removed when dropped.
+///
+/// The file is created with a random name and exclusive access
+/// (`O_EXCL`): an existing file or link is never opened.
+struct BenchFile {
+    file: NamedTempFile,
+}
+
+impl BenchFile {
+    /// Write pseudo-random bytes (xorshift): the content cannot be compressed.
+    fn new(size: usize) -> MyResult<Self> {
+        let mut file = tempfile::Builder::new().prefix("fif-bench-").tempfile()?;
+
+        let mut writer = BufWriter::new(&mut file);
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut chunk: Vec<u8> = vec![0; 1024 * 1024];
+
+        for _ in 0..size.div_ceil(chunk.len()) {
+            for bytes in chunk.chunks_exact_mut(8) {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                bytes.copy_from_slice(&state.to_le_bytes());
+            }
+            writer.write_all(&chunk)?;
+        }
+        writer.flush()?;
+        drop(writer);
+
+        Ok(BenchFile { file })
+    }
+}
+
+/**
+Measure the throughput of each hash algorithm on this machine (`--bench`).
+
+A temporary file of 256 MiB is hashed with buffered reads of several
+buffer sizes and, if supported by the algorithm, with memory-mapped I/O.
+The file is read once before the measures: it is in the page cache,
+so the speed of the algorithms is compared, not the speed of the disk.
+
+AHash and FxHash (64 bits) are measured but not recommended:
+collisions are more likely on large trees.
+*/
+pub fn run_bench(arguments: &Arguments) -> MyResult<BenchInfo> {
+    let bench_file = BenchFile::new(BENCH_SIZE).inspect_err(|error| {
+        eprintln!("fn run_bench()");
+        eprintln!("Couldn't write the file to {:?}", std::env::temp_dir());
+        eprintln!("Error: {error}");
+    })?;
+    let path: &Path = bench_file.file.path();
+
+    // Load the file in the page cache.
+    fs::read(path)?;
+
+    let mut mmap_arguments = arguments.clone();
+    mmap_arguments.mmap = true;
+    mmap_arguments.mmap_min_size = 0;
+
+    let mut results: Vec<BenchResult> = Vec::new();
+
+    for algorithm in Algorithm::value_variants() {
+        for buffer_size in BUFFER_SIZES {
+            if hash_is_stopped() {
+                break;
+            }
+
+            let time = Instant::now();
+            algorithm.calculate_hash(OpenFile::open(path)?, buffer_size)?;
+            let result = BenchResult::new(
+                *algorithm,
+                format!("buffer {}", human_readable_size(buffer_size)),
+                time.elapsed().as_secs_f64(),
+            );
+
+            if arguments.verbose {
+                eprintln!("{result}");
+            }
+            results.push(result);
+        }
+
+        let time = Instant::now();
+        if algorithm
+            .calculate_hash_mmap(path, BENCH_SIZE as u64, &mmap_arguments)
+            .is_some()
+        {
+            let result =
+                BenchResult::new(*algorithm, "mmap".to_string(), time.elapsed().as_secs_f64());
+
+            if arguments.verbose {
+                eprintln!("{result}");
+            }
+            results.push(result);
+        }
+    }
+
+    let recommended: Option<Algorithm> = results
+        .iter()
+        .filter(|result| !matches!(result.algorithm, Algorithm::Ahash | Algorithm::Fxhash))
+        .max_by(|a, b| a.throughput.total_cmp(&b.throughput))
+        .map(|result| result.algorithm);
+
+    Ok(BenchInfo {
+        size: BENCH_SIZE,
+        results,
+        recommended,
+    })
+}
+
+impl BenchResult {
+    fn new(algorithm: Algorithm, method: String, seconds: f64) -> Self {
+        BenchResult {
+            algorithm,
+            method,
+            seconds,
+            throughput: BENCH_SIZE as f64 / seconds.max(f64::EPSILON),
+        }
+    }
+}
+
+impl std::fmt::Display for BenchResult {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "{:<10} {:<14} {:>12}/s",
+            self.algorithm.to_string(),
+            self.method,
+            human_readable_size(self.throughput as usize)
+        )
+    }
+}
+
+impl BenchInfo {
+    /// Write the results in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize BenchInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize BenchInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                writeln!(write, "File size: {}", human_readable_size(self.size))?;
+                writeln!(write)?;
+                for result in &self.results {
+                    writeln!(write, "{result}")?;
+                }
+                writeln!(write)?;
+                if let Some(algorithm) = self.recommended {
+                    let value = algorithm
+                        .to_possible_value()
+                        .map(|value| value.get_name().to_string())
+                        .unwrap_or_default();
+                    writeln!(
+                        write,
+                        "Recommended algorithm: {algorithm} (--algorithm {value})"
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
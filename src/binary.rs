@@ -0,0 +1,65 @@
+use crate::{args::ResultFormat, MyResult};
+use serde::Serialize;
+use std::io::Write;
+
+/**
+Write the value in a compact binary format: CBOR (`-r cbor`) or MessagePack (`-r msgpack`).
+
+The values have the same fields as the JSON report. Like the JSON report,
+the result is a sequence of values (RFC 8742: CBOR sequences): the groups
+of identical files, one value per group, followed by the summary.
+
+MessagePack structs are written as maps, with the names of the fields.
+*/
+pub fn write_binary<T: Serialize + ?Sized>(
+    value: &T,
+    result_format: &ResultFormat,
+    write: &mut dyn Write,
+) -> MyResult<()> {
+    match result_format {
+        ResultFormat::Cbor => ciborium::into_writer(value, write)?,
+        ResultFormat::MsgPack => rmp_serde::encode::write_named(write, value)?,
+        _ => return Err(format!("{result_format:?} is not a binary format").into()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_binary {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Group {
+        #[serde(rename = "Paths")]
+        paths: Vec<String>,
+    }
+
+    /// cargo test -- --show-output test_write_binary
+    #[test]
+    fn test_write_binary() -> MyResult<()> {
+        let group = Group {
+            paths: vec!["a.txt".to_string(), "b.txt".to_string()],
+        };
+
+        // Sequence of two values.
+        let mut cbor: Vec<u8> = Vec::new();
+        write_binary(&group, &ResultFormat::Cbor, &mut cbor)?;
+        write_binary(&group, &ResultFormat::Cbor, &mut cbor)?;
+        let mut reader: &[u8] = &cbor;
+        let first: Group = ciborium::from_reader(&mut reader)?;
+        let second: Group = ciborium::from_reader(&mut reader)?;
+        assert_eq!(first, group);
+        assert_eq!(second, group);
+        assert!(reader.is_empty());
+
+        let mut msgpack: Vec<u8> = Vec::new();
+        write_binary(&group, &ResultFormat::MsgPack, &mut msgpack)?;
+        assert_eq!(rmp_serde::from_slice::<Group>(&msgpack)?, group);
+
+        assert!(write_binary(&group, &ResultFormat::Json, &mut Vec::new()).is_err());
+
+        Ok(())
+    }
+}
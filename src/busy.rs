@@ -0,0 +1,139 @@
+use crate::{is_virtual_file, skipped::add_busy};
+use std::{
+    fs, io,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+/// Files modified less than this time ago are considered still being written.
+const RECENTLY_MODIFIED: Duration = Duration::from_secs(5);
+
+/**
+Check if the file appears to be actively written (`--skip_busy`).
+
+A file is busy if:
+1. it was modified in the last few seconds (half-written downloads);
+2. another process holds a lock on it (`flock` on unix) or, on Windows,
+   has it open for writing (sharing violation).
+
+Busy files are recorded as skipped files and are neither hashed nor
+changed by the actions. Virtual files are never busy.
+*/
+pub fn is_busy(path: &Path) -> bool {
+    if is_virtual_file(path) {
+        return false;
+    }
+
+    let busy: bool = is_recently_modified(path) || is_locked(path).unwrap_or(false);
+
+    if busy {
+        add_busy(path);
+    }
+
+    busy
+}
+
+/// Check if the file was modified in the last few seconds.
+fn is_recently_modified(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed < RECENTLY_MODIFIED)
+}
+
+/**
+Probe the advisory lock of the file with a non-blocking shared `flock`.
+
+The lock is released immediately: only the files locked exclusively
+by another process (writers) are busy.
+*/
+#[cfg(unix)]
+fn is_locked(path: &Path) -> io::Result<bool> {
+    use fs2::FileExt;
+
+    let file = fs::File::open(path)?;
+
+    match FileExt::try_lock_shared(&file) {
+        Ok(()) => {
+            FileExt::unlock(&file)?;
+            Ok(false)
+        }
+        Err(error) if error.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+            Ok(true)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/**
+Open the file without sharing the write access.
+
+The open fails with a sharing violation if another process has the file
+open for writing, or with a lock violation if a region is locked.
+
+<https://learn.microsoft.com/en-us/windows/win32/debug/system-error-codes--0-499->
+*/
+#[cfg(windows)]
+fn is_locked(path: &Path) -> io::Result<bool> {
+    use std::os::windows::fs::OpenOptionsExt;
+
+    const FILE_SHARE_READ: u32 = 0x1;
+    const FILE_SHARE_DELETE: u32 = 0x4;
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    let result = fs::OpenOptions::new()
+        .read(true)
+        .share_mode(FILE_SHARE_READ | FILE_SHARE_DELETE)
+        .open(path);
+
+    match result {
+        Ok(_file) => Ok(false),
+        Err(error)
+            if matches!(
+                error.raw_os_error(),
+                Some(ERROR_SHARING_VIOLATION | ERROR_LOCK_VIOLATION)
+            ) =>
+        {
+            Ok(true)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// On other platforms, only the modification time is checked.
+#[cfg(not(any(unix, windows)))]
+fn is_locked(_path: &Path) -> io::Result<bool> {
+    Ok(false)
+}
+
+#[cfg(test)]
+mod test_busy {
+    use super::*;
+    use crate::MyResult;
+
+    /// cargo test -- --show-output test_busy_file
+    #[cfg(unix)]
+    #[test]
+    fn test_busy_file() -> MyResult<()> {
+        use fs2::FileExt;
+
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("busy.txt");
+        fs::write(&path, "downloading")?;
+
+        // Just written.
+        assert!(is_recently_modified(&path));
+        assert!(!is_locked(&path)?);
+
+        // A writer holds an exclusive lock.
+        let writer = fs::File::open(&path)?;
+        writer.lock_exclusive()?;
+        assert!(is_locked(&path)?);
+        writer.unlock()?;
+        assert!(!is_locked(&path)?);
+
+        Ok(())
+    }
+}
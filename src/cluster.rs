@@ -0,0 +1,104 @@
+use crate::{Arguments, FileInfo, GroupInfo, Key};
+use hashbrown::HashMap;
+
+/**
+Group the items that are similar, transitively (union-find).
+
+`is_similar(i, j)` is called for each pair of items.
+
+Returns the indexes of the items of each group with two or more items.
+*/
+pub fn get_clusters<F>(len: usize, is_similar: F) -> Vec<Vec<usize>>
+where
+    F: Fn(usize, usize) -> bool,
+{
+    /// Find the representative of the group (with path halving).
+    fn find(parents: &mut [usize], mut index: usize) -> usize {
+        while parents[index] != index {
+            parents[index] = parents[parents[index]];
+            index = parents[index];
+        }
+        index
+    }
+
+    let mut parents: Vec<usize> = (0..len).collect();
+
+    for i in 0..len {
+        for j in (i + 1)..len {
+            if is_similar(i, j) {
+                let (root_i, root_j) = (find(&mut parents, i), find(&mut parents, j));
+                parents[root_j] = root_i;
+            }
+        }
+    }
+
+    let mut clusters: HashMap<usize, Vec<usize>> = HashMap::new();
+    for index in 0..len {
+        let root = find(&mut parents, index);
+        clusters.entry(root).or_default().push(index);
+    }
+
+    clusters
+        .into_values()
+        .filter(|cluster| cluster.len() > 1)
+        .collect()
+}
+
+/**
+Build the group of similar (but not identical) files.
+
+The largest file is listed first and assumed to be kept:
+the wasted size is the size of the other files.
+
+Returns None if the group is excluded by `--min_frequency`,
+`--max_frequency`, `--min_wasted` or `--compare`.
+*/
+pub fn get_similar_group(
+    mut files: Vec<&FileInfo>,
+    hash: String,
+    arguments: &Arguments,
+) -> Option<GroupInfo> {
+    files.sort_by(|a, b| {
+        b.key
+            .size
+            .cmp(&a.key.size)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+
+    let largest: usize = files.first()?.key.size;
+    let num_file = files.len();
+    let sum_size: usize = files.iter().map(|file_info| file_info.key.size).sum();
+    let wasted_size: usize = sum_size - largest;
+    let paths: Vec<_> = files
+        .iter()
+        .map(|file_info| file_info.path.clone())
+        .collect();
+
+    (arguments.frequency_is_included(num_file)
+        && arguments.wasted_is_included(wasted_size)
+        && arguments.compare_is_included(&paths))
+    .then(|| GroupInfo {
+        key: Key::new(largest as u64, Some(hash)),
+        algorithm: None,
+        paths,
+        num_file,
+        sum_size,
+        wasted_size,
+    })
+}
+
+#[cfg(test)]
+mod test_cluster {
+    use super::*;
+
+    /// cargo test --features perceptual -- --show-output test_get_clusters
+    #[test]
+    fn test_get_clusters() {
+        // 1 ~ 2 and 2 ~ 4: transitively grouped.
+        let values: [i32; 5] = [1, 2, 4, 10, 20];
+        let mut clusters = get_clusters(values.len(), |i, j| (values[i] - values[j]).abs() <= 2);
+        clusters.sort();
+
+        assert_eq!(clusters, [vec![0, 1, 2]]);
+    }
+}
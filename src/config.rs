@@ -0,0 +1,213 @@
+use crate::{
+    args::{parse_size, ResultFormat},
+    Algorithm, Arguments, MyResult,
+};
+use clap::{parser::ValueSource, ArgMatches, ValueEnum};
+use serde::{Deserialize, Deserializer};
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Directory of the configuration file, inside the user configuration directory.
+const CONFIG_DIRNAME: &str = "find_duplicate_files";
+
+/// Name of the configuration file.
+const CONFIG_FILENAME: &str = "config.toml";
+
+/**
+Default values stored in the configuration file:
+
+`~/.config/find_duplicate_files/config.toml`
+
+```toml
+algorithm = "sha256"
+exclude = ["node_modules", ".git", "*.tmp"]
+result_format = "json"
+min_size = "10K"
+max_size = 2147483648
+```
+
+Options given on the command line take precedence over the configuration file.
+
+Use `--no_config` to skip loading it.
+*/
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Hash algorithm
+    #[serde(default, deserialize_with = "deserialize_value_enum")]
+    pub algorithm: Option<Algorithm>,
+    /// Glob patterns of directories and files to be skipped
+    #[serde(default)]
+    pub exclude: Option<Vec<String>>,
+    /// Result format
+    #[serde(default, deserialize_with = "deserialize_value_enum")]
+    pub result_format: Option<ResultFormat>,
+    /// Minimum file size
+    #[serde(default, deserialize_with = "deserialize_size")]
+    pub min_size: Option<u64>,
+    /// Maximum file size
+    #[serde(default, deserialize_with = "deserialize_size")]
+    pub max_size: Option<u64>,
+}
+
+impl Config {
+    /// Get the path of the configuration file.
+    ///
+    /// `$XDG_CONFIG_HOME` or `~/.config` (`%APPDATA%` on Windows).
+    pub fn get_path() -> Option<PathBuf> {
+        let config_dir: PathBuf = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+            .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+
+        Some(config_dir.join(CONFIG_DIRNAME).join(CONFIG_FILENAME))
+    }
+
+    /// Load the configuration file, if it exists.
+    pub fn load() -> MyResult<Self> {
+        match Self::get_path() {
+            Some(path) if path.is_file() => Self::read(&path),
+            _ => Ok(Config::default()),
+        }
+    }
+
+    /// Read the configuration file.
+    pub fn read(path: &Path) -> MyResult<Self> {
+        let content: String = fs::read_to_string(path)?;
+
+        toml::from_str(&content).map_err(|error| {
+            eprintln!("fn read()");
+            eprintln!("Failed to parse the configuration file {path:?}");
+            eprintln!("Error: {error}");
+            error.into()
+        })
+    }
+
+    /// Apply the configuration to the options not given on the command line.
+    pub fn apply(&self, arguments: &mut Arguments, matches: &ArgMatches) {
+        let is_default = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+        if let Some(algorithm) = self.algorithm.filter(|_| is_default("algorithm")) {
+            arguments.algorithm = algorithm;
+        }
+
+        if let Some(exclude) = self.exclude.as_ref().filter(|_| is_default("exclude")) {
+            arguments.exclude.clone_from(exclude);
+        }
+
+        if let Some(result_format) = self
+            .result_format
+            .as_ref()
+            .filter(|_| is_default("result_format"))
+        {
+            arguments.result_format = result_format.clone();
+        }
+
+        if let Some(min_size) = self.min_size.filter(|_| is_default("min_size")) {
+            arguments.min_size = min_size;
+        }
+
+        if let Some(max_size) = self.max_size.filter(|_| is_default("max_size")) {
+            arguments.max_size = max_size;
+        }
+    }
+}
+
+/// Deserialize an enumeration with the same names as the command line values.
+fn deserialize_value_enum<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: ValueEnum,
+{
+    let value = String::deserialize(deserializer)?;
+
+    T::from_str(&value, true)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+/// Deserialize a size given in bytes (integer) or with a unit (string, ex.: "10M").
+fn deserialize_size<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Size {
+        Bytes(u64),
+        Text(String),
+    }
+
+    match Size::deserialize(deserializer)? {
+        Size::Bytes(bytes) => Ok(Some(bytes)),
+        Size::Text(text) => parse_size(&text)
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod test_config {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+
+    fn get_arguments(config: &Config, args: &[&str]) -> Arguments {
+        let matches = Arguments::command().get_matches_from(args);
+        let mut arguments = Arguments::from_arg_matches(&matches).unwrap();
+        config.apply(&mut arguments, &matches);
+        arguments
+    }
+
+    /// cargo test -- --show-output test_config_merge
+    #[test]
+    fn test_config_merge() -> MyResult<()> {
+        let config: Config = toml::from_str(
+            r#"
+            algorithm = "sha256"
+            exclude = ["node_modules", ".git"]
+            result_format = "json"
+            min_size = "10K"
+            "#,
+        )?;
+
+        // Without command line options, the configuration is used.
+        let arguments = get_arguments(&config, &["find-identical-files"]);
+        assert_eq!(arguments.algorithm, Algorithm::SHA256);
+        assert_eq!(arguments.exclude, ["node_modules", ".git"]);
+        assert!(matches!(arguments.result_format, ResultFormat::Json));
+        assert_eq!(arguments.min_size, 10 * 1024);
+        assert_eq!(arguments.max_size, u64::MAX);
+
+        // Command line options override the configuration.
+        let arguments = get_arguments(
+            &config,
+            &[
+                "find-identical-files",
+                "-a",
+                "blake3",
+                "--exclude",
+                "target",
+                "--min_size",
+                "0",
+            ],
+        );
+        assert_eq!(arguments.algorithm, Algorithm::Blake3);
+        assert_eq!(arguments.exclude, ["target"]);
+        assert!(matches!(arguments.result_format, ResultFormat::Json));
+        assert_eq!(arguments.min_size, 0);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_config_errors
+    #[test]
+    fn test_config_errors() {
+        assert!(toml::from_str::<Config>("algorithm = \"md5\"").is_err());
+        assert!(toml::from_str::<Config>("min_size = \"10X\"").is_err());
+        assert!(toml::from_str::<Config>("unknown = true").is_err());
+        assert!(toml::from_str::<Config>("min_size = 4096").is_ok());
+    }
+}
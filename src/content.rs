@@ -0,0 +1,408 @@
+use crate::{
+    enumerations::algo::hash_reader_with_size, open_file, progress::ProgressReader, Algorithm,
+    MyResult, OpenFile,
+};
+use std::{
+    collections::VecDeque,
+    io::{BufReader, Read, Seek, SeekFrom},
+    ops::Range,
+    path::Path,
+};
+
+/// Readable and seekable source (file or in-memory buffer).
+pub trait Source: Read + Seek {}
+
+impl<T: Read + Seek> Source for T {}
+
+/**
+Extract the payload of a known format, without the embedded metadata (`--content_only`).
+
+Files that differ only in tags (ID3, Vorbis comments, EXIF, ...)
+have the same payload and are reported as identical.
+
+Adding a format only requires a new StreamExtractor implementation.
+*/
+pub trait StreamExtractor: Sync {
+    /// Name of the format.
+    fn name(&self) -> &'static str;
+
+    /// Lowercase file extensions of the format.
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// Get the byte ranges of the payload, in order.
+    ///
+    /// Returns an error if the source is not a valid file of the format.
+    fn get_payload(&self, source: &mut dyn Source) -> MyResult<Vec<Range<u64>>>;
+}
+
+/// MP3 files: skip the ID3v2 tag (beginning) and the ID3v1 tag (end).
+pub struct Mp3Extractor;
+
+/// FLAC files: skip the metadata blocks, except STREAMINFO.
+pub struct FlacExtractor;
+
+/// JPEG files: skip the APPn (EXIF, XMP, IPTC, ...) and COM segments.
+pub struct JpegExtractor;
+
+/// Stream extractors of the known formats.
+static EXTRACTORS: [&dyn StreamExtractor; 3] = [&Mp3Extractor, &FlacExtractor, &JpegExtractor];
+
+/// Get the stream extractor of the file, from its extension.
+pub fn get_extractor(path: &Path) -> Option<&'static dyn StreamExtractor> {
+    let extension: String = path.extension()?.to_str()?.to_lowercase();
+
+    EXTRACTORS
+        .iter()
+        .find(|extractor| extractor.extensions().contains(&extension.as_str()))
+        .copied()
+}
+
+/**
+Calculate the hash of the payload and the number of bytes hashed.
+
+Files of unknown formats, or that cannot be parsed, are entirely hashed.
+*/
+pub fn calculate_content_hash(
+    algorithm: &Algorithm,
+    path: &Path,
+    buffer_size: usize,
+) -> MyResult<(String, u64)> {
+    let Some(extractor) = get_extractor(path) else {
+        return algorithm.calculate_hash_and_size(path, buffer_size);
+    };
+
+    let mut file: OpenFile = open_file(&path)?;
+
+    let ranges: Vec<Range<u64>> = match extractor.get_payload(&mut file) {
+        Ok(ranges) => ranges,
+        Err(error) => {
+            eprintln!("fn calculate_content_hash()");
+            eprintln!("path: {path:?}");
+            eprintln!(
+                "Invalid {} file, hashing the entire file: {error}",
+                extractor.name()
+            );
+            return algorithm.calculate_hash_and_size(path, buffer_size);
+        }
+    };
+
+    let size: u64 = ranges.iter().map(|range| range.end - range.start).sum();
+    let reader = BufReader::with_capacity(
+        buffer_size.max(1),
+        ProgressReader::new(RangeReader::new(file, ranges)),
+    );
+
+    hash_reader_with_size(reader, algorithm.new_digest(size), buffer_size)
+}
+
+/// Read only the byte ranges of the source, in order.
+pub struct RangeReader<R> {
+    source: R,
+    ranges: VecDeque<Range<u64>>,
+    /// Bytes left in the current range
+    remaining: u64,
+}
+
+impl<R: Read + Seek> RangeReader<R> {
+    pub fn new(source: R, ranges: Vec<Range<u64>>) -> Self {
+        RangeReader {
+            source,
+            ranges: ranges.into(),
+            remaining: 0,
+        }
+    }
+}
+
+impl<R: Read + Seek> Read for RangeReader<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        while self.remaining == 0 {
+            let Some(range) = self.ranges.pop_front() else {
+                return Ok(0);
+            };
+            self.source.seek(SeekFrom::Start(range.start))?;
+            self.remaining = range.end.saturating_sub(range.start);
+        }
+
+        let max: usize = buffer
+            .len()
+            .min(self.remaining.try_into().unwrap_or(usize::MAX));
+        let count: usize = self.source.read(&mut buffer[..max])?;
+
+        if count == 0 {
+            // The file is shorter than expected.
+            self.remaining = 0;
+            self.ranges.clear();
+        }
+
+        self.remaining -= count as u64;
+        Ok(count)
+    }
+}
+
+/// Get the total length of the source.
+fn get_length(source: &mut dyn Source) -> MyResult<u64> {
+    Ok(source.seek(SeekFrom::End(0))?)
+}
+
+/// Read exactly `N` bytes at the position.
+fn read_at<const N: usize>(source: &mut dyn Source, position: u64) -> MyResult<[u8; N]> {
+    let mut bytes = [0_u8; N];
+    source.seek(SeekFrom::Start(position))?;
+    source.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Get the length of the ID3v2 tag at the beginning of the source (0 if absent).
+fn get_id3v2_length(source: &mut dyn Source, length: u64) -> MyResult<u64> {
+    if length < 10 {
+        return Ok(0);
+    }
+
+    let header: [u8; 10] = read_at(source, 0)?;
+    if &header[..3] != b"ID3" {
+        return Ok(0);
+    }
+
+    // Synchsafe integer: 7 bits per byte.
+    let size: u64 = header[6..10]
+        .iter()
+        .fold(0, |size, byte| (size << 7) | u64::from(byte & 0x7F));
+
+    // Footer present.
+    let footer: u64 = if header[5] & 0x10 != 0 { 10 } else { 0 };
+
+    Ok((10 + size + footer).min(length))
+}
+
+impl StreamExtractor for Mp3Extractor {
+    fn name(&self) -> &'static str {
+        "MP3"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["mp3"]
+    }
+
+    fn get_payload(&self, source: &mut dyn Source) -> MyResult<Vec<Range<u64>>> {
+        let length: u64 = get_length(source)?;
+        let start: u64 = get_id3v2_length(source, length)?;
+
+        let mut end: u64 = length;
+        if end >= start + 128 && &read_at::<3>(source, end - 128)? == b"TAG" {
+            end -= 128;
+        }
+
+        Ok(std::iter::once(start..end).collect())
+    }
+}
+
+impl StreamExtractor for FlacExtractor {
+    fn name(&self) -> &'static str {
+        "FLAC"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["flac"]
+    }
+
+    fn get_payload(&self, source: &mut dyn Source) -> MyResult<Vec<Range<u64>>> {
+        let length: u64 = get_length(source)?;
+
+        // Some tools add an ID3v2 tag before the FLAC stream.
+        let mut position: u64 = get_id3v2_length(source, length)?;
+
+        if &read_at::<4>(source, position)? != b"fLaC" {
+            return Err("missing fLaC marker".into());
+        }
+        position += 4;
+
+        let mut ranges: Vec<Range<u64>> = Vec::new();
+
+        loop {
+            let header: [u8; 4] = read_at(source, position)?;
+            let is_last: bool = header[0] & 0x80 != 0;
+            let block_type: u8 = header[0] & 0x7F;
+            let block_length: u64 =
+                u64::from_be_bytes([0, 0, 0, 0, 0, header[1], header[2], header[3]]);
+            let block_end: u64 = position + 4 + block_length;
+
+            if block_end > length {
+                return Err("truncated metadata block".into());
+            }
+
+            // STREAMINFO describes the audio stream: keep it.
+            if block_type == 0 {
+                ranges.push(position..block_end);
+            }
+
+            position = block_end;
+
+            if is_last {
+                break;
+            }
+        }
+
+        // Audio frames.
+        ranges.push(position..length);
+
+        Ok(ranges)
+    }
+}
+
+impl StreamExtractor for JpegExtractor {
+    fn name(&self) -> &'static str {
+        "JPEG"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["jpg", "jpeg"]
+    }
+
+    fn get_payload(&self, source: &mut dyn Source) -> MyResult<Vec<Range<u64>>> {
+        let length: u64 = get_length(source)?;
+
+        if read_at::<2>(source, 0)? != [0xFF, 0xD8] {
+            return Err("missing SOI marker".into());
+        }
+
+        // SOI marker.
+        let mut ranges: Vec<Range<u64>> = Vec::new();
+        ranges.push(0..2);
+        let mut position: u64 = 2;
+
+        loop {
+            let [prefix, marker] = read_at::<2>(source, position)?;
+            if prefix != 0xFF {
+                return Err("invalid segment marker".into());
+            }
+
+            match marker {
+                // Fill byte.
+                0xFF => {
+                    position += 1;
+                    continue;
+                }
+                // Start of scan: the compressed image data follows until the end.
+                0xDA => {
+                    ranges.push(position..length);
+                    break;
+                }
+                // Segments without length.
+                0x01 | 0xD0..=0xD7 => {
+                    ranges.push(position..position + 2);
+                    position += 2;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let segment_length: u64 =
+                u16::from_be_bytes(read_at::<2>(source, position + 2)?).into();
+            let segment_end: u64 = position + 2 + segment_length;
+
+            if segment_end > length {
+                return Err("truncated segment".into());
+            }
+
+            // APPn (EXIF, XMP, ICC, IPTC, ...) and COM segments are metadata.
+            let is_metadata: bool = matches!(marker, 0xE0..=0xEF | 0xFE);
+            if !is_metadata {
+                ranges.push(position..segment_end);
+            }
+
+            position = segment_end;
+        }
+
+        Ok(ranges)
+    }
+}
+
+#[cfg(test)]
+mod test_content {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Read the payload of the data.
+    fn get_payload(extractor: &dyn StreamExtractor, data: &[u8]) -> MyResult<Vec<u8>> {
+        let ranges = extractor.get_payload(&mut Cursor::new(data))?;
+        let mut payload: Vec<u8> = Vec::new();
+        RangeReader::new(Cursor::new(data), ranges).read_to_end(&mut payload)?;
+        Ok(payload)
+    }
+
+    /// cargo test -- --show-output test_mp3_extractor
+    #[test]
+    fn test_mp3_extractor() -> MyResult<()> {
+        let audio: &[u8] = b"mp3 audio frames";
+
+        // ID3v2 tag with 5 bytes of frames.
+        let mut tagged: Vec<u8> = b"ID3\x04\x00\x00\x00\x00\x00\x05title".to_vec();
+        tagged.extend_from_slice(audio);
+        // ID3v1 tag.
+        tagged.extend_from_slice(b"TAG");
+        tagged.extend_from_slice(&[b' '; 125]);
+
+        assert_eq!(get_payload(&Mp3Extractor, &tagged)?, audio);
+        assert_eq!(get_payload(&Mp3Extractor, audio)?, audio);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_flac_extractor
+    #[test]
+    fn test_flac_extractor() -> MyResult<()> {
+        let flac = |comment: &[u8]| {
+            let mut data: Vec<u8> = b"fLaC".to_vec();
+            // STREAMINFO (type 0), 4 bytes.
+            data.extend_from_slice(&[0x00, 0, 0, 4]);
+            data.extend_from_slice(b"info");
+            // VORBIS_COMMENT (type 4), last block.
+            data.extend_from_slice(&[0x84, 0, 0, comment.len() as u8]);
+            data.extend_from_slice(comment);
+            data.extend_from_slice(b"flac audio frames");
+            data
+        };
+
+        let payload = get_payload(&FlacExtractor, &flac(b"artist=A"))?;
+        assert_eq!(
+            payload,
+            get_payload(&FlacExtractor, &flac(b"artist=Another"))?
+        );
+        assert_eq!(payload, b"\x00\x00\x00\x04infoflac audio frames");
+        assert!(get_payload(&FlacExtractor, b"not a flac file").is_err());
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_jpeg_extractor
+    #[test]
+    fn test_jpeg_extractor() -> MyResult<()> {
+        let jpeg = |exif: &[u8]| {
+            let mut data: Vec<u8> = vec![0xFF, 0xD8];
+            // APP1 (EXIF)
+            data.extend_from_slice(&[0xFF, 0xE1, 0, exif.len() as u8 + 2]);
+            data.extend_from_slice(exif);
+            // DQT
+            data.extend_from_slice(&[0xFF, 0xDB, 0, 4, 1, 2]);
+            // SOS and image data
+            data.extend_from_slice(&[0xFF, 0xDA, 0, 2, 9, 9, 9, 0xFF, 0xD9]);
+            data
+        };
+
+        let payload = get_payload(&JpegExtractor, &jpeg(b"Exif camera A"))?;
+        assert_eq!(payload, get_payload(&JpegExtractor, &jpeg(b"Exif B"))?);
+        assert_eq!(
+            payload,
+            [0xFF, 0xD8, 0xFF, 0xDB, 0, 4, 1, 2, 0xFF, 0xDA, 0, 2, 9, 9, 9, 0xFF, 0xD9]
+        );
+        assert!(get_payload(&JpegExtractor, b"not a jpeg file").is_err());
+
+        assert_eq!(
+            get_extractor(Path::new("photo.JPG")).map(|e| e.name()),
+            Some("JPEG")
+        );
+        assert!(get_extractor(Path::new("notes.txt")).is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+use crate::{
+    add_thousands_separator,
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    get_group_id, human_readable_size, split_and_insert, Algorithm, Compression, MyResult,
+    SEPARATOR,
+};
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+/// Group of identical files of a JSON report, identified by its stable id.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ReportGroup {
+    /// Stable identifier of the group: (algorithm, digest, size)
+    #[serde(rename = "Group id")]
+    pub id: String,
+    /// Individual file size
+    #[serde(rename = "Size", serialize_with = "add_thousands_separator")]
+    pub size: usize,
+    /// Hash of the entire file
+    #[serde(rename = "Hash")]
+    pub hash: String,
+    /// File paths
+    #[serde(rename = "Paths")]
+    pub paths: Vec<PathBuf>,
+    /// Space that would be reclaimed by keeping only one file
+    #[serde(rename = "Wasted size", serialize_with = "add_thousands_separator")]
+    pub wasted_size: usize,
+}
+
+/// Group of identical files found in both reports with a different number of files.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChangedGroup {
+    /// The group in the old report
+    #[serde(rename = "Old")]
+    pub old: ReportGroup,
+    /// The group in the new report
+    #[serde(rename = "New")]
+    pub new: ReportGroup,
+}
+
+/// Comparison of two JSON reports (`--diff`).
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiffInfo {
+    /// Groups only in the new report
+    #[serde(rename = "Appeared")]
+    pub appeared: Vec<ReportGroup>,
+    /// Groups only in the old report
+    #[serde(rename = "Disappeared")]
+    pub disappeared: Vec<ReportGroup>,
+    /// Groups whose number of identical files changed
+    #[serde(rename = "Changed")]
+    pub changed: Vec<ChangedGroup>,
+    /// Total wasted size of the old report
+    #[serde(rename = "Old wasted size", serialize_with = "add_thousands_separator")]
+    pub old_wasted: usize,
+    /// Total wasted size of the new report
+    #[serde(rename = "New wasted size", serialize_with = "add_thousands_separator")]
+    pub new_wasted: usize,
+}
+
+impl DiffInfo {
+    /**
+    Compare the groups of identical files of two reports.
+
+    Groups are identified by their stable id, derived from (algorithm, digest, size):
+    the id does not depend on the paths, so a group is the same even if its files were moved.
+    */
+    pub fn new(old: Vec<ReportGroup>, new: Vec<ReportGroup>) -> Self {
+        let old_wasted: usize = old.iter().map(|group| group.wasted_size).sum();
+        let new_wasted: usize = new.iter().map(|group| group.wasted_size).sum();
+
+        let mut old: BTreeMap<String, ReportGroup> = merge_groups(old);
+        let new: BTreeMap<String, ReportGroup> = merge_groups(new);
+
+        let mut diff_info = DiffInfo {
+            old_wasted,
+            new_wasted,
+            ..Default::default()
+        };
+
+        for (id, new_group) in new {
+            match old.remove(&id) {
+                None => diff_info.appeared.push(new_group),
+                Some(old_group) if old_group.paths.len() != new_group.paths.len() => {
+                    diff_info.changed.push(ChangedGroup {
+                        old: old_group,
+                        new: new_group,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+        diff_info.disappeared = old.into_values().collect();
+
+        diff_info
+    }
+
+    /// Write the groups that appeared, disappeared or changed in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize DiffInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize DiffInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                for (label, groups) in [
+                    ("Appeared", &self.appeared),
+                    ("Disappeared", &self.disappeared),
+                ] {
+                    writeln!(write, "{label}: {} groups", groups.len())?;
+                    for group in groups {
+                        writeln!(
+                            write,
+                            "{:>12} {} files {:?}",
+                            human_readable_size(group.wasted_size),
+                            group.paths.len(),
+                            group.paths
+                        )?;
+                    }
+                    writeln!(write)?;
+                }
+
+                writeln!(write, "Changed: {} groups", self.changed.len())?;
+                for group in &self.changed {
+                    writeln!(
+                        write,
+                        "{:>12} -> {:<12} {} -> {} files {:?}",
+                        human_readable_size(group.old.wasted_size),
+                        human_readable_size(group.new.wasted_size),
+                        group.old.paths.len(),
+                        group.new.paths.len(),
+                        group.new.paths
+                    )?;
+                }
+                writeln!(write)?;
+
+                writeln!(
+                    write,
+                    "Wasted size: {} bytes ({}) -> {} bytes ({})",
+                    split_and_insert(self.old_wasted, SEPARATOR),
+                    human_readable_size(self.old_wasted),
+                    split_and_insert(self.new_wasted, SEPARATOR),
+                    human_readable_size(self.new_wasted)
+                )?;
+                writeln!(write)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Merge the groups with the same id (ex.: split by `--same_name_only`).
+fn merge_groups(groups: Vec<ReportGroup>) -> BTreeMap<String, ReportGroup> {
+    let mut merged: BTreeMap<String, ReportGroup> = BTreeMap::new();
+
+    for group in groups {
+        let entry = merged
+            .entry(group.id.clone())
+            .or_insert_with(|| ReportGroup {
+                id: group.id.clone(),
+                size: group.size,
+                hash: group.hash.clone(),
+                ..Default::default()
+            });
+
+        let mut paths: BTreeSet<PathBuf> = entry.paths.drain(..).collect();
+        paths.extend(group.paths);
+        entry.paths = paths.into_iter().collect();
+        entry.wasted_size = entry.size * entry.paths.len().saturating_sub(1);
+    }
+
+    merged
+}
+
+/**
+Read the groups of identical files of a report written with `-r json`
+(also compressed with gzip or zstd: `-O result.json.gz`, `--compress zstd`).
+
+The summary information is skipped.
+*/
+pub fn read_json_report(path: &Path) -> MyResult<Vec<ReportGroup>> {
+    let mut bytes: Vec<u8> = fs::read(path).inspect_err(|error| {
+        eprintln!("fn read_json_report()");
+        eprintln!("Couldn't read {path:?}");
+        eprintln!("Error: {error}");
+    })?;
+
+    match Compression::from_path(path) {
+        Some(Compression::Gzip) => {
+            let mut decompressed: Vec<u8> = Vec::new();
+            GzDecoder::new(&bytes[..]).read_to_end(&mut decompressed)?;
+            bytes = decompressed;
+        }
+        Some(Compression::Zstd) => bytes = zstd::decode_all(&bytes[..])?,
+        None => (),
+    }
+
+    let mut groups: Vec<ReportGroup> = Vec::new();
+
+    // The report is a sequence of JSON values: the groups and the summary.
+    for value in serde_json::Deserializer::from_slice(&bytes).into_iter::<Value>() {
+        let value: Value = value.inspect_err(|error| {
+            eprintln!("fn read_json_report()");
+            eprintln!("Invalid JSON report {path:?}");
+            eprintln!("Error: {error}");
+        })?;
+
+        if let Some(group) = get_report_group(&value) {
+            groups.push(group);
+        }
+    }
+
+    Ok(groups)
+}
+
+/// Get the group of identical files of a JSON value (None for the summary).
+fn get_report_group(value: &Value) -> Option<ReportGroup> {
+    let key: &Value = value.get("File information")?;
+    let size: usize = get_size(key.get("size")?)?;
+    // Reports of older versions have bare digests: `<hex>` instead of `blake3:<hex>`.
+    let (tag, hash) = Algorithm::split_digest(key.get("hash")?.as_str()?);
+    let algorithm: Option<Algorithm> = tag.or_else(|| {
+        value
+            .get("Algorithm")
+            .and_then(|algorithm| serde_json::from_value(algorithm.clone()).ok())
+    });
+    // Reports of older versions have no group id: it is derived from (algorithm, digest, size).
+    let id: String = match value.get("Group id").and_then(Value::as_str) {
+        Some(id) => id.to_string(),
+        None => {
+            let digest: String = match algorithm {
+                Some(algorithm) => algorithm.tag_digest(hash),
+                None => hash.to_string(),
+            };
+            get_group_id(&digest, size)
+        }
+    };
+    let hash: String = hash.to_string();
+    let paths: Vec<PathBuf> = value
+        .get("Paths")?
+        .as_array()?
+        .iter()
+        .filter_map(|path| path.as_str().map(PathBuf::from))
+        .collect();
+
+    Some(ReportGroup {
+        id,
+        size,
+        hash,
+        wasted_size: size * paths.len().saturating_sub(1),
+        paths,
+    })
+}
+
+/// Get a size written as a number or with thousands separators (ex.: "1.234.567").
+fn get_size(value: &Value) -> Option<usize> {
+    match value {
+        Value::Number(number) => number.as_u64().and_then(|size| size.try_into().ok()),
+        Value::String(string) => string
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test_diff {
+    use super::*;
+
+    /// cargo test -- --show-output test_diff_info
+    #[test]
+    fn test_diff_info() -> MyResult<()> {
+        let group = |hash: &str, paths: &[&str]| -> String {
+            serde_json::json!({
+                "File information": {"size": "1.000", "hash": hash},
+                "Paths": paths,
+            })
+            .to_string()
+        };
+        let summary = "{\"Total number of files\": 9}";
+
+        let temp_dir = tempfile::tempdir()?;
+        let old_path = temp_dir.path().join("old.json");
+        let new_path = temp_dir.path().join("new.json");
+        let old_report = [group("a", &["1", "2"]), group("b", &["3", "4"])].join("\n\n");
+        let new_report = [group("b", &["3", "4", "5"]), group("c", &["6", "7"])].join("\n\n");
+        fs::write(&old_path, format!("{old_report}\n\n{summary}\n"))?;
+        fs::write(&new_path, format!("{new_report}\n\n{summary}\n"))?;
+
+        let old = read_json_report(&old_path)?;
+        let new = read_json_report(&new_path)?;
+        assert_eq!(old.len(), 2);
+        assert_eq!(old[0].size, 1000);
+
+        let diff_info = DiffInfo::new(old, new);
+        assert_eq!(diff_info.appeared[0].hash, "c");
+        assert_eq!(diff_info.disappeared[0].hash, "a");
+        assert_eq!(diff_info.changed[0].new.paths.len(), 3);
+        assert_eq!((diff_info.old_wasted, diff_info.new_wasted), (2000, 3000));
+        assert_eq!(diff_info.appeared[0].id, get_group_id("c", 1000));
+
+        Ok(())
+    }
+}
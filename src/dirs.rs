@@ -0,0 +1,357 @@
+use crate::{
+    clear_progress,
+    enumerations::algo::hash_reader,
+    get_paths, log_procedure, run_io,
+    skipped::{add_error, add_skipped},
+    start_hashing, Arguments, FileExtension, FileInfo, GroupInfo, Key, MyResult, PathBufExtension,
+};
+use hashbrown::{HashMap, HashSet};
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+/// File, subdirectory or symbolic link of a directory.
+#[derive(Debug, Clone)]
+struct DirEntry {
+    /// Name, relative to the directory
+    name: String,
+    /// 'f' for files, 'd' for subdirectories, 'l' for symbolic links,
+    /// 'o' for other file types and 'e' for unreadable directories
+    kind: char,
+    /// Hash of the entire file, digest of the subdirectory or target of the link
+    digest: String,
+    /// Size of the file or sum of the file sizes of the subdirectory
+    size: usize,
+}
+
+/**
+Group directories with identical contents (`--dirs`).
+
+The digest of each directory is computed Merkle-style, from the
+names and digests of its files and subdirectories, so two directories
+are identical only if their entire trees are identical.
+
+The trees are walked again, without the filters of the search: the hidden,
+excluded, empty or out of range files, the symbolic links and the empty
+subdirectories are also compared.
+
+Only the top-most identical directories are reported:
+the identical subdirectories of identical directories are skipped.
+*/
+pub fn get_directory_groups(arguments: &Arguments, time: Instant) -> Vec<GroupInfo> {
+    let roots: Vec<PathBuf> = match get_roots(arguments) {
+        Ok(roots) => roots,
+        Err(error) => {
+            eprintln!("fn get_directory_groups()");
+            eprintln!("Error: {error}");
+            return Vec::new();
+        }
+    };
+
+    let mut tree = Tree::default();
+    for root in &roots {
+        // Overlapping input directories are walked once.
+        let is_nested: bool = roots
+            .iter()
+            .any(|other| other != root && root.starts_with(other));
+        if !is_nested && !tree.dirs.contains(root) {
+            tree.walk(root);
+        }
+    }
+
+    // The digests of all files are needed: a directory is identical only if all its files are.
+    start_hashing(tree.files.iter().map(|(_path, size)| *size as u64).sum());
+    let mut digests: Vec<(PathBuf, DirEntry)> = run_io(arguments, || {
+        tree.files
+            .par_iter() // rayon parallel iterator
+            .map(|(path, size)| {
+                let digest: String = match path.get_hash(arguments, 3) {
+                    Ok(hash) => hash.unwrap_or_default(),
+                    Err(error) => {
+                        eprintln!("fn get_directory_groups()");
+                        eprintln!("path: {path:?}");
+                        eprintln!("Error: {error}");
+                        add_error(path, &error);
+                        // Unreadable files make their directories unique.
+                        format!("unreadable: {path:?}")
+                    }
+                };
+                (path.clone(), DirEntry::new(path, 'f', digest, *size))
+            })
+            .collect()
+    });
+    clear_progress();
+
+    digests.append(&mut tree.others);
+
+    let dir_files: Vec<FileInfo> = get_directory_digests(&digests, &tree.dirs, &roots, arguments);
+    let identical_dirs: Vec<GroupInfo> = dir_files.get_grouped_files(arguments, 3);
+    let identical_dirs: Vec<GroupInfo> = remove_nested_groups(identical_dirs);
+
+    log_procedure(
+        arguments,
+        3,
+        "Number of identical directories",
+        identical_dirs.len(),
+        time,
+    );
+
+    identical_dirs
+}
+
+/// Get the input directories and the reference directory (`--compare`).
+fn get_roots(arguments: &Arguments) -> MyResult<Vec<PathBuf>> {
+    let mut roots: Vec<PathBuf> = get_paths(arguments)?;
+    roots.extend(arguments.compare.clone());
+    Ok(roots)
+}
+
+/// All the entries of the input directories.
+#[derive(Debug, Default)]
+struct Tree {
+    /// Directories, including the empty ones
+    dirs: Vec<PathBuf>,
+    /// Regular files: (path, size)
+    files: Vec<(PathBuf, usize)>,
+    /// Symbolic links, other file types and unreadable directories
+    others: Vec<(PathBuf, DirEntry)>,
+}
+
+impl Tree {
+    /// Walk the directory, without following the symbolic links.
+    fn walk(&mut self, dir: &Path) {
+        let read_dir = match fs::read_dir(dir) {
+            Ok(read_dir) => read_dir,
+            Err(error) => {
+                add_skipped(dir, &error);
+                // Unreadable directories make their parents unique.
+                let entry = DirEntry::new(dir, 'e', format!("unreadable: {dir:?}"), 0);
+                self.others.push((dir.to_path_buf(), entry));
+                return;
+            }
+        };
+
+        self.dirs.push(dir.to_path_buf());
+
+        for entry in read_dir.flatten() {
+            let path: PathBuf = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                self.walk(&path);
+            } else if file_type.is_file() {
+                let size: u64 = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+                self.files.push((path, size as usize));
+            } else if file_type.is_symlink() {
+                let target: String = fs::read_link(&path)
+                    .map(|target| target.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                self.others
+                    .push((path.clone(), DirEntry::new(&path, 'l', target, 0)));
+            } else {
+                self.others
+                    .push((path.clone(), DirEntry::new(&path, 'o', String::new(), 0)));
+            }
+        }
+    }
+}
+
+impl DirEntry {
+    /// Get the entry of the path in its directory.
+    fn new(path: &Path, kind: char, digest: String, size: usize) -> Self {
+        DirEntry {
+            name: get_name(path),
+            kind,
+            digest,
+            size,
+        }
+    }
+}
+
+/**
+Compute the digest of each directory, from the deepest to the input directories.
+
+Returns one [`FileInfo`] per directory, whose key is (sum of file sizes, digest).
+*/
+fn get_directory_digests(
+    digests: &[(PathBuf, DirEntry)],
+    dirs: &[PathBuf],
+    roots: &[PathBuf],
+    arguments: &Arguments,
+) -> Vec<FileInfo> {
+    let mut entries: HashMap<PathBuf, Vec<DirEntry>> =
+        dirs.iter().map(|dir| (dir.clone(), Vec::new())).collect();
+
+    for (path, entry) in digests {
+        if let Some(parent) = path.parent() {
+            if let Some(parent_entries) = entries.get_mut(parent) {
+                parent_entries.push(entry.clone());
+            }
+        }
+    }
+
+    // Subdirectories are processed before their parents.
+    let mut dirs: Vec<PathBuf> = dirs.to_vec();
+    dirs.sort_by_key(|dir| std::cmp::Reverse(dir.components().count()));
+
+    let mut dir_files: Vec<FileInfo> = Vec::with_capacity(dirs.len());
+
+    for dir in dirs {
+        let mut children: Vec<DirEntry> = entries.remove(&dir).unwrap_or_default();
+        children.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let size: usize = children.iter().map(|entry| entry.size).sum();
+        let text: String = children
+            .iter()
+            .map(|entry| {
+                format!(
+                    "{}\0{}\0{}\0{}\n",
+                    entry.kind, entry.name, entry.size, entry.digest
+                )
+            })
+            .collect();
+
+        let digest: String = match hash_reader(
+            text.as_bytes(),
+            arguments.algorithm.new_digest(text.len() as u64),
+            arguments.buffer_size,
+        ) {
+            Ok(digest) => digest,
+            Err(error) => {
+                eprintln!("fn get_directory_digests()");
+                eprintln!("path: {dir:?}");
+                eprintln!("Error: {error}");
+                continue;
+            }
+        };
+
+        // The input directories are not part of the trees of other input directories.
+        let is_root: bool = roots.iter().any(|root| root == &dir);
+        if let Some(parent) = dir.parent().filter(|_| !is_root) {
+            if let Some(parent_entries) = entries.get_mut(parent) {
+                parent_entries.push(DirEntry {
+                    name: get_name(&dir),
+                    kind: 'd',
+                    digest: digest.clone(),
+                    size,
+                });
+            }
+        }
+
+        // Directories without data (empty or with empty files only) are not reported.
+        if size == 0 {
+            continue;
+        }
+
+        dir_files.push(FileInfo {
+            key: Key::new(size as u64, Some(digest)),
+            path: dir,
+            inode: None,
+            modified: None,
+            allocated: None,
+        });
+    }
+
+    dir_files
+}
+
+/// Get the file name (or directory name) of the path.
+fn get_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+/// Skip groups whose directories are all inside identical directories.
+fn remove_nested_groups(groups: Vec<GroupInfo>) -> Vec<GroupInfo> {
+    let identical_dirs: HashSet<PathBuf> = groups
+        .iter()
+        .flat_map(|group_info| group_info.paths.iter().cloned())
+        .collect();
+
+    groups
+        .into_iter()
+        .filter(|group_info| {
+            !group_info.paths.iter().all(|path| {
+                path.parent()
+                    .is_some_and(|parent| identical_dirs.contains(parent))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_dirs {
+    use super::*;
+    use clap::Parser;
+    use std::fs;
+
+    /// cargo test -- --show-output test_directory_groups
+    #[test]
+    fn test_directory_groups() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        // backup_1 and backup_2 are identical trees; other differs by one file name.
+        // The others differ by a hidden file, an empty file or an empty subdirectory.
+        for (tree, name) in [
+            ("backup_1", "b.txt"),
+            ("backup_2", "b.txt"),
+            ("other", "c.txt"),
+            ("hidden", "b.txt"),
+            ("empty_file", "b.txt"),
+            ("empty_dir", "b.txt"),
+        ] {
+            let sub = dir.join(tree).join("sub");
+            fs::create_dir_all(&sub)?;
+            fs::write(dir.join(tree).join("a.txt"), "first file")?;
+            fs::write(sub.join(name), "second file")?;
+        }
+        fs::write(dir.join("hidden").join(".hidden"), "hidden file")?;
+        fs::write(dir.join("empty_file").join("empty.txt"), "")?;
+        fs::create_dir(dir.join("empty_dir").join("empty"))?;
+
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--dirs",
+            "-i",
+            &dir.to_string_lossy(),
+        ]);
+
+        // The directories are found inside the input directory, as given by `get_paths`.
+        let root: PathBuf = get_paths(&arguments)?.remove(0);
+
+        let groups = get_directory_groups(&arguments, Instant::now());
+
+        let paths = |size: usize| -> HashSet<PathBuf> {
+            groups
+                .iter()
+                .filter(|group_info| group_info.key.size == size)
+                .flat_map(|group_info| group_info.paths.clone())
+                .collect()
+        };
+
+        // Only backup_1 and backup_2 are identical trees.
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            paths(21),
+            HashSet::from([root.join("backup_1"), root.join("backup_2")])
+        );
+
+        // Their subdirectories are also identical to those of the other trees.
+        assert_eq!(
+            paths(11),
+            ["backup_1", "backup_2", "hidden", "empty_file", "empty_dir"]
+                .iter()
+                .map(|tree| root.join(tree).join("sub"))
+                .collect()
+        );
+
+        Ok(())
+    }
+}
@@ -2,20 +2,33 @@ use ahash::AHasher;
 use blake3::Hasher as Blake3Hasher;
 use clap::ValueEnum;
 use rustc_hash::FxHasher;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256, Sha512};
+use sha3::{Sha3_256, Sha3_512};
 use std::{
     fmt,
-    fs::File,
     hash::Hasher,
-    io::{BufReader, Read},
-    path::PathBuf,
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
 };
+use xxhash_rust::xxh3::Xxh3;
 
-use crate::{open_file, Arguments, MyResult};
+use crate::{
+    archive::{is_archive_member, open_reader},
+    index::{get_known_digest, insert_known_digest},
+    open_file,
+    progress::{inc_bytes, ProgressReader},
+    resume::is_resuming,
+    source::find_source,
+    throttle::throttle,
+    Arguments, MyResult, OpenFile,
+};
+
+/// Files at least this large are hashed by Blake3 with multiple threads.
+const BLAKE3_RAYON_THRESHOLD: u64 = 128 * 1024 * 1024;
 
-const FIRST_BYTES: usize = 1024;
-const BUFFER_SIZE: usize = 64 * 1024;
+/// Buffer size used by the multithreaded Blake3 hasher.
+const BLAKE3_RAYON_BUFFER_SIZE: usize = 16 * 1024 * 1024;
 const HEX: [char; 16] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f',
 ];
@@ -38,6 +51,9 @@ impl SliceExtension for &[u8] {
 
 pub trait PathBufExtension {
     fn get_hash(&self, arguments: &Arguments, procedure: u8) -> MyResult<Option<String>>;
+
+    /// Compare the contents of two files byte by byte.
+    fn is_identical_to(&self, other: &Path, buffer_size: usize) -> MyResult<bool>;
 }
 
 impl PathBufExtension for PathBuf {
@@ -45,27 +61,143 @@ impl PathBufExtension for PathBuf {
     ///
     /// <https://rust-lang-nursery.github.io/rust-cookbook/cryptography/hashing.html>
     fn get_hash(&self, arguments: &Arguments, procedure: u8) -> MyResult<Option<String>> {
-        let mut file: File = open_file(self)?;
+        // Digests of the imported or exported indexes.
+        if procedure == 3 {
+            if let Some(digest) = get_known_digest(self, arguments.algorithm) {
+                return Ok(Some(digest));
+            }
+        }
+
+        // Files of other sources are hashed by the source (`--s3`, `--remote`).
+        if let Some(source) = find_source(self) {
+            return source.get_hash(self, arguments, procedure).map(Some);
+        }
+
+        // Members of archives are decompressed while they are hashed (`--scan_archives`).
+        if is_archive_member(self) {
+            return get_member_hash(self, arguments, procedure).map(Some);
+        }
+
+        let mut file: OpenFile = open_file(self)?;
 
         let hash: String = if procedure == 3 {
             // Apply the chosen hash algorithm to the entire file.
-            arguments.algorithm.calculate_hash(file)?
+            let size: u64 = file.metadata()?.len();
+            match arguments
+                .algorithm
+                .calculate_hash_mmap(self, size, arguments)
+            {
+                Some(hash) => hash,
+                None => arguments
+                    .algorithm
+                    .calculate_hash(file, arguments.buffer_size)?,
+            }
         } else {
             // Apply the hash algorithm the first bytes of the file.
-            let mut buffer = [0_u8; FIRST_BYTES];
+            let prefilter_bytes: usize = arguments.prefilter_bytes;
+            let mut buffer = vec![0_u8; prefilter_bytes];
 
-            // Read up to FIRST_BYTES bytes.
-            let count = file.read(&mut buffer)?;
+            // Read up to prefilter_bytes bytes.
+            let count = read_chunk(&mut file, &mut buffer)?;
 
             let mut hasher = AHasher::default();
             hasher.write(&buffer[..count]);
+
+            // Also apply the hash algorithm to the last bytes of the file.
+            if arguments.prefilter_tail {
+                let size: u64 = file.metadata()?.len();
+
+                // Skip bytes already read.
+                let start: u64 = size
+                    .saturating_sub(prefilter_bytes as u64)
+                    .max(count as u64);
+
+                if start < size {
+                    file.seek(SeekFrom::Start(start))?;
+                    let count = read_chunk(&mut file, &mut buffer)?;
+                    hasher.write(&buffer[..count]);
+                }
+            }
+
             hasher.finish().to_string()
 
             //buffer[..count].to_hex_string()
         };
 
+        // With `--resume`, the digest is saved in the next checkpoint.
+        if procedure == 3 && is_resuming() {
+            insert_known_digest(self, hash.clone(), arguments.algorithm);
+        }
+
         Ok(Some(hash))
     }
+
+    fn is_identical_to(&self, other: &Path, buffer_size: usize) -> MyResult<bool> {
+        let buffer_size: usize = buffer_size.max(1);
+
+        let mut reader_a = BufReader::with_capacity(buffer_size, open_reader(self)?);
+        let mut reader_b = BufReader::with_capacity(buffer_size, open_reader(other)?);
+
+        let mut buffer_a = vec![0_u8; buffer_size];
+        let mut buffer_b = vec![0_u8; buffer_size];
+
+        loop {
+            // Compare chunk by chunk.
+            let count_a = read_chunk(&mut reader_a, &mut buffer_a)?;
+            let count_b = read_chunk(&mut reader_b, &mut buffer_b)?;
+
+            if buffer_a[..count_a] != buffer_b[..count_b] {
+                return Ok(false);
+            }
+
+            if count_a == 0 {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+/**
+Hash the first few bytes or the entire content of a member of an archive.
+
+Members cannot be memory-mapped and `--prefilter_tail` is ignored:
+the content is only available as a stream.
+*/
+fn get_member_hash(path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+    let mut reader = open_reader(path)?;
+
+    if procedure == 3 {
+        let reader = BufReader::with_capacity(arguments.buffer_size.max(1), reader);
+        return hash_reader(
+            reader,
+            arguments.algorithm.new_digest(0),
+            arguments.buffer_size,
+        );
+    }
+
+    let mut buffer = vec![0_u8; arguments.prefilter_bytes];
+    let count = read_chunk(&mut reader, &mut buffer)?;
+
+    let mut hasher = AHasher::default();
+    hasher.write(&buffer[..count]);
+
+    Ok(hasher.finish().to_string())
+}
+
+/// Read bytes until the buffer is full or the end of file is reached.
+fn read_chunk(reader: &mut impl Read, buffer: &mut [u8]) -> MyResult<usize> {
+    let mut count = 0;
+
+    while count < buffer.len() {
+        let n = reader.read(&mut buffer[count..])?;
+        if n == 0 {
+            break;
+        }
+        throttle(n);
+        count += n;
+    }
+
+    Ok(count)
 }
 
 // An attribute #[default], usable on enum unit variants, is introduced
@@ -73,7 +205,7 @@ impl PathBufExtension for PathBuf {
 // <https://rust-lang.github.io/rfcs/3107-derive-default-enum.html>
 // <https://serde.rs/attr-rename.html>
 /// Hash Algorithm
-#[derive(Debug, Default, Clone, Copy, ValueEnum, Serialize)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum Algorithm {
     Ahash,
@@ -82,6 +214,15 @@ pub enum Algorithm {
     Fxhash,
     SHA256,
     SHA512,
+    #[value(name = "sha3-256")]
+    #[serde(rename = "Sha3-256")]
+    Sha3_256,
+    #[value(name = "sha3-512")]
+    #[serde(rename = "Sha3-512")]
+    Sha3_512,
+    #[value(name = "xxh3-128")]
+    #[serde(rename = "Xxh3-128")]
+    Xxh3,
 }
 
 /// Display an enum Algorithm in serde PascalCase.
@@ -103,60 +244,267 @@ impl fmt::Display for Algorithm {
 
 impl Algorithm {
     /// Calculate file hash using some algorithm
-    pub fn calculate_hash(&self, file: File) -> MyResult<String> {
-        let reader: BufReader<File> = BufReader::with_capacity(BUFFER_SIZE, file);
+    pub fn calculate_hash(&self, file: OpenFile, buffer_size: usize) -> MyResult<String> {
+        let size: u64 = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        let reader = BufReader::with_capacity(buffer_size.max(1), ProgressReader::new(file));
+
+        hash_reader(reader, self.new_digest(size), buffer_size)
+    }
+
+    /**
+    Calculate file hash and the number of bytes hashed.
+
+    The number of bytes may differ from the size reported by the filesystem
+    (ex.: files of `/proc` or `/sys`, files being written).
+    */
+    pub fn calculate_hash_and_size(
+        &self,
+        path: &Path,
+        buffer_size: usize,
+    ) -> MyResult<(String, u64)> {
+        let file: OpenFile = open_file(&path)?;
+        let size: u64 = file.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+        let reader = BufReader::with_capacity(buffer_size.max(1), ProgressReader::new(file));
+
+        hash_reader_with_size(reader, self.new_digest(size), buffer_size)
+    }
 
+    /**
+    Calculate file hash using memory-mapped I/O.
+
+    Returns None if `--mmap` is not chosen, the algorithm does not support it,
+    the file is smaller than `--mmap_min_size` or the file cannot be mapped.
+
+    In these cases, buffered reads must be used.
+    */
+    pub fn calculate_hash_mmap(
+        &self,
+        path: &Path,
+        size: u64,
+        arguments: &Arguments,
+    ) -> Option<String> {
+        if !arguments.mmap || size < arguments.mmap_min_size || *self != Algorithm::Blake3 {
+            return None;
+        }
+
+        let mut hasher = Blake3Hasher::new();
+
+        let result = if size >= BLAKE3_RAYON_THRESHOLD {
+            hasher.update_mmap_rayon(path).map(|_| ())
+        } else {
+            hasher.update_mmap(path).map(|_| ())
+        };
+
+        match result {
+            Ok(()) => {
+                inc_bytes(size);
+                Some(hasher.finalize().to_string())
+            }
+            Err(_) => None,
+        }
+    }
+
+    /**
+    Check and normalize a digest calculated with this algorithm (`--find_digest`).
+
+    Cryptographic hashes, Blake3 and XXH3 are hexadecimal strings of fixed length.
+
+    AHash and FxHash are displayed as decimal numbers (u64).
+    */
+    pub fn parse_digest(&self, digest: &str) -> Result<String, String> {
+        // Self-describing digests: `blake3:<hex>`.
+        let digest: &str = match Algorithm::split_digest(digest.trim()) {
+            (Some(algorithm), _) if algorithm != *self => {
+                return Err(format!("{algorithm} digest, expected a {self} digest"));
+            }
+            (_, digest) => digest,
+        };
+
+        let hex_length: usize = match self {
+            Algorithm::Ahash | Algorithm::Fxhash => {
+                return digest
+                    .parse::<u64>()
+                    .map(|number| number.to_string())
+                    .map_err(|_| format!("{self} digest must be a decimal number (u64)"));
+            }
+            Algorithm::Xxh3 => 32,
+            Algorithm::Blake3 | Algorithm::SHA256 | Algorithm::Sha3_256 => 64,
+            Algorithm::SHA512 | Algorithm::Sha3_512 => 128,
+        };
+
+        if digest.len() != hex_length {
+            return Err(format!(
+                "{self} digest must have {hex_length} hexadecimal digits, found {}",
+                digest.len()
+            ));
+        }
+
+        if !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!("{self} digest must be a hexadecimal string"));
+        }
+
+        Ok(digest.to_ascii_lowercase())
+    }
+
+    /// Name of the algorithm in the self-describing digests (the value of `--algorithm`).
+    pub fn tag(&self) -> &'static str {
         match self {
-            Algorithm::Ahash => get_ahash(reader),
-            Algorithm::Blake3 => get_blake3(reader),
-            Algorithm::Fxhash => get_fxhash(reader),
-            Algorithm::SHA256 => get_sha256(reader),
-            Algorithm::SHA512 => get_sha512(reader),
+            Algorithm::Ahash => "ahash",
+            Algorithm::Blake3 => "blake3",
+            Algorithm::Fxhash => "fxhash",
+            Algorithm::SHA256 => "sha256",
+            Algorithm::SHA512 => "sha512",
+            Algorithm::Sha3_256 => "sha3-256",
+            Algorithm::Sha3_512 => "sha3-512",
+            Algorithm::Xxh3 => "xxh3-128",
+        }
+    }
+
+    /**
+    Write the digest in self-describing form: `<algorithm>:<digest>`
+    (ex.: `blake3:af1349b9...`).
+
+    Reports and indexes remain unambiguous when several algorithms are used.
+
+    Digests already prefixed by an algorithm are not changed.
+    */
+    pub fn tag_digest(&self, digest: &str) -> String {
+        match Algorithm::split_digest(digest) {
+            (Some(_algorithm), _digest) => digest.to_string(),
+            (None, _digest) => format!("{}:{digest}", self.tag()),
+        }
+    }
+
+    /// Split a self-describing digest into its algorithm and the digest itself.
+    ///
+    /// Digests without the prefix of an algorithm are returned unchanged.
+    pub fn split_digest(digest: &str) -> (Option<Algorithm>, &str) {
+        digest
+            .split_once(':')
+            .and_then(|(tag, value)| {
+                Algorithm::value_variants()
+                    .iter()
+                    .find(|algorithm| algorithm.tag() == tag)
+                    .map(|algorithm| (Some(*algorithm), value))
+            })
+            .unwrap_or((None, digest))
+    }
+
+    /// Create a new digest for a file of the given size.
+    ///
+    /// Adding an algorithm only requires a new FileDigest implementation.
+    pub fn new_digest(&self, size: u64) -> Box<dyn FileDigest> {
+        match self {
+            Algorithm::Ahash => Box::new(AHasher::default()),
+            Algorithm::Blake3 if size >= BLAKE3_RAYON_THRESHOLD => {
+                Box::new(Blake3Rayon(Blake3Hasher::new()))
+            }
+            Algorithm::Blake3 => Box::new(Blake3Hasher::new()),
+            Algorithm::Fxhash => Box::new(FxHasher::default()),
+            Algorithm::SHA256 => Box::new(Sha256::new()),
+            Algorithm::SHA512 => Box::new(Sha512::new()),
+            Algorithm::Sha3_256 => Box::new(Sha3_256::new()),
+            Algorithm::Sha3_512 => Box::new(Sha3_512::new()),
+            Algorithm::Xxh3 => Box::new(Xxh3::new()),
         }
     }
 }
 
-/// Calculates the aHash from Path.
+/// Generic digest abstraction shared by all hash algorithms.
+pub trait FileDigest {
+    /// Minimum size of the chunks passed to `update`.
+    fn min_buffer_size(&self) -> usize {
+        1
+    }
+
+    /// Process a chunk of data, updating the internal state.
+    fn update(&mut self, data: &[u8]);
+
+    /// Consume the digest and return the hash as a String.
+    fn finalize(self: Box<Self>) -> String;
+}
+
+/// Read the entire content and apply the digest.
+pub fn hash_reader(
+    reader: impl Read,
+    digest: Box<dyn FileDigest>,
+    buffer_size: usize,
+) -> MyResult<String> {
+    hash_reader_with_size(reader, digest, buffer_size).map(|(hash, _size)| hash)
+}
+
+/// Read the entire content and apply the digest.
 ///
-/// <https://crates.io/crates/ahash>
-fn get_ahash(mut reader: impl Read) -> MyResult<String> {
-    let mut buffer = [0_u8; BUFFER_SIZE];
-    let mut hasher = AHasher::default();
+/// Also returns the number of bytes read.
+pub(crate) fn hash_reader_with_size(
+    mut reader: impl Read,
+    mut digest: Box<dyn FileDigest>,
+    buffer_size: usize,
+) -> MyResult<(String, u64)> {
+    let mut buffer = vec![0_u8; buffer_size.max(digest.min_buffer_size())];
+    let mut size: u64 = 0;
 
     loop {
-        // read up to BUFFER_SIZE bytes to buffer
-        let count = reader.read(&mut buffer)?;
+        let count = read_chunk(&mut reader, &mut buffer)?;
         if count == 0 {
             break;
         }
-        hasher.write(&buffer[..count]);
+        digest.update(&buffer[..count]);
+        size += count as u64;
     }
 
-    Ok(hasher.finish().to_string())
+    Ok((digest.finalize(), size))
+}
+
+/// aHash
+///
+/// <https://crates.io/crates/ahash>
+impl FileDigest for AHasher {
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        self.finish().to_string()
+    }
 }
 
-/// Calculates the Blake3 hash from Path.
+/// Blake3
 ///
 /// <https://docs.rs/blake3/latest/blake3>
-fn get_blake3<R>(mut reader: R) -> MyResult<String>
-where
-    R: Read,
-{
-    let mut buffer = [0_u8; BUFFER_SIZE];
-    let mut hasher = Blake3Hasher::new();
+impl FileDigest for Blake3Hasher {
+    fn update(&mut self, data: &[u8]) {
+        Blake3Hasher::update(self, data);
+    }
 
-    loop {
-        let count = reader.read(&mut buffer)?;
-        if count == 0 {
-            break;
-        }
-        hasher.update(&buffer[..count]);
+    fn finalize(self: Box<Self>) -> String {
+        Blake3Hasher::finalize(&self).to_string()
     }
+}
+
+/// Blake3 using multiple threads.
+///
+/// Large files are read in big chunks and each chunk is hashed
+/// in parallel, so a single large file is not limited to one core.
+///
+/// <https://docs.rs/blake3/latest/blake3/struct.Hasher.html#method.update_rayon>
+struct Blake3Rayon(Blake3Hasher);
 
-    Ok(hasher.finalize().to_string())
+impl FileDigest for Blake3Rayon {
+    fn min_buffer_size(&self) -> usize {
+        BLAKE3_RAYON_BUFFER_SIZE
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        self.0.update_rayon(data);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        self.0.finalize().to_string()
+    }
 }
 
-/// Calculates the FxHash from Path.
+/// FxHash
 ///
 /// Fast, non-cryptographic hash function used by rustc and Firefox.
 ///
@@ -165,74 +513,196 @@ where
 /// <https://crates.io/crates/rustc-hash>
 ///
 /// <https://nnethercote.github.io/2021/12/08/a-brutally-effective-hash-function-in-rust.html>
-fn get_fxhash<R>(mut reader: R) -> MyResult<String>
-where
-    R: Read,
-{
-    let mut buffer = [0_u8; BUFFER_SIZE];
-    let mut hasher = FxHasher::default();
-
-    loop {
-        let count = reader.read(&mut buffer)?;
-        if count == 0 {
-            break;
-        }
-        hasher.write(&buffer[..count]);
+impl FileDigest for FxHasher {
+    fn update(&mut self, data: &[u8]) {
+        self.write(data);
     }
 
-    Ok(hasher.finish().to_string())
+    fn finalize(self: Box<Self>) -> String {
+        self.finish().to_string()
+    }
 }
 
-/// Calculates the SHA2 256 from Path.
+/// SHA2 256
 ///
 /// Verify with
 ///
 /// openssl dgst -sha256 Some_File
 ///
 /// <https://github.com/RustCrypto/hashes/tree/master/sha2>
-fn get_sha256<R>(mut reader: R) -> MyResult<String>
-where
-    R: Read,
-{
-    let mut buffer = [0_u8; BUFFER_SIZE];
-    let mut hasher = Sha256::new();
-
-    loop {
-        let count = reader.read(&mut buffer)?;
-        if count == 0 {
-            break;
-        }
-        // `update` can be called repeatedly and is generic over `AsRef<[u8]>`
-        hasher.update(&buffer[..count]);
+impl FileDigest for Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
     }
 
-    // Note that calling `finalize()` consumes hasher
-    Ok(hasher.finalize().to_hex_string())
+    fn finalize(self: Box<Self>) -> String {
+        Digest::finalize(*self).to_hex_string()
+    }
 }
 
-/// Calculates the SHA2 512 from Path.
+/// SHA2 512
 ///
 /// Verify with
 ///
 /// openssl dgst -sha512 Some_File
 ///
 /// <https://github.com/RustCrypto/hashes/tree/master/sha2>
-fn get_sha512<R>(mut reader: R) -> MyResult<String>
-where
-    R: Read,
-{
-    let mut buffer = [0_u8; BUFFER_SIZE];
-    let mut hasher = Sha512::new();
+impl FileDigest for Sha512 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
 
-    loop {
-        let count = reader.read(&mut buffer)?;
-        if count == 0 {
-            break;
-        }
-        // `update` can be called repeatedly and is generic over `AsRef<[u8]>`
-        hasher.update(&buffer[..count]);
+    fn finalize(self: Box<Self>) -> String {
+        Digest::finalize(*self).to_hex_string()
     }
+}
 
-    // Note that calling `finalize()` consumes hasher
-    Ok(hasher.finalize().to_hex_string())
+/// SHA3 256
+///
+/// Verify with
+///
+/// openssl dgst -sha3-256 Some_File
+///
+/// <https://github.com/RustCrypto/hashes/tree/master/sha3>
+impl FileDigest for Sha3_256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        Digest::finalize(*self).to_hex_string()
+    }
+}
+
+/// SHA3 512
+///
+/// Verify with
+///
+/// openssl dgst -sha3-512 Some_File
+///
+/// <https://github.com/RustCrypto/hashes/tree/master/sha3>
+impl FileDigest for Sha3_512 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        Digest::finalize(*self).to_hex_string()
+    }
+}
+
+/// XXH3 128 bits
+///
+/// Very fast, non-cryptographic hash function.
+///
+/// Consider combining it with `--paranoid`.
+///
+/// <https://crates.io/crates/xxhash-rust>
+impl FileDigest for Xxh3 {
+    fn update(&mut self, data: &[u8]) {
+        Xxh3::update(self, data);
+    }
+
+    fn finalize(self: Box<Self>) -> String {
+        format!("{:032x}", self.digest128())
+    }
+}
+
+#[cfg(test)]
+mod test_algo {
+    use super::*;
+
+    #[test]
+    fn blake3_rayon_matches_blake3() -> MyResult<()> {
+        // cargo test -- --show-output blake3_rayon_matches_blake3
+        let data: Vec<u8> = (0..BLAKE3_RAYON_BUFFER_SIZE + 12345)
+            .map(|i| (i % 251) as u8)
+            .collect();
+
+        let serial = hash_reader(&data[..], Algorithm::Blake3.new_digest(0), 64 * 1024)?;
+        let parallel = hash_reader(&data[..], Algorithm::Blake3.new_digest(u64::MAX), 64 * 1024)?;
+
+        assert_eq!(serial, parallel);
+        assert_eq!(serial, blake3::hash(&data).to_string());
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha256_of_known_input() -> MyResult<()> {
+        // cargo test -- --show-output sha256_of_known_input
+        let hash = hash_reader(&b"abc"[..], Algorithm::SHA256.new_digest(3), 64 * 1024)?;
+
+        assert_eq!(
+            hash,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn sha3_256_of_known_input() -> MyResult<()> {
+        // cargo test -- --show-output sha3_256_of_known_input
+        let hash = hash_reader(&b"abc"[..], Algorithm::Sha3_256.new_digest(3), 64 * 1024)?;
+
+        assert_eq!(
+            hash,
+            "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_digest_checks_length() {
+        // cargo test -- --show-output parse_digest_checks_length
+        let sha256 = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+
+        assert_eq!(
+            Algorithm::SHA256.parse_digest(sha256),
+            Ok(sha256.to_ascii_lowercase())
+        );
+        assert!(Algorithm::SHA512.parse_digest(sha256).is_err());
+        assert!(Algorithm::Xxh3.parse_digest(&sha256[..32]).is_ok());
+        assert!(Algorithm::Blake3
+            .parse_digest(&sha256.replace('A', "g"))
+            .is_err());
+        assert_eq!(Algorithm::Fxhash.parse_digest("42"), Ok("42".to_string()));
+        assert!(Algorithm::Ahash.parse_digest("ff").is_err());
+    }
+
+    #[test]
+    fn tag_digest_is_self_describing() {
+        // cargo test -- --show-output tag_digest_is_self_describing
+        let tagged: String = Algorithm::Blake3.tag_digest("abc");
+        assert_eq!(tagged, "blake3:abc");
+        assert_eq!(Algorithm::Blake3.tag_digest(&tagged), tagged);
+        assert_eq!(Algorithm::Sha3_256.tag_digest("abc"), "sha3-256:abc");
+
+        assert_eq!(
+            Algorithm::split_digest("sha512:abc"),
+            (Some(Algorithm::SHA512), "abc")
+        );
+        assert_eq!(Algorithm::split_digest("abc"), (None, "abc"));
+        assert!(Algorithm::SHA256
+            .parse_digest(&format!("blake3:{}", "0".repeat(64)))
+            .is_err());
+        assert!(Algorithm::SHA256
+            .parse_digest(&format!("sha256:{}", "0".repeat(64)))
+            .is_ok());
+    }
+
+    #[test]
+    fn hash_reader_counts_bytes() -> MyResult<()> {
+        // cargo test -- --show-output hash_reader_counts_bytes
+        let data: Vec<u8> = vec![7; 100_000];
+
+        let (hash, size) = hash_reader_with_size(&data[..], Algorithm::Blake3.new_digest(0), 4096)?;
+
+        assert_eq!(size, 100_000);
+        assert_eq!(hash, blake3::hash(&data).to_string());
+
+        Ok(())
+    }
 }
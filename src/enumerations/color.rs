@@ -0,0 +1,79 @@
+use anstyle::{AnsiColor, Color, Style};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    io::{self, IsTerminal},
+};
+
+use crate::Arguments;
+
+/// When to color the Personal format.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum ColorChoice {
+    /// Color only if stdout is a terminal and `NO_COLOR` is not set
+    #[default]
+    Auto,
+    /// Always color
+    Always,
+    /// Never color
+    Never,
+}
+
+impl ColorChoice {
+    /**
+    Check if the result must be colored.
+
+    With `auto`, the result is plain when piped, written to a file (`--output`, `--report_dir`)
+    or when the `NO_COLOR` environment variable is set (<https://no-color.org>).
+    */
+    pub fn is_enabled(&self, arguments: &Arguments) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_color
+                    && arguments.output.is_none()
+                    && arguments.report_dir.is_none()
+                    && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Styles of the Personal format.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Theme {
+    /// Labels (ex.: "Wasted size:")
+    pub label: Style,
+    /// Paths of the groups, alternating between two colors (banding)
+    pub bands: [Style; 2],
+    /// Wasted size
+    pub wasted: Style,
+}
+
+impl Theme {
+    /// Get the theme of the Personal format: no style if the result is not colored.
+    pub fn new(arguments: &Arguments) -> Self {
+        if !arguments.color.is_enabled(arguments) {
+            return Theme::default();
+        }
+
+        Theme {
+            label: Style::new().bold(),
+            bands: [
+                Style::new().fg_color(Some(Color::Ansi(AnsiColor::Cyan))),
+                Style::new().fg_color(Some(Color::Ansi(AnsiColor::Yellow))),
+            ],
+            wasted: Style::new()
+                .fg_color(Some(Color::Ansi(AnsiColor::Red)))
+                .bold(),
+        }
+    }
+
+    /// Style of the paths of the group (index: position of the group).
+    pub fn band(&self, index: usize) -> Style {
+        self.bands[index % self.bands.len()]
+    }
+}
@@ -0,0 +1,66 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Compression of the result files (`--compress`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum Compression {
+    /// gzip (`.gz`): readable everywhere
+    Gzip,
+    /// Zstandard (`.zst`): faster and smaller than gzip
+    Zstd,
+}
+
+impl Compression {
+    /// File extension of the compressed files.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+        }
+    }
+
+    /// Get the compression of a file from its extension (`.gz` or `.zst`).
+    pub fn from_path(path: &Path) -> Option<Compression> {
+        let extension = path.extension()?;
+
+        Compression::value_variants()
+            .iter()
+            .find(|compression| extension == compression.extension())
+            .copied()
+    }
+
+    /// Add the extension of the compression to the filename, if missing.
+    ///
+    /// Ex.: `result.json` -> `result.json.zst`.
+    pub fn add_extension(&self, path: PathBuf) -> PathBuf {
+        if Compression::from_path(&path) == Some(*self) {
+            return path;
+        }
+
+        let mut path = path.into_os_string();
+        path.push(".");
+        path.push(self.extension());
+        PathBuf::from(path)
+    }
+}
+
+#[cfg(test)]
+mod test_compression {
+    use super::*;
+
+    /// cargo test -- --show-output test_compression_extension
+    #[test]
+    fn test_compression_extension() {
+        let path = PathBuf::from("result.json");
+        assert_eq!(Compression::from_path(&path), None);
+        assert_eq!(
+            Compression::Zstd.add_extension(path),
+            PathBuf::from("result.json.zst")
+        );
+
+        let path = PathBuf::from("result.json.gz");
+        assert_eq!(Compression::from_path(&path), Some(Compression::Gzip));
+        assert_eq!(Compression::Gzip.add_extension(path.clone()), path);
+    }
+}
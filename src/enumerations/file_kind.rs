@@ -0,0 +1,18 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Type of a file detected from its content (`--file_type`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileKind {
+    /// JPEG, PNG, GIF, BMP, TIFF, WebP, ICO, HEIC and AVIF
+    Image,
+    /// MP4, QuickTime, Matroska, WebM, AVI, FLV, MPEG and WMV
+    Video,
+    /// MP3, FLAC, Ogg, WAV, AIFF, M4A, AAC and MIDI
+    Audio,
+    /// PDF, PostScript, RTF, EPUB, OpenDocument and Microsoft Office
+    Document,
+    /// ZIP, gzip, bzip2, xz, Zstandard, 7-Zip, RAR and tar
+    Archive,
+}
@@ -0,0 +1,11 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// How the files are grouped before hashing (`--heuristic`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum Heuristic {
+    /// Files with the same name and the same size
+    #[value(name = "name+size")]
+    #[serde(rename = "name+size")]
+    NameSize,
+}
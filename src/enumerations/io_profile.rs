@@ -0,0 +1,112 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{path::Path, sync::OnceLock};
+
+use crate::{get_paths, Arguments};
+
+/// Number of threads reading the files of spinning disks.
+const HDD_IO_THREADS: usize = 1;
+
+/// Profile of the disks chosen or detected once (`--io_profile`).
+static IO_PROFILE: OnceLock<IoProfile> = OnceLock::new();
+
+/// How to schedule the reading of the files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum IoProfile {
+    /// Detect if an input directory is on a spinning disk (Linux only)
+    #[default]
+    Auto,
+    /// Spinning disk: one reader, files hashed in physical order (inode)
+    Hdd,
+    /// Solid-state drive: as many readers as possible
+    Ssd,
+}
+
+impl IoProfile {
+    /**
+    Get the profile of the disks: `hdd` or `ssd`.
+
+    With `auto`, the profile is `hdd` if any input directory is on
+    a rotational device (`/sys/block/<device>/queue/rotational`), otherwise `ssd`.
+    */
+    pub fn resolve(arguments: &Arguments) -> IoProfile {
+        *IO_PROFILE.get_or_init(|| match arguments.io_profile {
+            IoProfile::Auto => {
+                let paths = get_paths(arguments).unwrap_or_default();
+                if paths
+                    .iter()
+                    .any(|path| is_rotational(path).unwrap_or(false))
+                {
+                    IoProfile::Hdd
+                } else {
+                    IoProfile::Ssd
+                }
+            }
+            profile => profile,
+        })
+    }
+
+    /**
+    Get the number of threads reading the files.
+
+    `--io_threads` takes precedence. Otherwise, one reader on spinning disks
+    (seeks are slower than hashing) and, on solid-state drives,
+    twice the number of CPUs (reads are waiting for the device).
+
+    With `--jobs`, the number of readers is at most the number of jobs.
+    */
+    pub fn get_io_threads(arguments: &Arguments) -> usize {
+        if arguments.io_threads > 0 {
+            return arguments.io_threads;
+        }
+
+        match IoProfile::resolve(arguments) {
+            IoProfile::Hdd => HDD_IO_THREADS,
+            _ if arguments.jobs.is_some() => rayon::current_num_threads(),
+            _ => 2 * rayon::current_num_threads(),
+        }
+    }
+}
+
+/// Check if the file or directory is on a rotational device.
+#[cfg(target_os = "linux")]
+fn is_rotational(path: &Path) -> Option<bool> {
+    use std::{fs, os::unix::fs::MetadataExt};
+
+    let (major, minor) = get_major_minor(fs::metadata(path).ok()?.dev());
+    let device = fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")).ok()?;
+
+    // Partitions (ex.: sda/sda1): the queue is in the parent directory.
+    device.ancestors().take(2).find_map(|dir| {
+        fs::read_to_string(dir.join("queue/rotational"))
+            .ok()
+            .map(|rotational| rotational.trim() == "1")
+    })
+}
+
+/// Check if the file or directory is on a rotational device.
+#[cfg(not(target_os = "linux"))]
+fn is_rotational(_path: &Path) -> Option<bool> {
+    None
+}
+
+/// Split the device number into (major, minor), as glibc `major()` and `minor()`.
+#[cfg(target_os = "linux")]
+fn get_major_minor(device: u64) -> (u64, u64) {
+    let major = ((device >> 32) & 0xffff_f000) | ((device >> 8) & 0x0000_0fff);
+    let minor = ((device >> 12) & 0xffff_ff00) | (device & 0x0000_00ff);
+    (major, minor)
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod test_io_profile {
+    use super::*;
+
+    /// cargo test -- --show-output test_get_major_minor
+    #[test]
+    fn test_get_major_minor() {
+        assert_eq!(get_major_minor(0x0801), (8, 1)); // sda1
+        assert_eq!(get_major_minor(0x10300), (259, 0)); // nvme0n1
+        assert_eq!(get_major_minor(0xfd00), (253, 0)); // dm-0
+    }
+}
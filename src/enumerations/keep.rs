@@ -0,0 +1,205 @@
+use crate::{filter::build_glob_set, Arguments, MyResult};
+use clap::ValueEnum;
+use globset::GlobSet;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Strategy used to choose which file to keep in each group of identical files.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub enum KeepStrategy {
+    /// Keep the most recently modified file.
+    Newest,
+    /// Keep the least recently modified file.
+    Oldest,
+    /// Keep the first file in alphabetical order.
+    #[default]
+    FirstAlphabetical,
+    /// Keep the file with the shortest path.
+    ShortestPath,
+}
+
+impl KeepStrategy {
+    /// Get the index of the file to be kept.
+    ///
+    /// Ties are broken by alphabetical order, so the choice does not
+    /// depend on the order in which the files were found.
+    pub fn select(&self, paths: &[PathBuf]) -> Option<usize> {
+        let indexes = 0..paths.len();
+
+        match self {
+            KeepStrategy::Newest => indexes.max_by(|&a, &b| {
+                let time_a = get_modified(&paths[a]);
+                let time_b = get_modified(&paths[b]);
+                time_a.cmp(&time_b).then(paths[b].cmp(&paths[a]))
+            }),
+            KeepStrategy::Oldest => indexes.min_by(|&a, &b| {
+                let time_a = get_modified(&paths[a]);
+                let time_b = get_modified(&paths[b]);
+                time_a.cmp(&time_b).then(paths[a].cmp(&paths[b]))
+            }),
+            KeepStrategy::FirstAlphabetical => indexes.min_by(|&a, &b| paths[a].cmp(&paths[b])),
+            KeepStrategy::ShortestPath => indexes.min_by(|&a, &b| {
+                let len_a = paths[a].as_os_str().len();
+                let len_b = paths[b].as_os_str().len();
+                len_a.cmp(&len_b).then(paths[a].cmp(&paths[b]))
+            }),
+        }
+    }
+}
+
+/**
+Rules used to choose the file to be kept in each group of identical files.
+
+Files matching `--prefer` patterns are kept first and files matching
+`--avoid` patterns are kept last (`--avoid` wins if both match).
+
+Among the files with the same priority, the `--keep` strategy is applied.
+*/
+#[derive(Debug, Clone)]
+pub struct KeepRules {
+    /// Glob patterns of files to be kept first
+    prefer: GlobSet,
+    /// Glob patterns of files to be kept last
+    avoid: GlobSet,
+    /// Strategy applied among files with the same priority
+    strategy: KeepStrategy,
+}
+
+impl KeepRules {
+    /// Build the rules from the command line arguments.
+    pub fn new(arguments: &Arguments) -> MyResult<Self> {
+        Ok(KeepRules {
+            prefer: build_glob_set(&arguments.prefer)?,
+            avoid: build_glob_set(&arguments.avoid)?,
+            strategy: arguments.keep,
+        })
+    }
+
+    /// Get the priority of the file: 0 (prefer), 1 (neutral) or 2 (avoid).
+    fn get_priority(&self, path: &Path) -> u8 {
+        if self.avoid.is_match(path) {
+            2
+        } else if self.prefer.is_match(path) {
+            0
+        } else {
+            1
+        }
+    }
+
+    /// Get the index of the file to be kept.
+    pub fn select(&self, paths: &[PathBuf]) -> Option<usize> {
+        let priorities: Vec<u8> = paths.iter().map(|path| self.get_priority(path)).collect();
+        let best: u8 = *priorities.iter().min()?;
+
+        let indexes: Vec<usize> = (0..paths.len())
+            .filter(|&index| priorities[index] == best)
+            .collect();
+
+        let candidates: Vec<PathBuf> = indexes.iter().map(|&index| paths[index].clone()).collect();
+
+        self.strategy
+            .select(&candidates)
+            .map(|index| indexes[index])
+    }
+}
+
+/// Get the last modification time of the file.
+///
+/// Files without this information are considered the oldest.
+fn get_modified(path: &Path) -> SystemTime {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+#[cfg(test)]
+mod test_keep {
+    use super::*;
+
+    /// cargo test -- --show-output test_keep_strategy
+    #[test]
+    fn test_keep_strategy() {
+        let paths: Vec<PathBuf> = ["/tmp/b/file.txt", "/tmp/a/long_name.txt", "/c.txt"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        let first = KeepStrategy::FirstAlphabetical.select(&paths);
+        let shortest = KeepStrategy::ShortestPath.select(&paths);
+
+        assert_eq!(first, Some(2));
+        assert_eq!(shortest, Some(2));
+
+        let paths: Vec<PathBuf> = ["/tmp/b/file.txt", "/tmp/a/long_name.txt"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        assert_eq!(KeepStrategy::FirstAlphabetical.select(&paths), Some(1));
+        assert_eq!(KeepStrategy::ShortestPath.select(&paths), Some(0));
+        assert_eq!(KeepStrategy::Newest.select(&[]), None);
+    }
+
+    /// cargo test -- --show-output test_keep_rules
+    #[test]
+    fn test_keep_rules() -> MyResult<()> {
+        use clap::Parser;
+
+        let paths: Vec<PathBuf> = [
+            "/tmp/a.txt",
+            "/home/user/originals/deep/z.txt",
+            "/home/user/copies/b.txt",
+            "/home/user/originals/y.txt",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--prefer",
+            "**/originals/**",
+            "--avoid",
+            "/tmp/**",
+        ]);
+        let rules = KeepRules::new(&arguments)?;
+
+        // The preferred files are chosen, even if not the first in alphabetical order.
+        assert_eq!(rules.select(&paths), Some(1));
+
+        // The keep strategy is applied among the preferred files.
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--prefer",
+            "**/originals/**",
+            "--keep",
+            "shortest-path",
+        ]);
+        assert_eq!(KeepRules::new(&arguments)?.select(&paths), Some(3));
+
+        // Avoided files are kept only if all files are avoided.
+        let arguments = Arguments::parse_from(["find-identical-files", "--avoid", "/home/**"]);
+        let rules = KeepRules::new(&arguments)?;
+        assert_eq!(rules.select(&paths), Some(0));
+        assert_eq!(rules.select(&paths[1..]), Some(1));
+
+        // If a file is both preferred and avoided, it is avoided.
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--prefer",
+            "**/originals/**",
+            "--avoid",
+            "**/deep/**",
+        ]);
+        assert_eq!(KeepRules::new(&arguments)?.select(&paths), Some(3));
+
+        assert_eq!(KeepRules::new(&arguments)?.select(&[]), None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Language of the messages and of the Personal format (`--lang`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum Lang {
+    /// English
+    #[default]
+    En,
+    /// Brazilian Portuguese
+    #[value(name = "pt-BR", alias = "pt_BR", alias = "pt")]
+    #[serde(rename = "pt-BR")]
+    PtBr,
+}
+
+impl Lang {
+    /**
+    Get the language of the environment: `LC_ALL`, `LC_MESSAGES` or `LANG`
+    (the first one that is set, as in gettext).
+
+    Ex.: `LANG=pt_BR.UTF-8` -> pt-BR. Unsupported languages fall back to English.
+    */
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Lang::En, |value| Lang::from_locale(&value))
+    }
+
+    /// Get the language of a locale (ex.: "pt_BR.UTF-8", "en_US", "C").
+    pub fn from_locale(locale: &str) -> Self {
+        let language: &str = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default();
+
+        if language.eq_ignore_ascii_case("pt") {
+            Lang::PtBr
+        } else {
+            Lang::En
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_lang {
+    use super::*;
+
+    /// cargo test -- --show-output test_lang_from_locale
+    #[test]
+    fn test_lang_from_locale() {
+        assert_eq!(Lang::from_locale("pt_BR.UTF-8"), Lang::PtBr);
+        assert_eq!(Lang::from_locale("pt"), Lang::PtBr);
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Lang::En);
+        assert_eq!(Lang::from_locale("C"), Lang::En);
+    }
+}
@@ -0,0 +1,31 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Level of the log messages (`--log_level`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Errors only
+    Error,
+    /// Errors and warnings
+    Warn,
+    /// Procedures of the search, with their timings
+    #[default]
+    Info,
+    /// Details of each procedure
+    Debug,
+    /// Everything
+    Trace,
+}
+
+impl From<LogLevel> for tracing::Level {
+    fn from(log_level: LogLevel) -> Self {
+        match log_level {
+            LogLevel::Error => tracing::Level::ERROR,
+            LogLevel::Warn => tracing::Level::WARN,
+            LogLevel::Info => tracing::Level::INFO,
+            LogLevel::Debug => tracing::Level::DEBUG,
+            LogLevel::Trace => tracing::Level::TRACE,
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+/// Metadata compared between identical files (`--compare_metadata`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MetadataKey {
+    /// Last modification time (in seconds)
+    Mtime,
+    /// User and group ids (unix only)
+    Owner,
+    /// Permission bits (unix only)
+    Perms,
+    /// Extended attributes: names and values (unix only)
+    Xattr,
+}
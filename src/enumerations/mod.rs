@@ -1,6 +1,31 @@
 pub mod algo;
+pub mod color;
+pub mod compression;
+pub mod file_kind;
+pub mod heuristic;
+pub mod io_profile;
+pub mod keep;
+pub mod lang;
+pub mod log_level;
+pub mod metadata;
+pub mod path_style;
+pub mod script;
+pub mod sort;
 
 /*
 Enumerations defined in this directory:
     Algorithm,
+    ColorChoice,
+    Compression,
+    FileKind,
+    Heuristic,
+    IoProfile,
+    KeepStrategy,
+    Lang,
+    LogLevel,
+    MetadataKey,
+    PathStyle,
+    ScriptFormat,
+    SortKey,
+    SortOrder,
 */
@@ -0,0 +1,124 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    borrow::Cow,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::{Arguments, MyResult};
+
+/// Style of the paths displayed in the reports (`--path_style`).
+static PATH_STYLE: OnceLock<PathStyle> = OnceLock::new();
+
+/// How the paths of identical files are displayed (`--path_style`).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum PathStyle {
+    /// Paths relative to the input directories, as given on the command line
+    #[default]
+    Relative,
+    /// Absolute paths, without resolving symbolic links
+    Absolute,
+    /// Absolute paths with symbolic links resolved (same as `-e`)
+    Canonical,
+    /// Absolute paths with the home directory abbreviated to `~`
+    Home,
+}
+
+impl PathStyle {
+    /**
+    Get the input directory to be walked.
+
+    The paths of the files found are then already in the chosen style,
+    except for `canonical` (links below the input directory) and `home` (`~`).
+    */
+    pub fn get_root(&self, path: PathBuf) -> MyResult<PathBuf> {
+        let root = match self {
+            PathStyle::Relative => path,
+            PathStyle::Absolute | PathStyle::Home => std::path::absolute(path)?,
+            PathStyle::Canonical => fs::canonicalize(path)?,
+        };
+        Ok(root)
+    }
+
+    /// Get the path of a file in the chosen style.
+    pub fn display<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        match self {
+            PathStyle::Relative | PathStyle::Absolute => Cow::Borrowed(path),
+            PathStyle::Canonical => match fs::canonicalize(path) {
+                Ok(canonical) => Cow::Owned(canonical),
+                Err(_) => Cow::Borrowed(path),
+            },
+            PathStyle::Home => match get_home_dir() {
+                Some(home) => abbreviate_home(path, &home),
+                None => Cow::Borrowed(path),
+            },
+        }
+    }
+}
+
+/// Set the style of the paths displayed in all output formats.
+pub fn init_path_style(arguments: &Arguments) {
+    let _ = PATH_STYLE.set(arguments.path_style);
+}
+
+/**
+Get the path of a file as displayed in the reports.
+
+Only the reports are changed: actions, scripts and `--print0`
+use the paths of the files found.
+*/
+pub fn display_path(path: &Path) -> Cow<'_, Path> {
+    match PATH_STYLE.get() {
+        Some(path_style) => path_style.display(path),
+        None => Cow::Borrowed(path),
+    }
+}
+
+/// Serialize the paths in the style of `--path_style`.
+pub fn serialize_paths<S>(paths: &[PathBuf], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(paths.iter().map(|path| display_path(path)))
+}
+
+/// Get the home directory: `$HOME` (`%USERPROFILE%` on Windows).
+fn get_home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Replace the home directory at the start of the path by `~`.
+fn abbreviate_home<'a>(path: &'a Path, home: &Path) -> Cow<'a, Path> {
+    match path.strip_prefix(home) {
+        Ok(relative) if relative.as_os_str().is_empty() => Cow::Owned(PathBuf::from("~")),
+        Ok(relative) => Cow::Owned(Path::new("~").join(relative)),
+        Err(_) => Cow::Borrowed(path),
+    }
+}
+
+#[cfg(test)]
+mod test_path_style {
+    use super::*;
+
+    /// cargo test -- --show-output test_abbreviate_home
+    #[test]
+    fn test_abbreviate_home() {
+        let home = Path::new("/home/user");
+
+        assert_eq!(
+            abbreviate_home(Path::new("/home/user/Photos/a.jpg"), home).as_ref(),
+            Path::new("~/Photos/a.jpg")
+        );
+        assert_eq!(abbreviate_home(home, home).as_ref(), Path::new("~"));
+        // Only whole components are abbreviated.
+        assert_eq!(
+            abbreviate_home(Path::new("/home/user2/a.jpg"), home).as_ref(),
+            Path::new("/home/user2/a.jpg")
+        );
+    }
+}
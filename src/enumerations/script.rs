@@ -0,0 +1,272 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{
+    actions::{get_symlink_target, Action, Task},
+    MyResult,
+};
+
+/// Format of the script of proposed actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum ScriptFormat {
+    /// Bash script (`$'...'` quoting)
+    Sh,
+    /// Windows batch file
+    Bat,
+    /// PowerShell script
+    Ps1,
+}
+
+impl ScriptFormat {
+    /// File extension of the script.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ScriptFormat::Sh => "sh",
+            ScriptFormat::Bat => "bat",
+            ScriptFormat::Ps1 => "ps1",
+        }
+    }
+
+    /// First lines of the script.
+    pub fn header(&self) -> String {
+        let comment = self.comment("Generated by find-identical-files: review before running!");
+        match self {
+            ScriptFormat::Sh => format!("#!/usr/bin/env bash\nset -e\n\n{comment}\n"),
+            ScriptFormat::Bat => format!("@echo off\n\n{comment}\n"),
+            ScriptFormat::Ps1 => format!("$ErrorActionPreference = 'Stop'\n\n{comment}\n"),
+        }
+    }
+
+    /// Comment line.
+    pub fn comment(&self, text: &str) -> String {
+        match self {
+            ScriptFormat::Sh | ScriptFormat::Ps1 => format!("# {text}"),
+            ScriptFormat::Bat => format!("REM {text}"),
+        }
+    }
+
+    /// Command lines that apply the action of the task.
+    ///
+    /// With `--move_to`, `destination` is the path in the quarantine directory.
+    pub fn commands(&self, task: &Task, destination: Option<&Path>) -> MyResult<Vec<String>> {
+        let path = self.quote(task.path)?;
+        let keeper = self.quote(task.keeper)?;
+
+        let commands = match (self, task.action) {
+            (ScriptFormat::Sh, Action::Delete) => vec![format!("rm -- {path}")],
+            (ScriptFormat::Sh, Action::Trash) => vec![format!("gio trash -- {path}")],
+            (ScriptFormat::Sh, Action::Hardlink) => vec![format!("ln -f -- {keeper} {path}")],
+            // GNU cp: on macOS, use `cp -c` instead.
+            (ScriptFormat::Sh, Action::Reflink) => {
+                vec![format!("cp --reflink=always -- {keeper} {path}")]
+            }
+            (ScriptFormat::Bat, Action::Delete) => vec![format!("del /f {path}")],
+            (ScriptFormat::Bat, Action::Trash) => {
+                vec![self.comment(&format!("trash not supported: {path}"))]
+            }
+            (ScriptFormat::Bat, Action::Hardlink) => {
+                vec![
+                    format!("del /f {path}"),
+                    format!("mklink /H {path} {keeper}"),
+                ]
+            }
+            (ScriptFormat::Ps1, Action::Delete) => vec![format!("Remove-Item -LiteralPath {path}")],
+            (ScriptFormat::Ps1, Action::Trash) => vec![
+                "Add-Type -AssemblyName Microsoft.VisualBasic".to_string(),
+                format!("[Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile({path}, 'OnlyErrorDialogs', 'SendToRecycleBin')"),
+            ],
+            (ScriptFormat::Ps1, Action::Hardlink) => vec![
+                format!("Remove-Item -LiteralPath {path}"),
+                format!("New-Item -ItemType HardLink -Path {path} -Target {keeper} | Out-Null"),
+            ],
+            (_, Action::Symlink | Action::RelativeSymlink) => {
+                // The target is resolved like the links created by the program.
+                let relative: bool = task.action == Action::RelativeSymlink;
+                let target = self.quote(&get_symlink_target(task.keeper, task.path, relative)?)?;
+                match self {
+                    ScriptFormat::Sh => vec![format!("ln -sf -- {target} {path}")],
+                    ScriptFormat::Bat => vec![
+                        format!("del /f {path}"),
+                        format!("mklink {path} {target}"),
+                    ],
+                    ScriptFormat::Ps1 => vec![
+                        format!("Remove-Item -LiteralPath {path}"),
+                        format!("New-Item -ItemType SymbolicLink -Path {path} -Target {target} | Out-Null"),
+                    ],
+                }
+            }
+            (ScriptFormat::Bat | ScriptFormat::Ps1, Action::Reflink) => {
+                vec![self.comment(&format!("reflink not supported: {path}"))]
+            }
+            (_, Action::Move) => {
+                let Some(destination) = destination else {
+                    let comment = format!("quarantine directory not defined: {path}");
+                    return Ok(vec![self.comment(&comment)]);
+                };
+                let dir = self.quote(destination.parent().unwrap_or(destination))?;
+                let destination = self.quote(destination)?;
+                match self {
+                    ScriptFormat::Sh => vec![
+                        format!("mkdir -p -- {dir}"),
+                        format!("mv -- {path} {destination}"),
+                    ],
+                    ScriptFormat::Bat => vec![
+                        format!("if not exist {dir} mkdir {dir}"),
+                        format!("move {path} {destination}"),
+                    ],
+                    ScriptFormat::Ps1 => vec![
+                        format!("New-Item -ItemType Directory -Force -Path {dir} | Out-Null"),
+                        format!("Move-Item -LiteralPath {path} -Destination {destination}"),
+                    ],
+                }
+            }
+        };
+
+        Ok(commands)
+    }
+
+    /**
+    Quote the path for the script.
+
+    Paths that are not valid UTF-8 are written byte by byte in the shell
+    script, with ANSI-C quoting (`$'caf\xe9.txt'`). The batch files and the
+    PowerShell scripts cannot name these files: they are refused.
+    */
+    pub fn quote(&self, path: &Path) -> MyResult<String> {
+        let Some(text) = path.to_str() else {
+            return match self {
+                ScriptFormat::Sh => Ok(quote_bytes(path)),
+                _ => Err(format!(
+                    "path is not valid UTF-8, not supported by the {} script: {path:?}",
+                    self.extension()
+                )
+                .into()),
+            };
+        };
+
+        let quoted = match self {
+            ScriptFormat::Sh => format!("'{}'", text.replace('\'', r"'\''")),
+            ScriptFormat::Bat => format!("\"{}\"", text.replace('%', "%%")),
+            ScriptFormat::Ps1 => format!("'{}'", text.replace('\'', "''")),
+        };
+
+        Ok(quoted)
+    }
+}
+
+/// Quote the bytes of the path for the shell: `$'...'`, with `\xHH` escapes
+/// for the control characters and the bytes that are not ASCII.
+fn quote_bytes(path: &Path) -> String {
+    let mut quoted = String::from("$'");
+
+    for byte in path.as_os_str().as_encoded_bytes() {
+        match byte {
+            b'\\' => quoted.push_str(r"\\"),
+            b'\'' => quoted.push_str(r"\'"),
+            0x20..=0x7e => quoted.push(char::from(*byte)),
+            _ => quoted.push_str(&format!("\\x{byte:02x}")),
+        }
+    }
+
+    quoted.push('\'');
+    quoted
+}
+
+#[cfg(test)]
+mod test_script {
+    use super::*;
+
+    /// cargo test -- --show-output test_quote
+    #[test]
+    fn test_quote() -> MyResult<()> {
+        let path = Path::new("/tmp/it's 100%.txt");
+
+        assert_eq!(ScriptFormat::Sh.quote(path)?, r"'/tmp/it'\''s 100%.txt'");
+        assert_eq!(ScriptFormat::Bat.quote(path)?, "\"/tmp/it's 100%%.txt\"");
+        assert_eq!(ScriptFormat::Ps1.quote(path)?, "'/tmp/it''s 100%.txt'");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_symlink_commands
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_commands() -> MyResult<()> {
+        use std::{fs, process::Command};
+
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        let keeper = dir.join("keeper.txt");
+        let absolute = dir.join("sub/absolute.txt");
+        let relative = dir.join("sub/relative.txt");
+        fs::create_dir(dir.join("sub"))?;
+        for path in [&keeper, &absolute, &relative] {
+            fs::write(path, "identical")?;
+        }
+
+        let format = ScriptFormat::Sh;
+        let mut script: String = format.header();
+        for (path, action) in [
+            (&absolute, Action::Symlink),
+            (&relative, Action::RelativeSymlink),
+        ] {
+            let task = Task {
+                keeper: &keeper,
+                path,
+                size: 9,
+                action,
+                move_to: None,
+                dry_run: false,
+                verify: false,
+            };
+            for command in format.commands(&task, None)? {
+                script.push_str(&command);
+                script.push('\n');
+            }
+        }
+
+        // The script is run from another directory: the links must not dangle.
+        let script_path = dir.join("script.sh");
+        fs::write(&script_path, script)?;
+        let status = Command::new("bash")
+            .arg(&script_path)
+            .current_dir("/")
+            .status()?;
+        assert!(status.success());
+
+        assert_eq!(fs::read_link(&absolute)?, fs::canonicalize(&keeper)?);
+        assert_eq!(fs::read_link(&relative)?, Path::new("../keeper.txt"));
+        for path in [&absolute, &relative] {
+            assert_eq!(fs::read_to_string(path)?, "identical");
+        }
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_quote_non_utf8
+    #[cfg(unix)]
+    #[test]
+    fn test_quote_non_utf8() -> MyResult<()> {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        // Latin-1 name with a quote, a backslash and a newline.
+        let path = Path::new(OsStr::from_bytes(b"/tmp/it's caf\xe9\\\n.txt"));
+
+        assert_eq!(
+            ScriptFormat::Sh.quote(path)?,
+            r"$'/tmp/it\'s caf\xe9\\\x0a.txt'"
+        );
+        assert!(ScriptFormat::Bat.quote(path).is_err());
+        assert!(ScriptFormat::Ps1.quote(path).is_err());
+
+        // The script header runs bash, which reads back the same bytes.
+        let quoted = ScriptFormat::Sh.quote(path)?;
+        let output = std::process::Command::new("bash")
+            .args(["-c", &format!("printf %s {quoted}")])
+            .output()?;
+        assert_eq!(output.stdout, path.as_os_str().as_bytes());
+
+        Ok(())
+    }
+}
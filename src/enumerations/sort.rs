@@ -0,0 +1,107 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, ffi::OsStr};
+
+use crate::GroupInfo;
+
+/// Key used to sort the groups of identical files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum SortKey {
+    /// File size
+    Size,
+    /// Number of identical files
+    Count,
+    /// Wasted size: size × (number of identical files − 1)
+    Wasted,
+    /// Path of the first file of the group
+    Path,
+    /// File name of the first file of the group
+    Name,
+}
+
+/// Direction of the sort.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Ascending order
+    #[default]
+    Asc,
+    /// Descending order
+    Desc,
+}
+
+impl SortKey {
+    /// Compare two groups of identical files by this key.
+    pub fn compare(&self, a: &GroupInfo, b: &GroupInfo) -> Ordering {
+        match self {
+            SortKey::Size => a.key.size.cmp(&b.key.size),
+            SortKey::Count => a.num_file.cmp(&b.num_file),
+            SortKey::Wasted => a.wasted_size.cmp(&b.wasted_size),
+            SortKey::Path => a.paths.first().cmp(&b.paths.first()),
+            SortKey::Name => get_file_name(a).cmp(&get_file_name(b)),
+        }
+    }
+}
+
+/// Get the file name of the first file of the group.
+fn get_file_name(group_info: &GroupInfo) -> Option<&OsStr> {
+    group_info.paths.first().and_then(|path| path.file_name())
+}
+
+/**
+Compare two groups of identical files by the keys, in the chosen order.
+
+Ties are broken by (file size, hash, paths) in ascending order,
+so the result does not depend on the order in which the groups were found
+(groups split by `--same_name_only` or `--compare_metadata` share the same hash).
+*/
+pub fn compare_groups(
+    a: &GroupInfo,
+    b: &GroupInfo,
+    keys: &[SortKey],
+    order: SortOrder,
+) -> Ordering {
+    let ordering = keys.iter().fold(Ordering::Equal, |ordering, key| {
+        ordering.then_with(|| key.compare(a, b))
+    });
+
+    let ordering = match order {
+        SortOrder::Asc => ordering,
+        SortOrder::Desc => ordering.reverse(),
+    };
+
+    ordering
+        .then_with(|| a.key.size.cmp(&b.key.size))
+        .then_with(|| a.key.hash.cmp(&b.key.hash))
+        .then_with(|| a.paths.cmp(&b.paths))
+}
+
+#[cfg(test)]
+mod test_sort {
+    use super::*;
+    use crate::Key;
+    use std::path::PathBuf;
+
+    /// cargo test -- --show-output test_compare_groups
+    #[test]
+    fn test_compare_groups() {
+        let group = |size: u64, num_file: usize, path: &str| GroupInfo {
+            key: Key::new(size, Some(path.to_string())),
+            algorithm: None,
+            paths: vec![PathBuf::from(path); num_file],
+            num_file,
+            sum_size: size as usize * num_file,
+            wasted_size: size as usize * (num_file - 1),
+        };
+        let mut groups = [group(10, 5, "b"), group(30, 2, "a"), group(20, 2, "c")];
+
+        groups.sort_by(|a, b| {
+            compare_groups(a, b, &[SortKey::Count, SortKey::Size], SortOrder::Desc)
+        });
+        let sizes: Vec<usize> = groups.iter().map(|group| group.key.size).collect();
+        assert_eq!(sizes, [10, 30, 20]);
+
+        groups.sort_by(|a, b| compare_groups(a, b, &[SortKey::Name], SortOrder::Asc));
+        let sizes: Vec<usize> = groups.iter().map(|group| group.key.size).collect();
+        assert_eq!(sizes, [30, 10, 20]);
+    }
+}
@@ -0,0 +1,161 @@
+use crate::{
+    limits::{add_file, walk_is_stopped},
+    progress::inc_files,
+    skipped::add_skipped,
+    structures::file_info::{get_allocated_size, get_inode},
+    Arguments, FileFilter, FileInfo, HashPipeline, Key, MyResult,
+};
+use rayon::prelude::*;
+use std::{
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+/**
+Read the list of candidate files (`--files_from`).
+
+One path per line, or NUL-delimited with `--null`
+(ex.: the output of `find . -print0` or `git ls-files -z`).
+
+`-` reads the list from the standard input.
+
+Empty lines are ignored.
+*/
+pub fn read_file_list(source: &Path, null: bool) -> MyResult<Vec<PathBuf>> {
+    let reader: Box<dyn Read> = if source == Path::new("-") {
+        Box::new(io::stdin().lock())
+    } else {
+        Box::new(File::open(source).inspect_err(|error| {
+            eprintln!("fn read_file_list()");
+            eprintln!("path: {source:?}");
+            eprintln!("Error: {error}");
+        })?)
+    };
+
+    parse_file_list(BufReader::new(reader), null)
+}
+
+/// Split the list into paths: by newline or by NUL (`--null`).
+fn parse_file_list<R: BufRead>(reader: R, null: bool) -> MyResult<Vec<PathBuf>> {
+    let delimiter: u8 = if null { b'\0' } else { b'\n' };
+    let mut paths: Vec<PathBuf> = Vec::new();
+
+    for item in reader.split(delimiter) {
+        let mut bytes: Vec<u8> = item?;
+
+        // Lists written on Windows end with "\r\n".
+        if !null && bytes.last() == Some(&b'\r') {
+            bytes.pop();
+        }
+
+        if !bytes.is_empty() {
+            paths.push(bytes_to_path(bytes));
+        }
+    }
+
+    Ok(paths)
+}
+
+/// Paths are bytes on Unix (not necessarily UTF-8).
+#[cfg(unix)]
+pub(crate) fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+    PathBuf::from(OsString::from_vec(bytes))
+}
+
+#[cfg(not(unix))]
+pub(crate) fn bytes_to_path(bytes: Vec<u8>) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/**
+Get the files of the list into one vector, instead of walking directories.
+
+The same filters as the walkers are applied: file type, symbolic links,
+hidden files, `--exclude`, `--include`, size, time and allocated size.
+
+Files that cannot be read are reported as skipped files.
+*/
+pub fn get_listed_files(
+    source: &Path,
+    arguments: &Arguments,
+    filter: &FileFilter,
+    pipeline: Option<&HashPipeline>,
+) -> MyResult<Vec<FileInfo>> {
+    let paths: Vec<PathBuf> = read_file_list(source, arguments.null)?;
+
+    let all_files: Vec<FileInfo> = paths
+        .into_par_iter() // rayon parallel iterator
+        .filter(|_| !walk_is_stopped())
+        .filter(|path| !filter.is_hidden(path) && !filter.is_excluded(path))
+        .filter_map(|path| {
+            let link_metadata = fs::symlink_metadata(&path)
+                .inspect_err(|error| add_skipped(&path, error))
+                .ok()?;
+            let is_symlink: bool = link_metadata.is_symlink();
+
+            // Follow symbolic links to get the size of the target file.
+            let metadata = if is_symlink {
+                fs::metadata(&path)
+                    .inspect_err(|error| add_skipped(&path, error))
+                    .ok()?
+            } else {
+                link_metadata
+            };
+
+            let file_size: u64 = metadata.len();
+            let modified = metadata.modified().ok();
+            let allocated = get_allocated_size(&metadata);
+
+            if filter.is_file(&path, metadata.file_type(), is_symlink)
+                && filter.is_included(&path)
+                && arguments.size_is_included(file_size)
+                && arguments.time_is_included(modified)
+                && arguments.allocated_is_included(allocated)
+                && filter.is_permitted(&metadata)
+                && add_file()
+            {
+                let key = Key::new(file_size, None);
+                let inode = get_inode(&metadata);
+                inc_files(1);
+                Some(FileInfo {
+                    key,
+                    path,
+                    inode,
+                    modified,
+                    allocated,
+                })
+            } else {
+                None
+            }
+        })
+        .inspect(|file_info| {
+            // Start hashing while the other files are processed.
+            if let Some(pipeline) = pipeline {
+                pipeline.push(file_info);
+            }
+        })
+        .collect();
+
+    Ok(all_files)
+}
+
+#[cfg(test)]
+mod test_file_list {
+    use super::*;
+
+    /// cargo test -- --show-output test_parse_file_list
+    #[test]
+    fn test_parse_file_list() -> MyResult<()> {
+        let lines = "a.txt\r\ndir/b c.txt\n\nd.txt";
+        let paths = parse_file_list(lines.as_bytes(), false)?;
+        assert_eq!(paths, ["a.txt", "dir/b c.txt", "d.txt"].map(PathBuf::from));
+
+        let names = "a.txt\0new\nline.txt\0\0";
+        let paths = parse_file_list(names.as_bytes(), true)?;
+        assert_eq!(paths, ["a.txt", "new\nline.txt"].map(PathBuf::from));
+
+        Ok(())
+    }
+}
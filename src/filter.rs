@@ -0,0 +1,554 @@
+use crate::{Arguments, MyResult};
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::RegexSet;
+use std::{
+    collections::HashSet,
+    fs::{FileType, Metadata},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+/// Directories excluded on all platforms: trash, snapshots and the system
+/// directories of Windows drives (also found on removable media).
+const DEFAULT_EXCLUDES: [&str; 4] = [
+    ".Trash*",
+    ".snapshots",
+    "System Volume Information",
+    "$RECYCLE.BIN",
+];
+
+/// Pseudo filesystems: their files are not regular files and some reads never end.
+#[cfg(target_os = "linux")]
+const PLATFORM_EXCLUDES: [&str; 3] = ["/proc", "/sys", "/dev"];
+
+/// Device files and the metadata of Spotlight and FSEvents.
+#[cfg(target_os = "macos")]
+const PLATFORM_EXCLUDES: [&str; 3] = ["/dev", ".Spotlight-V100", ".fseventsd"];
+
+/// Pseudo filesystems of the other unix systems.
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+const PLATFORM_EXCLUDES: [&str; 2] = ["/proc", "/dev"];
+
+#[cfg(not(unix))]
+const PLATFORM_EXCLUDES: [&str; 0] = [];
+
+/// Filters applied to directories and files while walking the input directories.
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    /// Glob patterns of directories and files to be skipped
+    exclude: GlobSet,
+    /// Glob patterns of files to be searched
+    include: GlobSet,
+    /// Regular expressions of files to be searched
+    include_regex: RegexSet,
+    /// Follow symbolic links to directories
+    follow_links: bool,
+    /// Treat symbolic links to files as regular files
+    symlink_files: bool,
+    /// Directories already visited, identified by (device, inode)
+    visited: Arc<Mutex<HashSet<(u64, u64)>>>,
+    /// Do not descend into directories on other filesystems
+    one_file_system: bool,
+    /// Skip hidden directories and files
+    omit_hidden: bool,
+    /// User id of the files to be searched (`--owner`)
+    owner: Option<u32>,
+    /// Group id of the files to be searched (`--group`)
+    group: Option<u32>,
+    /// Current user, to check the write permission (`--writable_only`)
+    current_user: Option<CurrentUser>,
+}
+
+impl FileFilter {
+    /// Build the filters from the command line arguments.
+    pub fn new(arguments: &Arguments) -> MyResult<Self> {
+        let include_regex = RegexSet::new(&arguments.include_regex).inspect_err(|error| {
+            eprintln!("fn FileFilter::new()");
+            eprintln!("Invalid regular expression: {:?}", arguments.include_regex);
+            eprintln!("Error: {error}");
+        })?;
+
+        let mut builder = GlobSetBuilder::new();
+        add_patterns(&mut builder, &arguments.exclude)?;
+        if !arguments.no_default_excludes {
+            add_default_excludes(&mut builder)?;
+        }
+
+        Ok(FileFilter {
+            exclude: builder.build()?,
+            include: build_glob_set(&arguments.include)?,
+            include_regex,
+            follow_links: arguments.follow_links,
+            symlink_files: arguments.symlink_files,
+            visited: Arc::new(Mutex::new(HashSet::new())),
+            one_file_system: arguments.one_file_system,
+            omit_hidden: arguments.omit_hidden,
+            owner: arguments.owner.as_deref().map(get_user_id).transpose()?,
+            group: arguments.group.as_deref().map(get_group_id).transpose()?,
+            current_user: arguments.writable_only.then(CurrentUser::new).transpose()?,
+        })
+    }
+
+    /**
+    Check if the directory or file must be skipped.
+
+    The patterns are matched against the name and against the full path.
+
+    Excluded directories are not traversed.
+    */
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        if self.exclude.is_empty() {
+            return false;
+        }
+
+        path.file_name()
+            .is_some_and(|name| self.exclude.is_match(name))
+            || self.exclude.is_match(path)
+    }
+
+    /**
+    Check if the file must be searched.
+
+    Glob patterns are matched against the file name and against the full path.
+
+    Regular expressions are matched against the full path.
+
+    If no pattern is given, all files are searched.
+    */
+    pub fn is_included(&self, path: &Path) -> bool {
+        if self.include.is_empty() && self.include_regex.is_empty() {
+            return true;
+        }
+
+        path.file_name()
+            .is_some_and(|name| self.include.is_match(name))
+            || self.include.is_match(path)
+            || self.include_regex.is_match(&path.to_string_lossy())
+    }
+
+    /**
+    Check if the entry is a file to be searched.
+
+    Symbolic links to files are ignored, unless `--symlink_files` is chosen.
+    */
+    pub fn is_file(&self, path: &Path, file_type: FileType, is_symlink: bool) -> bool {
+        if is_symlink {
+            self.symlink_files && path.is_file()
+        } else {
+            file_type.is_file()
+        }
+    }
+
+    /**
+    Check if the directory was already visited.
+
+    With `--follow_links`, the same directory can be reached through several
+    symbolic links, or a link can point to one of its ancestors.
+
+    Directories are identified by (device, inode), so each one is
+    traversed only once and the walker never loops.
+    */
+    pub fn is_visited_dir(&self, path: &Path) -> bool {
+        if !self.follow_links {
+            return false;
+        }
+
+        let Some(id) = get_dir_id(path) else {
+            return false;
+        };
+
+        match self.visited.lock() {
+            Ok(mut visited) => !visited.insert(id),
+            Err(_) => false,
+        }
+    }
+
+    /**
+    Check if the directory or file must be skipped with `--omit_hidden`.
+
+    Names starting with '.' are hidden on all platforms.
+
+    On Windows, entries with the hidden or system attribute are also hidden.
+    */
+    pub fn is_hidden(&self, path: &Path) -> bool {
+        if !self.omit_hidden {
+            return false;
+        }
+
+        path.file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            || has_hidden_attribute(path)
+    }
+
+    /**
+    Check the owner, the group and the write permission of the file
+    (`--owner`, `--group` and `--writable_only`).
+
+    Without these options, all files are searched.
+    */
+    pub fn is_permitted(&self, metadata: &Metadata) -> bool {
+        let (uid, gid) = match get_owner(metadata) {
+            Some((uid, gid)) => (Some(uid), Some(gid)),
+            None => (None, None),
+        };
+
+        self.owner.map_or(true, |owner| uid == Some(owner))
+            && self.group.map_or(true, |group| gid == Some(group))
+            && self
+                .current_user
+                .as_ref()
+                .map_or(true, |user| user.can_write(metadata))
+    }
+
+    /// Get the device of the root directory, if `--one_file_system` is chosen.
+    pub fn get_root_device(&self, path: &Path) -> Option<u64> {
+        if !self.one_file_system {
+            return None;
+        }
+
+        get_dir_id(path).map(|(device, _inode)| device)
+    }
+
+    /**
+    Check if the directory is on another filesystem than the root directory.
+
+    Mount points (network mounts, backup drives, ...) are not traversed.
+    */
+    pub fn is_other_filesystem(&self, path: &Path, root_device: Option<u64>) -> bool {
+        match (root_device, get_dir_id(path)) {
+            (Some(root_device), Some((device, _inode))) => device != root_device,
+            _ => false,
+        }
+    }
+}
+
+/// Check the hidden (`FILE_ATTRIBUTE_HIDDEN`) and system (`FILE_ATTRIBUTE_SYSTEM`) attributes.
+///
+/// <https://learn.microsoft.com/en-us/windows/win32/fileio/file-attribute-constants>
+#[cfg(windows)]
+fn has_hidden_attribute(path: &Path) -> bool {
+    use std::os::windows::fs::MetadataExt;
+
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+
+    std::fs::symlink_metadata(path).is_ok_and(|metadata| {
+        metadata.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0
+    })
+}
+
+/// On other platforms, only names starting with '.' are hidden.
+#[cfg(not(windows))]
+fn has_hidden_attribute(_path: &Path) -> bool {
+    false
+}
+
+/// Get the (device, inode) of the directory, following symbolic links.
+#[cfg(unix)]
+fn get_dir_id(path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+
+    std::fs::metadata(path)
+        .ok()
+        .map(|metadata| (metadata.dev(), metadata.ino()))
+}
+
+/// On other platforms, rely on the loop detection of the walker.
+#[cfg(not(unix))]
+fn get_dir_id(_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+/// Effective user of the process, with its groups.
+#[derive(Debug, Clone)]
+struct CurrentUser {
+    #[cfg(unix)]
+    uid: u32,
+    #[cfg(unix)]
+    gids: Vec<u32>,
+}
+
+impl CurrentUser {
+    /// Read the effective user and its groups from the users database.
+    #[cfg(unix)]
+    fn new() -> MyResult<Self> {
+        let mut gids: Vec<u32> = uzers::group_access_list()?
+            .iter()
+            .map(|group| group.gid())
+            .collect();
+        gids.push(uzers::get_effective_gid());
+
+        Ok(CurrentUser {
+            uid: uzers::get_effective_uid(),
+            gids,
+        })
+    }
+
+    #[cfg(not(unix))]
+    fn new() -> MyResult<Self> {
+        Ok(CurrentUser {})
+    }
+
+    /**
+    Check the permission bits of the owner, the group or the others,
+    as the kernel does. The superuser can write to any file.
+
+    ACLs and read-only filesystems are not taken into account.
+    */
+    #[cfg(unix)]
+    fn can_write(&self, metadata: &Metadata) -> bool {
+        use std::os::unix::fs::MetadataExt;
+
+        let mode: u32 = metadata.mode();
+
+        if self.uid == 0 {
+            true
+        } else if metadata.uid() == self.uid {
+            mode & 0o200 != 0
+        } else if self.gids.contains(&metadata.gid()) {
+            mode & 0o020 != 0
+        } else {
+            mode & 0o002 != 0
+        }
+    }
+
+    /// Check the read-only attribute.
+    #[cfg(not(unix))]
+    fn can_write(&self, metadata: &Metadata) -> bool {
+        !metadata.permissions().readonly()
+    }
+}
+
+/// Get the (uid, gid) of the file.
+#[cfg(unix)]
+fn get_owner(metadata: &Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.uid(), metadata.gid()))
+}
+
+/// On other platforms, files have no uid and gid.
+#[cfg(not(unix))]
+fn get_owner(_metadata: &Metadata) -> Option<(u32, u32)> {
+    None
+}
+
+/// Get the uid of the user name (`--owner`) from the users database.
+///
+/// Numeric ids are accepted.
+#[cfg(unix)]
+fn get_user_id(name: &str) -> MyResult<u32> {
+    if let Ok(uid) = name.parse::<u32>() {
+        return Ok(uid);
+    }
+
+    uzers::get_user_by_name(name)
+        .map(|user| user.uid())
+        .ok_or_else(|| format!("unknown user: {name:?}").into())
+}
+
+#[cfg(not(unix))]
+fn get_user_id(name: &str) -> MyResult<u32> {
+    Err(format!("--owner {name:?}: file owners are only available on unix").into())
+}
+
+/// Get the gid of the group name (`--group`) from the groups database.
+///
+/// Numeric ids are accepted.
+#[cfg(unix)]
+fn get_group_id(name: &str) -> MyResult<u32> {
+    if let Ok(gid) = name.parse::<u32>() {
+        return Ok(gid);
+    }
+
+    uzers::get_group_by_name(name)
+        .map(|group| group.gid())
+        .ok_or_else(|| format!("unknown group: {name:?}").into())
+}
+
+#[cfg(not(unix))]
+fn get_group_id(name: &str) -> MyResult<u32> {
+    Err(format!("--group {name:?}: file groups are only available on unix").into())
+}
+
+/// Compile the glob patterns into a single set.
+pub fn build_glob_set(patterns: &[String]) -> MyResult<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    add_patterns(&mut builder, patterns)?;
+    Ok(builder.build()?)
+}
+
+/// Add the glob patterns to the set.
+fn add_patterns(builder: &mut GlobSetBuilder, patterns: &[String]) -> MyResult<()> {
+    for pattern in patterns {
+        let glob = Glob::new(pattern).inspect_err(|error| {
+            eprintln!("fn build_glob_set()");
+            eprintln!("Invalid glob pattern: {pattern:?}");
+            eprintln!("Error: {error}");
+        })?;
+        builder.add(glob);
+    }
+
+    Ok(())
+}
+
+/**
+Add the directories excluded by default (see `--no_default_excludes`).
+
+The names are matched case-insensitively: `$Recycle.Bin` on NTFS, `$RECYCLE.BIN` on FAT.
+*/
+fn add_default_excludes(builder: &mut GlobSetBuilder) -> MyResult<()> {
+    for pattern in DEFAULT_EXCLUDES.iter().chain(PLATFORM_EXCLUDES.iter()) {
+        builder.add(GlobBuilder::new(pattern).case_insensitive(true).build()?);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_filter {
+    use super::*;
+    use clap::Parser;
+
+    /// cargo test -- --show-output test_exclude
+    #[test]
+    fn test_exclude() -> MyResult<()> {
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--exclude",
+            "node_modules",
+            "--exclude",
+            "*.tmp",
+        ]);
+        let filter = FileFilter::new(&arguments)?;
+
+        assert!(filter.is_excluded(Path::new("/home/user/project/node_modules")));
+        assert!(filter.is_excluded(Path::new("./data/file.tmp")));
+        assert!(!filter.is_excluded(Path::new("./data/file.txt")));
+        assert!(!filter.is_excluded(Path::new("./node_modules_backup")));
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_default_excludes
+    #[test]
+    fn test_default_excludes() -> MyResult<()> {
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let filter = FileFilter::new(&arguments)?;
+
+        assert!(filter.is_excluded(Path::new("/media/usb/.Trash-1000")));
+        assert!(filter.is_excluded(Path::new("/mnt/usb/System Volume Information")));
+        assert!(filter.is_excluded(Path::new("/mnt/usb/$Recycle.Bin")));
+        assert!(filter.is_excluded(Path::new("/.snapshots")));
+        assert!(!filter.is_excluded(Path::new("/home/user/snapshots")));
+
+        #[cfg(target_os = "linux")]
+        {
+            assert!(filter.is_excluded(Path::new("/proc")));
+            assert!(!filter.is_excluded(Path::new("/home/user/proc")));
+        }
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--no_default_excludes"]);
+        let filter = FileFilter::new(&arguments)?;
+        assert!(!filter.is_excluded(Path::new("/mnt/usb/System Volume Information")));
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_hidden
+    #[test]
+    fn test_hidden() -> MyResult<()> {
+        let arguments = Arguments::parse_from(["find-identical-files", "--omit_hidden"]);
+        let filter = FileFilter::new(&arguments)?;
+
+        assert!(filter.is_hidden(Path::new("./project/.git")));
+        assert!(filter.is_hidden(Path::new(".hidden_file")));
+        assert!(!filter.is_hidden(Path::new("./project/src")));
+        assert!(!filter.is_hidden(Path::new("./project/file.txt")));
+
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let filter = FileFilter::new(&arguments)?;
+        assert!(!filter.is_hidden(Path::new("./project/.git")));
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_include
+    #[test]
+    fn test_include() -> MyResult<()> {
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--include",
+            "*.jpg,*.png",
+            "--include_regex",
+            r"\.raw$",
+        ]);
+        let filter = FileFilter::new(&arguments)?;
+
+        assert!(filter.is_included(Path::new("./photos/a.jpg")));
+        assert!(filter.is_included(Path::new("./photos/b.png")));
+        assert!(filter.is_included(Path::new("./photos/c.raw")));
+        assert!(!filter.is_included(Path::new("./photos/d.txt")));
+
+        // Without patterns, all files are searched.
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let filter = FileFilter::new(&arguments)?;
+        assert!(filter.is_included(Path::new("./photos/d.txt")));
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_permitted
+    #[cfg(unix)]
+    #[test]
+    fn test_permitted() -> MyResult<()> {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("permitted.txt");
+        std::fs::write(&path, "text")?;
+        let metadata = std::fs::metadata(&path)?;
+
+        let uid = metadata.uid().to_string();
+        let gid = metadata.gid().to_string();
+        let arguments =
+            Arguments::parse_from(["find-identical-files", "--owner", &uid, "--group", &gid]);
+        assert!(FileFilter::new(&arguments)?.is_permitted(&metadata));
+
+        let other_uid = (metadata.uid() + 1).to_string();
+        let arguments = Arguments::parse_from(["find-identical-files", "--owner", &other_uid]);
+        assert!(!FileFilter::new(&arguments)?.is_permitted(&metadata));
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--writable_only"]);
+        let filter = FileFilter::new(&arguments)?;
+        assert!(filter.is_permitted(&metadata));
+
+        // The superuser can write to read-only files.
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o444))?;
+        let metadata = std::fs::metadata(&path)?;
+        assert_eq!(
+            filter.is_permitted(&metadata),
+            uzers::get_effective_uid() == 0
+        );
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_visited_dir
+    #[cfg(unix)]
+    #[test]
+    fn test_visited_dir() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        let link = dir.join("loop");
+        std::os::unix::fs::symlink(dir, &link)?;
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--follow_links"]);
+        let filter = FileFilter::new(&arguments)?;
+
+        assert!(!filter.is_visited_dir(dir));
+        // The link points to a directory already visited.
+        assert!(filter.is_visited_dir(&link));
+
+        Ok(())
+    }
+}
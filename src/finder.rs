@@ -0,0 +1,433 @@
+use crate::{
+    get_all_files, get_identical_groups, init_search, limits::reset_num_files, run_io,
+    skipped::reset_skipped, Algorithm, Arguments, FileExtension, FileKind, GroupExtension,
+    GroupInfo, MyResult, SortKey, SortOrder,
+};
+use clap::Parser;
+use std::{path::PathBuf, time::Instant};
+
+/// Number of groups of identical size processed at a time, per thread.
+const GROUPS_PER_THREAD: usize = 16;
+
+/// A group of identical files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateGroup {
+    /// Stable identifier of the group, the same in repeated searches
+    pub id: String,
+    /// Hash of the entire file
+    pub digest: String,
+    /// Individual file size (in bytes)
+    pub size: u64,
+    /// Paths of the identical files
+    pub paths: Vec<PathBuf>,
+}
+
+impl From<GroupInfo> for DuplicateGroup {
+    fn from(group_info: GroupInfo) -> Self {
+        DuplicateGroup {
+            id: group_info.get_id(),
+            digest: group_info.key.hash.unwrap_or_default(),
+            size: group_info.key.size as u64,
+            paths: group_info.paths,
+        }
+    }
+}
+
+/**
+Find identical files from other Rust programs.
+
+The builder methods mirror the command line options.
+
+Each call to `find` or `find_each` is a new search: the count of files
+found and the skipped files ([`get_skipped_files`](crate::get_skipped_files))
+are reset. The I/O thread pool is created once and shared by all searches
+of the process. Searches must not run concurrently.
+
+### Example
+
+```no_run
+use find_identical_files::{Algorithm, DuplicateFinder, MyResult};
+
+fn main() -> MyResult<()> {
+    let groups = DuplicateFinder::new()
+        .input_dir("/tmp")
+        .algorithm(Algorithm::SHA256)
+        .min_size(1024)
+        .find()?;
+
+    for group in groups {
+        println!("{} ({} bytes): {:?}", group.digest, group.size, group.paths);
+    }
+
+    // Or process each group as soon as it is confirmed:
+    DuplicateFinder::new()
+        .input_dir("/tmp")
+        .find_each(|group| println!("{:?}", group.paths))?;
+
+    Ok(())
+}
+```
+*/
+#[derive(Debug, Clone)]
+pub struct DuplicateFinder {
+    arguments: Arguments,
+}
+
+impl Default for DuplicateFinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DuplicateFinder {
+    /// Create a DuplicateFinder with the default options.
+    pub fn new() -> Self {
+        DuplicateFinder {
+            arguments: Arguments::parse_from(["find-identical-files"]),
+        }
+    }
+
+    /// Add a directory to be searched (default: current directory).
+    pub fn input_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.arguments.input_dir.push(path.into());
+        self
+    }
+
+    /// Choose the hash algorithm.
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.arguments.algorithm = algorithm;
+        self
+    }
+
+    /// Set a minimum file size (in bytes).
+    pub fn min_size(mut self, min_size: u64) -> Self {
+        self.arguments.min_size = min_size;
+        self
+    }
+
+    /// Set a maximum file size (in bytes).
+    pub fn max_size(mut self, max_size: u64) -> Self {
+        self.arguments.max_size = max_size;
+        self
+    }
+
+    /// Set the minimum depth to search for identical files.
+    pub fn min_depth(mut self, min_depth: usize) -> Self {
+        self.arguments.min_depth = min_depth;
+        self
+    }
+
+    /// Set the maximum depth to search for identical files.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.arguments.max_depth = max_depth;
+        self
+    }
+
+    /// Set the minimum frequency (number of identical files).
+    pub fn min_frequency(mut self, min_frequency: u64) -> Self {
+        self.arguments.min_frequency = min_frequency;
+        self
+    }
+
+    /// Set the maximum frequency (number of identical files).
+    pub fn max_frequency(mut self, max_frequency: u64) -> Self {
+        self.arguments.max_frequency = max_frequency;
+        self
+    }
+
+    /// Add a glob pattern of directories and files to be skipped.
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.arguments.exclude.push(pattern.into());
+        self
+    }
+
+    /// Add a glob pattern of files to be searched.
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.arguments.include.push(pattern.into());
+        self
+    }
+
+    /// Add a regular expression of files to be searched.
+    pub fn include_regex(mut self, pattern: impl Into<String>) -> Self {
+        self.arguments.include_regex.push(pattern.into());
+        self
+    }
+
+    /// Include empty files (zero bytes) in the search.
+    pub fn include_empty(mut self, yes: bool) -> Self {
+        self.arguments.include_empty = yes;
+        self
+    }
+
+    /// Omit hidden files (starts with '.').
+    pub fn omit_hidden(mut self, yes: bool) -> Self {
+        self.arguments.omit_hidden = yes;
+        self
+    }
+
+    /// Follow symbolic links to directories.
+    pub fn follow_links(mut self, yes: bool) -> Self {
+        self.arguments.follow_links = yes;
+        self
+    }
+
+    /// Treat symbolic links to files as regular files.
+    pub fn symlink_files(mut self, yes: bool) -> Self {
+        self.arguments.symlink_files = yes;
+        self
+    }
+
+    /// Do not report hard links to the same file as identical files (default: true).
+    pub fn ignore_hardlinks(mut self, yes: bool) -> Self {
+        self.arguments.ignore_hardlinks = yes;
+        self
+    }
+
+    /// Stay on the filesystem of each input directory.
+    pub fn one_file_system(mut self, yes: bool) -> Self {
+        self.arguments.one_file_system = yes;
+        self
+    }
+
+    /// Compare identical files byte by byte.
+    pub fn paranoid(mut self, yes: bool) -> Self {
+        self.arguments.paranoid = yes;
+        self
+    }
+
+    /// Set the number of bytes of the prefilter (0 disables the prefilter).
+    pub fn prefilter_bytes(mut self, prefilter_bytes: usize) -> Self {
+        self.arguments.prefilter_bytes = prefilter_bytes;
+        self
+    }
+
+    /// Also hash the last bytes of each file in the prefilter.
+    pub fn prefilter_tail(mut self, yes: bool) -> Self {
+        self.arguments.prefilter_tail = yes;
+        self
+    }
+
+    /// Set the size (in bytes) of the buffer used to read each file.
+    pub fn buffer_size(mut self, buffer_size: usize) -> Self {
+        self.arguments.buffer_size = buffer_size;
+        self
+    }
+
+    /// Memory-map files with at least `mmap_min_size` bytes (blake3 only).
+    pub fn mmap(mut self, yes: bool) -> Self {
+        self.arguments.mmap = yes;
+        self
+    }
+
+    /// Sort the groups by one or more keys, in ascending or descending order.
+    pub fn sort_by(mut self, keys: &[SortKey], order: SortOrder) -> Self {
+        self.arguments.sort_by = keys.to_vec();
+        self.arguments.order = order;
+        self
+    }
+
+    /// Sort the paths of each group, so the result does not depend on the directory walk.
+    pub fn deterministic(mut self, yes: bool) -> Self {
+        self.arguments.deterministic = yes;
+        self
+    }
+
+    /// Search only files of these types, detected from their content.
+    pub fn file_type(mut self, kinds: &[FileKind]) -> Self {
+        self.arguments.file_type = kinds.to_vec();
+        self
+    }
+
+    /// Keep only the N groups wasting the most space.
+    pub fn top(mut self, top: usize) -> Self {
+        self.arguments.top = Some(top);
+        self
+    }
+
+    /// Search the directories and return the groups of identical files.
+    ///
+    /// The options are validated like the command line options.
+    pub fn find(&self) -> MyResult<impl Iterator<Item = DuplicateGroup>> {
+        let arguments: Arguments = self.get_arguments()?;
+        let all_files = get_all_files(&arguments)?;
+        let groups = get_identical_groups(&all_files, &arguments, Instant::now());
+
+        Ok(groups.into_iter().map(DuplicateGroup::from))
+    }
+
+    /**
+    Search the directories and call the closure with each group of identical files
+    as soon as the group is confirmed.
+
+    The groups are not collected into memory first and are not sorted,
+    which is suitable for scans of tens of millions of files.
+    */
+    pub fn find_each<F>(&self, mut callback: F) -> MyResult<()>
+    where
+        F: FnMut(DuplicateGroup),
+    {
+        let arguments: &Arguments = &self.get_arguments()?;
+
+        // Procedure 1: group files by size.
+        let identical_size: Vec<GroupInfo> =
+            get_all_files(arguments)?.get_grouped_files(arguments, 1);
+
+        let chunk_size: usize = rayon::current_num_threads() * GROUPS_PER_THREAD;
+
+        for groups in identical_size.chunks(chunk_size) {
+            run_io(arguments, || confirm_groups(groups, arguments))
+                .into_iter()
+                .for_each(|group_info| callback(group_info.into()));
+        }
+
+        Ok(())
+    }
+
+    /// Validate the options and initialize the search, like the command line.
+    fn get_arguments(&self) -> MyResult<Arguments> {
+        let mut arguments: Arguments = self.arguments.clone();
+        arguments.validate()?;
+        init_search(&arguments);
+        reset_num_files();
+        reset_skipped();
+        Ok(arguments)
+    }
+}
+
+/// Apply procedures 2 to 4 to groups of files with identical size.
+fn confirm_groups(groups: &[GroupInfo], arguments: &Arguments) -> Vec<GroupInfo> {
+    let groups: Vec<GroupInfo> = if arguments.file_type.is_empty() {
+        groups.to_vec()
+    } else {
+        groups.filter_by_file_type(arguments)
+    };
+
+    let identical_bytes: Vec<GroupInfo> = if arguments.prefilter_is_enabled() {
+        groups.get_identical_files(arguments, 2)
+    } else {
+        groups
+    };
+
+    let identical_hash: Vec<GroupInfo> = identical_bytes.get_identical_files(arguments, 3);
+
+    if arguments.paranoid {
+        identical_hash.verify_identical_files(arguments)
+    } else {
+        identical_hash
+    }
+}
+
+#[cfg(test)]
+mod test_finder {
+    use super::*;
+    use std::{collections::HashSet, fs, path::Path};
+
+    /// Get the file names of the paths.
+    fn get_names(paths: &[PathBuf]) -> HashSet<String> {
+        paths
+            .iter()
+            .filter_map(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    /// Write two identical files and a different one.
+    fn write_files(dir: &Path) -> MyResult<()> {
+        fs::write(dir.join("a.txt"), "identical")?;
+        fs::write(dir.join("b.txt"), "identical")?;
+        fs::write(dir.join("c.txt"), "different")?;
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_find
+    #[test]
+    fn test_find() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        write_files(temp_dir.path())?;
+
+        let groups: Vec<DuplicateGroup> = DuplicateFinder::new()
+            .input_dir(temp_dir.path())
+            .algorithm(Algorithm::SHA256)
+            .find()?
+            .collect();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 9);
+        assert_eq!(groups[0].digest.len(), 64);
+        assert_eq!(
+            get_names(&groups[0].paths),
+            HashSet::from(["a.txt".into(), "b.txt".into()])
+        );
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_find_each
+    #[test]
+    fn test_find_each() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        write_files(temp_dir.path())?;
+
+        let finder = DuplicateFinder::new().input_dir(temp_dir.path());
+        let mut groups: Vec<DuplicateGroup> = Vec::new();
+        finder.find_each(|group| groups.push(group))?;
+
+        // The same groups as `find`.
+        assert_eq!(groups, finder.find()?.collect::<Vec<_>>());
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            get_names(&groups[0].paths),
+            HashSet::from(["a.txt".into(), "b.txt".into()])
+        );
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_find_twice
+    #[test]
+    fn test_find_twice() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        write_files(temp_dir.path())?;
+
+        let finder = DuplicateFinder::new()
+            .input_dir(temp_dir.path())
+            .deterministic(true);
+
+        // Each call is a new search with the same result.
+        let first: Vec<DuplicateGroup> = finder.find()?.collect();
+        let second: Vec<DuplicateGroup> = finder.find()?.collect();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(first, second);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_find_invalid
+    #[test]
+    fn test_find_invalid() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let finder = DuplicateFinder::new().input_dir(temp_dir.path());
+
+        // The options are validated like the command line options.
+        assert!(finder.clone().min_size(10).max_size(5).find().is_err());
+        assert!(finder
+            .clone()
+            .min_depth(3)
+            .max_depth(2)
+            .find_each(|_| ())
+            .is_err());
+        assert!(finder
+            .clone()
+            .mmap(true)
+            .algorithm(Algorithm::SHA256)
+            .find()
+            .is_err());
+        assert!(DuplicateFinder::new()
+            .input_dir(temp_dir.path().join("not_found"))
+            .find()
+            .is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use crate::{
+    display_path, human_readable_size, split_and_insert, GroupInfo, MyResult, TotalInfo, SEPARATOR,
+};
+use std::{
+    fs,
+    io::Write,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Style and script of the report: no external resources are loaded.
+const HTML_HEADER: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Identical files</title>
+<style>
+body { font-family: sans-serif; margin: 2em; color: #222; }
+details { border: 1px solid #ccc; border-radius: 4px; margin: 0.5em 0; padding: 0.3em 0.6em; }
+summary { cursor: pointer; }
+.bar { display: inline-block; height: 0.8em; background: #d9534f; vertical-align: middle; margin-left: 1em; }
+table { border-collapse: collapse; margin: 0.5em 0; }
+th, td { border: 1px solid #ddd; padding: 0.2em 0.6em; text-align: left; }
+th { background: #f2f2f2; cursor: pointer; user-select: none; }
+td.number { text-align: right; }
+code { word-break: break-all; }
+</style>
+</head>
+<body>
+<h1>Identical files</h1>
+<p>
+<button onclick="toggleAll(true)">Expand all</button>
+<button onclick="toggleAll(false)">Collapse all</button>
+</p>
+"#;
+
+/// Sort the tables by the clicked column and show the dates in local time.
+const HTML_FOOTER: &str = r#"<script>
+function toggleAll(open) {
+  document.querySelectorAll("details").forEach(function (d) { d.open = open; });
+}
+document.querySelectorAll("td[data-time]").forEach(function (td) {
+  var secs = Number(td.dataset.time);
+  td.textContent = secs > 0 ? new Date(secs * 1000).toLocaleString() : "unknown";
+});
+document.querySelectorAll("th").forEach(function (th) {
+  th.addEventListener("click", function () {
+    var table = th.closest("table");
+    var body = table.tBodies[0];
+    var column = th.cellIndex;
+    var ascending = th.dataset.order !== "asc";
+    th.dataset.order = ascending ? "asc" : "desc";
+    var key = function (row) {
+      var cell = row.cells[column];
+      return cell.dataset.sort !== undefined ? Number(cell.dataset.sort) : cell.textContent;
+    };
+    Array.from(body.rows)
+      .sort(function (a, b) {
+        var x = key(a), y = key(b);
+        var order = typeof x === "number" ? x - y : String(x).localeCompare(String(y));
+        return ascending ? order : -order;
+      })
+      .forEach(function (row) { body.appendChild(row); });
+  });
+});
+</script>
+</body>
+</html>
+"#;
+
+/// Escape the characters with special meaning in HTML (text and attribute values).
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Format the size in bytes, followed by the human-readable size.
+fn format_size(size: usize) -> String {
+    format!(
+        "{} bytes ({})",
+        split_and_insert(size, SEPARATOR),
+        human_readable_size(size)
+    )
+}
+
+/// Get the modification time of the file (seconds since the Unix epoch).
+fn get_modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified: SystemTime| modified.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Write the beginning of the report: style, script and title.
+pub fn write_html_header(write: &mut dyn Write) -> MyResult<()> {
+    write.write_all(HTML_HEADER.as_bytes())?;
+    Ok(())
+}
+
+/**
+Write one group of identical files as a collapsible section.
+
+The bar shows the wasted size relative to the largest wasted size (`max_wasted`).
+
+The table of paths can be sorted by clicking on the column headers.
+*/
+pub fn write_html_group(
+    group_info: &GroupInfo,
+    max_wasted: usize,
+    write: &mut dyn Write,
+) -> MyResult<()> {
+    let percent: f64 = if max_wasted == 0 {
+        0.0
+    } else {
+        100.0 * group_info.wasted_size as f64 / max_wasted as f64
+    };
+
+    writeln!(write, "<details open>")?;
+    writeln!(
+        write,
+        "<summary>{} identical files of {}, wasted size: {}<span class=\"bar\" style=\"width: {:.1}px\"></span></summary>",
+        group_info.num_file,
+        escape_html(&format_size(group_info.key.size)),
+        escape_html(&format_size(group_info.wasted_size)),
+        2.0 * percent,
+    )?;
+    writeln!(
+        write,
+        "<p>hash: <code>{}</code></p>",
+        escape_html(&group_info.get_digest().unwrap_or_default())
+    )?;
+    writeln!(
+        write,
+        "<p>group id: <code>{}</code></p>",
+        group_info.get_id()
+    )?;
+
+    writeln!(write, "<table>")?;
+    writeln!(
+        write,
+        "<thead><tr><th>#</th><th>File name</th><th>Directory</th><th>Modified</th></tr></thead>"
+    )?;
+    writeln!(write, "<tbody>")?;
+    for (index, path) in group_info.paths.iter().enumerate() {
+        let modified: u64 = get_modified_secs(path);
+        let path = display_path(path);
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let directory = path.parent().unwrap_or(Path::new("")).to_string_lossy();
+        writeln!(
+            write,
+            "<tr><td class=\"number\" data-sort=\"{}\">{}</td><td><code>{}</code></td><td><code>{}</code></td><td data-sort=\"{modified}\" data-time=\"{modified}\"></td></tr>",
+            index + 1,
+            index + 1,
+            escape_html(&name),
+            escape_html(&directory),
+        )?;
+    }
+    writeln!(write, "</tbody>")?;
+    writeln!(write, "</table>")?;
+    writeln!(write, "</details>")?;
+
+    Ok(())
+}
+
+/// Write the summary information and the end of the report.
+pub fn write_html_summary(total_info: &TotalInfo, write: &mut dyn Write) -> MyResult<()> {
+    let mut rows: Vec<(&str, String)> = Vec::new();
+
+    if let Some(reason) = &total_info.incomplete {
+        rows.push(("Incomplete scan", reason.clone()));
+    }
+    if let Some(reason) = &total_info.unverified {
+        rows.push(("Unverified candidates", reason.clone()));
+    }
+    rows.extend([
+        ("Hashing algorithm", total_info.algorithm.to_string()),
+        (
+            "Total number of files",
+            total_info.total_num_files.to_string(),
+        ),
+        (
+            "Total number of identical files",
+            total_info.total_num_identical.to_string(),
+        ),
+        (
+            "Total number of different hashes",
+            total_info.total_num_hashes.to_string(),
+        ),
+        (
+            "Total size of identical files",
+            format_size(total_info.total_size),
+        ),
+        (
+            "Total reclaimable space",
+            format_size(total_info.total_wasted),
+        ),
+    ]);
+    if let Some(total_wasted_allocated) = total_info.total_wasted_allocated {
+        rows.push((
+            "Total reclaimable disk space",
+            format_size(total_wasted_allocated),
+        ));
+    }
+    if !total_info.skipped_files.is_empty() {
+        rows.push(("Skipped files", total_info.skipped_files.len().to_string()));
+    }
+
+    writeln!(write, "<h2>Summary</h2>")?;
+    writeln!(write, "<table>")?;
+    writeln!(write, "<tbody>")?;
+    for (name, value) in rows {
+        writeln!(
+            write,
+            "<tr><td>{}</td><td>{}</td></tr>",
+            escape_html(name),
+            escape_html(&value)
+        )?;
+    }
+    writeln!(write, "</tbody>")?;
+    writeln!(write, "</table>")?;
+
+    if !total_info.skipped_files.is_empty() {
+        writeln!(write, "<details>")?;
+        writeln!(write, "<summary>Skipped files</summary>")?;
+        writeln!(write, "<table>")?;
+        writeln!(
+            write,
+            "<thead><tr><th>Path</th><th>Reason</th></tr></thead>"
+        )?;
+        writeln!(write, "<tbody>")?;
+        for skipped in &total_info.skipped_files {
+            writeln!(
+                write,
+                "<tr><td><code>{}</code></td><td>{}</td></tr>",
+                escape_html(&skipped.path.to_string_lossy()),
+                escape_html(&skipped.reason.to_string())
+            )?;
+        }
+        writeln!(write, "</tbody>")?;
+        writeln!(write, "</table>")?;
+        writeln!(write, "</details>")?;
+    }
+
+    write.write_all(HTML_FOOTER.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_html {
+    use super::*;
+    use crate::Key;
+    use std::path::PathBuf;
+
+    /// cargo test -- --show-output test_escape_html
+    #[test]
+    fn test_escape_html() -> MyResult<()> {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+
+        let group_info = GroupInfo {
+            key: Key::new(10, Some("abc".to_string())),
+            algorithm: None,
+            paths: vec![PathBuf::from("dir/<script>.txt")],
+            num_file: 1,
+            sum_size: 10,
+            wasted_size: 0,
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        write_html_group(&group_info, 0, &mut buffer)?;
+        let html = String::from_utf8(buffer)?;
+        assert!(html.contains("&lt;script&gt;.txt"));
+        assert!(!html.contains("<script>"));
+
+        Ok(())
+    }
+}
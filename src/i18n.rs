@@ -0,0 +1,105 @@
+use crate::{Arguments, Lang};
+use std::sync::OnceLock;
+
+/// Language chosen once (`--lang`, `LC_ALL`, `LC_MESSAGES` or `LANG`).
+static LANG: OnceLock<Lang> = OnceLock::new();
+
+/**
+Set the language of the messages and of the Personal format.
+
+`--lang` takes precedence over the environment variables.
+*/
+pub fn init_lang(arguments: &Arguments) {
+    let _ = LANG.set(arguments.lang.unwrap_or_else(Lang::from_env));
+}
+
+/// Get the language of the messages (English if not initialized).
+pub fn get_lang() -> Lang {
+    LANG.get().copied().unwrap_or_default()
+}
+
+/**
+Translate a message (gettext style): the English text is the key.
+
+Messages without translation are displayed in English.
+
+Only the human-readable output is translated: the keys of JSON, YAML, XML
+and CSV reports are part of their format and do not change.
+*/
+pub fn tr(message: &'static str) -> &'static str {
+    match get_lang() {
+        Lang::En => message,
+        Lang::PtBr => pt_br(message).unwrap_or(message),
+    }
+}
+
+/// Brazilian Portuguese catalog.
+fn pt_br(message: &str) -> Option<&'static str> {
+    let translation = match message {
+        // Group of identical files (Personal format)
+        "size:" => "tamanho:",
+        "algorithm:" => "algoritmo:",
+        "group id:" => "id do grupo:",
+        "Paths:" => "Caminhos:",
+        "Number of identical files:" => "Número de arquivos idênticos:",
+        "Sum of file sizes:" => "Soma dos tamanhos:",
+        "Wasted size:" => "Espaço desperdiçado:",
+        "Wasted disk space:" => "Espaço desperdiçado em disco:",
+        "apparent size" => "tamanho aparente",
+        "allocated size" => "tamanho alocado",
+        "unknown" => "desconhecido",
+        "unverified (same size only)" => "não verificado (apenas mesmo tamanho)",
+
+        // Summary information
+        "Incomplete scan:" => "Busca incompleta:",
+        "Unverified candidates:" => "Candidatos não verificados:",
+        "Hashing algorithm:" => "Algoritmo de hash:",
+        "Total number of files:" => "Número total de arquivos:",
+        "Total number of identical files:" => "Número total de arquivos idênticos:",
+        "Total number of different hashes:" => "Número total de hashes diferentes:",
+        "Total size of identical files:" => "Tamanho total dos arquivos idênticos:",
+        "Total reclaimable space:" => "Espaço total recuperável:",
+        "Total reclaimable disk space:" => "Espaço total recuperável em disco:",
+        "Wasted size by directory:" => "Espaço desperdiçado por diretório:",
+        "files" => "arquivos",
+        "Metadata differences:" => "Diferenças de metadados:",
+        "Skipped files:" => "Arquivos ignorados:",
+        "Skipped files (busy):" => "Arquivos ignorados (em uso):",
+        "Use --verbose to list the skipped files." => {
+            "Use --verbose para listar os arquivos ignorados."
+        }
+
+        // Actions
+        "Dry run: no file was changed." => "Simulação: nenhum arquivo foi alterado.",
+        "Number of processed files:" => "Número de arquivos processados:",
+        "Number of failed actions:" => "Número de ações que falharam:",
+        "Changed files (skipped):" => "Arquivos alterados (ignorados):",
+        "Total size of reclaimed space:" => "Espaço total recuperado:",
+        "The search was interrupted: no action applied." => {
+            "A busca foi interrompida: nenhuma ação foi aplicada."
+        }
+
+        // Runtime messages
+        "Total Execution Time:" => "Tempo total de execução:",
+        "Time per stage:" => "Tempo por etapa:",
+
+        _ => return None,
+    };
+
+    Some(translation)
+}
+
+#[cfg(test)]
+mod test_i18n {
+    use super::*;
+
+    /// cargo test -- --show-output test_pt_br_catalog
+    #[test]
+    fn test_pt_br_catalog() {
+        assert_eq!(
+            pt_br("Total number of files:"),
+            Some("Número total de arquivos:")
+        );
+        assert_eq!(pt_br("not translated"), None);
+    }
+}
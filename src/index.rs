@@ -0,0 +1,426 @@
+use crate::{
+    limits::hash_is_stopped, skipped::add_error, Algorithm, Arguments, FileInfo, Key, MyResult,
+    PathBufExtension,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File, Metadata},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+    time::{Duration, SystemTime},
+};
+
+/// Version of the digest index format.
+///
+/// Version 2: self-describing digests (`blake3:<hex>`).
+pub const INDEX_VERSION: u32 = 2;
+
+/// Magic bytes at the beginning of a binary digest index.
+const MAGIC: &[u8; 8] = b"FIFINDEX";
+
+/// Digests of the local files (exported indexes, `--resume`), by path.
+static KNOWN_DIGESTS: LazyLock<RwLock<HashMap<PathBuf, IndexEntry>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Digests of the imported indexes, by path qualified with the index (`<index>::<path>`).
+static IMPORTED_DIGESTS: LazyLock<RwLock<HashMap<PathBuf, IndexEntry>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Separator between the path of an imported index and the path of an indexed file.
+const INDEX_SEPARATOR: &str = "::";
+
+/**
+Digest index: path -> (size, digest) of all files.
+
+Saved with `--export_index` and loaded with `--import_index`,
+to find identical files across machines without reading the files again.
+
+The digests are self-describing (`blake3:<hex>`): an index may contain
+the digests of several algorithms, and only those of `--algorithm` are used.
+
+If the filename ends in `.json`, the index is written in JSON format,
+otherwise in a compact binary format.
+*/
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DigestIndex {
+    /// Version of the index format
+    pub version: u32,
+    /// Algorithm chosen when the index was created
+    pub algorithm: Algorithm,
+    /// Indexed files
+    pub entries: Vec<IndexEntry>,
+}
+
+/// Indexed file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// File path
+    pub path: PathBuf,
+    /// File size (in bytes)
+    pub size: u64,
+    /// Last modification time (seconds since the Unix epoch)
+    pub modified: Option<u64>,
+    /// Hash of the entire file, prefixed by its algorithm: `blake3:<hex>`
+    pub digest: String,
+}
+
+impl IndexEntry {
+    /// Get the digest, if it was produced by the algorithm.
+    pub fn get_digest(&self, algorithm: Algorithm) -> Option<&str> {
+        match Algorithm::split_digest(&self.digest) {
+            (Some(tag), digest) if tag == algorithm => Some(digest),
+            _ => None,
+        }
+    }
+
+    /// Get the entry of a local file.
+    pub fn new(path: &Path, digest: String) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+
+        Some(IndexEntry {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            modified: get_modified(&metadata),
+            digest,
+        })
+    }
+}
+
+impl DigestIndex {
+    /// Hash all files and build the index.
+    ///
+    /// Digests already known are not calculated again.
+    pub fn new(all_files: &[FileInfo], arguments: &Arguments) -> Self {
+        let entries: Vec<IndexEntry> = all_files
+            .par_iter() // rayon parallel iterator
+            .filter_map(|file_info| {
+                // With `--timeout` or Ctrl+C, the index contains the files hashed so far.
+                if hash_is_stopped() {
+                    return None;
+                }
+
+                let digest: String = match file_info.path.get_hash(arguments, 3) {
+                    Ok(digest) => digest?,
+                    Err(error) => {
+                        eprintln!("fn DigestIndex::new()");
+                        eprintln!("path: {:?}", file_info.path);
+                        eprintln!("Error: {error}");
+                        add_error(&file_info.path, &error);
+                        return None;
+                    }
+                };
+
+                let modified = fs::metadata(&file_info.path)
+                    .ok()
+                    .and_then(|metadata| get_modified(&metadata));
+
+                Some(IndexEntry {
+                    path: file_info.path.clone(),
+                    size: file_info.key.size as u64,
+                    modified,
+                    digest: arguments.algorithm.tag_digest(&digest),
+                })
+            })
+            .collect();
+
+        // Reuse these digests to find the identical files.
+        insert_known_digests(&entries);
+
+        DigestIndex {
+            version: INDEX_VERSION,
+            algorithm: arguments.algorithm,
+            entries,
+        }
+    }
+
+    /// Update the entry of the file: remove it and, if a digest is given, insert it again.
+    pub fn update(&mut self, path: &Path, digest: Option<String>) {
+        self.entries.retain(|entry| entry.path != path);
+
+        let digest: Option<String> = digest.map(|digest| self.algorithm.tag_digest(&digest));
+
+        if let Some(entry) = digest.and_then(|digest| IndexEntry::new(path, digest)) {
+            insert_known_digests(std::slice::from_ref(&entry));
+            self.entries.push(entry);
+        }
+    }
+
+    /// Read the index in binary or JSON format.
+    pub fn read(path: &Path) -> MyResult<Self> {
+        let bytes: Vec<u8> = fs::read(path).inspect_err(|error| {
+            eprintln!("fn DigestIndex::read()");
+            eprintln!("Couldn't read {path:?}");
+            eprintln!("Error: {error}");
+        })?;
+
+        let mut index: DigestIndex = match bytes.strip_prefix(MAGIC) {
+            Some(binary) => bincode::deserialize(binary)?,
+            None => serde_json::from_slice(&bytes)?,
+        };
+
+        // Version 1: all the digests were produced by the algorithm of the index.
+        if index.version == 1 {
+            for entry in &mut index.entries {
+                entry.digest = index.algorithm.tag_digest(&entry.digest);
+            }
+            index.version = INDEX_VERSION;
+        }
+
+        if index.version != INDEX_VERSION {
+            eprintln!("fn DigestIndex::read()");
+            eprintln!("Unsupported index version: {}", index.version);
+            eprintln!("Supported version: {INDEX_VERSION}");
+            return Err(format!("unsupported index version in {path:?}").into());
+        }
+
+        Ok(index)
+    }
+
+    /// Write the index in binary or JSON format.
+    pub fn write(&self, path: &Path) -> MyResult<()> {
+        self.save(path)?;
+        eprintln!("Write digest index: {path:?}");
+
+        Ok(())
+    }
+
+    /// Write the index without message (ex.: periodic checkpoints of `--resume`).
+    pub(crate) fn save(&self, path: &Path) -> MyResult<()> {
+        let file: File = File::create(path).inspect_err(|error| {
+            eprintln!("fn DigestIndex::save()");
+            eprintln!("Couldn't create {path:?}");
+            eprintln!("Error: {error}");
+        })?;
+        let mut writer = BufWriter::new(file);
+
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            serde_json::to_writer_pretty(&mut writer, self)?;
+        } else {
+            writer.write_all(MAGIC)?;
+            bincode::serialize_into(&mut writer, self)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/**
+Load the indexes given by `--import_index`.
+
+Returns the indexed files (ex.: files of another machine), with their paths
+qualified by the index: `/tmp/pictures.idx::/home/user/Pictures/a.jpg`.
+
+An index of another machine may contain the same paths as the local files:
+the indexed files are never confused with the local files.
+*/
+pub fn get_indexed_files(arguments: &Arguments) -> MyResult<Vec<FileInfo>> {
+    let mut entries: Vec<IndexEntry> = Vec::new();
+
+    for path in &arguments.import_index {
+        let index = DigestIndex::read(path)?;
+        let num_entries: usize = index.entries.len();
+
+        // Digests of other algorithms are ignored.
+        let matching: Vec<IndexEntry> = index
+            .entries
+            .into_iter()
+            .filter(|entry| entry.get_digest(arguments.algorithm).is_some())
+            .map(|entry| IndexEntry {
+                path: get_imported_path(path, &entry.path),
+                ..entry
+            })
+            .collect();
+
+        if matching.is_empty() && num_entries > 0 {
+            eprintln!("fn get_indexed_files()");
+            eprintln!(
+                "The index {path:?} has no digest of the {} algorithm.",
+                arguments.algorithm
+            );
+            eprintln!("Choose the algorithm of the index with the `--algorithm` option.");
+            return Err(format!("hash algorithm mismatch in {path:?}").into());
+        }
+
+        entries.extend(matching);
+    }
+
+    if let Ok(mut imported) = IMPORTED_DIGESTS.write() {
+        for entry in &entries {
+            imported.insert(entry.path.clone(), entry.clone());
+        }
+    }
+
+    let indexed_files: Vec<FileInfo> = entries
+        .into_iter()
+        .filter(|entry| arguments.size_is_included(entry.size))
+        .map(|entry| FileInfo {
+            key: Key::new(entry.size, None),
+            path: entry.path,
+            inode: None,
+            modified: entry
+                .modified
+                .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+            allocated: None,
+        })
+        .filter(|file_info| arguments.time_is_included(file_info.modified))
+        .collect();
+
+    Ok(indexed_files)
+}
+
+/// Get the path of an indexed file, qualified by the index: `<index>::<path>`.
+fn get_imported_path(index: &Path, path: &Path) -> PathBuf {
+    let mut imported = index.as_os_str().to_os_string();
+    imported.push(INDEX_SEPARATOR);
+    imported.push(path);
+    PathBuf::from(imported)
+}
+
+/**
+Get the digest of the file from the imported or exported indexes.
+
+Only the digests produced by the algorithm are used. The digest of a
+local file is used only if its size and modification time have not changed.
+
+The digests of the imported indexes are only used for the indexed files
+(`<index>::<path>`), never for a local file with the same path.
+*/
+pub fn get_known_digest(path: &Path, algorithm: Algorithm) -> Option<String> {
+    // File of an imported index
+    if let Some(entry) = IMPORTED_DIGESTS.read().ok()?.get(path) {
+        return entry.get_digest(algorithm).map(str::to_string);
+    }
+
+    let known = KNOWN_DIGESTS.read().ok()?;
+    let entry = known.get(path)?;
+    let digest: String = entry.get_digest(algorithm)?.to_string();
+    let metadata = fs::metadata(path).ok()?;
+
+    (metadata.len() == entry.size && get_modified(&metadata) == entry.modified).then_some(digest)
+}
+
+/// Add the digest of a local file, produced by the algorithm, to the known digests.
+pub fn insert_known_digest(path: &Path, digest: String, algorithm: Algorithm) {
+    if let Some(entry) = IndexEntry::new(path, algorithm.tag_digest(&digest)) {
+        insert_known_digests(&[entry]);
+    }
+}
+
+/// Get all the known digests of the local files.
+pub(crate) fn get_known_entries() -> Vec<IndexEntry> {
+    KNOWN_DIGESTS
+        .read()
+        .map(|known| known.values().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Add the entries to the known digests.
+pub(crate) fn insert_known_digests(entries: &[IndexEntry]) {
+    if let Ok(mut known) = KNOWN_DIGESTS.write() {
+        for entry in entries {
+            known.insert(entry.path.clone(), entry.clone());
+        }
+    }
+}
+
+/// Get the last modification time (seconds since the Unix epoch).
+fn get_modified(metadata: &Metadata) -> Option<u64> {
+    metadata
+        .modified()
+        .ok()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod test_index {
+    use super::*;
+    use clap::Parser;
+
+    /// cargo test -- --show-output test_index_round_trip
+    #[test]
+    fn test_index_round_trip() -> MyResult<()> {
+        let index = DigestIndex {
+            version: INDEX_VERSION,
+            algorithm: Algorithm::Blake3,
+            entries: vec![IndexEntry {
+                path: PathBuf::from("/other/machine/file.txt"),
+                size: 3,
+                modified: Some(1_700_000_000),
+                digest: "blake3:abc".to_string(),
+            }],
+        };
+
+        let temp_dir = tempfile::tempdir()?;
+
+        for name in ["index.bin", "index.json"] {
+            let path = temp_dir.path().join(name);
+            index.write(&path)?;
+            let result = DigestIndex::read(&path)?;
+
+            assert_eq!(result.version, INDEX_VERSION);
+            assert_eq!(result.algorithm, Algorithm::Blake3);
+            assert_eq!(result.entries.len(), 1);
+            assert_eq!(result.entries[0].path, index.entries[0].path);
+            assert_eq!(result.entries[0].digest, "blake3:abc");
+            assert_eq!(result.entries[0].get_digest(Algorithm::Blake3), Some("abc"));
+            assert_eq!(result.entries[0].get_digest(Algorithm::SHA256), None);
+        }
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_indexed_files
+    #[test]
+    fn test_indexed_files() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let local = temp_dir.path().join("file.txt");
+        fs::write(&local, "abc")?;
+        let metadata = fs::metadata(&local)?;
+
+        // File of another machine with the same path, size and modification time.
+        let index = DigestIndex {
+            version: INDEX_VERSION,
+            algorithm: Algorithm::Blake3,
+            entries: vec![IndexEntry {
+                path: local.clone(),
+                size: 3,
+                modified: get_modified(&metadata),
+                digest: "blake3:remote".to_string(),
+            }],
+        };
+
+        let index_path = temp_dir.path().join("index.json");
+        index.write(&index_path)?;
+
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--import_index",
+            &index_path.to_string_lossy(),
+        ]);
+        let indexed_files = get_indexed_files(&arguments)?;
+        let imported = get_imported_path(&index_path, &local);
+
+        // The indexed file is not dropped.
+        assert_eq!(indexed_files.len(), 1);
+        assert_eq!(indexed_files[0].path, imported);
+
+        // Its digest is not used for the local file.
+        assert_eq!(get_known_digest(&local, Algorithm::Blake3), None);
+        assert_eq!(
+            get_known_digest(&imported, Algorithm::Blake3),
+            Some("remote".to_string())
+        );
+
+        Ok(())
+    }
+}
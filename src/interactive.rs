@@ -0,0 +1,227 @@
+use crate::{
+    actions::{execute_tasks, Action, Task},
+    is_virtual_file, split_and_insert, ActionInfo, Arguments, GroupInfo, MyResult, SEPARATOR,
+};
+use std::{
+    io::{self, BufRead, Lines, Write},
+    path::Path,
+};
+
+/// Answer given by the user for each file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Answer {
+    Keep,
+    Apply(Action),
+    Skip,
+    Quit,
+}
+
+/**
+Walk through each group of identical files and ask the user what to do with each file.
+
+Similar to `fdupes -d`.
+
+Nothing is changed until the user confirms the planned actions.
+
+Prompts are written to stderr, so stdout can still be redirected.
+
+Like the other actions, the files of the reference directory (`--compare`)
+are always kept and the virtual files (archive members and S3 objects)
+are never changed: the user is not asked about them.
+*/
+pub fn resolve_interactively(groups: &[GroupInfo], arguments: &Arguments) -> MyResult<ActionInfo> {
+    let mut lines = io::stdin().lock().lines();
+    let tasks: Vec<Task> = plan_actions(&mut lines, groups, arguments)?;
+
+    if tasks.is_empty() || !confirm(&mut lines, &tasks)? {
+        eprintln!("No action applied.");
+        return Ok(ActionInfo::default());
+    }
+
+    Ok(execute_tasks(&tasks))
+}
+
+/// Ask the user what to do with each file and get the planned actions.
+fn plan_actions<'a, B: BufRead>(
+    lines: &mut Lines<B>,
+    groups: &'a [GroupInfo],
+    arguments: &Arguments,
+) -> MyResult<Vec<Task<'a>>> {
+    let mut tasks: Vec<Task> = Vec::new();
+    let total = groups.len();
+
+    'groups: for (number, group_info) in groups.iter().enumerate() {
+        // Only files with the same hash are identical.
+        if group_info.key.hash.is_none() {
+            continue;
+        }
+
+        // No file of the group can be changed.
+        if group_info
+            .paths
+            .iter()
+            .all(|path| arguments.is_reference(path) || is_virtual_file(path))
+        {
+            continue;
+        }
+
+        loop {
+            eprintln!(
+                "\nSet {} of {total}, size: {} bytes, hash: {}",
+                number + 1,
+                split_and_insert(group_info.key.size, SEPARATOR),
+                group_info.key.hash.clone().unwrap_or_default()
+            );
+
+            let mut kept: Vec<&Path> = Vec::new();
+            let mut planned: Vec<(&Path, Action)> = Vec::new();
+
+            for (index, path) in group_info.paths.iter().enumerate() {
+                if arguments.is_reference(path) {
+                    eprintln!("[{}] {path:?}: reference file, kept", index + 1);
+                    kept.insert(0, path.as_path());
+                    continue;
+                }
+
+                if is_virtual_file(path) {
+                    eprintln!("[{}] {path:?}: virtual file, not changed", index + 1);
+                    continue;
+                }
+
+                match ask(lines, index + 1, path)? {
+                    Answer::Keep => kept.push(path.as_path()),
+                    Answer::Apply(action) => planned.push((path.as_path(), action)),
+                    Answer::Skip => continue 'groups,
+                    Answer::Quit => break 'groups,
+                }
+            }
+
+            // Ask again if no file was kept.
+            let Some(&keeper) = kept.first() else {
+                eprintln!("At least one file must be kept in each group!");
+                continue;
+            };
+
+            tasks.extend(planned.into_iter().map(|(path, action)| Task {
+                keeper,
+                path,
+                size: group_info.key.size,
+                // With `--trash`, deleted files are sent to the trash.
+                action: match action {
+                    Action::Delete if arguments.trash => Action::Trash,
+                    action => action,
+                },
+                move_to: None,
+                dry_run: arguments.dry_run,
+                verify: arguments.verify_before_action,
+            }));
+
+            break;
+        }
+    }
+
+    Ok(tasks)
+}
+
+/// Ask the user what to do with the file.
+fn ask<B: BufRead>(lines: &mut Lines<B>, index: usize, path: &Path) -> MyResult<Answer> {
+    loop {
+        eprint!(
+            "[{index}] {path:?}: (k)eep, (d)elete, (h)ardlink, (r)eflink, (s)kip group, (q)uit? "
+        );
+        io::stderr().flush()?;
+
+        // End of input: stop asking.
+        let Some(line) = lines.next() else {
+            return Ok(Answer::Quit);
+        };
+
+        match line?.trim().to_lowercase().as_str() {
+            "k" | "keep" => return Ok(Answer::Keep),
+            "d" | "delete" => return Ok(Answer::Apply(Action::Delete)),
+            "h" | "hardlink" => return Ok(Answer::Apply(Action::Hardlink)),
+            "r" | "reflink" => return Ok(Answer::Apply(Action::Reflink)),
+            "s" | "skip" => return Ok(Answer::Skip),
+            "q" | "quit" => return Ok(Answer::Quit),
+            answer => eprintln!("Invalid answer: {answer:?}"),
+        }
+    }
+}
+
+/// Show the planned actions and ask the user for confirmation.
+fn confirm<B: BufRead>(lines: &mut Lines<B>, tasks: &[Task]) -> MyResult<bool> {
+    let size: usize = tasks.iter().map(|task| task.size).sum();
+
+    eprintln!("\nPlanned actions:");
+    for task in tasks {
+        eprintln!("{:?}: {:?}", task.action, task.path);
+    }
+
+    eprint!(
+        "Apply {} actions ({} bytes)? [y/N] ",
+        tasks.len(),
+        split_and_insert(size, SEPARATOR)
+    );
+    io::stderr().flush()?;
+
+    match lines.next() {
+        Some(line) => Ok(matches!(line?.trim().to_lowercase().as_str(), "y" | "yes")),
+        None => Ok(false),
+    }
+}
+
+#[cfg(test)]
+mod test_interactive {
+    use super::*;
+    use crate::Key;
+    use clap::Parser;
+    use std::{fs, io::Cursor, path::PathBuf};
+
+    /// cargo test -- --show-output test_plan_actions_compare
+    #[test]
+    fn test_plan_actions_compare() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = fs::canonicalize(temp_dir.path())?;
+        let reference = dir.join("reference");
+        let target = dir.join("target");
+        fs::create_dir(&reference)?;
+        fs::create_dir(&target)?;
+
+        let paths: Vec<PathBuf> = vec![
+            target.join("b.txt"),
+            reference.join("a.txt"),
+            target.join("c.txt"),
+        ];
+        let group_info = GroupInfo {
+            key: Key::new(9, Some("abc".to_string())),
+            algorithm: None,
+            paths: paths.clone(),
+            num_file: 3,
+            sum_size: 27,
+            wasted_size: 18,
+        };
+
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "-i",
+            &target.to_string_lossy(),
+            "--compare",
+            &reference.to_string_lossy(),
+        ]);
+
+        // Only the target files are asked: the reference file is kept.
+        let mut lines = Cursor::new("d\nd\nq\n").lines();
+        let groups = [group_info];
+        let tasks = plan_actions(&mut lines, &groups, &arguments)?;
+
+        assert_eq!(tasks.len(), 2);
+        for (task, path) in tasks.iter().zip([&paths[0], &paths[2]]) {
+            assert_eq!(task.keeper, paths[1]);
+            assert_eq!(task.path, path.as_path());
+            assert_eq!(task.action, Action::Delete);
+        }
+        assert_eq!(lines.next().transpose()?.as_deref(), Some("q"));
+
+        Ok(())
+    }
+}
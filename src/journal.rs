@@ -0,0 +1,556 @@
+use crate::{
+    actions::{copy_and_remove, is_cross_device, Action, Task},
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    create_new_file, create_temp_file,
+    structures::file_info::get_inode,
+    MyResult,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Process exit code of `--undo` when some actions could not be undone.
+pub const UNDO_FAILED_EXIT_CODE: i32 = 7;
+
+/// Undo journal of the applied actions (`--journal`).
+static JOURNAL: OnceLock<Mutex<File>> = OnceLock::new();
+
+/// Entry of the undo journal: one applied action (JSON Lines).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Action applied to the file
+    pub action: Action,
+    /// Path of the changed file
+    pub path: PathBuf,
+    /// File kept in the group, identical to the changed file
+    pub keeper: PathBuf,
+    /// File size (in bytes)
+    pub size: usize,
+    /// Path of the file in the quarantine directory (`--move_to`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_to: Option<PathBuf>,
+    /// Last modification time before the action (seconds since the Unix epoch)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+}
+
+/// Action that could not be undone.
+#[derive(Debug, Clone, Serialize)]
+pub struct UndoFailure {
+    /// Path of the changed file
+    pub path: PathBuf,
+    /// Action applied to the file
+    pub action: Action,
+    /// Why the action was not undone
+    pub reason: String,
+}
+
+/// Result of `--undo`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UndoInfo {
+    /// Number of actions undone
+    #[serde(rename = "Number of actions undone")]
+    pub num_undone: usize,
+    /// Number of actions with nothing to undo (reflinks)
+    #[serde(rename = "Number of actions with nothing to undo")]
+    pub num_unchanged: usize,
+    /// Actions that could not be undone
+    #[serde(rename = "Failures")]
+    pub failures: Vec<UndoFailure>,
+    /// Only print what would be done (`--dry_run`)
+    #[serde(rename = "Dry run")]
+    pub dry_run: bool,
+}
+
+/// Open the undo journal in append mode (`--journal`).
+pub fn init_journal(arguments: &Arguments) -> MyResult<()> {
+    let Some(path) = &arguments.journal else {
+        return Ok(());
+    };
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .inspect_err(|error| {
+            eprintln!("fn init_journal()");
+            eprintln!("Couldn't open the journal {path:?}");
+            eprintln!("Error: {error}");
+        })?;
+
+    let _ = JOURNAL.set(Mutex::new(file));
+
+    Ok(())
+}
+
+/// Check if the applied actions are written to the undo journal.
+pub fn is_journaling() -> bool {
+    JOURNAL.get().is_some()
+}
+
+/**
+Write the applied action to the undo journal.
+
+Each entry is flushed at once: the journal is complete even if
+the program is interrupted.
+*/
+pub fn record(
+    task: &Task,
+    moved_to: Option<PathBuf>,
+    modified: Option<SystemTime>,
+) -> MyResult<()> {
+    let Some(journal) = JOURNAL.get() else {
+        return Ok(());
+    };
+
+    // Absolute paths: the journal can be undone from another directory.
+    let absolute = |path: &Path| std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+
+    let entry = JournalEntry {
+        action: task.action,
+        path: absolute(task.path),
+        keeper: absolute(task.keeper),
+        size: task.size,
+        moved_to,
+        modified: modified
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs()),
+    };
+
+    let mut file = journal.lock().map_err(|error| error.to_string())?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    file.flush()?;
+
+    Ok(())
+}
+
+/// Read the entries of an undo journal.
+fn read_journal(path: &Path) -> MyResult<Vec<JournalEntry>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries: Vec<JournalEntry> = Vec::new();
+
+    for (number, line) in reader.lines().enumerate() {
+        let line: String = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: JournalEntry =
+            serde_json::from_str(&line).map_err(|error| format!("line {}: {error}", number + 1))?;
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/**
+Undo the actions of a journal, in reverse order (`--undo`).
+
+- `--move_to`: the file is moved back from the quarantine directory.
+- `--hardlink`, `--symlink`: the link is replaced with a copy of the kept file,
+  if the path is still a link to the kept file.
+- `--delete`: the file is restored as a copy of the kept file.
+- `--trash`: the file is restored from the trash (Linux and Windows).
+- `--reflink`: the clone is already an independent copy: nothing to undo.
+
+Copies get the modification time of the original file. The kept file
+must still exist with the same size; files that exist again are not
+overwritten. With `--dry_run`, nothing is changed.
+*/
+pub fn undo_journal(path: &Path, arguments: &Arguments) -> MyResult<UndoInfo> {
+    let entries: Vec<JournalEntry> = read_journal(path).inspect_err(|error| {
+        eprintln!("fn undo_journal()");
+        eprintln!("Couldn't read the journal {path:?}");
+        eprintln!("Error: {error}");
+    })?;
+
+    let mut undo_info = UndoInfo {
+        dry_run: arguments.dry_run,
+        ..Default::default()
+    };
+
+    for entry in entries.iter().rev() {
+        match undo_entry(entry, arguments.dry_run) {
+            Ok(true) => undo_info.num_undone += 1,
+            Ok(false) => undo_info.num_unchanged += 1,
+            Err(error) => undo_info.failures.push(UndoFailure {
+                path: entry.path.clone(),
+                action: entry.action,
+                reason: error.to_string(),
+            }),
+        }
+    }
+
+    Ok(undo_info)
+}
+
+/// Undo one action. Returns false if there was nothing to undo.
+fn undo_entry(entry: &JournalEntry, dry_run: bool) -> MyResult<bool> {
+    let prefix: &str = if dry_run { "[dry run] " } else { "" };
+    let path: &Path = &entry.path;
+
+    match entry.action {
+        Action::Reflink => Ok(false),
+        Action::Move => {
+            let moved_to: &Path = entry
+                .moved_to
+                .as_deref()
+                .ok_or("quarantine path not recorded")?;
+            check_missing(path)?;
+            if !moved_to.is_file() {
+                return Err(format!("{moved_to:?} not found in the quarantine directory").into());
+            }
+
+            eprintln!("{prefix}Restore file: {moved_to:?} -> {path:?}");
+            if !dry_run {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                match fs::rename(moved_to, path) {
+                    Ok(()) => {}
+                    // Files cannot be renamed across filesystems: copy and remove.
+                    Err(error) if is_cross_device(&error) => copy_and_remove(moved_to, path)?,
+                    Err(error) => return Err(error.into()),
+                }
+            }
+            Ok(true)
+        }
+        Action::Hardlink | Action::Symlink | Action::RelativeSymlink => {
+            check_keeper(entry)?;
+            check_link(entry)?;
+            eprintln!(
+                "{prefix}Replace link: {path:?} <- copy of {:?}",
+                entry.keeper
+            );
+            if !dry_run {
+                // Copy to a temporary file, then rename over the link.
+                create_temp_file(path, |temp_path| copy_keeper(entry, temp_path))?
+                    .persist(path)
+                    .map_err(|error| error.error)?;
+            }
+            Ok(true)
+        }
+        Action::Delete => {
+            check_missing(path)?;
+            check_keeper(entry)?;
+            eprintln!(
+                "{prefix}Restore file: {path:?} <- copy of {:?}",
+                entry.keeper
+            );
+            if !dry_run {
+                copy_keeper(entry, path)?;
+            }
+            Ok(true)
+        }
+        Action::Trash => {
+            check_missing(path)?;
+            eprintln!("{prefix}Restore file from trash: {path:?}");
+            if !dry_run {
+                restore_from_trash(path)?;
+            }
+            Ok(true)
+        }
+    }
+}
+
+/// The file must not exist: it is never overwritten.
+fn check_missing(path: &Path) -> MyResult<()> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Err(format!("{path:?} already exists").into());
+    }
+    Ok(())
+}
+
+/// The kept file must still exist with the same size.
+fn check_keeper(entry: &JournalEntry) -> MyResult<()> {
+    let size: u64 = fs::metadata(&entry.keeper)
+        .map_err(|error| format!("kept file {:?}: {error}", entry.keeper))?
+        .len();
+
+    if size != entry.size as u64 {
+        return Err(format!("kept file {:?} has changed", entry.keeper).into());
+    }
+    Ok(())
+}
+
+/**
+The file must still be the link created by the action: a hard link to
+the kept file (same device and inode) or a symbolic link resolving to it.
+
+A file written at the path after the action is never replaced.
+*/
+fn check_link(entry: &JournalEntry) -> MyResult<()> {
+    let path: &Path = &entry.path;
+    let metadata = fs::symlink_metadata(path).map_err(|error| format!("{path:?}: {error}"))?;
+
+    let is_link: bool = match entry.action {
+        Action::Hardlink => {
+            let keeper = fs::metadata(&entry.keeper)?;
+            !metadata.file_type().is_symlink()
+                && get_inode(&metadata).is_some()
+                && get_inode(&metadata) == get_inode(&keeper)
+        }
+        _ => {
+            metadata.file_type().is_symlink()
+                && fs::canonicalize(path).ok() == fs::canonicalize(&entry.keeper).ok()
+        }
+    };
+
+    if !is_link {
+        return Err(format!("{path:?} is no longer a link to {:?}", entry.keeper).into());
+    }
+    Ok(())
+}
+
+/// Copy the kept file with the modification time of the original file.
+///
+/// The destination is a new file: an existing file or link is never overwritten.
+/// A partial copy is removed.
+fn copy_keeper(entry: &JournalEntry, destination: &Path) -> io::Result<()> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file: File = create_new_file(destination)?;
+
+    let mut copy = || -> io::Result<()> {
+        let mut keeper = File::open(&entry.keeper)?;
+        io::copy(&mut keeper, &mut file)?;
+        file.set_permissions(keeper.metadata()?.permissions())?;
+
+        if let Some(secs) = entry.modified {
+            file.set_modified(UNIX_EPOCH + Duration::from_secs(secs))?;
+        }
+        Ok(())
+    };
+
+    copy().inspect_err(|_| {
+        let _ = fs::remove_file(destination);
+    })
+}
+
+/// Restore the file from the trash of the platform.
+#[cfg(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+))]
+fn restore_from_trash(path: &Path) -> MyResult<()> {
+    use trash::os_limited::{list, restore_all};
+
+    let original: PathBuf = std::path::absolute(path)?;
+    let item = list()
+        .map_err(|error| error.to_string())?
+        .into_iter()
+        .filter(|item| item.original_path() == original)
+        .max_by_key(|item| item.time_deleted)
+        .ok_or("not found in the trash")?;
+
+    restore_all([item]).map_err(|error| error.to_string())?;
+    Ok(())
+}
+
+/// Restore the file from the trash of the platform.
+#[cfg(not(any(
+    windows,
+    all(
+        unix,
+        not(target_os = "macos"),
+        not(target_os = "ios"),
+        not(target_os = "android")
+    )
+)))]
+fn restore_from_trash(_path: &Path) -> MyResult<()> {
+    Err("files cannot be restored from the trash on this platform: use the Finder".into())
+}
+
+impl UndoInfo {
+    /// Check if all the actions were undone.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Write the result in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize UndoInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize UndoInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                for failure in &self.failures {
+                    writeln!(
+                        write,
+                        "NOT UNDONE: {:?} ({:?}): {}",
+                        failure.path, failure.action, failure.reason
+                    )?;
+                }
+                if !self.is_ok() {
+                    writeln!(write)?;
+                }
+                let prefix: &str = if self.dry_run { "[dry run] " } else { "" };
+                writeln!(
+                    write,
+                    "{prefix}Number of actions undone: {}",
+                    self.num_undone
+                )?;
+                writeln!(
+                    write,
+                    "Number of actions with nothing to undo: {}",
+                    self.num_unchanged
+                )?;
+                writeln!(
+                    write,
+                    "Number of actions not undone: {}",
+                    self.failures.len()
+                )?;
+                writeln!(write)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test_journal {
+    use super::*;
+    use clap::Parser;
+
+    /// cargo test -- --show-output test_undo_delete_and_move
+    #[test]
+    fn test_undo_delete_and_move() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        fs::create_dir_all(dir.join("quarantine"))?;
+
+        let keeper = dir.join("keeper.txt");
+        let deleted = dir.join("deleted.txt");
+        let moved = dir.join("moved.txt");
+        let moved_to = dir.join("quarantine/moved.txt");
+        fs::write(&keeper, "same")?;
+        fs::write(&moved_to, "same")?;
+
+        let journal = dir.join("journal.jsonl");
+        let entries = [
+            JournalEntry {
+                action: Action::Delete,
+                path: deleted.clone(),
+                keeper: keeper.clone(),
+                size: 4,
+                moved_to: None,
+                modified: Some(1_000_000_000),
+            },
+            JournalEntry {
+                action: Action::Move,
+                path: moved.clone(),
+                keeper: keeper.clone(),
+                size: 4,
+                moved_to: Some(moved_to.clone()),
+                modified: None,
+            },
+            JournalEntry {
+                action: Action::Reflink,
+                path: keeper.clone(),
+                keeper: keeper.clone(),
+                size: 4,
+                moved_to: None,
+                modified: None,
+            },
+        ];
+        let lines: Vec<String> = entries
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<_, _>>()?;
+        fs::write(&journal, lines.join("\n"))?;
+
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let undo_info = undo_journal(&journal, &arguments)?;
+
+        assert!(undo_info.is_ok());
+        assert_eq!(undo_info.num_undone, 2);
+        assert_eq!(undo_info.num_unchanged, 1);
+        assert_eq!(fs::read_to_string(&deleted)?, "same");
+        assert_eq!(
+            fs::metadata(&deleted)?.modified()?,
+            UNIX_EPOCH + Duration::from_secs(1_000_000_000)
+        );
+        assert_eq!(fs::read_to_string(&moved)?, "same");
+        assert!(!moved_to.exists());
+
+        // Files that exist again are not overwritten.
+        let undo_info = undo_journal(&journal, &arguments)?;
+        assert_eq!(undo_info.failures.len(), 2);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_undo_links
+    #[cfg(unix)]
+    #[test]
+    fn test_undo_links() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        let keeper = dir.join("keeper.txt");
+        let hardlink = dir.join("hardlink.txt");
+        let symlink = dir.join("symlink.txt");
+        let rewritten = dir.join("rewritten.txt");
+        fs::write(&keeper, "same")?;
+        fs::hard_link(&keeper, &hardlink)?;
+        std::os::unix::fs::symlink(&keeper, &symlink)?;
+        // Linked by the action, then replaced by a new file.
+        fs::write(&rewritten, "new!")?;
+
+        let entry = |action: Action, path: &PathBuf| JournalEntry {
+            action,
+            path: path.clone(),
+            keeper: keeper.clone(),
+            size: 4,
+            moved_to: None,
+            modified: None,
+        };
+        let entries = [
+            entry(Action::Hardlink, &hardlink),
+            entry(Action::Symlink, &symlink),
+            entry(Action::Hardlink, &rewritten),
+        ];
+        let lines: Vec<String> = entries
+            .iter()
+            .map(serde_json::to_string)
+            .collect::<Result<_, _>>()?;
+        let journal = dir.join("journal.jsonl");
+        fs::write(&journal, lines.join("\n"))?;
+
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let undo_info = undo_journal(&journal, &arguments)?;
+
+        assert_eq!(undo_info.num_undone, 2);
+        assert_eq!(undo_info.failures.len(), 1);
+        assert_eq!(undo_info.failures[0].path, rewritten);
+        assert_eq!(fs::read_to_string(&rewritten)?, "new!");
+        assert!(!fs::symlink_metadata(&symlink)?.file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&symlink)?, "same");
+        assert_eq!(fs::read_to_string(&hardlink)?, "same");
+        assert_ne!(
+            get_inode(&fs::metadata(&hardlink)?),
+            get_inode(&fs::metadata(&keeper)?)
+        );
+
+        Ok(())
+    }
+}
@@ -1,37 +1,141 @@
+mod actions;
+mod archive;
 mod args;
+#[cfg(feature = "audio")]
+mod audio;
+mod bench;
+mod binary;
+mod busy;
+#[cfg(any(feature = "perceptual", feature = "audio"))]
+mod cluster;
+mod config;
+mod content;
+mod diff;
+mod dirs;
 mod enumerations;
 mod excel;
+mod file_list;
+mod filter;
+mod finder;
+mod html;
+mod i18n;
+mod index;
+mod interactive;
+mod journal;
+mod limits;
+mod logging;
+mod magic;
+mod manifest;
+mod markdown;
+mod open_files;
+mod output;
+#[cfg(feature = "perceptual")]
+mod perceptual;
+mod pipeline;
+mod progress;
+mod query;
+mod resume;
+#[cfg(feature = "s3")]
+mod s3;
+mod selftest;
+mod serve;
+mod sftp;
+mod skipped;
+mod source;
+mod spill;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod stages;
 mod structures;
+mod subset;
+mod throttle;
+mod watch;
+mod xml;
 
 // https://crates.io/crates/cfg-if
 cfg_if::cfg_if! {
     if #[cfg(feature = "walkdir")] {
         mod with_walkdir;
-        pub use with_walkdir::get_all_files;
+        use with_walkdir::get_files;
     } else {
         // default: use jwalk
         mod with_jwalk;
-        pub use with_jwalk::get_all_files;
+        use with_jwalk::get_files;
     }
 }
 
 pub use self::{
-    args::Arguments,
-    enumerations::algo::{Algorithm, PathBufExtension},
+    actions::{Action, ActionExtension},
+    args::{Arguments, Commands, VerifyTarget},
+    config::Config,
+    content::{get_extractor, StreamExtractor},
+    enumerations::algo::{Algorithm, FileDigest, PathBufExtension},
+    enumerations::color::ColorChoice,
+    enumerations::compression::Compression,
+    enumerations::file_kind::FileKind,
+    enumerations::heuristic::Heuristic,
+    enumerations::io_profile::IoProfile,
+    enumerations::keep::{KeepRules, KeepStrategy},
+    enumerations::lang::Lang,
+    enumerations::log_level::LogLevel,
+    enumerations::metadata::MetadataKey,
+    enumerations::path_style::{display_path, init_path_style, serialize_paths, PathStyle},
+    enumerations::script::ScriptFormat,
+    enumerations::sort::{SortKey, SortOrder},
+    filter::FileFilter,
+    finder::{DuplicateFinder, DuplicateGroup},
+    index::{DigestIndex, IndexEntry, INDEX_VERSION},
+    structures::action_info::ActionInfo,
+    structures::dir_info::DirInfo,
     structures::file_info::{FileExtension, FileInfo},
-    structures::group_info::{GroupExtension, GroupInfo},
+    structures::group_info::{get_group_id, GroupExtension, GroupInfo},
     structures::key_info::Key,
+    structures::metadata_info::{FileMetadata, MetadataDiff},
     structures::path_info::PathInfo,
+    structures::summary_info::{SummaryInfo, DUPLICATES_FOUND_EXIT_CODE},
     structures::total_info::TotalInfo,
 };
+pub use archive::{is_archive_member, open_reader, split_member_path, ARCHIVE_SEPARATOR};
+pub use bench::{run_bench, BenchInfo, BenchResult};
+pub use diff::{read_json_report, ChangedGroup, DiffInfo, ReportGroup};
 pub use excel::write_xlsx;
+pub use i18n::{get_lang, init_lang, tr};
+pub use interactive::resolve_interactively;
+pub use journal::{init_journal, undo_journal, JournalEntry, UndoInfo, UNDO_FAILED_EXIT_CODE};
+pub use limits::{
+    exit_on_interrupt, get_incomplete_reason, init_limits, is_interrupted, INTERRUPTED_EXIT_CODE,
+};
+pub use logging::{init_logging, log_procedure};
+pub use magic::{detect_file_kind, get_file_kind};
+pub use manifest::{verify_manifest, write_manifest, ManifestInfo, MANIFEST_MISMATCH_EXIT_CODE};
+pub use open_files::{init_open_files, OpenFile};
+pub use output::Output;
+pub use pipeline::HashPipeline;
+pub use progress::{clear_progress, init_progress, start_hashing};
+use rayon::prelude::*;
+pub use resume::{finish_resume, init_resume, write_checkpoint};
+pub use selftest::{run_selftest, SelftestInfo, SELFTEST_FAILED_EXIT_CODE};
 use serde::Serializer;
+pub use serve::{serve, Answer, Query, QueryIndex};
+pub use skipped::{get_skipped_files, init_strict, SkipReason, SkippedFile, READ_ERROR_EXIT_CODE};
+use source::{get_local_sources, get_remote_sources};
+pub use source::{DirSource, FileSource, ListSource};
+#[cfg(feature = "sqlite")]
+pub use sqlite::export_to_sqlite;
+pub use stages::{get_stages, write_stages, Stage, StageInfo};
 use std::{
-    fs::{self, File},
+    collections::{HashMap, HashSet},
+    fs, io,
     path::{Path, PathBuf},
     process::Command,
     str,
+    sync::OnceLock,
+    time::Instant,
 };
+pub use subset::{verify_subset, MissingFile, SubsetInfo, MISSING_FILES_EXIT_CODE};
+use tempfile::NamedTempFile;
+pub use throttle::init_throttle;
+pub use watch::{watch, WatchState};
 
 pub type MyError = Box<dyn std::error::Error + Send + Sync>;
 pub type MyResult<T> = Result<T, MyError>;
@@ -58,39 +162,790 @@ pub fn set_env_variables() {
     std::env::set_var("RUST_MIN_STACK", STACK_SIZE.to_string());
 }
 
+/**
+Set the number of threads of the global rayon thread pool (`--jobs`).
+
+Must be called before any parallel work: the directory walk
+(`rayon::current_num_threads()`) and the I/O thread pool depend on it.
+*/
+pub fn init_threads(arguments: &Arguments) -> MyResult<()> {
+    if let Some(jobs) = arguments.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .inspect_err(|error| {
+                eprintln!("fn init_threads()");
+                eprintln!("Error: {error}");
+            })?;
+    }
+
+    Ok(())
+}
+
+/**
+Initialize the limits of the search: `--throttle`, `--idle`,
+`--max_open_files` and `--strict`.
+
+Called by the command line and by [`DuplicateFinder`].
+*/
+pub fn init_search(arguments: &Arguments) {
+    init_throttle(arguments);
+    init_open_files(arguments);
+    init_strict(arguments);
+}
+
+/// Thread pool dedicated to reading and hashing files.
+static IO_POOL: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+
+/**
+Run the operation in the I/O thread pool.
+
+The I/O thread pool is created with `--io_threads` threads
+(or according to `--io_profile`), separately from the global rayon thread pool.
+
+If the pool cannot be created, the operation runs in the global rayon thread pool.
+*/
+pub fn run_io<T, F>(arguments: &Arguments, operation: F) -> T
+where
+    F: FnOnce() -> T + Send,
+    T: Send,
+{
+    let pool = IO_POOL.get_or_init(|| {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(IoProfile::get_io_threads(arguments))
+            .thread_name(|index| format!("io-{index}"))
+            .build()
+            .inspect_err(|error| {
+                eprintln!("fn run_io()");
+                eprintln!("Error: {error}");
+            })
+            .ok()
+    });
+
+    match pool {
+        Some(pool) => pool.install(operation),
+        None => operation(),
+    }
+}
+
 /// File is an object providing access to an open file on the filesystem.
-pub fn open_file<P>(path: &P) -> MyResult<File>
+///
+/// The file is opened for reading, within the limit of `--max_open_files`.
+pub fn open_file<P>(path: &P) -> MyResult<OpenFile>
 where
     P: AsRef<Path> + std::fmt::Debug,
 {
-    let file: File = fs::OpenOptions::new()
-        .read(true)
-        .write(false) // This option, when false, will indicate that the file should not be writable if opened.
-        .create(false) // No files will be created
-        .open(path)
-        .inspect_err(|error| {
-            // Add a custom error message
-            eprintln!("Failed to open file {path:?}");
-            eprintln!("Perhaps some temporary files no longer exist!");
-            eprintln!("Or lack of permission to read this file!");
-            eprintln!("Error: {error}");
-        })?;
+    let file: OpenFile = OpenFile::open(path.as_ref()).inspect_err(|error| {
+        // Add a custom error message
+        eprintln!("Failed to open file {path:?}");
+        eprintln!("Perhaps some temporary files no longer exist!");
+        eprintln!("Or lack of permission to read this file!");
+        eprintln!("Error: {error}");
+    })?;
 
     Ok(file)
 }
 
-/// Get path from arguments or from default (current directory).
-pub fn get_path(arguments: &Arguments) -> MyResult<PathBuf> {
-    let path: PathBuf = match &arguments.input_dir {
-        Some(path) => path.to_owned(),
-        None => PathBuf::from("."),
+/// Get paths from arguments or from default (current directory).
+///
+/// Repeated paths are searched only once.
+pub fn get_paths(arguments: &Arguments) -> MyResult<Vec<PathBuf>> {
+    // Only the files of the other sources are searched (`--s3`, `--remote`).
+    if arguments.input_dir.is_empty() && source::has_remote_sources(arguments) {
+        return Ok(Vec::new());
+    }
+
+    let paths: Vec<PathBuf> = if arguments.input_dir.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        arguments.input_dir.clone()
     };
 
-    if arguments.extended_path {
-        Ok(fs::canonicalize(path)?) // full path
+    let mut unique_paths: Vec<PathBuf> = Vec::new();
+
+    for path in paths {
+        // With `--compare`, full paths are compared with the reference directory.
+        let path = if arguments.compare.is_some() {
+            fs::canonicalize(path)? // full path
+        } else {
+            arguments.path_style.get_root(path)?
+        };
+
+        // On Windows, files deeper than MAX_PATH (260 characters) can be read.
+        let path = get_long_path(&path)?;
+
+        if !unique_paths.contains(&path) {
+            unique_paths.push(path);
+        }
+    }
+
+    Ok(unique_paths)
+}
+
+/**
+Get the path with the `\\?\` prefix on Windows.
+
+Paths longer than MAX_PATH (260 characters) can then be read.
+
+<https://learn.microsoft.com/en-us/windows/win32/fileio/maximum-file-path-limitation>
+*/
+#[cfg(windows)]
+pub fn get_long_path(path: &Path) -> MyResult<PathBuf> {
+    // Also normalize the separators and remove `.` and `..` components.
+    let path: PathBuf = std::path::absolute(path)?;
+    let path_str: String = path.to_string_lossy().into_owned();
+
+    let long_path = if path_str.starts_with(r"\\?\") {
+        path
+    } else if let Some(unc) = path_str.strip_prefix(r"\\") {
+        // Network path: \\server\share -> \\?\UNC\server\share
+        PathBuf::from(format!(r"\\?\UNC\{unc}"))
     } else {
-        Ok(path) // relative path
+        PathBuf::from(format!(r"\\?\{path_str}"))
+    };
+
+    Ok(long_path)
+}
+
+/// Get the path unchanged: long paths are supported on other platforms.
+#[cfg(not(windows))]
+pub fn get_long_path(path: &Path) -> MyResult<PathBuf> {
+    Ok(path.to_path_buf())
+}
+
+/// Check if the path is a virtual file: a member of an archive
+/// (`--scan_archives`) or a file of another source (`--s3`, `--remote`).
+pub fn is_virtual_file(path: &Path) -> bool {
+    source::find_source(path).is_some() || is_archive_member(path)
+}
+
+/**
+Keep only one path of each file found through overlapping input directories
+(ex.: `-i . -i Pictures` finds `./Pictures/a.txt` and `Pictures/a.txt`).
+
+The paths of the same (device, inode) are compared by their canonical path:
+hard links, with different canonical paths, are kept.
+*/
+fn dedup_same_files(all_files: &mut Vec<FileInfo>) {
+    let mut num_paths: HashMap<(u64, u64), usize> = HashMap::new();
+    for inode in all_files.iter().filter_map(|file_info| file_info.inode) {
+        *num_paths.entry(inode).or_default() += 1;
     }
+
+    let mut canonical_paths: HashSet<PathBuf> = HashSet::new();
+    all_files.retain(|file_info| {
+        // Without inode (not unix), all local paths are compared.
+        let is_shared: bool = file_info
+            .inode
+            .map_or(true, |inode| num_paths.get(&inode) > Some(&1));
+
+        match get_canonical_path(&file_info.path).filter(|_| is_shared) {
+            Some(canonical_path) => canonical_paths.insert(canonical_path),
+            None => true,
+        }
+    });
+}
+
+/// Get the canonical path of the file: the link itself is not resolved (`--symlink_files`).
+fn get_canonical_path(path: &Path) -> Option<PathBuf> {
+    let parent: &Path = match path.parent()? {
+        parent if parent.as_os_str().is_empty() => Path::new("."),
+        parent => parent,
+    };
+    Some(fs::canonicalize(parent).ok()?.join(path.file_name()?))
+}
+
+/// Get all files from all input directories into one vector.
+///
+/// If the input directories overlap (ex.: `-i ~ -i ~/Pictures`),
+/// each file is reported only once.
+///
+/// With `--compare`, the reference directory is indexed first:
+/// only target files with the same size as a reference file are kept.
+pub fn get_all_files(arguments: &Arguments) -> MyResult<Vec<FileInfo>> {
+    let paths: Vec<PathBuf> = get_paths(arguments)?;
+    let filter = FileFilter::new(arguments)?;
+
+    // Hash files of the same size while walking (`--pipeline`).
+    let pipeline: Option<HashPipeline> = HashPipeline::start(arguments);
+    let pipe: Option<&HashPipeline> = pipeline.as_ref();
+
+    let mut all_files: Vec<FileInfo> = Vec::new();
+
+    // With `--compare`, the reference directory is indexed first.
+    let reference_sizes: Option<HashSet<usize>> = match (&arguments.files_from, &arguments.compare)
+    {
+        (None, Some(reference)) => {
+            let reference_files: Vec<FileInfo> =
+                DirSource::new(reference).list_files(arguments, &filter, pipe)?;
+            let sizes: HashSet<usize> = reference_files
+                .iter()
+                .map(|file_info| file_info.key.size)
+                .collect();
+            all_files.extend(reference_files);
+            Some(sizes)
+        }
+        _ => None,
+    };
+
+    // Only target files with the same size as a reference file are kept.
+    let is_candidate = |file_info: &FileInfo| {
+        reference_sizes
+            .as_ref()
+            .map_or(true, |sizes| sizes.contains(&file_info.key.size))
+    };
+
+    // Walk the input directories or read the list of files (`--files_from`).
+    for source in get_local_sources(arguments, &paths) {
+        let files: Vec<FileInfo> = source.list_files(arguments, &filter, pipe)?;
+        all_files.extend(
+            files
+                .into_iter()
+                .filter(|file_info| is_candidate(file_info)),
+        );
+    }
+
+    // Members of zip and tar archives are searched as virtual files.
+    if arguments.scan_archives {
+        let members: Vec<FileInfo> = all_files
+            .par_iter() // rayon parallel iterator
+            .flat_map(|file_info| archive::get_archive_files(file_info, arguments, &filter))
+            .collect();
+        all_files.extend(members);
+    }
+
+    // Objects of a bucket and files of a remote server are searched as virtual files.
+    for source in get_remote_sources(arguments)? {
+        let files: Vec<FileInfo> =
+            source
+                .list_files(arguments, &filter, pipe)
+                .inspect_err(|error| {
+                    eprintln!("fn get_all_files()");
+                    eprintln!("Couldn't list the files of {:?}", source.name());
+                    eprintln!("Error: {error}");
+                })?;
+        all_files.extend(
+            files
+                .into_iter()
+                .filter(|file_info| is_candidate(file_info)),
+        );
+    }
+
+    if paths.len() > 1
+        || arguments.ignore_hardlinks
+        || arguments.compare.is_some()
+        || arguments.files_from.is_some()
+    {
+        all_files.par_sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        all_files.dedup_by(|a, b| a.path == b.path);
+        dedup_same_files(&mut all_files);
+    }
+
+    // Hard links share the same data and do not waste space:
+    // keep only the first path (in alphabetical order) of each (device, inode).
+    if arguments.ignore_hardlinks {
+        let mut inodes: HashSet<(u64, u64)> = HashSet::new();
+        all_files.retain(|file_info| file_info.inode.map_or(true, |inode| inodes.insert(inode)));
+    }
+
+    // Files of the imported indexes (ex.: files of another machine).
+    if !arguments.import_index.is_empty() {
+        let indexed_files: Vec<FileInfo> = index::get_indexed_files(arguments)?;
+        all_files.extend(indexed_files);
+    }
+
+    if let Some(pipeline) = pipeline {
+        pipeline.finish();
+    }
+
+    clear_progress();
+
+    Ok(all_files)
+}
+
+/**
+Group the identical files and sort the groups.
+
+To skip useless files, the following procedures are performed:
+
+1. Group files by size;
+2. Group files by the hash of the first bytes (prefilter);
+3. Group files by the hash of the entire file;
+4. Compare files byte by byte (optional, with `--paranoid`);
+5. Split groups by file name (optional, with `--same_name_only`).
+6. Split groups by metadata (optional, with `--compare_metadata`).
+
+With `--by_content` or `--content_only`, procedures 1 and 2 are skipped.
+
+With `--size_only`, procedures 2 and 3 are skipped: the groups are unverified candidates.
+
+With `--heuristic name+size`, procedure 5 is performed before procedure 2.
+
+With `--dirs`, directories with identical trees are grouped instead of files.
+
+With `--perceptual`, visually identical images are grouped instead of identical files.
+
+With `--audio`, the same songs in different encodings are grouped instead of identical files.
+
+With `--unique`, the files that have no identical file are reported instead.
+
+With `--find_copies_of`, only the copies of the given file are searched.
+
+With `--find_digest`, the files with the given digest are reported, even a single file.
+*/
+pub fn get_identical_groups(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    #[cfg(feature = "audio")]
+    if arguments.audio {
+        let identical_songs: Vec<GroupInfo> = audio::get_audio_groups(all_files, arguments, time);
+        return sort_and_select(identical_songs, all_files, arguments);
+    }
+
+    #[cfg(feature = "perceptual")]
+    if arguments.perceptual {
+        let identical_images: Vec<GroupInfo> =
+            perceptual::get_perceptual_groups(all_files, arguments, time);
+        return sort_and_select(identical_images, all_files, arguments);
+    }
+
+    if arguments.unique {
+        let unique_files: Vec<GroupInfo> = get_unique_files(all_files, arguments, time);
+        return sort_and_select(unique_files, all_files, arguments);
+    }
+
+    if arguments.dirs {
+        let identical_dirs: Vec<GroupInfo> = dirs::get_directory_groups(arguments, time);
+        return sort_and_select(identical_dirs, all_files, arguments);
+    }
+
+    if let Some(digest) = &arguments.find_digest {
+        let matches: Vec<GroupInfo> = query::get_digest_matches(all_files, digest, arguments, time);
+        return sort_and_select(matches, all_files, arguments);
+    }
+
+    let mut identical_hash: Vec<GroupInfo> = if let Some(file) = &arguments.find_copies_of {
+        query::get_copies_of(all_files, file, arguments, time)
+    } else if arguments.by_content || arguments.content_only {
+        get_content_groups(all_files, arguments, time)
+    } else {
+        get_hash_groups(all_files, arguments, time)
+    };
+
+    // Procedure 4 (optional). Compare files byte by byte.
+    if arguments.paranoid {
+        let stage = Stage::start("byte comparison");
+        let (num_files, sum_size) = (
+            get_num_files(&identical_hash),
+            get_sum_size(&identical_hash),
+        );
+        identical_hash = run_io(arguments, || {
+            identical_hash.verify_identical_files(arguments)
+        });
+        stage.finish(num_files, sum_size);
+
+        log_procedure(
+            arguments,
+            4,
+            "Number of files with identical contents",
+            identical_hash.len(),
+            time,
+        );
+    }
+
+    // Procedure 5 (optional). Split groups by file name.
+    if arguments.same_name_only {
+        identical_hash = identical_hash.filter_by_name(arguments);
+
+        log_procedure(
+            arguments,
+            5,
+            "Number of files with identical names",
+            identical_hash.len(),
+            time,
+        );
+    }
+
+    // Procedure 6 (optional). Split groups by metadata.
+    if !arguments.compare_metadata.is_empty() && !arguments.metadata_diff {
+        identical_hash = identical_hash.filter_by_metadata(arguments);
+
+        log_procedure(
+            arguments,
+            6,
+            "Number of files with identical metadata",
+            identical_hash.len(),
+            time,
+        );
+    }
+
+    // Sort the list of identical files.
+    sort_and_select(identical_hash, all_files, arguments)
+}
+
+/**
+Get the files that have no identical file (`--unique`).
+
+The identical files are searched as usual (groups of two or more files,
+without the group filters): the other files are unique.
+
+Each unique file is reported as a group of one file.
+*/
+fn get_unique_files(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    let mut search = arguments.clone();
+    search.unique = false;
+    search.min_frequency = 2;
+    search.max_frequency = u64::MAX;
+    search.min_wasted = 0;
+    search.top = None;
+    search.skip = 0;
+    search.limit = None;
+
+    let identical_hash: Vec<GroupInfo> = get_identical_groups(all_files, &search, time);
+
+    let identical_paths: HashSet<&Path> = identical_hash
+        .iter()
+        .flat_map(|group_info| group_info.paths.iter().map(PathBuf::as_path))
+        .collect();
+
+    let unique_files: Vec<GroupInfo> = all_files
+        .par_iter() // rayon parallel iterator
+        .filter(|file_info| !identical_paths.contains(file_info.path.as_path()))
+        .map(|file_info| GroupInfo {
+            key: file_info.key.clone(),
+            algorithm: None,
+            paths: vec![file_info.path.clone()],
+            num_file: 1,
+            sum_size: file_info.key.size,
+            wasted_size: 0,
+        })
+        .collect();
+
+    log_procedure(
+        arguments,
+        6,
+        "Number of unique files",
+        unique_files.len(),
+        time,
+    );
+
+    unique_files
+}
+
+/**
+Sort the groups of identical files and keep the chosen ones.
+
+1. Keep the N groups wasting the most space (`--top`);
+2. Skip the first groups (`--skip`);
+3. Keep at most N groups (`--limit`).
+
+The sizes allocated on disk of the files of the chosen groups are kept
+to report the wasted disk space.
+*/
+fn sort_and_select(
+    mut identical_hash: Vec<GroupInfo>,
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+) -> Vec<GroupInfo> {
+    // With `--deterministic`, the order of the paths does not depend on the directory walk.
+    if arguments.deterministic {
+        identical_hash
+            .par_iter_mut()
+            .for_each(|group_info| group_info.paths.sort_unstable());
+    }
+
+    identical_hash.sort_identical_files(arguments);
+
+    if let Some(top) = arguments.top {
+        identical_hash.truncate(top);
+    }
+
+    let identical_hash: Vec<GroupInfo> = identical_hash
+        .into_iter()
+        .skip(arguments.skip)
+        .take(arguments.limit.unwrap_or(usize::MAX))
+        .collect();
+
+    structures::file_info::insert_allocated_sizes(all_files, &identical_hash);
+
+    identical_hash
+}
+
+/// Group files by size, by the hash of the first bytes and by the hash of the entire file.
+fn get_hash_groups(all_files: &[FileInfo], arguments: &Arguments, time: Instant) -> Vec<GroupInfo> {
+    // To skip useless files, 3 procedures will be performed:
+
+    // Procedure 1. Group files by <size> such that the key: (size, None);
+    // Ignore filegroups containing only one file.
+    // With `--max_group_memory`, the files may be grouped on disk.
+    let stage = Stage::start("size grouping");
+    let mut identical_size: Vec<GroupInfo> = match arguments.max_group_memory {
+        Some(max_group_memory) => spill::get_grouped_files(all_files, arguments, max_group_memory),
+        None => all_files.get_grouped_files(arguments, 1),
+    };
+
+    // With `--heuristic name+size`, only the files with the same name are hashed.
+    if arguments.heuristic == Some(Heuristic::NameSize) {
+        identical_size = identical_size.filter_by_name(arguments);
+    }
+
+    // With `--file_type`, only the files of the chosen types are kept:
+    // the first bytes of each file with the same size as another file are read.
+    if !arguments.file_type.is_empty() {
+        identical_size = run_io(arguments, || identical_size.filter_by_file_type(arguments));
+    }
+
+    // With `--skip_busy`, the files being written are not hashed.
+    if arguments.skip_busy {
+        identical_size = identical_size.filter_busy_files(arguments);
+    }
+    stage.finish(all_files.len(), get_total_size(all_files));
+
+    log_procedure(
+        arguments,
+        1,
+        "Number of files of identical size",
+        identical_size.len(),
+        time,
+    );
+
+    // With `--size_only`, the contents are not read: the groups are unverified candidates.
+    if arguments.size_only {
+        return identical_size;
+    }
+
+    // Procedure 2. Group files by <hash(first_bytes)> such that the key: (size, Some(hash(first_bytes)));
+    // Ignore filegroups containing only one file.
+    // This prefilter is skipped if prefilter_bytes = 0 or if digest indexes are imported.
+    let identical_bytes: Vec<GroupInfo> = if arguments.prefilter_is_enabled() {
+        let stage = Stage::start("partial hash");
+        let identical_bytes = run_io(arguments, || {
+            identical_size.get_identical_files(arguments, 2)
+        });
+        stage.finish(
+            get_num_files(&identical_size),
+            get_sum_size(&identical_size),
+        );
+        identical_bytes
+    } else {
+        identical_size
+    };
+
+    log_procedure(
+        arguments,
+        2,
+        "Number of files with identical first bytes",
+        identical_bytes.len(),
+        time,
+    );
+
+    // Procedure 3. Group files by <hash(entire_file)> such that the key: (size, Some(hash(entire_file))).
+    // Ignore filegroups containing only one file.
+    let stage = Stage::start("full hash");
+    start_hashing(identical_bytes.iter().map(|g| g.sum_size as u64).sum());
+    if IoProfile::resolve(arguments) == IoProfile::Hdd {
+        hash_in_physical_order(&identical_bytes, arguments);
+    }
+    let identical_hash: Vec<GroupInfo> = run_io(arguments, || {
+        identical_bytes.get_identical_files(arguments, 3)
+    });
+    clear_progress();
+    stage.finish(
+        get_num_files(&identical_bytes),
+        get_sum_size(&identical_bytes),
+    );
+
+    // For testing purposes only:
+    // https://rustlang.github.io/asyncbook/01_getting_started/04_async_await_primer.html
+    // let mut identical_hash: Vec<GroupInfo> = block_on(get_groups(&identical_bytes, arguments, 16));
+
+    log_procedure(
+        arguments,
+        3,
+        "Number of files with identical hashes",
+        identical_hash.len(),
+        time,
+    );
+
+    identical_hash
+}
+
+/// Get the number of files of the groups.
+fn get_num_files(groups: &[GroupInfo]) -> usize {
+    groups.iter().map(|group_info| group_info.num_file).sum()
+}
+
+/// Get the total size of the files of the groups (in bytes).
+fn get_sum_size(groups: &[GroupInfo]) -> u64 {
+    groups
+        .iter()
+        .map(|group_info| group_info.sum_size as u64)
+        .sum()
+}
+
+/// Get the total size of the files (in bytes).
+pub fn get_total_size(all_files: &[FileInfo]) -> u64 {
+    all_files
+        .iter()
+        .map(|file_info| file_info.key.size as u64)
+        .sum()
+}
+
+/**
+Hash the files of the groups in physical order (`--io_profile hdd`).
+
+Files are sorted by (device, inode): on most filesystems, files with
+close inodes are close on disk, so the disk head seeks less.
+
+The digests are saved for procedure 3. Files that cannot be read
+are reported by procedure 3.
+*/
+fn hash_in_physical_order(groups: &[GroupInfo], arguments: &Arguments) {
+    let mut paths: Vec<(Option<(u64, u64)>, &PathBuf)> = groups
+        .par_iter() // rayon parallel iterator
+        .flat_map(|group_info| group_info.paths.par_iter())
+        .map(|path| {
+            let inode = fs::metadata(path)
+                .ok()
+                .and_then(|metadata| structures::file_info::get_inode(&metadata));
+            (inode, path)
+        })
+        .collect();
+    paths.par_sort_unstable();
+
+    run_io(arguments, || {
+        paths.par_iter().for_each(|(_inode, path)| {
+            // With `--timeout`, the files not yet hashed are skipped.
+            if limits::hash_is_stopped() {
+                return;
+            }
+
+            if let Ok(Some(digest)) = path.get_hash(arguments, 3) {
+                index::insert_known_digest(path, digest, arguments.algorithm);
+            }
+        })
+    });
+}
+
+/**
+Group files by the hash of the entire file only (`--by_content`).
+
+The size reported by the filesystem is ignored: it may differ from the
+content length (ex.: files of `/proc` or `/sys`).
+The size of each group is the number of bytes hashed.
+
+With `--content_only`, only the payload of known formats is hashed (without tags).
+*/
+fn get_content_groups(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    start_hashing(
+        all_files
+            .iter()
+            .map(|file_info| file_info.key.size as u64)
+            .sum(),
+    );
+    let hashed_files: Vec<FileInfo> = run_io(arguments, || {
+        all_files
+            .par_iter() // rayon parallel iterator
+            .filter_map(|file_info| {
+                // With `--timeout`, the files not yet hashed are skipped.
+                if limits::hash_is_stopped() {
+                    return None;
+                }
+
+                // With `--skip_busy`, the files being written are not hashed.
+                if arguments.skip_busy && busy::is_busy(&file_info.path) {
+                    return None;
+                }
+
+                let result = if arguments.content_only {
+                    content::calculate_content_hash(
+                        &arguments.algorithm,
+                        &file_info.path,
+                        arguments.buffer_size,
+                    )
+                } else {
+                    arguments
+                        .algorithm
+                        .calculate_hash_and_size(&file_info.path, arguments.buffer_size)
+                };
+
+                match result {
+                    Ok((hash, size)) => Some(FileInfo {
+                        key: Key::new(size, Some(hash)),
+                        ..file_info.clone()
+                    }),
+                    Err(error) => {
+                        eprintln!("fn get_content_groups()");
+                        eprintln!("path: {:?}", file_info.path);
+                        eprintln!("Error: {error}");
+                        skipped::add_error(&file_info.path, &error);
+                        None
+                    }
+                }
+            })
+            .collect()
+    });
+    clear_progress();
+
+    let identical_hash: Vec<GroupInfo> = hashed_files.get_grouped_files(arguments, 3);
+
+    log_procedure(
+        arguments,
+        3,
+        "Number of files with identical hashes",
+        identical_hash.len(),
+        time,
+    );
+
+    identical_hash
+}
+
+/**
+Create a temporary file in the same directory as the file, to be renamed over it
+with `persist`.
+
+Example: "/tmp/file.txt" -> "/tmp/.file.txt.Xa3k9Q.fif-tmp"
+
+The name is random and `create` must create a new file at the given path
+(ex.: `create_new`, a hard link or a symbolic link): an existing file or
+link is never opened nor followed. Another name is tried if the path exists.
+
+The temporary file is removed when dropped, unless it is persisted.
+*/
+pub fn create_temp_file<R>(
+    path: &Path,
+    create: impl FnMut(&Path) -> io::Result<R>,
+) -> io::Result<NamedTempFile<R>> {
+    let dir: &Path = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut prefix = std::ffi::OsString::from(".");
+    if let Some(name) = path.file_name() {
+        prefix.push(name);
+    }
+    prefix.push(".");
+
+    tempfile::Builder::new()
+        .prefix(&prefix)
+        .suffix(".fif-tmp")
+        .make_in(dir, create)
+}
+
+/// Create a new file, with the default permissions: never an existing file or link.
+pub fn create_new_file(path: &Path) -> io::Result<fs::File> {
+    fs::File::options().write(true).create_new(true).open(path)
 }
 
 /// Print buffer to stdout
@@ -151,6 +1006,25 @@ pub fn split_and_insert(integer: usize, insert: char) -> String {
     string_splitted
 }
 
+/// Format the size (in bytes) in human-readable binary units (ex.: 1.50 MiB).
+pub fn human_readable_size(size: usize) -> String {
+    const UNITS: [&str; 7] = ["bytes", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let mut value: f64 = size as f64;
+    let mut unit: usize = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{size} bytes")
+    } else {
+        format!("{value:.2} {}", UNITS[unit])
+    }
+}
+
 /// Serialize usize with fn split_and_insert().
 pub fn add_thousands_separator<S>(size: &usize, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -163,6 +1037,17 @@ where
 mod test_lib {
     use super::*;
 
+    #[test]
+    fn format_human_readable_size() {
+        // cargo test -- --show-output format_human_readable_size
+
+        assert_eq!(human_readable_size(0), "0 bytes");
+        assert_eq!(human_readable_size(1023), "1023 bytes");
+        assert_eq!(human_readable_size(1024), "1.00 KiB");
+        assert_eq!(human_readable_size(1536 * 1024), "1.50 MiB");
+        assert_eq!(human_readable_size(5 * 1024 * 1024 * 1024), "5.00 GiB");
+    }
+
     #[test]
     fn split_integer_into_groups() {
         // cargo test -- --show-output split_integer_into_groups
@@ -193,4 +1078,83 @@ mod test_lib {
 
         assert_eq!(valid, result);
     }
+
+    #[test]
+    fn empty_files_are_skipped_by_default() -> MyResult<()> {
+        // cargo test -- --show-output empty_files_are_skipped_by_default
+        use clap::Parser;
+
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        for name in ["empty_1", "empty_2", "empty_3"] {
+            fs::write(dir.join(name), b"")?;
+        }
+        for name in ["abc_1", "abc_2"] {
+            fs::write(dir.join(name), b"abc")?;
+        }
+
+        let input_dir = dir.to_string_lossy().to_string();
+
+        let arguments = Arguments::parse_from(["find-identical-files", "-i", &input_dir]);
+        let all_files = get_all_files(&arguments)?;
+        let groups = all_files.get_grouped_files(&arguments, 1);
+        assert_eq!(all_files.len(), 2);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].key.size, 3);
+
+        let arguments =
+            Arguments::parse_from(["find-identical-files", "-i", &input_dir, "--include_empty"]);
+        let all_files = get_all_files(&arguments)?;
+        let groups = all_files.get_grouped_files(&arguments, 1);
+        assert_eq!(all_files.len(), 5);
+        assert_eq!(groups.len(), 2);
+        assert!(groups
+            .iter()
+            .any(|group| group.key.size == 0 && group.num_file == 3));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn overlapping_input_dirs_find_each_file_once() -> MyResult<()> {
+        // cargo test -- --show-output overlapping_input_dirs_find_each_file_once
+        use clap::Parser;
+
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        let sub = dir.join("sub");
+        fs::create_dir(&sub)?;
+        fs::write(sub.join("a.txt"), b"abc")?;
+        fs::write(sub.join("b.txt"), b"abc")?;
+        fs::hard_link(sub.join("a.txt"), sub.join("c.txt"))?;
+
+        // Like `-i . -i sub`: `<dir>/sub/a.txt` and `<dir>/sub/../sub/a.txt` are the same file.
+        let input_dir = dir.to_string_lossy().to_string();
+        let sub_dir = sub.join("..").join("sub").to_string_lossy().to_string();
+
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "-i",
+            &input_dir,
+            "-i",
+            &sub_dir,
+            "--ignore_hardlinks",
+            "false",
+        ]);
+        let all_files = get_all_files(&arguments)?;
+        let names: Vec<_> = all_files
+            .iter()
+            .filter_map(|f| f.path.file_name())
+            .collect();
+        println!("names: {names:?}");
+
+        // The hard link c.txt is another path of a.txt: it is kept.
+        assert_eq!(all_files.len(), 3);
+        for name in ["a.txt", "b.txt", "c.txt"] {
+            assert_eq!(names.iter().filter(|n| **n == name).count(), 1);
+        }
+
+        Ok(())
+    }
 }
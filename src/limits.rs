@@ -0,0 +1,133 @@
+use crate::{output::remove_temp_output, Arguments, MyResult};
+use std::{
+    process,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        OnceLock,
+    },
+    time::Instant,
+};
+
+/// Time at which the search stops (`--timeout`).
+static DEADLINE: OnceLock<Instant> = OnceLock::new();
+
+/// Maximum number of files found (`--max_files`).
+static MAX_FILES: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Number of files found so far.
+static NUM_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// `--timeout` is reached or the search is interrupted (Ctrl+C).
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+/// The search is interrupted (Ctrl+C).
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Exit immediately on Ctrl+C, after the search (`--serve`).
+static EXIT_ON_INTERRUPT: AtomicBool = AtomicBool::new(false);
+
+/// Process exit code when the search is interrupted (128 + SIGINT).
+pub const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+/// Reason why the search was stopped (the first one is kept).
+static INCOMPLETE: OnceLock<String> = OnceLock::new();
+
+/**
+Start counting the files and the time (`--max_files` and `--timeout`).
+
+Also handle Ctrl+C: the first interruption stops the search and the
+identical files found so far are printed; the second one exits.
+*/
+pub fn init_limits(arguments: &Arguments) -> MyResult<()> {
+    if let Some(max_files) = arguments.max_files {
+        MAX_FILES.store(max_files, Ordering::Relaxed);
+    }
+
+    if let Some(timeout) = arguments.timeout {
+        let _ = DEADLINE.set(Instant::now() + timeout);
+    }
+
+    ctrlc::set_handler(|| {
+        if EXIT_ON_INTERRUPT.load(Ordering::Relaxed) || INTERRUPTED.swap(true, Ordering::Relaxed) {
+            remove_temp_output();
+            process::exit(INTERRUPTED_EXIT_CODE);
+        }
+
+        CANCELLED.store(true, Ordering::Relaxed);
+        stop("interrupted (Ctrl+C)".to_string());
+        eprintln!(
+            "\nInterrupted: printing the identical files found so far (Ctrl+C again to quit)"
+        );
+    })?;
+
+    Ok(())
+}
+
+/// Check if the search was interrupted (Ctrl+C).
+pub fn is_interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+/// Exit immediately on the next Ctrl+C (ex.: while serving queries).
+pub fn exit_on_interrupt() {
+    EXIT_ON_INTERRUPT.store(true, Ordering::Relaxed);
+}
+
+/// Mark the scan as incomplete: the walkers finish early.
+fn stop(reason: String) {
+    let _ = INCOMPLETE.set(reason);
+}
+
+/// Check if `--timeout` is reached or the search is interrupted:
+/// the walkers and the hashing threads must stop.
+pub fn hash_is_stopped() -> bool {
+    if CANCELLED.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    if DEADLINE
+        .get()
+        .is_some_and(|deadline| Instant::now() >= *deadline)
+    {
+        CANCELLED.store(true, Ordering::Relaxed);
+        stop("timeout reached".to_string());
+        return true;
+    }
+
+    false
+}
+
+/**
+Check if the walkers must stop (`--max_files`, `--timeout` or Ctrl+C).
+
+The walkers check it cooperatively and return the files found so far,
+which are still compared (unless the timeout is reached or the search is interrupted).
+*/
+pub fn walk_is_stopped() -> bool {
+    INCOMPLETE.get().is_some() || hash_is_stopped()
+}
+
+/// Reset the number of files found, before a new search.
+pub(crate) fn reset_num_files() {
+    NUM_FILES.store(0, Ordering::Relaxed);
+}
+
+/// Count a file found by the walker.
+///
+/// Returns false if `--max_files` is reached: the file must be skipped.
+pub fn add_file() -> bool {
+    let max_files: usize = MAX_FILES.load(Ordering::Relaxed);
+    let num_files: usize = NUM_FILES.fetch_add(1, Ordering::Relaxed);
+
+    if num_files >= max_files {
+        stop(format!("maximum number of files ({max_files}) reached"));
+        return false;
+    }
+
+    true
+}
+
+/// Get the reason why the search was stopped, if the scan is incomplete.
+pub fn get_incomplete_reason() -> Option<String> {
+    INCOMPLETE.get().cloned()
+}
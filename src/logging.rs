@@ -0,0 +1,74 @@
+use crate::{Arguments, MyResult};
+use std::{fs::OpenOptions, io, sync::Mutex, time::Instant};
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/**
+Start the structured log (`--log_level`, `--log_file` and `--log_json`).
+
+The procedures of the search are spans: their duration is logged when they close.
+
+Without these options, nothing is logged (`--verbose` still prints the procedures).
+The log file is opened in append mode, so scheduled scans can be audited.
+*/
+pub fn init_logging(arguments: &Arguments) -> MyResult<()> {
+    if arguments.log_level.is_none() && arguments.log_file.is_none() {
+        return Ok(());
+    }
+
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::from(
+            arguments.log_level.unwrap_or_default(),
+        ))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_thread_names(true);
+
+    match &arguments.log_file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .inspect_err(|error| {
+                    eprintln!("fn init_logging()");
+                    eprintln!("Couldn't open {path:?}");
+                    eprintln!("Error: {error}");
+                })?;
+            let builder = builder.with_ansi(false).with_writer(Mutex::new(file));
+
+            if arguments.log_json {
+                builder.json().try_init()?
+            } else {
+                builder.try_init()?
+            }
+        }
+        None => {
+            let builder = builder.with_writer(io::stderr);
+
+            if arguments.log_json {
+                builder.json().try_init()?
+            } else {
+                builder.try_init()?
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the result of a procedure of the search: on stderr with `--verbose` and in the log.
+pub fn log_procedure(
+    arguments: &Arguments,
+    procedure: u8,
+    message: &str,
+    count: usize,
+    time: Instant,
+) {
+    if arguments.verbose {
+        eprintln!(
+            "{procedure}. {message:<43}: {count:>10}, time_elapsed: {:?}",
+            time.elapsed()
+        );
+    }
+
+    tracing::info!(procedure, count, elapsed = ?time.elapsed(), "{message}");
+}
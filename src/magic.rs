@@ -0,0 +1,147 @@
+use crate::{FileKind, OpenFile};
+use std::{io::Read, path::Path};
+
+/// Number of bytes read to detect the type: the tar signature ends at byte 262.
+const HEADER_SIZE: u64 = 262;
+
+/// Signatures (magic numbers): (offset, bytes, type).
+///
+/// More specific signatures come first (ex.: ZIP-based documents before ZIP).
+const SIGNATURES: &[(usize, &[u8], FileKind)] = &[
+    // Images
+    (0, b"\xFF\xD8\xFF", FileKind::Image),
+    (0, b"\x89PNG\r\n\x1A\n", FileKind::Image),
+    (0, b"GIF87a", FileKind::Image),
+    (0, b"GIF89a", FileKind::Image),
+    (0, b"II*\x00", FileKind::Image),
+    (0, b"MM\x00*", FileKind::Image),
+    (0, b"\x00\x00\x01\x00", FileKind::Image),
+    (0, b"BM", FileKind::Image),
+    (4, b"ftypheic", FileKind::Image),
+    (4, b"ftypheix", FileKind::Image),
+    (4, b"ftypmif1", FileKind::Image),
+    (4, b"ftypavif", FileKind::Image),
+    // Audio
+    (4, b"ftypM4A ", FileKind::Audio),
+    (0, b"ID3", FileKind::Audio),
+    (0, b"\xFF\xFB", FileKind::Audio),
+    (0, b"\xFF\xF3", FileKind::Audio),
+    (0, b"\xFF\xF2", FileKind::Audio),
+    (0, b"\xFF\xF1", FileKind::Audio),
+    (0, b"\xFF\xF9", FileKind::Audio),
+    (0, b"fLaC", FileKind::Audio),
+    (0, b"OggS", FileKind::Audio),
+    (0, b"MThd", FileKind::Audio),
+    // Video
+    (4, b"ftyp", FileKind::Video),
+    (0, b"\x1A\x45\xDF\xA3", FileKind::Video),
+    (0, b"FLV", FileKind::Video),
+    (0, b"\x00\x00\x01\xBA", FileKind::Video),
+    (0, b"\x00\x00\x01\xB3", FileKind::Video),
+    (0, b"\x30\x26\xB2\x75", FileKind::Video),
+    // Documents
+    (0, b"%PDF", FileKind::Document),
+    (0, b"%!PS", FileKind::Document),
+    (0, b"{\\rtf", FileKind::Document),
+    (0, b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1", FileKind::Document),
+    (30, b"mimetype", FileKind::Document),
+    (30, b"[Content_Types].xml", FileKind::Document),
+    (30, b"word/", FileKind::Document),
+    (30, b"xl/", FileKind::Document),
+    (30, b"ppt/", FileKind::Document),
+    // Archives
+    (0, b"PK\x03\x04", FileKind::Archive),
+    (0, b"PK\x05\x06", FileKind::Archive),
+    (0, b"\x1F\x8B", FileKind::Archive),
+    (0, b"BZh", FileKind::Archive),
+    (0, b"\xFD7zXZ\x00", FileKind::Archive),
+    (0, b"\x28\xB5\x2F\xFD", FileKind::Archive),
+    (0, b"7z\xBC\xAF\x27\x1C", FileKind::Archive),
+    (0, b"Rar!\x1A\x07", FileKind::Archive),
+    (257, b"ustar", FileKind::Archive),
+];
+
+/**
+Get the type of the file from its first bytes, regardless of its extension.
+
+Only the first 262 bytes are read.
+*/
+pub fn get_file_kind(path: &Path) -> Option<FileKind> {
+    let mut header: Vec<u8> = Vec::with_capacity(HEADER_SIZE as usize);
+    OpenFile::open(path)
+        .and_then(|file| file.take(HEADER_SIZE).read_to_end(&mut header))
+        .ok()?;
+
+    detect_file_kind(&header)
+}
+
+/// Get the type of the content from its first bytes.
+pub fn detect_file_kind(header: &[u8]) -> Option<FileKind> {
+    // RIFF containers: the type follows the size of the chunk.
+    if header.starts_with(b"RIFF") {
+        return match header.get(8..12)? {
+            b"WEBP" => Some(FileKind::Image),
+            b"WAVE" => Some(FileKind::Audio),
+            b"AVI " => Some(FileKind::Video),
+            _ => None,
+        };
+    }
+
+    // IFF containers (AIFF).
+    if header.starts_with(b"FORM") {
+        return matches!(header.get(8..12)?, b"AIFF" | b"AIFC").then_some(FileKind::Audio);
+    }
+
+    // ZIP-based documents: the name of the first entry is checked only in ZIP files.
+    SIGNATURES
+        .iter()
+        .filter(|(offset, _, _)| *offset != 30 || header.starts_with(b"PK\x03\x04"))
+        .find(|(offset, signature, _)| {
+            header
+                .get(*offset..)
+                .is_some_and(|bytes| bytes.starts_with(signature))
+        })
+        .map(|(_, _, file_kind)| *file_kind)
+}
+
+#[cfg(test)]
+mod test_magic {
+    use super::*;
+
+    /// cargo test -- --show-output test_detect_file_kind
+    #[test]
+    fn test_detect_file_kind() {
+        let zip_entry = |name: &str| [b"PK\x03\x04".as_slice(), &[0; 26], name.as_bytes()].concat();
+        let mut tar = vec![0; 262];
+        tar[257..262].copy_from_slice(b"ustar");
+
+        assert_eq!(detect_file_kind(b"\xFF\xD8\xFF\xE0"), Some(FileKind::Image));
+        assert_eq!(
+            detect_file_kind(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some(FileKind::Image)
+        );
+        assert_eq!(
+            detect_file_kind(b"RIFF\0\0\0\0WAVEfmt "),
+            Some(FileKind::Audio)
+        );
+        assert_eq!(
+            detect_file_kind(b"\0\0\0\x20ftypisom"),
+            Some(FileKind::Video)
+        );
+        assert_eq!(
+            detect_file_kind(b"\0\0\0\x20ftypM4A "),
+            Some(FileKind::Audio)
+        );
+        assert_eq!(detect_file_kind(b"%PDF-1.7"), Some(FileKind::Document));
+        assert_eq!(
+            detect_file_kind(&zip_entry("word/document.xml")),
+            Some(FileKind::Document)
+        );
+        assert_eq!(
+            detect_file_kind(&zip_entry("photos/a.jpg")),
+            Some(FileKind::Archive)
+        );
+        assert_eq!(detect_file_kind(&tar), Some(FileKind::Archive));
+        assert_eq!(detect_file_kind(b"plain text"), None);
+    }
+}
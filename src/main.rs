@@ -4,7 +4,7 @@ use std::time::Instant;
 //use futures::{executor::block_on, future::join_all};
 //use rayon::prelude::*;
 
-/**
+/*
     cargo fmt
     cargo clippy --features walkdir
     clear && cargo test -- --show-output
@@ -25,90 +25,221 @@ fn main() -> MyResult<()> {
     set_env_variables();
     let time = Instant::now();
     let arguments = Arguments::build()?;
+    init_threads(&arguments)?;
+    init_search(&arguments);
+    init_progress(&arguments);
+    init_limits(&arguments)?;
+    init_path_style(&arguments);
+    init_lang(&arguments);
+    init_logging(&arguments)?;
+    init_resume(&arguments)?;
+    init_journal(&arguments)?;
 
-    // Get useful (identical) and useless (non-identical) files.
-    let all_files: Vec<FileInfo> = get_all_files(&arguments)?;
+    // Undo the actions of a journal.
+    if let Some(journal) = &arguments.undo {
+        let undo_info = undo_journal(journal, &arguments)?;
+        let mut output = Output::new(&arguments)?;
+        undo_info.write(&arguments, &mut output)?;
+        output.finish()?;
 
-    if arguments.verbose {
-        eprintln!(
-            "0. {:<43}: {:>10}, time_elapsed: {:?}",
-            "Total number of files",
-            all_files.len(),
-            time.elapsed()
-        );
+        if !undo_info.is_ok() {
+            std::process::exit(UNDO_FAILED_EXIT_CODE);
+        }
+        return Ok(());
     }
 
-    // To skip useless files, 3 procedures will be performed:
+    // Report the groups that appeared, disappeared or changed between two reports.
+    if let [old, new] = arguments.diff.as_slice() {
+        let diff_info = DiffInfo::new(read_json_report(old)?, read_json_report(new)?);
+        let mut output = Output::new(&arguments)?;
+        diff_info.write(&arguments, &mut output)?;
+        output.finish()?;
+        return Ok(());
+    }
 
-    // Procedure 1. Group files by <size> such that the key: (size, None);
-    // Ignore filegroups containing only one file.
-    let identical_size: Vec<GroupInfo> = all_files.get_grouped_files(&arguments, 1);
+    // Measure the throughput of each hash algorithm.
+    if arguments.bench {
+        let bench_info = run_bench(&arguments)?;
+        let mut output = Output::new(&arguments)?;
+        bench_info.write(&arguments, &mut output)?;
+        output.finish()?;
+        return Ok(());
+    }
 
-    if arguments.verbose {
-        eprintln!(
-            "1. {:<43}: {:>10}, time_elapsed: {:?}",
-            "Number of files of identical size",
-            identical_size.len(),
-            time.elapsed()
-        );
+    // Check the search with a synthetic tree of known identical files.
+    if arguments.selftest {
+        let selftest_info = run_selftest(&arguments)?;
+        let mut output = Output::new(&arguments)?;
+        selftest_info.write(&arguments, &mut output)?;
+        output.finish()?;
+
+        if !selftest_info.is_ok() {
+            std::process::exit(SELFTEST_FAILED_EXIT_CODE);
+        }
+        return Ok(());
     }
 
-    // Procedure 2. Group files by <hash(first_bytes)> such that the key: (size, Some(hash(first_bytes)));
-    // Ignore filegroups containing only one file.
-    let identical_bytes: Vec<GroupInfo> = identical_size.get_identical_files(&arguments, 2);
+    // Report the files of SOURCE missing in BACKUP.
+    if let [source, backup] = arguments.verify_subset.as_slice() {
+        let subset_info = verify_subset(source, backup, &arguments)?;
+        let mut output = Output::new(&arguments)?;
+        subset_info.write(&arguments, &mut output)?;
+        output.finish()?;
+
+        if arguments.time {
+            println!("{} {:?}", tr("Total Execution Time:"), time.elapsed());
+        }
 
-    if arguments.verbose {
-        eprintln!(
-            "2. {:<43}: {:>10}, time_elapsed: {:?}",
-            "Number of files with identical first bytes",
-            identical_bytes.len(),
-            time.elapsed()
-        );
+        if !subset_info.missing_files.is_empty() && !is_interrupted() {
+            std::process::exit(MISSING_FILES_EXIT_CODE);
+        }
+        if is_interrupted() {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        return Ok(());
     }
 
-    // Procedure 3. Group files by <hash(entire_file)> such that the key: (size, Some(hash(entire_file))).
-    // Ignore filegroups containing only one file.
-    let mut identical_hash: Vec<GroupInfo> = identical_bytes.get_identical_files(&arguments, 3);
+    // Report the files changed or missing since the manifest was written.
+    if let Some(path) = &arguments.verify_manifest {
+        let manifest_info = verify_manifest(path, &arguments)?;
+        let mut output = Output::new(&arguments)?;
+        manifest_info.write(&arguments, &mut output)?;
+        output.finish()?;
 
-    // For testing purposes only:
-    // https://rustlang.github.io/asyncbook/01_getting_started/04_async_await_primer.html
-    // let mut identical_hash: Vec<GroupInfo> = block_on(get_groups(&identical_bytes, &arguments, 16));
+        if arguments.time {
+            println!("{} {:?}", tr("Total Execution Time:"), time.elapsed());
+        }
+
+        if is_interrupted() {
+            std::process::exit(INTERRUPTED_EXIT_CODE);
+        }
+        if !manifest_info.is_ok() {
+            std::process::exit(MANIFEST_MISMATCH_EXIT_CODE);
+        }
+        return Ok(());
+    }
+
+    // Get useful (identical) and useless (non-identical) files.
+    let stage = Stage::start("walk");
+    let all_files: Vec<FileInfo> = get_all_files(&arguments)?;
+    stage.finish(all_files.len(), get_total_size(&all_files));
+
+    log_procedure(
+        &arguments,
+        0,
+        "Total number of files",
+        all_files.len(),
+        time,
+    );
+
+    // Save the digests of all files.
+    if let Some(path) = &arguments.export_index {
+        DigestIndex::new(&all_files, &arguments).write(path)?;
+    }
+
+    // The `cache` subcommand only saves the digest index.
+    if arguments.is_cache_only() {
+        finish_resume(get_incomplete_reason().is_none())?;
+        if arguments.time {
+            write_stages(&mut std::io::stdout())?;
+            println!("{} {:?}", tr("Total Execution Time:"), time.elapsed());
+        }
+        return Ok(());
+    }
 
-    if arguments.verbose {
-        eprintln!(
-            "3. {:<43}: {:>10}, time_elapsed: {:?}",
-            "Number of files with identical hashes",
-            identical_hash.len(),
-            time.elapsed()
-        );
+    // Save the digests of all files in the format of `sha256sum` and `b3sum`.
+    if let Some(path) = &arguments.write_manifest {
+        write_manifest(&all_files, &arguments, path)?;
     }
 
-    // Sort the list of identical files.
-    identical_hash.sort_identical_files(&arguments);
+    // Group identical files (procedures 1 to 4) and sort them.
+    let identical_hash: Vec<GroupInfo> = get_identical_groups(&all_files, &arguments, time);
+
+    // With `--resume`, the state file is kept only if the scan is incomplete.
+    finish_resume(get_incomplete_reason().is_none())?;
 
     // Print the identical files and the summary information.
-    TotalInfo::get_summary(&identical_hash, &arguments, all_files.len())
-        .print_summary(&arguments)?;
+    let stage = Stage::start("output");
+    let mut output = Output::new(&arguments)?;
+    let total_info =
+        TotalInfo::get_summary(&identical_hash, &arguments, all_files.len(), &mut output)?;
+    total_info.write_summary(&arguments, &mut output)?;
+
+    // Also show the summary information on the terminal.
+    if output.is_file() {
+        total_info.print_summary(&arguments)?;
+    }
+    output.finish()?;
+    stage.finish(total_info.total_num_identical, total_info.total_size as u64);
 
     // Export identical file information simultaneously to CSV and/or XLSX format.
     std::thread::scope(|s| {
         s.spawn(|| -> MyResult<()> {
-            if let Some(dir_path) = arguments.csv_dir {
-                identical_hash.export_to_csv(dir_path)?;
+            if let Some(dir_path) = &arguments.csv_dir {
+                identical_hash.export_to_csv(dir_path.to_owned(), arguments.compress)?;
             }
             Ok(())
         });
 
         s.spawn(|| -> MyResult<()> {
-            if let Some(dir_path) = arguments.xlsx_dir {
-                identical_hash.export_to_xlsx(dir_path)?;
+            if let Some(dir_path) = &arguments.xlsx_dir {
+                identical_hash.export_to_xlsx(dir_path.to_owned())?;
             }
             Ok(())
         });
     });
 
+    // Save the files, digests and groups to a SQLite database.
+    #[cfg(feature = "sqlite")]
+    if let Some(path) = &arguments.export_sqlite {
+        export_to_sqlite(&all_files, &identical_hash, &arguments, path)?;
+    }
+
+    // Remove or replace identical files, keeping one file per group.
+    // After Ctrl+C, the search is incomplete: no file is changed.
+    let has_action =
+        arguments.script.is_some() || arguments.interactive || arguments.get_action().is_some();
+
+    if has_action && is_interrupted() {
+        eprintln!("{}", tr("The search was interrupted: no action applied."));
+    } else if let Some(format) = arguments.script {
+        identical_hash.write_script(&arguments, format)?;
+    } else if arguments.interactive {
+        resolve_interactively(&identical_hash, &arguments)?.print_summary(&arguments)?;
+    } else if arguments.get_action().is_some() {
+        identical_hash
+            .apply_actions(&arguments)?
+            .print_summary(&arguments)?;
+    }
+
     if arguments.time {
-        println!("Total Execution Time: {:?}", time.elapsed());
+        write_stages(&mut std::io::stdout())?;
+        println!("{} {:?}", tr("Total Execution Time:"), time.elapsed());
+    }
+
+    // Report new identical files until the process is interrupted.
+    if arguments.watch && !is_interrupted() {
+        watch(&all_files, &identical_hash, &arguments)?;
+    }
+
+    // Answer queries of other processes until the process is interrupted.
+    if let Some(address) = arguments.serve.as_ref().filter(|_| !is_interrupted()) {
+        exit_on_interrupt();
+        serve(&all_files, &arguments, address)?;
+    }
+
+    // Machine-readable summary and exit code.
+    if let Some(path) = &arguments.summary {
+        let summary = SummaryInfo::new(&identical_hash, all_files.len(), time.elapsed());
+        summary.write(path.as_deref())?;
+
+        if summary.has_duplicates() && !is_interrupted() {
+            std::process::exit(DUPLICATES_FOUND_EXIT_CODE);
+        }
+    }
+
+    if is_interrupted() {
+        std::process::exit(INTERRUPTED_EXIT_CODE);
     }
 
     Ok(())
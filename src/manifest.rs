@@ -0,0 +1,314 @@
+use crate::{
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    clear_progress,
+    file_list::bytes_to_path,
+    limits::hash_is_stopped,
+    run_io,
+    skipped::add_error,
+    start_hashing, Algorithm, DigestIndex, FileInfo, IndexEntry, MyResult, PathBufExtension,
+};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Process exit code of `--verify_manifest` when files were changed or are missing.
+pub const MANIFEST_MISMATCH_EXIT_CODE: i32 = 5;
+
+/**
+Write a checksum manifest of all files (`--write_manifest`).
+
+One line per file: `<digest>  <path>`, sorted by path, in the format of
+`sha256sum` and `b3sum` (check with `sha256sum -c` or `b3sum -c`
+according to `--algorithm`).
+
+As in these tools, a path with a backslash or a newline is escaped
+and its line begins with a backslash.
+*/
+pub fn write_manifest(all_files: &[FileInfo], arguments: &Arguments, path: &Path) -> MyResult<()> {
+    start_hashing(
+        all_files
+            .iter()
+            .map(|file_info| file_info.key.size as u64)
+            .sum(),
+    );
+    let mut entries: Vec<IndexEntry> =
+        run_io(arguments, || DigestIndex::new(all_files, arguments).entries);
+    clear_progress();
+
+    entries.par_sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+    let file: File = File::create(path).inspect_err(|error| {
+        eprintln!("fn write_manifest()");
+        eprintln!("Couldn't create {path:?}");
+        eprintln!("Error: {error}");
+    })?;
+    let mut writer = BufWriter::new(file);
+
+    for entry in &entries {
+        let (escaped, line_path) = escape_path(entry.path.as_os_str().as_encoded_bytes());
+        if escaped {
+            writer.write_all(b"\\")?;
+        }
+        // Without the prefix of the algorithm, like `sha256sum`.
+        let (_algorithm, digest) = Algorithm::split_digest(&entry.digest);
+        writer.write_all(digest.as_bytes())?;
+        writer.write_all(b"  ")?;
+        writer.write_all(&line_path)?;
+        writer.write_all(b"\n")?;
+    }
+
+    writer.flush()?;
+    eprintln!("Write manifest: {path:?}");
+
+    Ok(())
+}
+
+/// Escape the backslashes and newlines of the path (like `sha256sum`).
+///
+/// Returns true if the path was escaped.
+fn escape_path(bytes: &[u8]) -> (bool, Vec<u8>) {
+    if !bytes.iter().any(|byte| matches!(byte, b'\\' | b'\n')) {
+        return (false, bytes.to_vec());
+    }
+
+    let mut escaped: Vec<u8> = Vec::with_capacity(bytes.len() + 2);
+    for &byte in bytes {
+        match byte {
+            b'\\' => escaped.extend_from_slice(b"\\\\"),
+            b'\n' => escaped.extend_from_slice(b"\\n"),
+            _ => escaped.push(byte),
+        }
+    }
+    (true, escaped)
+}
+
+/// Restore the backslashes and newlines of an escaped path.
+fn unescape_path(bytes: &[u8]) -> Vec<u8> {
+    let mut unescaped: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter();
+    while let Some(&byte) = iter.next() {
+        match (byte, iter.as_slice().first()) {
+            (b'\\', Some(b'\\')) => {
+                unescaped.push(b'\\');
+                iter.next();
+            }
+            (b'\\', Some(b'n')) => {
+                unescaped.push(b'\n');
+                iter.next();
+            }
+            _ => unescaped.push(byte),
+        }
+    }
+    unescaped
+}
+
+/**
+Parse the lines of a manifest: `<digest>  <path>` or `<digest> *<path>`.
+
+Empty lines are ignored.
+*/
+fn parse_manifest(bytes: &[u8]) -> MyResult<Vec<(String, PathBuf)>> {
+    let mut entries: Vec<(String, PathBuf)> = Vec::new();
+
+    for (number, line) in bytes.split(|&byte| byte == b'\n').enumerate() {
+        let line: &[u8] = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+
+        let (escaped, line) = match line.strip_prefix(b"\\") {
+            Some(line) => (true, line),
+            None => (false, line),
+        };
+
+        let Some(position) = line.iter().position(|&byte| byte == b' ') else {
+            eprintln!("fn parse_manifest()");
+            eprintln!("line {}: {:?}", number + 1, String::from_utf8_lossy(line));
+            return Err(format!("invalid manifest line {}", number + 1).into());
+        };
+
+        let digest: String = String::from_utf8_lossy(&line[..position]).into_owned();
+        // Self-describing digests (`blake3:<hex>`) are also accepted.
+        let digest: String = Algorithm::split_digest(&digest).1.to_string();
+        // Text mode (two spaces) or binary mode (space and asterisk).
+        let path: &[u8] = line.get(position + 2..).unwrap_or_default();
+        let path: Vec<u8> = if escaped {
+            unescape_path(path)
+        } else {
+            path.to_vec()
+        };
+
+        entries.push((digest, bytes_to_path(path)));
+    }
+
+    Ok(entries)
+}
+
+/// Result of `--verify_manifest`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ManifestInfo {
+    /// Manifest file
+    #[serde(rename = "Manifest")]
+    pub manifest: PathBuf,
+    /// Number of files of the manifest
+    #[serde(rename = "Number of files")]
+    pub num_file: usize,
+    /// Files whose digest differs from the manifest
+    #[serde(rename = "Changed files")]
+    pub changed: Vec<PathBuf>,
+    /// Files of the manifest that no longer exist
+    #[serde(rename = "Missing files")]
+    pub missing: Vec<PathBuf>,
+}
+
+impl ManifestInfo {
+    /// Check if all files of the manifest are unchanged.
+    pub fn is_ok(&self) -> bool {
+        self.changed.is_empty() && self.missing.is_empty()
+    }
+
+    /// Write the changed and missing files in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize ManifestInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize ManifestInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                for path in &self.changed {
+                    writeln!(write, "CHANGED: {path:?}")?;
+                }
+                for path in &self.missing {
+                    writeln!(write, "MISSING: {path:?}")?;
+                }
+                if !self.is_ok() {
+                    writeln!(write)?;
+                }
+                writeln!(write, "Manifest: {:?}", self.manifest)?;
+                writeln!(write, "Number of files: {}", self.num_file)?;
+                writeln!(write, "Number of changed files: {}", self.changed.len())?;
+                writeln!(write, "Number of missing files: {}", self.missing.len())?;
+                writeln!(write)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// State of a file of the manifest.
+enum FileState {
+    Unchanged,
+    Changed,
+    Missing,
+}
+
+/**
+Hash again the files of a manifest written with `--write_manifest`,
+`sha256sum` or `b3sum` (`--verify_manifest`).
+
+The digests are calculated with `--algorithm`: it must be the algorithm
+used to write the manifest.
+*/
+pub fn verify_manifest(path: &Path, arguments: &Arguments) -> MyResult<ManifestInfo> {
+    let bytes: Vec<u8> = fs::read(path).inspect_err(|error| {
+        eprintln!("fn verify_manifest()");
+        eprintln!("Couldn't read {path:?}");
+        eprintln!("Error: {error}");
+    })?;
+    let entries: Vec<(String, PathBuf)> = parse_manifest(&bytes)?;
+
+    start_hashing(
+        entries
+            .iter()
+            .filter_map(|(_digest, path)| fs::metadata(path).ok())
+            .map(|metadata| metadata.len())
+            .sum(),
+    );
+
+    let states: Vec<(FileState, &PathBuf)> = run_io(arguments, || {
+        entries
+            .par_iter() // rayon parallel iterator
+            .filter_map(|(digest, path)| {
+                // With `--timeout` or Ctrl+C, only the files hashed so far are checked.
+                if hash_is_stopped() {
+                    return None;
+                }
+
+                if !path.is_file() {
+                    return Some((FileState::Missing, path));
+                }
+
+                match path.get_hash(arguments, 3) {
+                    Ok(hash) if hash.as_ref() == Some(digest) => Some((FileState::Unchanged, path)),
+                    Ok(_) => Some((FileState::Changed, path)),
+                    Err(error) => {
+                        eprintln!("fn verify_manifest()");
+                        eprintln!("path: {path:?}");
+                        eprintln!("Error: {error}");
+                        add_error(path, &error);
+                        Some((FileState::Changed, path))
+                    }
+                }
+            })
+            .collect()
+    });
+    clear_progress();
+
+    let mut manifest_info = ManifestInfo {
+        manifest: path.to_path_buf(),
+        num_file: entries.len(),
+        ..Default::default()
+    };
+
+    for (state, path) in states {
+        match state {
+            FileState::Unchanged => {}
+            FileState::Changed => manifest_info.changed.push(path.clone()),
+            FileState::Missing => manifest_info.missing.push(path.clone()),
+        }
+    }
+
+    Ok(manifest_info)
+}
+
+#[cfg(test)]
+mod test_manifest {
+    use super::*;
+
+    /// cargo test -- --show-output test_parse_manifest
+    #[test]
+    fn test_parse_manifest() -> MyResult<()> {
+        let (escaped, line_path) = escape_path(b"dir\\new\nline.txt");
+        assert!(escaped);
+        assert_eq!(line_path, b"dir\\\\new\\nline.txt");
+
+        let mut manifest: Vec<u8> = b"abc123  a.txt\ndef456 *dir/b c.txt\r\n\n\\".to_vec();
+        manifest.extend_from_slice(b"0789  ");
+        manifest.extend_from_slice(&line_path);
+        manifest.push(b'\n');
+
+        let entries = parse_manifest(&manifest)?;
+        assert_eq!(
+            entries,
+            [
+                ("abc123".to_string(), PathBuf::from("a.txt")),
+                ("def456".to_string(), PathBuf::from("dir/b c.txt")),
+                ("0789".to_string(), PathBuf::from("dir\\new\nline.txt")),
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    display_path, human_readable_size, split_and_insert, Arguments, GroupInfo, MyResult, TotalInfo,
+    SEPARATOR,
+};
+use std::io::Write;
+
+/**
+Escape the characters with special meaning in Markdown.
+
+Paths are written as plain text: `|` would split the table cells,
+and `*`, `_`, `[`, ... would be rendered as formatting.
+*/
+pub fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' | '`' | '*' | '_' | '{' | '}' | '[' | ']' | '<' | '>' | '(' | ')' | '#' | '+'
+            | '-' | '!' | '|' | '~' => {
+                escaped.push('\\');
+                escaped.push(character);
+            }
+            // A newline would end the table row.
+            '\n' | '\r' => escaped.push(' '),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Format the size in bytes, followed by the human-readable size.
+fn format_size(size: usize) -> String {
+    format!(
+        "{} bytes ({})",
+        split_and_insert(size, SEPARATOR),
+        human_readable_size(size)
+    )
+}
+
+/// Write the title and the scan parameters.
+pub fn write_markdown_header(arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+    writeln!(write, "# Identical files\n")?;
+    writeln!(write, "## Options\n")?;
+    writeln!(write, "| Option | Value |")?;
+    writeln!(write, "| --- | --- |")?;
+    for (name, value) in arguments.get_scan_options() {
+        writeln!(write, "| {name} | {} |", escape_markdown(&value))?;
+    }
+    writeln!(write, "\n## Groups\n")?;
+    Ok(())
+}
+
+/// Write one group of identical files: a heading and a table of paths.
+pub fn write_markdown_group(group_info: &GroupInfo, write: &mut dyn Write) -> MyResult<()> {
+    writeln!(
+        write,
+        "### {} identical files of {}\n",
+        group_info.num_file,
+        format_size(group_info.key.size)
+    )?;
+    writeln!(
+        write,
+        "- hash: `{}`",
+        group_info.get_digest().unwrap_or_default()
+    )?;
+    if let Some(algorithm) = group_info.algorithm {
+        writeln!(write, "- algorithm: {algorithm}")?;
+    }
+    writeln!(write, "- group id: `{}`", group_info.get_id())?;
+    writeln!(
+        write,
+        "- wasted size: {}\n",
+        format_size(group_info.wasted_size)
+    )?;
+
+    writeln!(write, "| # | Path |")?;
+    writeln!(write, "| ---: | --- |")?;
+    for (index, path) in group_info.paths.iter().enumerate() {
+        writeln!(
+            write,
+            "| {} | {} |",
+            index + 1,
+            escape_markdown(&display_path(path).to_string_lossy())
+        )?;
+    }
+    writeln!(write)?;
+
+    Ok(())
+}
+
+/// Write the summary information.
+pub fn write_markdown_summary(total_info: &TotalInfo, write: &mut dyn Write) -> MyResult<()> {
+    writeln!(write, "## Summary\n")?;
+    writeln!(write, "| | |")?;
+    writeln!(write, "| --- | ---: |")?;
+    if let Some(reason) = &total_info.incomplete {
+        writeln!(write, "| Incomplete scan | {} |", escape_markdown(reason))?;
+    }
+    if let Some(reason) = &total_info.unverified {
+        writeln!(
+            write,
+            "| Unverified candidates | {} |",
+            escape_markdown(reason)
+        )?;
+    }
+    writeln!(write, "| Hashing algorithm | {} |", total_info.algorithm)?;
+    writeln!(
+        write,
+        "| Total number of files | {} |",
+        total_info.total_num_files
+    )?;
+    writeln!(
+        write,
+        "| Total number of identical files | {} |",
+        total_info.total_num_identical
+    )?;
+    writeln!(
+        write,
+        "| Total number of different hashes | {} |",
+        total_info.total_num_hashes
+    )?;
+    writeln!(
+        write,
+        "| Total size of identical files | {} |",
+        format_size(total_info.total_size)
+    )?;
+    writeln!(
+        write,
+        "| Total reclaimable space | {} |",
+        format_size(total_info.total_wasted)
+    )?;
+    if let Some(total_wasted_allocated) = total_info.total_wasted_allocated {
+        writeln!(
+            write,
+            "| Total reclaimable disk space | {} |",
+            format_size(total_wasted_allocated)
+        )?;
+    }
+    if !total_info.skipped_files.is_empty() {
+        writeln!(
+            write,
+            "| Skipped files | {} |",
+            total_info.skipped_files.len()
+        )?;
+    }
+    writeln!(write)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_markdown {
+    use super::*;
+
+    /// cargo test -- --show-output test_escape_markdown
+    #[test]
+    fn test_escape_markdown() {
+        assert_eq!(
+            escape_markdown("a|b/*draft*_v1 [1].txt"),
+            r"a\|b/\*draft\*\_v1 \[1\].txt"
+        );
+        assert_eq!(escape_markdown("new\nline"), "new line");
+    }
+}
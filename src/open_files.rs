@@ -0,0 +1,200 @@
+use crate::{Arguments, IoProfile};
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    ops::Deref,
+    path::Path,
+    sync::{Condvar, Mutex},
+    time::Duration,
+};
+
+/// Files currently open and maximum number of open files (0: no limit).
+static OPEN_FILES: Mutex<OpenFiles> = Mutex::new(OpenFiles { open: 0, max: 0 });
+
+/// Notified each time a file is closed.
+static CLOSED: Condvar = Condvar::new();
+
+/// Attempts to open a file when the process runs out of file descriptors.
+const MAX_ATTEMPTS: u32 = 8;
+
+/// First wait after `EMFILE`, doubled at each attempt.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(10);
+
+#[derive(Debug)]
+struct OpenFiles {
+    open: usize,
+    max: usize,
+}
+
+/**
+Bound the number of files opened at the same time (`--max_open_files`).
+
+Each reader may hold two files (byte comparison), so the limit is
+raised to at least two files per thread: a lower limit could deadlock.
+The user is warned when the limit is raised.
+*/
+pub fn init_open_files(arguments: &Arguments) {
+    let Some(max_open_files) = arguments.max_open_files else {
+        return;
+    };
+
+    let threads: usize = rayon::current_num_threads() + IoProfile::get_io_threads(arguments);
+    let max: usize = max_open_files.max(2 * threads);
+
+    if max > max_open_files {
+        eprintln!(
+            "--max_open_files {max_open_files} is too low for {threads} threads: raised to {max}."
+        );
+    }
+
+    if let Ok(mut open_files) = OPEN_FILES.lock() {
+        open_files.max = max;
+    }
+}
+
+/// Slot of an open file, released when the file is closed.
+#[derive(Debug)]
+struct Permit;
+
+impl Permit {
+    /// Wait until a file can be opened without exceeding `--max_open_files`.
+    fn acquire() -> Self {
+        let Ok(mut open_files) = OPEN_FILES.lock() else {
+            return Permit;
+        };
+
+        while open_files.max > 0 && open_files.open >= open_files.max {
+            open_files = match CLOSED.wait(open_files) {
+                Ok(open_files) => open_files,
+                Err(_) => return Permit,
+            };
+        }
+
+        open_files.open += 1;
+        Permit
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Ok(mut open_files) = OPEN_FILES.lock() {
+            open_files.open = open_files.open.saturating_sub(1);
+        }
+        CLOSED.notify_one();
+    }
+}
+
+/// Wait until another file is closed, at most `timeout`.
+fn wait_for_close(timeout: Duration) {
+    if let Ok(open_files) = OPEN_FILES.lock() {
+        let _ = CLOSED.wait_timeout(open_files, timeout);
+    }
+}
+
+/**
+File opened for reading, counted in the open files of the search.
+
+The slot is released when the file is closed (dropped).
+*/
+#[derive(Debug)]
+pub struct OpenFile {
+    file: File,
+    _permit: Permit,
+}
+
+impl OpenFile {
+    /**
+    Open the file for reading, within the limit of `--max_open_files`.
+
+    If the process runs out of file descriptors (`EMFILE`), wait for
+    other files to be closed and try again, instead of skipping the file.
+    */
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut backoff: Duration = INITIAL_BACKOFF;
+        let mut attempt: u32 = 1;
+
+        loop {
+            let permit = Permit::acquire();
+
+            match File::open(path) {
+                Ok(file) => {
+                    return Ok(OpenFile {
+                        file,
+                        _permit: permit,
+                    })
+                }
+                Err(error) if is_too_many_open_files(&error) && attempt < MAX_ATTEMPTS => {
+                    drop(permit);
+                    wait_for_close(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Deref for OpenFile {
+    type Target = File;
+
+    fn deref(&self) -> &File {
+        &self.file
+    }
+}
+
+impl Read for OpenFile {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buffer)
+    }
+}
+
+impl Seek for OpenFile {
+    fn seek(&mut self, position: SeekFrom) -> io::Result<u64> {
+        self.file.seek(position)
+    }
+}
+
+/// Check for `EMFILE` (process limit) and `ENFILE` (system limit).
+#[cfg(unix)]
+fn is_too_many_open_files(error: &io::Error) -> bool {
+    const ENFILE: i32 = 23;
+    const EMFILE: i32 = 24;
+    matches!(error.raw_os_error(), Some(ENFILE | EMFILE))
+}
+
+/// Check for `ERROR_TOO_MANY_OPEN_FILES`.
+#[cfg(not(unix))]
+fn is_too_many_open_files(error: &io::Error) -> bool {
+    const ERROR_TOO_MANY_OPEN_FILES: i32 = 4;
+    error.raw_os_error() == Some(ERROR_TOO_MANY_OPEN_FILES)
+}
+
+#[cfg(test)]
+mod test_open_files {
+    use super::*;
+    use crate::MyResult;
+
+    /// cargo test -- --show-output test_open_file
+    #[test]
+    fn test_open_file() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("open.txt");
+        std::fs::write(&path, "content")?;
+
+        let mut file = OpenFile::open(&path)?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        assert_eq!(content, "content");
+        assert_eq!(file.metadata()?.len(), 7);
+        drop(file);
+
+        #[cfg(unix)]
+        assert!(is_too_many_open_files(&io::Error::from_raw_os_error(24)));
+        assert!(!is_too_many_open_files(&io::Error::from(
+            io::ErrorKind::NotFound
+        )));
+
+        Ok(())
+    }
+}
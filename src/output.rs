@@ -0,0 +1,461 @@
+use crate::{
+    args::{Arguments, ResultFormat},
+    create_new_file, create_temp_file, Compression, MyResult,
+};
+use flate2::write::GzEncoder;
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tempfile::TempPath;
+
+/// Name of the link to the last report of `--report_dir`.
+const LATEST: &str = "latest";
+
+/// Compression level of zstd (0: default level).
+const ZSTD_LEVEL: i32 = 0;
+
+/// Temporary file of the result, removed if the process exits before it is finished.
+static TEMP_OUTPUT: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Writer of the result: the data are compressed as they are written.
+pub enum Writer {
+    Stdout(io::Stdout),
+    File(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+    Zstd(zstd::Encoder<'static, BufWriter<File>>),
+}
+
+impl Writer {
+    /// Write to the file, compressed with gzip or zstd.
+    pub fn new(file: File, compression: Option<Compression>) -> MyResult<Self> {
+        let buffer = BufWriter::new(file);
+
+        Ok(match compression {
+            None => Writer::File(buffer),
+            Some(Compression::Gzip) => {
+                Writer::Gzip(GzEncoder::new(buffer, flate2::Compression::default()))
+            }
+            Some(Compression::Zstd) => Writer::Zstd(zstd::Encoder::new(buffer, ZSTD_LEVEL)?),
+        })
+    }
+
+    /// Flush the data and finish the compressed stream.
+    pub fn finish(self) -> MyResult<()> {
+        match self {
+            Writer::Stdout(mut stdout) => stdout.flush()?,
+            Writer::File(mut buffer) => buffer.flush()?,
+            Writer::Gzip(encoder) => encoder.finish()?.flush()?,
+            Writer::Zstd(encoder) => encoder.finish()?.flush()?,
+        }
+
+        Ok(())
+    }
+}
+
+impl Write for Writer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Writer::Stdout(stdout) => stdout.write(buf),
+            Writer::File(buffer) => buffer.write(buf),
+            Writer::Gzip(encoder) => encoder.write(buf),
+            Writer::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Writer::Stdout(stdout) => stdout.flush(),
+            Writer::File(buffer) => buffer.flush(),
+            Writer::Gzip(encoder) => encoder.flush(),
+            Writer::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/**
+Destination of the result: stdout (default) or the file given by `--output`.
+
+The file is written atomically: the result is written to a temporary file
+in the same directory, with a random name, which is renamed when finished.
+
+If the filename ends in `.gz` or `.zst`, the result is compressed with gzip or zstd.
+With `--compress`, the extension of the compression is added to the filename.
+
+With `--report_dir`, the result is written to a timestamped file of the directory
+and the `latest` link of the directory points to it.
+
+If the result is not finished (error, panic or second Ctrl+C),
+the temporary file is removed.
+*/
+pub struct Output {
+    writer: Writer,
+    /// (temporary path, final path)
+    paths: Option<(TempPath, PathBuf)>,
+    /// Directory of the timestamped reports
+    report_dir: Option<PathBuf>,
+}
+
+impl Output {
+    /// Open the result destination.
+    pub fn new(arguments: &Arguments) -> MyResult<Self> {
+        let path: PathBuf = match (&arguments.output, &arguments.report_dir) {
+            (Some(path), _) => get_output_path(path, arguments),
+            (None, Some(dir_path)) => get_output_path(
+                &get_report_path(dir_path, &arguments.result_format)?,
+                arguments,
+            ),
+            (None, None) => {
+                return Ok(Output {
+                    writer: Writer::Stdout(io::stdout()),
+                    paths: None,
+                    report_dir: None,
+                })
+            }
+        };
+
+        let (file, temp_path) = create_temp_file(&path, create_new_file)
+            .inspect_err(|error| {
+                eprintln!("fn Output::new()");
+                eprintln!("Couldn't create a temporary file for {path:?}");
+                eprintln!("Error: {error}");
+            })?
+            .into_parts();
+
+        if let Ok(mut temp_output) = TEMP_OUTPUT.lock() {
+            *temp_output = Some(temp_path.to_path_buf());
+        }
+
+        let writer = Writer::new(file, Compression::from_path(&path))?;
+
+        Ok(Output {
+            writer,
+            paths: Some((temp_path, path)),
+            report_dir: arguments.report_dir.clone(),
+        })
+    }
+
+    /// Check if the result is written to a file.
+    pub fn is_file(&self) -> bool {
+        self.paths.is_some()
+    }
+
+    /// Flush the result and move the temporary file to its final path.
+    pub fn finish(mut self) -> MyResult<()> {
+        mem::replace(&mut self.writer, Writer::Stdout(io::stdout())).finish()?;
+
+        if let Some((temp_path, path)) = self.paths.take() {
+            // On failure, the temporary file is removed with its path.
+            remove_temp_output_path();
+            let path: PathBuf = match &self.report_dir {
+                Some(_) => persist_new(temp_path, &path)?,
+                None => {
+                    temp_path.persist(&path).map_err(|error| error.error)?;
+                    path
+                }
+            };
+            eprintln!("Write Output File: {path:?}");
+
+            if let Some(dir_path) = &self.report_dir {
+                update_latest(dir_path, &path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for Output {
+    /// Remove the temporary file if the result is not finished.
+    fn drop(&mut self) {
+        // The temporary file is removed with its path.
+        if self.paths.take().is_some() {
+            remove_temp_output_path();
+        }
+    }
+}
+
+/// Remove the temporary file of an unfinished result.
+///
+/// Called before the process exits (`--strict`, second Ctrl+C).
+pub fn remove_temp_output() {
+    if let Some(temp_path) = remove_temp_output_path() {
+        let _ = fs::remove_file(temp_path);
+    }
+}
+
+/// Forget the temporary file of the result.
+fn remove_temp_output_path() -> Option<PathBuf> {
+    TEMP_OUTPUT.lock().ok()?.take()
+}
+
+/// Get the path of a result file: with `--compress`, the extension of the compression is added.
+pub fn get_output_path(path: &Path, arguments: &Arguments) -> PathBuf {
+    match arguments.compress {
+        Some(compression) => compression.add_extension(path.to_path_buf()),
+        None => path.to_path_buf(),
+    }
+}
+
+/**
+Get the path of a new report in the directory (`--report_dir`).
+
+Ex.: `reports/fif-2024-01-31_183000.json` (UTC).
+
+The directory is created if it does not exist.
+The reports are never overwritten (see `persist_new`).
+*/
+fn get_report_path(dir_path: &Path, result_format: &ResultFormat) -> MyResult<PathBuf> {
+    fs::create_dir_all(dir_path).inspect_err(|error| {
+        eprintln!("fn get_report_path()");
+        eprintln!("Couldn't create {dir_path:?}");
+        eprintln!("Error: {error}");
+    })?;
+
+    let extension: &str = match result_format {
+        ResultFormat::Json => "json",
+        ResultFormat::Yaml => "yaml",
+        ResultFormat::Personal => "txt",
+        ResultFormat::Html => "html",
+        ResultFormat::Markdown => "md",
+        ResultFormat::Xml => "xml",
+        ResultFormat::Cbor => "cbor",
+        ResultFormat::MsgPack => "msgpack",
+    };
+
+    let secs: u64 = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    Ok(dir_path.join(format!("fif-{}.{extension}", format_timestamp(secs))))
+}
+
+/**
+Move the temporary file to the path of the report, without replacing an existing file.
+
+Runs in the same second get the same timestamp: a numeric suffix is added.
+
+Ex.: `fif-2024-01-31_183000.json` -> `fif-2024-01-31_183000-2.json`
+
+Returns the path of the report.
+*/
+fn persist_new(mut temp_path: TempPath, path: &Path) -> MyResult<PathBuf> {
+    let mut number: usize = 1;
+
+    loop {
+        let report: PathBuf = get_numbered_path(path, number);
+
+        match temp_path.persist_noclobber(&report) {
+            Ok(()) => return Ok(report),
+            Err(error) if error.error.kind() == io::ErrorKind::AlreadyExists => {
+                temp_path = error.path;
+                number += 1;
+            }
+            Err(error) => return Err(error.error.into()),
+        }
+    }
+}
+
+/// Add the number to the file name, before the extensions (the first is not numbered).
+fn get_numbered_path(path: &Path, number: usize) -> PathBuf {
+    if number <= 1 {
+        return path.to_path_buf();
+    }
+
+    let file_name: String = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    let (stem, extensions) = file_name.split_at(file_name.find('.').unwrap_or(file_name.len()));
+    path.with_file_name(format!("{stem}-{number}{extensions}"))
+}
+
+/// Format the time (seconds since the Unix epoch, UTC) as `YYYY-MM-DD_HHMMSS`.
+fn format_timestamp(secs: u64) -> String {
+    let (days, seconds) = (secs / 86400, secs % 86400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}_{:02}{:02}{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Date (year, month, day) of the number of days since 1970-01-01.
+///
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days: u64 = days + 719468;
+    let era: u64 = days / 146097;
+    let day_of_era: u64 = days - era * 146097;
+    let year_of_era: u64 =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year: u64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp: u64 = (5 * day_of_year + 2) / 153;
+    let day: u64 = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month: u64 = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year: u64 = year_of_era + era * 400 + u64::from(month <= 2);
+
+    (year, month, day)
+}
+
+/// Point the `latest` symbolic link of the directory to the report.
+///
+/// The link is replaced atomically: a new link is renamed over the previous one.
+#[cfg(unix)]
+fn update_latest(dir_path: &Path, report: &Path) -> MyResult<()> {
+    let Some(file_name) = report.file_name() else {
+        return Ok(());
+    };
+
+    let latest: PathBuf = dir_path.join(LATEST);
+    let temp_link = create_temp_file(&latest, |temp_path| {
+        std::os::unix::fs::symlink(file_name, temp_path)
+    })?;
+
+    temp_link.persist(&latest).map_err(|error| {
+        eprintln!("fn update_latest()");
+        eprintln!("Couldn't update {latest:?}");
+        eprintln!("Error: {}", error.error);
+        error.error
+    })?;
+
+    Ok(())
+}
+
+/// Copy the report to the `latest` file of the directory
+/// (symbolic links require privileges on Windows).
+#[cfg(not(unix))]
+fn update_latest(dir_path: &Path, report: &Path) -> MyResult<()> {
+    let latest: PathBuf = dir_path.join(LATEST);
+    fs::copy(report, &latest).inspect_err(|error| {
+        eprintln!("fn update_latest()");
+        eprintln!("Couldn't update {latest:?}");
+        eprintln!("Error: {error}");
+    })?;
+
+    Ok(())
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod test_output {
+    use super::*;
+
+    /// cargo test -- --show-output test_format_timestamp
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01_000000");
+        assert_eq!(format_timestamp(951782400 + 3723), "2000-02-29_010203");
+        assert_eq!(format_timestamp(1706725800), "2024-01-31_183000");
+    }
+
+    /// cargo test -- --show-output test_persist_new
+    #[test]
+    fn test_persist_new() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("fif-2024-01-31_183000.json.gz");
+
+        // Two reports in the same second.
+        let mut reports: Vec<PathBuf> = Vec::new();
+        for content in ["first", "second"] {
+            let temp_file = create_temp_file(&path, create_new_file)?;
+            fs::write(temp_file.path(), content)?;
+            reports.push(persist_new(temp_file.into_temp_path(), &path)?);
+        }
+
+        assert_eq!(reports[0], path);
+        assert_eq!(
+            reports[1],
+            temp_dir.path().join("fif-2024-01-31_183000-2.json.gz")
+        );
+        assert_eq!(fs::read_to_string(&reports[0])?, "first");
+        assert_eq!(fs::read_to_string(&reports[1])?, "second");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_compressed_writer
+    #[test]
+    fn test_compressed_writer() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("output.zst");
+
+        let mut writer = Writer::new(File::create(&path)?, Compression::from_path(&path))?;
+        writer.write_all(b"identical files")?;
+        writer.finish()?;
+
+        let decompressed: Vec<u8> = zstd::decode_all(File::open(&path)?)?;
+        assert_eq!(decompressed, b"identical files");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_unfinished_output
+    #[test]
+    fn test_unfinished_output() -> MyResult<()> {
+        use clap::Parser;
+
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("result.txt");
+        let arguments =
+            Arguments::parse_from(["find-identical-files", "--output", &path.to_string_lossy()]);
+
+        let num_temp_files = || -> MyResult<usize> {
+            let entries = fs::read_dir(temp_dir.path())?.collect::<io::Result<Vec<_>>>()?;
+            Ok(entries
+                .iter()
+                .filter(|entry| entry.file_name().to_string_lossy().ends_with(".fif-tmp"))
+                .count())
+        };
+
+        // A link with the former name of the temporary file is never followed.
+        #[cfg(unix)]
+        {
+            let target = temp_dir.path().join("target.txt");
+            fs::write(&target, b"not changed")?;
+            std::os::unix::fs::symlink(&target, temp_dir.path().join(".result.txt.fif-tmp"))?;
+        }
+
+        // Unfinished: the temporary file is removed.
+        let mut output = Output::new(&arguments)?;
+        output.write_all(b"identical files")?;
+        let temp_files = num_temp_files()?;
+        drop(output);
+        assert_eq!(num_temp_files()?, temp_files - 1);
+        assert!(!path.exists());
+
+        // Finished: the temporary file is renamed.
+        let mut output = Output::new(&arguments)?;
+        output.write_all(b"identical files")?;
+        output.finish()?;
+        assert_eq!(num_temp_files()?, temp_files - 1);
+        assert_eq!(fs::read(&path)?, b"identical files");
+
+        #[cfg(unix)]
+        assert_eq!(
+            fs::read(temp_dir.path().join("target.txt"))?,
+            b"not changed"
+        );
+
+        Ok(())
+    }
+}
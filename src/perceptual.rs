@@ -0,0 +1,153 @@
+use crate::{
+    cluster::{get_clusters, get_similar_group},
+    log_procedure, run_io, Arguments, FileInfo, GroupInfo, MyResult,
+};
+use image::imageops::FilterType;
+use rayon::prelude::*;
+use std::{path::Path, time::Instant};
+
+/// Extensions of the image files compared with `--perceptual`.
+const IMAGE_EXTENSIONS: [&str; 4] = ["jpg", "jpeg", "png", "webp"];
+
+/// Check if the file is an image supported by `--perceptual`.
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|image| extension.eq_ignore_ascii_case(image))
+        })
+}
+
+/**
+Get the perceptual hash (dHash) of the image.
+
+The image is reduced to 9×8 pixels in grayscale, and each bit
+records whether a pixel is brighter than its right neighbor.
+
+Recompressed, resized or retagged copies of an image have
+the same hash, or a hash that differs in a few bits.
+
+<https://www.hackerfactor.com/blog/index.php?/archives/529-Kind-of-Like-That.html>
+*/
+pub fn get_dhash(path: &Path) -> MyResult<u64> {
+    let pixels = image::open(path)?
+        .resize_exact(9, 8, FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash: u64 = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = pixels.get_pixel(x, y)[0];
+            let right = pixels.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+
+    Ok(hash)
+}
+
+/// Number of different bits between two perceptual hashes.
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/**
+Group visually identical images (`--perceptual`).
+
+Images whose perceptual hashes differ by at most `--max_distance` bits
+are grouped (transitively), even if their contents are different.
+
+The size of each group is the size of the largest image, which is
+assumed to be kept: the wasted size is the size of the other images.
+*/
+pub fn get_perceptual_groups(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    let hashed_images: Vec<(&FileInfo, u64)> = run_io(arguments, || {
+        all_files
+            .par_iter() // rayon parallel iterator
+            .filter(|file_info| is_image(&file_info.path))
+            .filter_map(|file_info| match get_dhash(&file_info.path) {
+                Ok(hash) => Some((file_info, hash)),
+                Err(error) => {
+                    eprintln!("fn get_perceptual_groups()");
+                    eprintln!("path: {:?}", file_info.path);
+                    eprintln!("Error: {error}");
+                    None
+                }
+            })
+            .collect()
+    });
+
+    log_procedure(arguments, 1, "Number of images", hashed_images.len(), time);
+
+    let clusters: Vec<Vec<usize>> = get_clusters(hashed_images.len(), |i, j| {
+        hamming_distance(hashed_images[i].1, hashed_images[j].1) <= arguments.max_distance
+    });
+
+    let identical_images: Vec<GroupInfo> = clusters
+        .into_iter()
+        .filter_map(|cluster| {
+            let images: Vec<&FileInfo> = cluster
+                .iter()
+                .map(|&index| hashed_images[index].0)
+                .collect();
+            let hash: u64 = hashed_images[cluster[0]].1;
+            get_similar_group(images, format!("{hash:016x}"), arguments)
+        })
+        .collect();
+
+    log_procedure(
+        arguments,
+        2,
+        "Number of visually identical images",
+        identical_images.len(),
+        time,
+    );
+
+    identical_images
+}
+
+#[cfg(test)]
+mod test_perceptual {
+    use super::*;
+    use image::{ImageBuffer, Luma};
+
+    /// cargo test --features perceptual -- --show-output test_perceptual_hash
+    #[test]
+    fn test_perceptual_hash() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        // A horizontal gradient and the same gradient at another resolution and format.
+        let gradient = |width: u32, height: u32| {
+            ImageBuffer::from_fn(width, height, |x, _y| Luma([255 - (x * 255 / width) as u8]))
+        };
+        let original = dir.join("original.png");
+        let resized = dir.join("resized.jpg");
+        let inverted = dir.join("inverted.png");
+        gradient(64, 64).save(&original)?;
+        gradient(128, 96).save(&resized)?;
+        ImageBuffer::from_fn(64, 64, |x, _y| Luma([(x * 4) as u8])).save(&inverted)?;
+
+        let hash = get_dhash(&original)?;
+        assert!(hamming_distance(hash, get_dhash(&resized)?) <= 4);
+        assert!(hamming_distance(hash, get_dhash(&inverted)?) > 32);
+
+        assert!(is_image(Path::new("photo.JPG")));
+        assert!(!is_image(Path::new("notes.txt")));
+
+        let hashes: [u64; 3] = [0b0000, 0b0001, 0b1111_0000];
+        assert_eq!(
+            get_clusters(hashes.len(), |i, j| hamming_distance(hashes[i], hashes[j])
+                <= 1),
+            [vec![0, 1]]
+        );
+
+        Ok(())
+    }
+}
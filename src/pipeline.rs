@@ -0,0 +1,191 @@
+use crate::{
+    enumerations::algo::hash_reader, index::insert_known_digest, limits::hash_is_stopped,
+    skipped::add_error, Arguments, FileInfo, IoProfile, MyResult, OpenFile,
+};
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use hashbrown::{hash_map::Entry, HashMap, HashSet};
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+};
+
+/**
+Hash files while the directories are still being walked (`--pipeline`).
+
+The walker (producer) sends each file found to the pipeline.
+As soon as two files have the same size, they are sent to the
+hashing threads (consumers), which save the digests for procedure 3.
+
+Files with a unique size are never hashed.
+*/
+pub struct HashPipeline {
+    /// Paths to be hashed
+    sender: Sender<PathBuf>,
+    /// First path of each size, sent when a second file of the same size is found
+    sizes: Mutex<HashMap<usize, Option<PathBuf>>>,
+    /// Hard links already sent (with `--ignore_hardlinks`)
+    inodes: Mutex<HashSet<(u64, u64)>>,
+    /// Ignore hard links to the same file
+    ignore_hardlinks: bool,
+    /// Hashing threads
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl HashPipeline {
+    /// Start the hashing threads, if `--pipeline` is chosen.
+    pub fn start(arguments: &Arguments) -> Option<Self> {
+        if !arguments.pipeline {
+            return None;
+        }
+
+        let (sender, receiver) = unbounded::<PathBuf>();
+        let arguments = Arc::new(arguments.clone());
+
+        let num_threads: usize = IoProfile::get_io_threads(&arguments);
+
+        let workers: Vec<JoinHandle<()>> = (0..num_threads)
+            .map(|_| {
+                let receiver: Receiver<PathBuf> = receiver.clone();
+                let arguments = Arc::clone(&arguments);
+                thread::spawn(move || {
+                    for path in receiver {
+                        // With `--timeout`, the remaining files are not hashed.
+                        if hash_is_stopped() {
+                            continue;
+                        }
+
+                        match hash_file(&path, &arguments) {
+                            Ok(digest) => insert_known_digest(&path, digest, arguments.algorithm),
+                            Err(error) => {
+                                eprintln!("fn HashPipeline::start()");
+                                eprintln!("path: {path:?}");
+                                eprintln!("Error: {error}");
+                                add_error(&path, &error);
+                            }
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Some(HashPipeline {
+            sender,
+            sizes: Mutex::new(HashMap::new()),
+            inodes: Mutex::new(HashSet::new()),
+            ignore_hardlinks: arguments.ignore_hardlinks,
+            workers,
+        })
+    }
+
+    /// Receive a file found by the walker.
+    pub fn push(&self, file_info: &FileInfo) {
+        if let Some(inode) = file_info.inode.filter(|_| self.ignore_hardlinks) {
+            if !self
+                .inodes
+                .lock()
+                .is_ok_and(|mut inodes| inodes.insert(inode))
+            {
+                return;
+            }
+        }
+
+        let Ok(mut sizes) = self.sizes.lock() else {
+            return;
+        };
+
+        match sizes.entry(file_info.key.size) {
+            Entry::Vacant(entry) => {
+                entry.insert(Some(file_info.path.clone()));
+            }
+            Entry::Occupied(mut entry) => {
+                if let Some(first) = entry.get_mut().take() {
+                    let _ = self.sender.send(first);
+                }
+                let _ = self.sender.send(file_info.path.clone());
+            }
+        }
+    }
+
+    /// Wait for the hashing threads to finish.
+    pub fn finish(self) {
+        let HashPipeline {
+            sender, workers, ..
+        } = self;
+
+        // Close the channel: the hashing threads stop when it is empty.
+        drop(sender);
+
+        for worker in workers {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Calculate the hash of the entire file.
+///
+/// The bytes are not reported to the progress bar, which counts the files found.
+fn hash_file(path: &Path, arguments: &Arguments) -> MyResult<String> {
+    let file = OpenFile::open(path)?;
+    let size: u64 = file.metadata()?.len();
+    let reader = BufReader::with_capacity(arguments.buffer_size.max(1), file);
+
+    hash_reader(
+        reader,
+        arguments.algorithm.new_digest(size),
+        arguments.buffer_size,
+    )
+}
+
+#[cfg(test)]
+mod test_pipeline {
+    use super::*;
+    use crate::{index::get_known_digest, Key};
+    use clap::Parser;
+    use std::fs;
+
+    /// cargo test -- --show-output test_hash_pipeline
+    #[test]
+    fn test_hash_pipeline() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        let paths: Vec<PathBuf> = ["a.txt", "b.txt", "c.txt"]
+            .iter()
+            .map(|name| dir.join(name))
+            .collect();
+        fs::write(&paths[0], "same")?;
+        fs::write(&paths[1], "same")?;
+        fs::write(&paths[2], "unique size")?;
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--pipeline"]);
+        let pipeline = HashPipeline::start(&arguments).expect("pipeline");
+
+        for path in &paths {
+            let size = fs::metadata(path)?.len();
+            pipeline.push(&FileInfo {
+                key: Key::new(size, None),
+                path: path.clone(),
+                inode: None,
+                modified: None,
+                allocated: None,
+            });
+        }
+        pipeline.finish();
+
+        // Files with the same size were hashed in advance.
+        let expected = arguments
+            .algorithm
+            .calculate_hash(OpenFile::open(&paths[0])?, arguments.buffer_size)?;
+        let algorithm = arguments.algorithm;
+        assert_eq!(
+            get_known_digest(&paths[0], algorithm),
+            Some(expected.clone())
+        );
+        assert_eq!(get_known_digest(&paths[1], algorithm), Some(expected));
+        assert_eq!(get_known_digest(&paths[2], algorithm), None);
+
+        Ok(())
+    }
+}
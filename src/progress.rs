@@ -0,0 +1,85 @@
+use crate::Arguments;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{
+    io::{self, IsTerminal, Read},
+    sync::OnceLock,
+    time::Duration,
+};
+
+/// Progress bar shared by the walking and hashing threads.
+static PROGRESS: OnceLock<ProgressBar> = OnceLock::new();
+
+/**
+Enable the progress bar with `--progress`.
+
+The progress bar is written to stderr and is suppressed
+when stdout or stderr is not a terminal (ex.: redirected to a file).
+*/
+pub fn init_progress(arguments: &Arguments) {
+    if !arguments.progress || !io::stdout().is_terminal() || !io::stderr().is_terminal() {
+        return;
+    }
+
+    let bar = ProgressBar::new_spinner();
+    if let Ok(style) = ProgressStyle::with_template("{spinner} [{elapsed_precise}] {msg}") {
+        bar.set_style(style);
+    }
+    bar.enable_steady_tick(Duration::from_millis(100));
+
+    let _ = PROGRESS.set(bar);
+}
+
+/// Show the number of files discovered while walking the directories.
+pub fn inc_files(count: u64) {
+    if let Some(bar) = PROGRESS.get() {
+        bar.inc(count);
+        bar.set_message(format!("Files discovered: {}", bar.position()));
+    }
+}
+
+/// Switch to the hashing stage: show bytes hashed, throughput and ETA.
+pub fn start_hashing(total_bytes: u64) {
+    if let Some(bar) = PROGRESS.get() {
+        if let Ok(style) = ProgressStyle::with_template(
+            "{spinner} [{elapsed_precise}] [{wide_bar}] {bytes}/{total_bytes} ({binary_bytes_per_sec}, ETA: {eta})",
+        ) {
+            bar.set_style(style.progress_chars("=> "));
+        }
+        bar.reset();
+        bar.set_length(total_bytes);
+        bar.enable_steady_tick(Duration::from_millis(100));
+    }
+}
+
+/// Show the bytes hashed.
+pub fn inc_bytes(count: u64) {
+    if let Some(bar) = PROGRESS.get() {
+        bar.inc(count);
+    }
+}
+
+/// Clear the progress bar at the end of each stage.
+pub fn clear_progress() {
+    if let Some(bar) = PROGRESS.get() {
+        bar.finish_and_clear();
+    }
+}
+
+/// Reader that reports the number of bytes read to the progress bar.
+pub struct ProgressReader<R> {
+    inner: R,
+}
+
+impl<R: Read> ProgressReader<R> {
+    pub fn new(inner: R) -> Self {
+        ProgressReader { inner }
+    }
+}
+
+impl<R: Read> Read for ProgressReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let count = self.inner.read(buf)?;
+        inc_bytes(count as u64);
+        Ok(count)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    clear_progress, limits::hash_is_stopped, log_procedure, run_io, skipped::add_error,
+    start_hashing, Arguments, FileInfo, GroupExtension, GroupInfo, Key, PathBufExtension,
+};
+use hashbrown::HashMap;
+use rayon::prelude::*;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+
+/**
+Get the files with the same content as FILE (`--find_copies_of`).
+
+Only the files with the same size as FILE are hashed: the groups of
+the other files are never built.
+
+The group starts with FILE, followed by its copies. FILE itself is not
+reported twice if it is inside the input directories.
+*/
+pub fn get_copies_of(
+    all_files: &[FileInfo],
+    file: &Path,
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    let Ok(metadata) = fs::metadata(file) else {
+        return Vec::new();
+    };
+    let key = Key::new(metadata.len(), None);
+    let target: Option<PathBuf> = fs::canonicalize(file).ok();
+
+    let mut paths: Vec<PathBuf> = vec![file.to_path_buf()];
+    paths.extend(
+        all_files
+            .iter()
+            .filter(|file_info| file_info.key.size == key.size)
+            .filter(|file_info| fs::canonicalize(&file_info.path).ok() != target)
+            .map(|file_info| file_info.path.clone()),
+    );
+
+    log_procedure(
+        arguments,
+        1,
+        "Number of files of identical size",
+        paths.len() - 1,
+        time,
+    );
+
+    let num_file = paths.len();
+    let candidates = [GroupInfo {
+        key: key.clone(),
+        algorithm: None,
+        paths,
+        num_file,
+        sum_size: key.size * num_file,
+        wasted_size: key.size * (num_file - 1),
+    }];
+
+    // Procedures 2 and 3: hash the first bytes and then the entire files.
+    start_hashing((key.size * num_file) as u64);
+    let identical_hash: Vec<GroupInfo> = run_io(arguments, || {
+        let identical_bytes: Vec<GroupInfo> = if arguments.prefilter_is_enabled() {
+            candidates.get_identical_files(arguments, 2)
+        } else {
+            candidates.to_vec()
+        };
+        identical_bytes.get_identical_files(arguments, 3)
+    });
+    clear_progress();
+
+    // Only the group of FILE is reported.
+    let copies: Vec<GroupInfo> = identical_hash
+        .into_iter()
+        .filter(|group_info| group_info.paths.iter().any(|path| path == file))
+        .collect();
+
+    log_procedure(
+        arguments,
+        3,
+        "Number of copies found",
+        copies
+            .iter()
+            .map(|group_info| group_info.num_file - 1)
+            .sum::<usize>(),
+        time,
+    );
+
+    copies
+}
+
+/**
+Get the files whose hash is the given digest (`--find_digest`).
+
+All files are hashed with `--algorithm`, except those whose digest is
+already known (`--import_index`). The matches are grouped by size.
+
+A single match is also reported.
+*/
+pub fn get_digest_matches(
+    all_files: &[FileInfo],
+    digest: &str,
+    arguments: &Arguments,
+    time: Instant,
+) -> Vec<GroupInfo> {
+    let total_size: usize = all_files.iter().map(|file_info| file_info.key.size).sum();
+
+    // Procedure 3: hash the entire files.
+    start_hashing(total_size as u64);
+    let matches: Vec<&FileInfo> = run_io(arguments, || {
+        all_files
+            .par_iter() // rayon parallel iterator
+            .filter(|file_info| {
+                if hash_is_stopped() {
+                    return false;
+                }
+
+                match file_info.path.get_hash(arguments, 3) {
+                    Ok(hash) => hash.as_deref() == Some(digest),
+                    Err(why) => {
+                        eprintln!("fn get_digest_matches()");
+                        eprintln!("path: {:?}", file_info.path);
+                        eprintln!("Error: {why}");
+                        add_error(&file_info.path, &why);
+                        false
+                    }
+                }
+            })
+            .collect()
+    });
+    clear_progress();
+
+    let mut paths_by_size: HashMap<usize, Vec<PathBuf>> = HashMap::new();
+    for file_info in matches {
+        paths_by_size
+            .entry(file_info.key.size)
+            .or_default()
+            .push(file_info.path.clone());
+    }
+
+    let groups: Vec<GroupInfo> = paths_by_size
+        .into_iter()
+        .map(|(size, paths)| {
+            let num_file = paths.len();
+            GroupInfo {
+                key: Key::new(size as u64, Some(digest.to_string())),
+                algorithm: Some(arguments.algorithm),
+                paths,
+                num_file,
+                sum_size: size * num_file,
+                wasted_size: size * (num_file - 1),
+            }
+        })
+        .collect();
+
+    log_procedure(
+        arguments,
+        3,
+        "Number of files with the digest",
+        groups
+            .iter()
+            .map(|group_info| group_info.num_file)
+            .sum::<usize>(),
+        time,
+    );
+
+    groups
+}
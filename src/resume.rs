@@ -0,0 +1,124 @@
+use crate::{
+    index::{get_known_entries, insert_known_digests},
+    Algorithm, Arguments, DigestIndex, MyResult, INDEX_VERSION,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    thread,
+    time::Duration,
+};
+
+/// Time between two checkpoints of the state file.
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// State file and algorithm of `--resume`.
+static STATE: OnceLock<(PathBuf, Algorithm)> = OnceLock::new();
+
+/**
+Load the digests of an interrupted scan and start the checkpoints (`--resume`).
+
+The state file is a digest index (see `--export_index`): the digests of the
+files already hashed are written every 30 seconds. A digest is reused only
+if the size and the modification time of the file have not changed, so
+restarting the scan any number of times gives the same result.
+*/
+pub fn init_resume(arguments: &Arguments) -> MyResult<()> {
+    let Some(path) = &arguments.resume else {
+        return Ok(());
+    };
+
+    if path.exists() {
+        let index = DigestIndex::read(path)?;
+
+        if index.algorithm != arguments.algorithm {
+            eprintln!("fn init_resume()");
+            eprintln!(
+                "The state file {path:?} was created with the {} algorithm.",
+                index.algorithm
+            );
+            eprintln!("Choose the same algorithm with the `--algorithm` option.");
+            return Err(format!("hash algorithm mismatch in {path:?}").into());
+        }
+
+        eprintln!(
+            "Resume the scan: {} digests loaded from {path:?}",
+            index.entries.len()
+        );
+        insert_known_digests(&index.entries);
+    }
+
+    let _ = STATE.set((path.clone(), arguments.algorithm));
+
+    thread::Builder::new()
+        .name("checkpoint".to_string())
+        .spawn(|| loop {
+            thread::sleep(CHECKPOINT_INTERVAL);
+            let _ = write_checkpoint();
+        })?;
+
+    Ok(())
+}
+
+/// Check if the digests must be saved in the state file (`--resume`).
+pub fn is_resuming() -> bool {
+    STATE.get().is_some()
+}
+
+/**
+Write the digests known so far to the state file.
+
+The file is written next to the state file and then renamed:
+a crash while writing never corrupts the previous checkpoint.
+*/
+pub fn write_checkpoint() -> MyResult<()> {
+    let Some((path, algorithm)) = STATE.get() else {
+        return Ok(());
+    };
+
+    let index = DigestIndex {
+        version: INDEX_VERSION,
+        algorithm: *algorithm,
+        entries: get_known_entries(),
+    };
+
+    let temporary: PathBuf = get_temporary_path(path);
+    index
+        .save(&temporary)
+        .and_then(|()| Ok(fs::rename(&temporary, path)?))
+        .inspect_err(|error| {
+            eprintln!("fn write_checkpoint()");
+            eprintln!("Couldn't write the state file {path:?}");
+            eprintln!("Error: {error}");
+        })
+}
+
+/**
+Finish the scan: the state file is removed if the scan is complete,
+otherwise the last checkpoint is written (`--timeout`, Ctrl+C, `--max_files`).
+*/
+pub fn finish_resume(complete: bool) -> MyResult<()> {
+    let Some((path, _algorithm)) = STATE.get() else {
+        return Ok(());
+    };
+
+    if complete {
+        match fs::remove_file(path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(error.into()),
+            _ => Ok(()),
+        }
+    } else {
+        write_checkpoint()?;
+        eprintln!("Scan state saved: continue with `--resume {path:?}`");
+        Ok(())
+    }
+}
+
+/// Get the path of the temporary file, with the same extension (same format).
+fn get_temporary_path(path: &Path) -> PathBuf {
+    match path.extension() {
+        Some(extension) => path.with_extension(format!("tmp.{}", extension.to_string_lossy())),
+        None => path.with_extension("tmp"),
+    }
+}
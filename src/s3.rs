@@ -0,0 +1,520 @@
+use crate::{
+    enumerations::algo::{hash_reader, SliceExtension},
+    output::civil_from_days,
+    source::{get_prefilter_hash, FileSource},
+    throttle::throttle,
+    Arguments, FileFilter, FileInfo, HashPipeline, Key, MyResult,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::HashMap,
+    io::{BufReader, Read},
+    path::{Path, PathBuf},
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Prefix of the virtual paths of the objects: `s3://bucket/key`.
+pub const S3_PREFIX: &str = "s3://";
+
+/// Payload hash of the signed requests: the body of a GET is empty.
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+/// Credentials and endpoint of the S3 requests.
+#[derive(Debug, Clone)]
+struct S3Config {
+    access_key: String,
+    secret_key: String,
+    session_token: Option<String>,
+    region: String,
+    /// S3-compatible endpoint (MinIO, Ceph, ...): path-style requests.
+    endpoint: Option<String>,
+}
+
+impl S3Config {
+    /**
+    Read the configuration from the usual AWS environment variables:
+
+    `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, `AWS_SESSION_TOKEN`,
+    `AWS_REGION` (or `AWS_DEFAULT_REGION`) and `AWS_ENDPOINT_URL`.
+    */
+    fn from_env() -> MyResult<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        let (Some(access_key), Some(secret_key)) =
+            (var("AWS_ACCESS_KEY_ID"), var("AWS_SECRET_ACCESS_KEY"))
+        else {
+            return Err("AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY must be set".into());
+        };
+
+        Ok(S3Config {
+            access_key,
+            secret_key,
+            session_token: var("AWS_SESSION_TOKEN"),
+            region: var("AWS_REGION")
+                .or_else(|| var("AWS_DEFAULT_REGION"))
+                .unwrap_or_else(|| "us-east-1".to_string()),
+            endpoint: var("AWS_ENDPOINT_URL").map(|url| url.trim_end_matches('/').to_string()),
+        })
+    }
+
+    /// Get the host, the URL and the canonical URI of an object (or of the bucket if the key is empty).
+    fn locate(&self, bucket: &str, key: &str) -> (String, String, String) {
+        match &self.endpoint {
+            Some(endpoint) => {
+                let host = endpoint
+                    .split_once("://")
+                    .map_or(endpoint.as_str(), |(_scheme, host)| host)
+                    .to_string();
+                let uri = format!("/{bucket}/{}", uri_encode(key, false));
+                (host, format!("{endpoint}{uri}"), uri)
+            }
+            None => {
+                let host = format!("{bucket}.s3.{}.amazonaws.com", self.region);
+                let uri = format!("/{}", uri_encode(key, false));
+                (host.clone(), format!("https://{host}{uri}"), uri)
+            }
+        }
+    }
+
+    /**
+    Build a GET request signed with AWS Signature Version 4.
+
+    <https://docs.aws.amazon.com/IAM/latest/UserGuide/create-signed-request.html>
+    */
+    fn request(&self, bucket: &str, key: &str, query: &[(&str, &str)]) -> ureq::Request {
+        let (host, url, uri) = self.locate(bucket, key);
+
+        let mut pairs: Vec<(String, String)> = query
+            .iter()
+            .map(|(name, value)| (uri_encode(name, true), uri_encode(value, true)))
+            .collect();
+        pairs.sort();
+        let canonical_query: String = pairs
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<String>>()
+            .join("&");
+
+        let (date, amz_date) = get_amz_date(SystemTime::now());
+
+        let mut headers: Vec<(&str, String)> = vec![
+            ("host", host),
+            ("x-amz-content-sha256", UNSIGNED_PAYLOAD.to_string()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(token) = &self.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{value}\n"))
+            .collect();
+        let signed_headers: String = headers
+            .iter()
+            .map(|(name, _value)| *name)
+            .collect::<Vec<&str>>()
+            .join(";");
+
+        let canonical_request = format!(
+            "GET\n{uri}\n{canonical_query}\n{canonical_headers}\n{signed_headers}\n{UNSIGNED_PAYLOAD}"
+        );
+
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            Sha256::digest(canonical_request.as_bytes())
+                .as_slice()
+                .to_hex_string()
+        );
+
+        let mut signing_key: [u8; 32] = hmac_sha256(
+            format!("AWS4{}", self.secret_key).as_bytes(),
+            date.as_bytes(),
+        );
+        for part in [self.region.as_str(), "s3", "aws4_request"] {
+            signing_key = hmac_sha256(&signing_key, part.as_bytes());
+        }
+        let signature = hmac_sha256(&signing_key, string_to_sign.as_bytes())
+            .as_slice()
+            .to_hex_string();
+
+        let url = if canonical_query.is_empty() {
+            url
+        } else {
+            format!("{url}?{canonical_query}")
+        };
+
+        let mut request = ureq::get(&url).set(
+            "Authorization",
+            &format!(
+                "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+                self.access_key
+            ),
+        );
+        for (name, value) in &headers {
+            if *name != "host" {
+                request = request.set(name, value);
+            }
+        }
+        request
+    }
+}
+
+/// Object of a bucket, listed by ListObjectsV2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Object {
+    key: String,
+    size: u64,
+    /// ETag (kept only if it is used as the digest)
+    etag: Option<String>,
+}
+
+/**
+Objects of `bucket/prefix` (`--s3`).
+
+The path of an object is `s3://bucket/key`.
+
+When only the bucket is searched, the ETags of objects uploaded in a
+single part (the MD5 of the content) are used as digests: these objects
+are not downloaded. The other objects are streamed and hashed.
+*/
+pub struct S3Source {
+    config: S3Config,
+    bucket: String,
+    prefix: String,
+    /// URL of the source: `s3://bucket/prefix`
+    name: String,
+    /// Listed objects, by path.
+    objects: RwLock<HashMap<PathBuf, Object>>,
+}
+
+impl S3Source {
+    /// Create the source of `bucket/prefix` (the `s3://` scheme is optional).
+    pub fn new(source: &str) -> MyResult<Self> {
+        let config = S3Config::from_env().inspect_err(|error| {
+            eprintln!("fn S3Source::new()");
+            eprintln!("Couldn't read the S3 credentials");
+            eprintln!("Error: {error}");
+        })?;
+
+        let source: &str = source.strip_prefix(S3_PREFIX).unwrap_or(source);
+        let (bucket, prefix) = source.split_once('/').unwrap_or((source, ""));
+
+        Ok(S3Source {
+            config,
+            bucket: bucket.to_string(),
+            prefix: prefix.to_string(),
+            name: format!("{S3_PREFIX}{source}"),
+            objects: RwLock::new(HashMap::new()),
+        })
+    }
+
+    /// Get the key of an object from its virtual path.
+    fn get_key<'a>(&self, path: &'a Path) -> MyResult<&'a str> {
+        path.to_str()
+            .and_then(|path| path.strip_prefix(S3_PREFIX))
+            .and_then(|path| path.strip_prefix(self.bucket.as_str()))
+            .and_then(|path| path.strip_prefix('/'))
+            .ok_or_else(|| format!("{path:?} is not an object of {}", self.name).into())
+    }
+
+    /// Read a range of bytes of an object: `bytes=start-end` (inclusive).
+    fn read_range(&self, path: &Path, start: u64, end: u64) -> MyResult<Vec<u8>> {
+        let response = self
+            .config
+            .request(&self.bucket, self.get_key(path)?, &[])
+            .set("Range", &format!("bytes={start}-{end}"))
+            .call()?;
+
+        let mut bytes: Vec<u8> = Vec::new();
+        response
+            .into_reader()
+            .take(end - start + 1)
+            .read_to_end(&mut bytes)?;
+        throttle(bytes.len());
+
+        Ok(bytes)
+    }
+}
+
+impl FileSource for S3Source {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.get_key(path).is_ok()
+    }
+
+    fn list_files(
+        &self,
+        arguments: &Arguments,
+        filter: &FileFilter,
+        // Objects are hashed by the source, not by the local pipeline.
+        _pipeline: Option<&HashPipeline>,
+    ) -> MyResult<Vec<FileInfo>> {
+        let objects: Vec<Object> = list_objects(&self.config, &self.bucket, &self.prefix)?;
+
+        // Digests of local files cannot be compared with ETags.
+        let use_etags: bool = arguments.input_dir.is_empty()
+            && arguments.files_from.is_none()
+            && arguments.compare.is_none()
+            && arguments.remote.is_none();
+
+        let mut known = self.objects.write().map_err(|error| error.to_string())?;
+
+        let object_files: Vec<FileInfo> = objects
+            .into_iter()
+            .filter(|object| arguments.size_is_included(object.size))
+            .map(|object| {
+                let path = PathBuf::from(format!("{S3_PREFIX}{}/{}", self.bucket, object.key));
+                (path, object)
+            })
+            .filter(|(path, _object)| filter.is_included(path) && !filter.is_excluded(path))
+            .map(|(path, mut object)| {
+                object.etag = object.etag.filter(|etag| use_etags && is_simple_etag(etag));
+                let size: u64 = object.size;
+                known.insert(path.clone(), object);
+                FileInfo {
+                    key: Key::new(size, None),
+                    path,
+                    inode: None,
+                    modified: None,
+                    allocated: None,
+                }
+            })
+            .collect();
+
+        Ok(object_files)
+    }
+
+    fn open(&self, path: &Path) -> MyResult<Box<dyn Read + Send>> {
+        let response = self
+            .config
+            .request(&self.bucket, self.get_key(path)?, &[])
+            .call()?;
+        Ok(Box::new(response.into_reader()))
+    }
+
+    /// The ETag is used when available. Otherwise the first bytes (and the
+    /// last bytes, with `--prefilter_tail`) are read with range requests,
+    /// or the entire object is streamed.
+    fn get_hash(&self, path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+        let object: Object = self
+            .objects
+            .read()
+            .ok()
+            .and_then(|known| known.get(path).cloned())
+            .ok_or("object not listed")?;
+
+        if let Some(etag) = object.etag {
+            return Ok(format!("etag:{etag}"));
+        }
+
+        if procedure == 3 {
+            let reader = BufReader::with_capacity(arguments.buffer_size.max(1), self.open(path)?);
+            return hash_reader(
+                reader,
+                arguments.algorithm.new_digest(object.size),
+                arguments.buffer_size,
+            );
+        }
+
+        get_prefilter_hash(object.size, arguments, |start, end| {
+            self.read_range(path, start, end)
+        })
+    }
+}
+
+/// List all the objects of the prefix, following the continuation tokens.
+fn list_objects(config: &S3Config, bucket: &str, prefix: &str) -> MyResult<Vec<Object>> {
+    let mut objects: Vec<Object> = Vec::new();
+    let mut token: Option<String> = None;
+
+    loop {
+        let mut query: Vec<(&str, &str)> = vec![("list-type", "2"), ("prefix", prefix)];
+        if let Some(token) = &token {
+            query.push(("continuation-token", token));
+        }
+
+        let body: String = config.request(bucket, "", &query).call()?.into_string()?;
+        objects.extend(parse_objects(&body));
+
+        token = match get_element(&body, "IsTruncated") {
+            Some("true") => get_element(&body, "NextContinuationToken").map(xml_unescape),
+            _ => None,
+        };
+
+        if token.is_none() {
+            return Ok(objects);
+        }
+    }
+}
+
+/// Get the objects of a ListObjectsV2 response. Folder markers (`dir/`) are ignored.
+fn parse_objects(xml: &str) -> Vec<Object> {
+    get_elements(xml, "Contents")
+        .into_iter()
+        .filter_map(|contents| {
+            let key: String = xml_unescape(get_element(contents, "Key")?);
+            let size: u64 = get_element(contents, "Size")?.trim().parse().ok()?;
+            let etag: Option<String> = get_element(contents, "ETag")
+                .map(|etag| xml_unescape(etag).trim_matches('"').to_lowercase());
+            Some(Object { key, size, etag })
+        })
+        .filter(|object| !object.key.ends_with('/'))
+        .collect()
+}
+
+/// Get the contents of all the `<tag>...</tag>` elements.
+fn get_elements<'a>(xml: &'a str, tag: &str) -> Vec<&'a str> {
+    let (open, close) = (format!("<{tag}>"), format!("</{tag}>"));
+    let mut elements: Vec<&str> = Vec::new();
+    let mut rest: &str = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let content: &str = &rest[start + open.len()..];
+        let Some(end) = content.find(&close) else {
+            break;
+        };
+        elements.push(&content[..end]);
+        rest = &content[end + close.len()..];
+    }
+
+    elements
+}
+
+/// Get the contents of the first `<tag>...</tag>` element.
+fn get_element<'a>(xml: &'a str, tag: &str) -> Option<&'a str> {
+    get_elements(xml, tag).into_iter().next()
+}
+
+/// Replace the predefined XML entities.
+fn xml_unescape(text: &str) -> String {
+    text.replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/**
+Check if the ETag is the MD5 of the content.
+
+The ETags of multipart uploads (`md5-parts`) depend on the part size,
+and the ETags of objects encrypted with SSE-KMS are not MD5 digests:
+these objects are hashed.
+*/
+fn is_simple_etag(etag: &str) -> bool {
+    etag.len() == 32 && etag.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// Encode the text as required by AWS Signature Version 4 (RFC 3986 unreserved characters).
+fn uri_encode(text: &str, encode_slash: bool) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            b'/' if !encode_slash => "/".to_string(),
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+/// Get the date (`YYYYMMDD`) and the date-time (`YYYYMMDDTHHMMSSZ`) of the request, in UTC.
+fn get_amz_date(time: SystemTime) -> (String, String) {
+    let secs: u64 = time
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let (days, seconds) = (secs / 86400, secs % 86400);
+    let (year, month, day) = civil_from_days(days);
+
+    let date = format!("{year:04}{month:02}{day:02}");
+    let amz_date = format!(
+        "{date}T{:02}{:02}{:02}Z",
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    );
+
+    (date, amz_date)
+}
+
+/// HMAC-SHA256 (RFC 2104).
+fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    const BLOCK_SIZE: usize = 64;
+
+    let mut block = [0_u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    inner.update(data);
+
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner.finalize());
+
+    outer.finalize().into()
+}
+
+#[cfg(test)]
+mod test_s3 {
+    use super::*;
+
+    /// cargo test --features s3 -- --show-output test_hmac_sha256
+    ///
+    /// <https://www.rfc-editor.org/rfc/rfc4231#section-4.3>
+    #[test]
+    fn test_hmac_sha256() {
+        let mac = hmac_sha256(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(
+            mac.as_slice().to_hex_string(),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// cargo test --features s3 -- --show-output test_parse_objects
+    #[test]
+    fn test_parse_objects() {
+        let xml = r#"<ListBucketResult>
+            <IsTruncated>false</IsTruncated>
+            <Contents><Key>photos/</Key><Size>0</Size></Contents>
+            <Contents><Key>photos/a&amp;b.jpg</Key><Size>1024</Size>
+                <ETag>&quot;9E107D9D372BB6826BD81D3542A419D6&quot;</ETag></Contents>
+            <Contents><Key>big.iso</Key><Size>9000000</Size>
+                <ETag>"d41d8cd98f00b204e9800998ecf8427e-3"</ETag></Contents>
+        </ListBucketResult>"#;
+
+        let objects = parse_objects(xml);
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].key, "photos/a&b.jpg");
+        assert_eq!(objects[0].size, 1024);
+        assert!(is_simple_etag(
+            objects[0].etag.as_deref().unwrap_or_default()
+        ));
+        assert!(!is_simple_etag(
+            objects[1].etag.as_deref().unwrap_or_default()
+        ));
+        assert_eq!(get_element(xml, "IsTruncated"), Some("false"));
+    }
+
+    /// cargo test --features s3 -- --show-output test_uri_encode
+    #[test]
+    fn test_uri_encode() {
+        assert_eq!(uri_encode("a b/ç~.txt", false), "a%20b/%C3%A7~.txt");
+        assert_eq!(uri_encode("a/b", true), "a%2Fb");
+        assert_eq!(
+            get_amz_date(UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000)),
+            ("20231114".to_string(), "20231114T221320Z".to_string())
+        );
+    }
+}
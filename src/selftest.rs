@@ -0,0 +1,252 @@
+use crate::{
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    get_all_files, get_identical_groups, MyResult,
+};
+use clap::{Parser, ValueEnum};
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    ffi::OsStr,
+    fs::{self, File},
+    io::{Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tempfile::TempDir;
+
+/// Process exit code of `--selftest` when the groups found are not the expected ones.
+pub const SELFTEST_FAILED_EXIT_CODE: i32 = 6;
+
+/// Size of the sparse files: they use almost no disk space.
+const SPARSE_SIZE: u64 = 256 * 1024 * 1024;
+
+/// Depth of the deeply nested directories.
+const NESTING_DEPTH: usize = 64;
+
+/// Group of identical files, with paths relative to the synthetic tree.
+type Group = BTreeSet<PathBuf>;
+
+/// Result of `--selftest`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SelftestInfo {
+    /// Number of files of the synthetic tree
+    #[serde(rename = "Number of files")]
+    pub num_file: usize,
+    /// Number of expected groups
+    #[serde(rename = "Number of expected groups")]
+    pub num_expected: usize,
+    /// Expected groups not found
+    #[serde(rename = "Missing groups")]
+    pub missing: Vec<Group>,
+    /// Groups found but not expected
+    #[serde(rename = "Unexpected groups")]
+    pub unexpected: Vec<Group>,
+}
+
+/// Synthetic tree in the temporary directory, removed when dropped.
+///
+/// The directory is created with a random name and private permissions:
+/// an existing directory or link is never reused.
+struct SyntheticTree {
+    dir: TempDir,
+}
+
+impl SyntheticTree {
+    /**
+    Create the tree and return the expected groups of identical files.
+
+    Edge cases: empty files, large sparse files (same size and same first
+    bytes but different last bytes), unicode names, deep nesting and files
+    of the same size with different contents.
+    */
+    fn new() -> MyResult<(Self, Vec<Group>)> {
+        let dir = tempfile::Builder::new().prefix("fif-selftest-").tempdir()?;
+        let tree = SyntheticTree { dir };
+
+        let deep: PathBuf = ["deep"; NESTING_DEPTH].iter().collect();
+        let mut expected: Vec<Group> = Vec::new();
+
+        let mut add_group = |paths: &[PathBuf], content: &[u8]| -> MyResult<()> {
+            for path in paths {
+                tree.write(path, content)?;
+            }
+            if paths.len() > 1 {
+                expected.push(paths.iter().cloned().collect());
+            }
+            Ok(())
+        };
+
+        // Regular copies in different directories.
+        add_group(
+            &["photos/img.jpg".into(), "backup/photos/img copy.jpg".into()],
+            &[0xFF, 0xD8, 0xFF, 0xE0, 1, 2, 3, 4],
+        )?;
+        // Unicode names.
+        add_group(
+            &[
+                "ação/relatório.txt".into(),
+                "日本語/ファイル.txt".into(),
+                "emoji/😀.txt".into(),
+            ],
+            "conteúdo idêntico\n".as_bytes(),
+        )?;
+        // Deep nesting.
+        add_group(&[deep.join("nested.bin"), "shallow.bin".into()], &[7; 4096])?;
+        // Same size, different contents: not identical.
+        add_group(&["same_size_a.bin".into()], &[1; 4096 + 1])?;
+        add_group(&["same_size_b.bin".into()], &[2; 4096 + 1])?;
+        // Unique file.
+        add_group(&["unique.txt".into()], b"unique content\n")?;
+        // Empty files.
+        add_group(&["empty/a".into(), "empty/b".into()], b"")?;
+
+        // Sparse files: two identical, one with a different last byte.
+        let sparse: [&str; 3] = ["sparse/zeros_1", "sparse/zeros_2", "sparse/last_byte"];
+        for name in sparse {
+            tree.create_sparse(Path::new(name), name == "sparse/last_byte")?;
+        }
+        expected.push(sparse[..2].iter().map(PathBuf::from).collect());
+
+        Ok((tree, expected))
+    }
+
+    /// Root directory of the tree.
+    fn root(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Write a file of the tree (relative path).
+    fn write(&self, path: &Path, content: &[u8]) -> MyResult<()> {
+        let path = self.root().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+        Ok(())
+    }
+
+    /// Create a sparse file of zeros, optionally with a non-zero last byte.
+    fn create_sparse(&self, path: &Path, last_byte: bool) -> MyResult<()> {
+        let path = self.root().join(path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = File::create(path)?;
+        if last_byte {
+            file.seek(SeekFrom::Start(SPARSE_SIZE - 1))?;
+            file.write_all(&[1])?;
+        } else {
+            file.set_len(SPARSE_SIZE)?;
+        }
+        Ok(())
+    }
+}
+
+/**
+Generate a synthetic tree with known identical files and check that the
+search finds exactly the expected groups (`--selftest`).
+
+The search uses the default options and the chosen `--algorithm`:
+the configuration file and the other options are ignored.
+*/
+pub fn run_selftest(arguments: &Arguments) -> MyResult<SelftestInfo> {
+    let (tree, expected) = SyntheticTree::new().inspect_err(|error| {
+        eprintln!("fn run_selftest()");
+        eprintln!("Couldn't create the tree in {:?}", std::env::temp_dir());
+        eprintln!("Error: {error}");
+    })?;
+
+    let algorithm = arguments
+        .algorithm
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default();
+
+    let search = Arguments::try_parse_from([
+        OsStr::new("find-identical-files"),
+        OsStr::new("--include_empty"),
+        OsStr::new("--algorithm"),
+        OsStr::new(&algorithm),
+        OsStr::new("--input_dir"),
+        tree.root().as_os_str(),
+    ])?;
+
+    let all_files = get_all_files(&search)?;
+    let identical_hash = get_identical_groups(&all_files, &search, Instant::now());
+
+    // Paths relative to the root of the tree.
+    let root: PathBuf = fs::canonicalize(tree.root())?;
+    let relative_path = |path: &PathBuf| -> PathBuf {
+        let path: PathBuf = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        path.strip_prefix(&root).unwrap_or(&path).to_path_buf()
+    };
+
+    let found: Vec<Group> = identical_hash
+        .iter()
+        .map(|group_info| group_info.paths.iter().map(relative_path).collect())
+        .collect();
+
+    Ok(SelftestInfo {
+        num_file: all_files.len(),
+        num_expected: expected.len(),
+        missing: expected
+            .iter()
+            .filter(|group| !found.contains(group))
+            .cloned()
+            .collect(),
+        unexpected: found
+            .iter()
+            .filter(|group| !expected.contains(group))
+            .cloned()
+            .collect(),
+    })
+}
+
+impl SelftestInfo {
+    /// Check if exactly the expected groups were found.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+
+    /// Write the result in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize SelftestInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize SelftestInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                for group in &self.missing {
+                    writeln!(write, "MISSING: {group:?}")?;
+                }
+                for group in &self.unexpected {
+                    writeln!(write, "UNEXPECTED: {group:?}")?;
+                }
+                if !self.is_ok() {
+                    writeln!(write)?;
+                }
+                writeln!(write, "Number of files: {}", self.num_file)?;
+                writeln!(write, "Number of expected groups: {}", self.num_expected)?;
+                writeln!(write, "Number of missing groups: {}", self.missing.len())?;
+                writeln!(
+                    write,
+                    "Number of unexpected groups: {}",
+                    self.unexpected.len()
+                )?;
+                let result = if self.is_ok() { "passed" } else { "FAILED" };
+                writeln!(write, "Self-test {result}")?;
+                writeln!(write)?;
+            }
+        }
+        Ok(())
+    }
+}
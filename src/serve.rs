@@ -0,0 +1,296 @@
+use crate::{Algorithm, Arguments, DigestIndex, FileInfo, MyResult};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::Arc,
+    thread,
+};
+
+/**
+Query sent by other processes (one JSON object per line).
+
+```json
+{"digest": "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262", "size": 1024}
+```
+
+Both fields are optional: a query with only the size finds files that *may* be identical.
+
+The digest may be self-describing (`blake3:<hex>`): digests of another algorithm are never found.
+*/
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Query {
+    /// Hash of the entire file (same algorithm as the server)
+    pub digest: Option<String>,
+    /// File size (in bytes)
+    pub size: Option<u64>,
+}
+
+/// Answer sent back to the client (one JSON object per line).
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Answer {
+    /// A file with the digest and/or size was found
+    pub found: bool,
+    /// Algorithm that produced the digests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<Algorithm>,
+    /// Paths of the files found
+    pub paths: Vec<PathBuf>,
+    /// Invalid query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// In-memory index of the files, by digest and by size.
+#[derive(Debug, Default)]
+pub struct QueryIndex {
+    /// Algorithm that produced the digests
+    algorithm: Algorithm,
+    /// (size, path) by digest
+    by_digest: HashMap<String, Vec<(u64, PathBuf)>>,
+    /// Paths by size
+    by_size: HashMap<u64, Vec<PathBuf>>,
+}
+
+impl QueryIndex {
+    /// Build the query index from a digest index.
+    pub fn new(index: DigestIndex) -> Self {
+        let mut query_index = QueryIndex {
+            algorithm: index.algorithm,
+            ..Default::default()
+        };
+
+        for entry in index.entries {
+            query_index
+                .by_size
+                .entry(entry.size)
+                .or_default()
+                .push(entry.path.clone());
+            if let Some(digest) = entry.get_digest(index.algorithm) {
+                query_index
+                    .by_digest
+                    .entry(digest.to_string())
+                    .or_default()
+                    .push((entry.size, entry.path));
+            }
+        }
+
+        query_index
+    }
+
+    /// Get the digest of the query without its prefix, if produced by the algorithm of the index.
+    fn get_digest<'a>(&self, digest: &'a str) -> Option<&'a str> {
+        match Algorithm::split_digest(digest) {
+            (Some(algorithm), _digest) if algorithm != self.algorithm => None,
+            (_algorithm, digest) => Some(digest),
+        }
+    }
+
+    /// Answer the query.
+    pub fn answer(&self, query: &Query) -> Answer {
+        let paths: Vec<PathBuf> = match (&query.digest, query.size) {
+            (Some(digest), size) => self
+                .by_digest
+                .get(self.get_digest(digest).unwrap_or_default())
+                .into_iter()
+                .flatten()
+                .filter(|(file_size, _path)| size.map_or(true, |size| *file_size == size))
+                .map(|(_size, path)| path.clone())
+                .collect(),
+            (None, Some(size)) => self.by_size.get(&size).cloned().unwrap_or_default(),
+            (None, None) => {
+                return Answer {
+                    error: Some("expected a digest and/or a size".to_string()),
+                    ..Default::default()
+                }
+            }
+        };
+
+        Answer {
+            found: !paths.is_empty(),
+            algorithm: Some(self.algorithm),
+            paths,
+            error: None,
+        }
+    }
+
+    /// Answer the queries of a client, line by line, until the connection is closed.
+    pub fn handle_client(&self, reader: impl BufRead, mut writer: impl Write) -> MyResult<()> {
+        for line in reader.lines() {
+            let line = line?;
+
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let answer: Answer = match serde_json::from_str::<Query>(&line) {
+                Ok(query) => self.answer(&query),
+                Err(error) => Answer {
+                    error: Some(format!("invalid query: {error}")),
+                    ..Default::default()
+                },
+            };
+
+            serde_json::to_writer(&mut writer, &answer)?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+Serve queries about the indexed files (`--serve`).
+
+The address is a TCP port (on localhost) or, on unix, the path of a socket.
+
+Each connection is handled in its own thread, until the process is interrupted (Ctrl+C).
+*/
+pub fn serve(all_files: &[FileInfo], arguments: &Arguments, address: &str) -> MyResult<()> {
+    let index = Arc::new(QueryIndex::new(DigestIndex::new(all_files, arguments)));
+
+    if let Ok(port) = address.parse::<u16>() {
+        let listener = TcpListener::bind(("127.0.0.1", port)).inspect_err(|error| {
+            eprintln!("fn serve()");
+            eprintln!("Couldn't listen on port {port}");
+            eprintln!("Error: {error}");
+        })?;
+        eprintln!("Serving queries on 127.0.0.1:{port} (Ctrl+C to stop)...");
+
+        for stream in listener.incoming().flatten() {
+            let index = Arc::clone(&index);
+            thread::spawn(move || index.handle_client(BufReader::new(&stream), &stream));
+        }
+
+        return Ok(());
+    }
+
+    serve_unix_socket(index, address)
+}
+
+/// Serve queries on a unix socket.
+#[cfg(unix)]
+fn serve_unix_socket(index: Arc<QueryIndex>, address: &str) -> MyResult<()> {
+    use std::os::unix::{fs::FileTypeExt, net::UnixListener};
+
+    // Remove the socket of a previous server, but never another file.
+    match std::fs::symlink_metadata(address) {
+        Ok(metadata) if metadata.file_type().is_socket() => std::fs::remove_file(address)?,
+        Ok(_) => {
+            eprintln!("fn serve_unix_socket()");
+            eprintln!("{address:?} exists and is not a socket");
+            return Err(format!("refusing to replace {address:?}: not a socket").into());
+        }
+        Err(_) => {}
+    }
+
+    let listener = UnixListener::bind(address).inspect_err(|error| {
+        eprintln!("fn serve_unix_socket()");
+        eprintln!("Couldn't listen on {address:?}");
+        eprintln!("Error: {error}");
+    })?;
+    eprintln!("Serving queries on {address:?} (Ctrl+C to stop)...");
+
+    for stream in listener.incoming().flatten() {
+        let index = Arc::clone(&index);
+        thread::spawn(move || index.handle_client(BufReader::new(&stream), &stream));
+    }
+
+    Ok(())
+}
+
+/// Unix sockets are not available: only TCP ports.
+#[cfg(not(unix))]
+fn serve_unix_socket(_index: Arc<QueryIndex>, address: &str) -> MyResult<()> {
+    eprintln!("fn serve_unix_socket()");
+    eprintln!("Invalid port: {address:?}");
+    Err(format!("unix sockets are not supported: {address:?}").into())
+}
+
+#[cfg(test)]
+mod test_serve {
+    use super::*;
+    use crate::{IndexEntry, INDEX_VERSION};
+
+    /// cargo test -- --show-output test_query_index
+    #[test]
+    fn test_query_index() -> MyResult<()> {
+        let entry = |path: &str, size: u64, digest: &str| IndexEntry {
+            path: PathBuf::from(path),
+            size,
+            modified: None,
+            digest: digest.to_string(),
+        };
+
+        let index = QueryIndex::new(DigestIndex {
+            version: INDEX_VERSION,
+            algorithm: Algorithm::Blake3,
+            entries: vec![
+                entry("/data/a.txt", 3, "blake3:abc"),
+                entry("/data/b.txt", 3, "blake3:abc"),
+                entry("/data/c.txt", 3, "blake3:def"),
+            ],
+        });
+
+        let input = concat!(
+            r#"{"digest": "abc"}"#,
+            "\n",
+            r#"{"digest": "abc", "size": 4}"#,
+            "\n",
+            r#"{"size": 3}"#,
+            "\n",
+            "{}\n",
+            "not json\n",
+            r#"{"digest": "blake3:abc"}"#,
+            "\n",
+            r#"{"digest": "sha256:abc"}"#,
+            "\n",
+        );
+
+        let mut output: Vec<u8> = Vec::new();
+        index.handle_client(input.as_bytes(), &mut output)?;
+
+        let answers: Vec<serde_json::Value> = output
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.is_empty())
+            .map(serde_json::from_slice)
+            .collect::<Result<_, _>>()?;
+
+        assert_eq!(answers.len(), 7);
+        assert_eq!(answers[0]["found"], true);
+        assert_eq!(answers[0]["paths"].as_array().map(Vec::len), Some(2));
+        assert_eq!(answers[1]["found"], false);
+        assert_eq!(answers[2]["paths"].as_array().map(Vec::len), Some(3));
+        assert!(answers[3]["error"].is_string());
+        assert!(answers[4]["error"].is_string());
+        // Self-describing digests
+        assert_eq!(answers[5]["found"], true);
+        assert_eq!(answers[6]["found"], false);
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_serve_existing_file
+    #[cfg(unix)]
+    #[test]
+    fn test_serve_existing_file() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let path = temp_dir.path().join("report.json");
+        std::fs::write(&path, "not a socket")?;
+
+        let index = Arc::new(QueryIndex::new(DigestIndex {
+            version: INDEX_VERSION,
+            algorithm: Algorithm::Blake3,
+            entries: Vec::new(),
+        }));
+
+        // A regular file is never replaced by the socket.
+        assert!(serve_unix_socket(index, &path.to_string_lossy()).is_err());
+        assert_eq!(std::fs::read_to_string(&path)?, "not a socket");
+
+        Ok(())
+    }
+}
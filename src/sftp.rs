@@ -0,0 +1,316 @@
+use crate::{
+    enumerations::algo::hash_reader,
+    source::{get_prefilter_hash, FileSource},
+    throttle::throttle,
+    Algorithm, Arguments, FileFilter, FileInfo, HashPipeline, Key, MyResult,
+};
+use std::{
+    collections::HashMap,
+    io::{self, BufReader, Read},
+    path::{Path, PathBuf},
+    process::{Child, ChildStdout, Command, Stdio},
+    sync::{OnceLock, RwLock},
+};
+
+/// Scheme of the remote trees: `sftp://user@host:port/path`.
+pub const SFTP_PREFIX: &str = "sftp://";
+
+/**
+Tree of a remote server (`--remote sftp://user@host/path`).
+
+Commands are run with the `ssh` client of the system: the keys, agents
+and `~/.ssh/config` of the user are used. The connection is shared by
+all the commands (ControlMaster) on unix.
+
+The remote server needs a POSIX shell and GNU `find`. Files are hashed
+by the remote `sha256sum`, `sha512sum` or `b3sum` when available for the
+chosen algorithm; otherwise their contents are streamed and hashed locally.
+*/
+pub struct SftpSource {
+    /// Destination of ssh: `user@host`
+    destination: String,
+    port: Option<u16>,
+    /// Remote directory
+    root: String,
+    /// URL of the source, without the path: `sftp://user@host:port`
+    name: String,
+    /// Sizes of the listed files, by path.
+    sizes: RwLock<HashMap<PathBuf, u64>>,
+    /// Whether the remote hash command of the algorithm is available.
+    helper: OnceLock<bool>,
+}
+
+impl SftpSource {
+    /// Create the source of `sftp://user@host[:port]/path`.
+    pub fn new(url: &str) -> MyResult<Self> {
+        let Some((authority, path)) = url
+            .strip_prefix(SFTP_PREFIX)
+            .and_then(|rest| rest.find('/').map(|index| rest.split_at(index)))
+        else {
+            return Err(
+                format!("invalid remote URL {url:?}: expected sftp://user@host/path").into(),
+            );
+        };
+
+        let (destination, port) = match authority.rsplit_once(':') {
+            Some((destination, port)) => (destination, Some(port.parse::<u16>()?)),
+            None => (authority, None),
+        };
+
+        Ok(SftpSource {
+            destination: destination.to_string(),
+            port,
+            root: path.to_string(),
+            name: format!("{SFTP_PREFIX}{authority}"),
+            sizes: RwLock::new(HashMap::new()),
+            helper: OnceLock::new(),
+        })
+    }
+
+    /// Build the ssh command that runs the remote command.
+    fn command(&self, remote_command: &str) -> Command {
+        let mut command = Command::new("ssh");
+        command.args(["-o", "BatchMode=yes"]);
+
+        // Share one connection between all the commands.
+        #[cfg(unix)]
+        {
+            let control_path =
+                std::env::temp_dir().join(format!("fif-{}-ssh-%C", std::process::id()));
+            command.args(["-o", "ControlMaster=auto", "-o", "ControlPersist=60"]);
+            command
+                .arg("-o")
+                .arg(format!("ControlPath={}", control_path.display()));
+        }
+
+        if let Some(port) = self.port {
+            command.arg("-p").arg(port.to_string());
+        }
+        command.arg(&self.destination).arg(remote_command);
+        command.stdin(Stdio::null());
+        command
+    }
+
+    /// Run the remote command and get its output.
+    fn run(&self, remote_command: &str) -> MyResult<Vec<u8>> {
+        let output = self.command(remote_command).output()?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!(
+                "ssh {}: {} {}",
+                self.destination,
+                output.status,
+                stderr.trim()
+            )
+            .into());
+        }
+
+        Ok(output.stdout)
+    }
+
+    /// Get the remote path of a file from its virtual path.
+    fn get_remote_path<'a>(&self, path: &'a Path) -> MyResult<&'a str> {
+        path.to_str()
+            .and_then(|path| path.strip_prefix(self.name.as_str()))
+            .filter(|remote_path| remote_path.starts_with('/'))
+            .ok_or_else(|| format!("{path:?} is not a file of {}", self.name).into())
+    }
+
+    /// Read a range of bytes of a file, from start to end (inclusive).
+    fn read_range(&self, path: &Path, start: u64, end: u64) -> MyResult<Vec<u8>> {
+        let remote_path: String = shell_quote(self.get_remote_path(path)?);
+        let bytes: Vec<u8> = self.run(&format!(
+            "tail -c +{} -- {remote_path} | head -c {}",
+            start + 1,
+            end - start + 1
+        ))?;
+        throttle(bytes.len());
+
+        Ok(bytes)
+    }
+
+    /// Hash the entire file with the remote command of the algorithm, if available.
+    fn get_remote_hash(&self, path: &Path, algorithm: Algorithm) -> MyResult<Option<String>> {
+        let Some(program) = get_hash_program(algorithm) else {
+            return Ok(None);
+        };
+
+        let available: bool = *self.helper.get_or_init(|| {
+            self.run(&format!("command -v {program}"))
+                .is_ok_and(|output| !output.is_empty())
+        });
+        if !available {
+            return Ok(None);
+        }
+
+        let remote_path: String = shell_quote(self.get_remote_path(path)?);
+        let output: Vec<u8> = self.run(&format!("{program} -- {remote_path}"))?;
+
+        // Output: `<digest>  <path>`
+        let digest: Option<String> = String::from_utf8_lossy(&output)
+            .split_whitespace()
+            .next()
+            .and_then(|digest| algorithm.parse_digest(digest).ok());
+
+        Ok(digest)
+    }
+}
+
+impl FileSource for SftpSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.get_remote_path(path).is_ok()
+    }
+
+    fn list_files(
+        &self,
+        arguments: &Arguments,
+        filter: &FileFilter,
+        // Remote files are hashed on the server, not by the local pipeline.
+        _pipeline: Option<&HashPipeline>,
+    ) -> MyResult<Vec<FileInfo>> {
+        // Records: `<size> <path>\0`
+        let output: Vec<u8> = self.run(&format!(
+            "find {} -type f -printf '%s %p\\0'",
+            shell_quote(&self.root)
+        ))?;
+
+        let mut sizes = self.sizes.write().map_err(|error| error.to_string())?;
+
+        let remote_files: Vec<FileInfo> = output
+            .split(|&byte| byte == 0)
+            .filter_map(|record| {
+                let record: &str = std::str::from_utf8(record).ok()?;
+                let (size, remote_path) = record.split_once(' ')?;
+                Some((size.parse::<u64>().ok()?, remote_path))
+            })
+            .filter(|(size, _remote_path)| arguments.size_is_included(*size))
+            .map(|(size, remote_path)| (size, PathBuf::from(format!("{}{remote_path}", self.name))))
+            .filter(|(_size, path)| filter.is_included(path) && !filter.is_excluded(path))
+            .map(|(size, path)| {
+                sizes.insert(path.clone(), size);
+                FileInfo {
+                    key: Key::new(size, None),
+                    path,
+                    inode: None,
+                    modified: None,
+                    allocated: None,
+                }
+            })
+            .collect();
+
+        Ok(remote_files)
+    }
+
+    fn open(&self, path: &Path) -> MyResult<Box<dyn Read + Send>> {
+        let remote_path: String = shell_quote(self.get_remote_path(path)?);
+        let mut child: Child = self
+            .command(&format!("cat -- {remote_path}"))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdout: ChildStdout = child.stdout.take().ok_or("ssh without stdout")?;
+
+        Ok(Box::new(RemoteReader { child, stdout }))
+    }
+
+    fn get_hash(&self, path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+        let size: u64 = self
+            .sizes
+            .read()
+            .ok()
+            .and_then(|sizes| sizes.get(path).copied())
+            .ok_or("file not listed")?;
+
+        if procedure != 3 {
+            return get_prefilter_hash(size, arguments, |start, end| {
+                self.read_range(path, start, end)
+            });
+        }
+
+        if let Some(digest) = self.get_remote_hash(path, arguments.algorithm)? {
+            return Ok(digest);
+        }
+
+        let reader = BufReader::with_capacity(arguments.buffer_size.max(1), self.open(path)?);
+        hash_reader(
+            reader,
+            arguments.algorithm.new_digest(size),
+            arguments.buffer_size,
+        )
+    }
+}
+
+/// Output of a remote `cat`: the exit status is checked at the end of the stream.
+struct RemoteReader {
+    child: Child,
+    stdout: ChildStdout,
+}
+
+impl Read for RemoteReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let count: usize = self.stdout.read(buffer)?;
+
+        if count == 0 && !buffer.is_empty() {
+            let status = self.child.wait()?;
+            if !status.success() {
+                return Err(io::Error::other(format!("remote cat: {status}")));
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+impl Drop for RemoteReader {
+    fn drop(&mut self) {
+        // The stream may not have been read to the end (byte comparison).
+        if let Ok(None) = self.child.try_wait() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// Remote program that prints the same digest as the algorithm.
+fn get_hash_program(algorithm: Algorithm) -> Option<&'static str> {
+    match algorithm {
+        Algorithm::Blake3 => Some("b3sum"),
+        Algorithm::SHA256 => Some("sha256sum"),
+        Algorithm::SHA512 => Some("sha512sum"),
+        _ => None,
+    }
+}
+
+/// Quote the text for a POSIX shell: `it's` -> `'it'\''s'`.
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod test_sftp {
+    use super::*;
+
+    /// cargo test -- --show-output test_sftp_source
+    #[test]
+    fn test_sftp_source() -> MyResult<()> {
+        let source = SftpSource::new("sftp://user@nas:2222/volume1/photos")?;
+        assert_eq!(source.destination, "user@nas");
+        assert_eq!(source.port, Some(2222));
+        assert_eq!(source.root, "/volume1/photos");
+
+        let path = Path::new("sftp://user@nas:2222/volume1/photos/a b.jpg");
+        assert!(source.contains(path));
+        assert_eq!(source.get_remote_path(path)?, "/volume1/photos/a b.jpg");
+        assert!(!source.contains(Path::new("/volume1/photos/a b.jpg")));
+
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+        assert!(SftpSource::new("user@nas/photos").is_err());
+
+        Ok(())
+    }
+}
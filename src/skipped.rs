@@ -0,0 +1,186 @@
+use crate::{output::remove_temp_output, Arguments, MyError};
+use serde::Serialize;
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+/// Files and directories that could not be read.
+static SKIPPED_FILES: Mutex<Vec<SkippedFile>> = Mutex::new(Vec::new());
+
+/// Abort the search on the first read error (`--strict`).
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Process exit code used with `--strict` when a file cannot be read.
+pub const READ_ERROR_EXIT_CODE: i32 = 3;
+
+/**
+Choose what happens when a file or directory cannot be read.
+
+By default (lenient), the errors are tallied and reported as skipped files.
+
+With `--strict`, the search is aborted with a nonzero exit code.
+*/
+pub fn init_strict(arguments: &Arguments) {
+    STRICT.store(arguments.strict, Ordering::Relaxed);
+}
+
+/// Why a file or directory was skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum SkipReason {
+    /// No permission to read the file or directory
+    PermissionDenied,
+    /// Symbolic link to a file that does not exist
+    BrokenSymlink,
+    /// The file was removed during the search
+    NotFound,
+    /// Other I/O errors
+    Io,
+    /// The file appears to be actively written (`--skip_busy`)
+    Busy,
+}
+
+impl fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            SkipReason::PermissionDenied => "permission denied",
+            SkipReason::BrokenSymlink => "broken symbolic link",
+            SkipReason::NotFound => "not found",
+            SkipReason::Io => "I/O error",
+            SkipReason::Busy => "busy",
+        };
+        write!(f, "{reason}")
+    }
+}
+
+/// File or directory skipped during the search
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct SkippedFile {
+    /// File path
+    #[serde(rename = "Path")]
+    pub path: PathBuf,
+    /// Why the file was skipped
+    #[serde(rename = "Reason")]
+    pub reason: SkipReason,
+    /// Error message
+    #[serde(rename = "Error")]
+    pub error: String,
+}
+
+impl SkippedFile {
+    /// Classify the error of the file.
+    pub fn new(path: &Path, error: &io::Error) -> Self {
+        let reason = match error.kind() {
+            io::ErrorKind::PermissionDenied => SkipReason::PermissionDenied,
+            io::ErrorKind::NotFound if is_symlink(path) => SkipReason::BrokenSymlink,
+            io::ErrorKind::NotFound => SkipReason::NotFound,
+            _ => SkipReason::Io,
+        };
+
+        SkippedFile {
+            path: path.to_path_buf(),
+            reason,
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Check if the path is a symbolic link (without following it).
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_symlink())
+}
+
+/// Record a file or directory that could not be read.
+///
+/// With `--strict`, the search is aborted.
+pub fn add_skipped(path: &Path, error: &io::Error) {
+    let skipped = SkippedFile::new(path, error);
+
+    if STRICT.load(Ordering::Relaxed) {
+        eprintln!("fn add_skipped()");
+        eprintln!("Couldn't read {:?}: {}", skipped.path, skipped.reason);
+        eprintln!("Error: {}", skipped.error);
+        eprintln!("Search aborted (--strict).");
+        remove_temp_output();
+        process::exit(READ_ERROR_EXIT_CODE);
+    }
+
+    if let Ok(mut skipped_files) = SKIPPED_FILES.lock() {
+        skipped_files.push(skipped);
+    }
+}
+
+/// Record a file that appears to be actively written (`--skip_busy`).
+///
+/// Busy files are not read errors: the search is not aborted with `--strict`.
+pub fn add_busy(path: &Path) {
+    let skipped = SkippedFile {
+        path: path.to_path_buf(),
+        reason: SkipReason::Busy,
+        error: "open for writing, locked or recently modified".to_string(),
+    };
+
+    if let Ok(mut skipped_files) = SKIPPED_FILES.lock() {
+        skipped_files.push(skipped);
+    }
+}
+
+/// Record an error while reading or hashing the file.
+pub fn add_error(path: &Path, error: &MyError) {
+    match error.downcast_ref::<io::Error>() {
+        Some(error) => add_skipped(path, error),
+        None => add_skipped(path, &io::Error::other(error.to_string())),
+    }
+}
+
+/// Record an error of the walker (jwalk or walkdir).
+pub fn add_walk_error(path: Option<&Path>, error: Option<&io::Error>, message: &dyn fmt::Display) {
+    let path: &Path = path.unwrap_or(Path::new(""));
+
+    match error {
+        Some(error) => add_skipped(path, error),
+        None => add_skipped(path, &io::Error::other(message.to_string())),
+    }
+}
+
+/// Forget the files skipped by a previous search.
+pub(crate) fn reset_skipped() {
+    if let Ok(mut skipped_files) = SKIPPED_FILES.lock() {
+        skipped_files.clear();
+    }
+}
+
+/// Get the files and directories that could not be read, sorted by path.
+pub fn get_skipped_files() -> Vec<SkippedFile> {
+    let mut skipped_files: Vec<SkippedFile> = SKIPPED_FILES
+        .lock()
+        .map(|skipped_files| skipped_files.clone())
+        .unwrap_or_default();
+
+    skipped_files.sort();
+    skipped_files.dedup();
+    skipped_files
+}
+
+#[cfg(test)]
+mod test_skipped {
+    use super::*;
+
+    /// cargo test -- --show-output test_skipped_file
+    #[test]
+    fn test_skipped_file() {
+        let path = Path::new("/nonexistent/file.txt");
+
+        let skipped = SkippedFile::new(path, &io::Error::from(io::ErrorKind::NotFound));
+        assert_eq!(skipped.reason, SkipReason::NotFound);
+
+        let skipped = SkippedFile::new(path, &io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(skipped.reason, SkipReason::PermissionDenied);
+        assert_eq!(skipped.reason.to_string(), "permission denied");
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{
+    file_list::get_listed_files, get_files, open_file, sftp::SftpSource, Arguments, FileFilter,
+    FileInfo, HashPipeline, MyResult, PathBufExtension,
+};
+use ahash::AHasher;
+use std::{
+    hash::Hasher,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+};
+
+/// Remote sources of virtual files, created by the first search.
+static SOURCES: OnceLock<Vec<Box<dyn FileSource>>> = OnceLock::new();
+
+/// Serializes the creation of the remote sources.
+static INIT: Mutex<()> = Mutex::new(());
+
+/**
+Source of the files searched for duplicates.
+
+Local sources are the walked directories ([`DirSource`]) and the lists
+of files (`--files_from`, [`ListSource`]). Remote sources are the objects
+of a bucket (`--s3`) and the files of a remote server (`--remote`).
+
+The files of a remote source are virtual files: their paths start with
+the URL of the source (`s3://bucket/key`, `sftp://user@host/path`).
+They are grouped and reported like regular files, but are never
+changed by the actions.
+
+All the sources feed the same pipeline: grouping by size, hashing
+and reporting. Adding a remote source only requires a new FileSource
+implementation and a line in [`get_remote_sources`].
+*/
+pub trait FileSource: Send + Sync {
+    /// Name of the source (path or URL), used in messages.
+    fn name(&self) -> &str;
+
+    /// Check if the path is a file of this source.
+    fn contains(&self, path: &Path) -> bool;
+
+    /// List the files of the source, with the filters of regular files
+    /// (`--include`, `--exclude`, `--min_size`, ...).
+    ///
+    /// With `--pipeline`, local files are pushed to the hash pipeline as they
+    /// are found. Virtual files are never pushed: only their source can read them.
+    fn list_files(
+        &self,
+        arguments: &Arguments,
+        filter: &FileFilter,
+        pipeline: Option<&HashPipeline>,
+    ) -> MyResult<Vec<FileInfo>>;
+
+    /// Open a file for reading (streaming).
+    fn open(&self, path: &Path) -> MyResult<Box<dyn Read + Send>>;
+
+    /// Hash the first few bytes (procedure 2) or the entire content (procedure 3).
+    fn get_hash(&self, path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String>;
+}
+
+/// Directory walked with jwalk (default) or walkdir.
+pub struct DirSource {
+    root: PathBuf,
+    name: String,
+}
+
+impl DirSource {
+    pub fn new(root: &Path) -> Self {
+        DirSource {
+            root: root.to_path_buf(),
+            name: root.display().to_string(),
+        }
+    }
+}
+
+impl FileSource for DirSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        path.starts_with(&self.root)
+    }
+
+    fn list_files(
+        &self,
+        arguments: &Arguments,
+        filter: &FileFilter,
+        pipeline: Option<&HashPipeline>,
+    ) -> MyResult<Vec<FileInfo>> {
+        get_files(&self.root, arguments, filter, pipeline)
+    }
+
+    fn open(&self, path: &Path) -> MyResult<Box<dyn Read + Send>> {
+        Ok(Box::new(open_file(&path)?))
+    }
+
+    fn get_hash(&self, path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+        get_local_hash(path, arguments, procedure)
+    }
+}
+
+/// List of files read instead of walking (`--files_from`).
+pub struct ListSource {
+    list: PathBuf,
+    name: String,
+    /// Paths of the listed files, known after [`FileSource::list_files`].
+    paths: OnceLock<Vec<PathBuf>>,
+}
+
+impl ListSource {
+    pub fn new(list: &Path) -> Self {
+        ListSource {
+            list: list.to_path_buf(),
+            name: list.display().to_string(),
+            paths: OnceLock::new(),
+        }
+    }
+}
+
+impl FileSource for ListSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains(&self, path: &Path) -> bool {
+        self.paths
+            .get()
+            .is_some_and(|paths| paths.iter().any(|listed| listed == path))
+    }
+
+    fn list_files(
+        &self,
+        arguments: &Arguments,
+        filter: &FileFilter,
+        pipeline: Option<&HashPipeline>,
+    ) -> MyResult<Vec<FileInfo>> {
+        let files: Vec<FileInfo> = get_listed_files(&self.list, arguments, filter, pipeline)?;
+        let _ = self.paths.set(
+            files
+                .iter()
+                .map(|file_info| file_info.path.clone())
+                .collect(),
+        );
+        Ok(files)
+    }
+
+    fn open(&self, path: &Path) -> MyResult<Box<dyn Read + Send>> {
+        Ok(Box::new(open_file(&path)?))
+    }
+
+    fn get_hash(&self, path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+        get_local_hash(path, arguments, procedure)
+    }
+}
+
+/// Hash a local file (or a member of a local archive).
+fn get_local_hash(path: &Path, arguments: &Arguments, procedure: u8) -> MyResult<String> {
+    path.to_path_buf()
+        .get_hash(arguments, procedure)?
+        .ok_or_else(|| format!("{path:?} has no digest").into())
+}
+
+/**
+Get the local sources of the arguments: the list of files (`--files_from`)
+or the input directories.
+
+With `--compare`, the reference directory is not included.
+*/
+pub fn get_local_sources(arguments: &Arguments, paths: &[PathBuf]) -> Vec<Box<dyn FileSource>> {
+    match &arguments.files_from {
+        Some(list) => vec![Box::new(ListSource::new(list))],
+        None => paths
+            .iter()
+            .map(|path| Box::new(DirSource::new(path)) as Box<dyn FileSource>)
+            .collect(),
+    }
+}
+
+/// Create the remote sources of the arguments, once.
+pub fn get_remote_sources(arguments: &Arguments) -> MyResult<&'static [Box<dyn FileSource>]> {
+    let _guard = INIT.lock().map_err(|error| error.to_string())?;
+
+    if let Some(sources) = SOURCES.get() {
+        return Ok(sources);
+    }
+
+    let mut sources: Vec<Box<dyn FileSource>> = Vec::new();
+
+    #[cfg(feature = "s3")]
+    if let Some(source) = &arguments.s3 {
+        sources.push(Box::new(crate::s3::S3Source::new(source)?));
+    }
+
+    if let Some(url) = &arguments.remote {
+        sources.push(Box::new(SftpSource::new(url)?));
+    }
+
+    Ok(SOURCES.get_or_init(|| sources))
+}
+
+/// Check if the arguments have remote sources.
+pub fn has_remote_sources(arguments: &Arguments) -> bool {
+    #[cfg(feature = "s3")]
+    if arguments.s3.is_some() {
+        return true;
+    }
+
+    arguments.remote.is_some()
+}
+
+/// Get the remote source of a virtual file (None for local files).
+pub fn find_source(path: &Path) -> Option<&'static dyn FileSource> {
+    SOURCES
+        .get()?
+        .iter()
+        .find(|source| source.contains(path))
+        .map(|source| source.as_ref())
+}
+
+/**
+Hash the first few bytes of a file of `size` bytes and, with
+`--prefilter_tail`, the last bytes, reading only these ranges.
+
+`read_range(start, end)` reads the bytes from start to end (inclusive).
+The digests are the same as the digests of local files with the same content.
+*/
+pub fn get_prefilter_hash(
+    size: u64,
+    arguments: &Arguments,
+    read_range: impl Fn(u64, u64) -> MyResult<Vec<u8>>,
+) -> MyResult<String> {
+    let prefilter_bytes: u64 = arguments.prefilter_bytes as u64;
+    let mut hasher = AHasher::default();
+
+    let count: u64 = prefilter_bytes.min(size);
+    if count > 0 {
+        hasher.write(&read_range(0, count - 1)?);
+    }
+
+    // Also apply the hash algorithm to the last bytes of the file.
+    if arguments.prefilter_tail {
+        let start: u64 = size.saturating_sub(prefilter_bytes).max(count);
+        if start < size {
+            hasher.write(&read_range(start, size - 1)?);
+        }
+    }
+
+    Ok(hasher.finish().to_string())
+}
+
+#[cfg(test)]
+mod test_source {
+    use super::*;
+    use clap::Parser;
+    use std::fs;
+
+    /// cargo test -- --show-output test_dir_source
+    #[test]
+    fn test_dir_source() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("sub"))?;
+        fs::write(root.join("a.txt"), "same")?;
+        fs::write(root.join("sub/b.txt"), "same")?;
+
+        let arguments = Arguments::parse_from(["find-identical-files"]);
+        let filter = FileFilter::new(&arguments)?;
+        let source = DirSource::new(root);
+
+        let mut paths: Vec<PathBuf> = source
+            .list_files(&arguments, &filter, None)?
+            .into_iter()
+            .map(|file_info| file_info.path)
+            .collect();
+        paths.sort();
+
+        assert_eq!(paths, vec![root.join("a.txt"), root.join("sub/b.txt")]);
+        assert!(source.contains(&paths[1]));
+
+        let mut content = String::new();
+        source.open(&paths[1])?.read_to_string(&mut content)?;
+        assert_eq!(content, "same");
+
+        Ok(())
+    }
+}
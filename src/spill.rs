@@ -0,0 +1,302 @@
+use crate::{Arguments, FileExtension, FileInfo, GroupInfo, MyResult};
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    fs::File,
+    io::{BufReader, BufWriter, ErrorKind, Read, Write},
+    mem,
+    path::PathBuf,
+};
+use tempfile::TempPath;
+
+/// Record of a sorted run: (file size, index in the list of files).
+type Record = (u64, u64);
+
+/// Size of a record in memory and on disk (in bytes).
+const RECORD_SIZE: usize = mem::size_of::<Record>();
+
+/// Buffer size used to read each sorted run.
+const RUN_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Maximum number of sorted runs merged at the same time (one open file each).
+const MAX_FAN_IN: usize = 64;
+
+/// Temporary file of a sorted run, removed when dropped.
+///
+/// The file is created with a random name and exclusive access
+/// (`O_EXCL`): an existing file or link is never opened.
+/// Only its path is kept: the file is opened again to be merged.
+struct SortedRun {
+    path: TempPath,
+}
+
+impl SortedRun {
+    /// Write the records, already sorted, to a temporary file.
+    fn create(records: impl IntoIterator<Item = MyResult<Record>>) -> MyResult<Self> {
+        let file = tempfile::Builder::new().prefix("fif-run-").tempfile()?;
+        let (file, path) = file.into_parts();
+
+        let mut writer = BufWriter::new(file);
+        for record in records {
+            let (size, index) = record?;
+            writer.write_all(&size.to_le_bytes())?;
+            writer.write_all(&index.to_le_bytes())?;
+        }
+        writer.flush()?;
+
+        Ok(SortedRun { path })
+    }
+
+    /// Sort the records and write them to a temporary file.
+    fn write(records: &mut [Record]) -> MyResult<Self> {
+        records.sort_unstable();
+        Self::create(records.iter().copied().map(Ok))
+    }
+
+    /// Merge the runs into one sorted run.
+    fn merge(runs: &[SortedRun]) -> MyResult<Self> {
+        Self::create(MergedRuns::new(runs)?)
+    }
+
+    /// Open the run to read the records in order.
+    fn reader(&self) -> MyResult<BufReader<File>> {
+        Ok(BufReader::with_capacity(
+            RUN_BUFFER_SIZE,
+            File::open(&self.path)?,
+        ))
+    }
+}
+
+/// Read the next record of a run (None at the end of the run).
+fn read_record(reader: &mut impl Read) -> MyResult<Option<Record>> {
+    let mut bytes = [0_u8; RECORD_SIZE];
+
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => {
+            let (size, index) = bytes.split_at(RECORD_SIZE / 2);
+            Ok(Some((
+                u64::from_le_bytes(size.try_into()?),
+                u64::from_le_bytes(index.try_into()?),
+            )))
+        }
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// K-way merge: the records of the runs in order.
+struct MergedRuns {
+    readers: Vec<BufReader<File>>,
+    /// The smallest record of each run
+    heap: BinaryHeap<Reverse<(Record, usize)>>,
+}
+
+impl MergedRuns {
+    /// Open the runs: one file for each run.
+    fn new(runs: &[SortedRun]) -> MyResult<Self> {
+        let mut readers: Vec<BufReader<File>> = runs
+            .iter()
+            .map(SortedRun::reader)
+            .collect::<MyResult<_>>()?;
+        let mut heap = BinaryHeap::new();
+
+        for (run, reader) in readers.iter_mut().enumerate() {
+            if let Some(record) = read_record(reader)? {
+                heap.push(Reverse((record, run)));
+            }
+        }
+
+        Ok(MergedRuns { readers, heap })
+    }
+
+    /// The smallest record of the runs (None when all runs are read).
+    fn next_record(&mut self) -> MyResult<Option<Record>> {
+        let Some(Reverse((record, run))) = self.heap.pop() else {
+            return Ok(None);
+        };
+
+        if let Some(next) = read_record(&mut self.readers[run])? {
+            self.heap.push(Reverse((next, run)));
+        }
+
+        Ok(Some(record))
+    }
+}
+
+impl Iterator for MergedRuns {
+    type Item = MyResult<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_record().transpose()
+    }
+}
+
+/**
+Merge the runs by groups of `fan_in` until at most `fan_in` runs are left,
+so that no more than `fan_in` files are open at the same time.
+*/
+fn reduce_runs(mut runs: Vec<SortedRun>, fan_in: usize) -> MyResult<Vec<SortedRun>> {
+    let fan_in: usize = fan_in.max(2);
+
+    while runs.len() > fan_in {
+        runs = runs
+            .chunks(fan_in)
+            .map(SortedRun::merge)
+            .collect::<MyResult<_>>()?;
+    }
+
+    Ok(runs)
+}
+
+/// Number of runs merged at the same time, within `--max_open_files`.
+fn get_fan_in(arguments: &Arguments) -> usize {
+    match arguments.max_open_files {
+        // One more file is open to write the merged run.
+        Some(max_open_files) => max_open_files.saturating_sub(1).clamp(2, MAX_FAN_IN),
+        None => MAX_FAN_IN,
+    }
+}
+
+/**
+Group files by size with a bounded size index (`--max_group_memory`).
+
+Procedure 1 keeps a map of all paths by size. With a budget, the
+(size, index) records are written to temporary sorted runs of at most
+`max_group_memory` bytes each, then merged: only the files of one size
+are kept in memory at a time, plus the groups with two or more files.
+
+The runs are merged by groups of at most 64 files (or `--max_open_files`).
+
+The list of files found by the walk is not spilled: this budget does not
+limit the total memory used by the search.
+*/
+pub fn get_grouped_files(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    max_group_memory: u64,
+) -> Vec<GroupInfo> {
+    // The map of procedure 1 fits in the budget: group in memory.
+    let estimated: usize = all_files
+        .iter()
+        .map(|file_info| RECORD_SIZE + mem::size_of::<PathBuf>() + file_info.path.as_os_str().len())
+        .sum();
+
+    if estimated as u64 <= max_group_memory {
+        return all_files.get_grouped_files(arguments, 1);
+    }
+
+    match get_spilled_groups(all_files, arguments, max_group_memory) {
+        Ok(groups) => groups,
+        Err(error) => {
+            eprintln!("fn get_grouped_files()");
+            eprintln!("Failed to group files on disk: {:?}", std::env::temp_dir());
+            eprintln!("Error: {error}");
+            all_files.get_grouped_files(arguments, 1)
+        }
+    }
+}
+
+/// Write the sorted runs and merge them, one size at a time.
+fn get_spilled_groups(
+    all_files: &[FileInfo],
+    arguments: &Arguments,
+    max_group_memory: u64,
+) -> MyResult<Vec<GroupInfo>> {
+    let run_length: usize = (max_group_memory as usize / RECORD_SIZE).max(1);
+
+    let mut runs: Vec<SortedRun> = Vec::new();
+    let mut records: Vec<Record> = Vec::with_capacity(run_length.min(all_files.len()));
+
+    for (index, file_info) in all_files.iter().enumerate() {
+        records.push((file_info.key.size as u64, index as u64));
+
+        if records.len() == run_length {
+            runs.push(SortedRun::write(&mut records)?);
+            records.clear();
+        }
+    }
+
+    if !records.is_empty() {
+        runs.push(SortedRun::write(&mut records)?);
+    }
+    drop(records);
+
+    if arguments.verbose {
+        eprintln!("Files grouped by size on disk: {} sorted runs", runs.len());
+    }
+
+    let runs: Vec<SortedRun> = reduce_runs(runs, get_fan_in(arguments))?;
+
+    let mut groups: Vec<GroupInfo> = Vec::new();
+    let mut same_size: Vec<FileInfo> = Vec::new();
+
+    for record in MergedRuns::new(&runs)? {
+        let (size, index) = record?;
+
+        if same_size
+            .first()
+            .is_some_and(|file_info| file_info.key.size as u64 != size)
+        {
+            add_group(&mut groups, &mut same_size, arguments);
+        }
+
+        same_size.push(all_files[index as usize].clone());
+    }
+    add_group(&mut groups, &mut same_size, arguments);
+
+    Ok(groups)
+}
+
+/// Add the files of the same size as a group, with the filters of procedure 1.
+fn add_group(groups: &mut Vec<GroupInfo>, same_size: &mut Vec<FileInfo>, arguments: &Arguments) {
+    if same_size.len() > 1 {
+        groups.extend(same_size.get_grouped_files(arguments, 1));
+    }
+    same_size.clear();
+}
+
+#[cfg(test)]
+mod test_spill {
+    use super::*;
+
+    /// cargo test -- --show-output test_sorted_run
+    #[test]
+    fn test_sorted_run() -> MyResult<()> {
+        let mut records: Vec<Record> = vec![(30, 0), (10, 1), (20, 2), (10, 3)];
+        let run = SortedRun::write(&mut records)?;
+        let path = run.path.to_path_buf();
+
+        let mut reader = run.reader()?;
+        let mut merged: Vec<Record> = Vec::new();
+        while let Some(record) = read_record(&mut reader)? {
+            merged.push(record);
+        }
+        drop(reader);
+
+        assert_eq!(merged, vec![(10, 1), (10, 3), (20, 2), (30, 0)]);
+
+        // The temporary file is removed with the run.
+        drop(run);
+        assert!(!path.exists());
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_reduce_runs
+    #[test]
+    fn test_reduce_runs() -> MyResult<()> {
+        // 7 runs merged 2 at a time: 7 -> 4 -> 2 runs.
+        let runs: Vec<SortedRun> = (0..7_u64)
+            .map(|run| SortedRun::write(&mut [(7 - run, run), (run, run)]))
+            .collect::<MyResult<_>>()?;
+
+        let runs = reduce_runs(runs, 2)?;
+        assert_eq!(runs.len(), 2);
+
+        let merged: Vec<Record> = MergedRuns::new(&runs)?.collect::<MyResult<_>>()?;
+        assert_eq!(merged.len(), 14);
+        assert!(merged.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use crate::{get_group_id, Arguments, FileInfo, GroupInfo, MyResult};
+use rusqlite::{params, Connection, Transaction};
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/**
+Tables of the database.
+
+- `scans`: one row per scan (time, options and totals);
+- `files`: path, size, modification time and digest of each file found;
+- `duplicate_groups`: groups of identical files of each scan, with their
+  stable identifier (`group_key`, the same in all the scans);
+- `group_files`: files of each group.
+
+Files without digest (ex.: files of unique size) have a NULL digest.
+*/
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS scans (
+    id INTEGER PRIMARY KEY,
+    time INTEGER NOT NULL,
+    options TEXT NOT NULL,
+    total_files INTEGER NOT NULL,
+    total_groups INTEGER NOT NULL,
+    total_wasted INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS files (
+    id INTEGER PRIMARY KEY,
+    path TEXT NOT NULL UNIQUE,
+    size INTEGER NOT NULL,
+    modified INTEGER,
+    digest TEXT,
+    first_scan INTEGER NOT NULL REFERENCES scans(id),
+    last_scan INTEGER NOT NULL REFERENCES scans(id)
+);
+CREATE TABLE IF NOT EXISTS duplicate_groups (
+    id INTEGER PRIMARY KEY,
+    scan INTEGER NOT NULL REFERENCES scans(id),
+    size INTEGER NOT NULL,
+    digest TEXT NOT NULL,
+    num_files INTEGER NOT NULL,
+    wasted_size INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS group_files (
+    group_id INTEGER NOT NULL REFERENCES duplicate_groups(id),
+    file_id INTEGER NOT NULL REFERENCES files(id),
+    PRIMARY KEY (group_id, file_id)
+);
+CREATE INDEX IF NOT EXISTS files_digest ON files(digest);
+CREATE INDEX IF NOT EXISTS duplicate_groups_scan ON duplicate_groups(scan);
+";
+
+/// Remove the results of the previous scans (without `--sqlite_upsert`).
+const CLEAR: &str = "
+DELETE FROM group_files;
+DELETE FROM duplicate_groups;
+DELETE FROM files;
+DELETE FROM scans;
+";
+
+/**
+Write the files, digests and groups of identical files to a SQLite database
+(`--export_sqlite`).
+
+By default, the results of the previous scans are replaced.
+
+With `--sqlite_upsert`, each scan is added to the database: the files are
+inserted or updated (`first_scan` and `last_scan` track their history)
+and the groups of the previous scans are kept.
+
+Ex.: the identical files of the last scan:
+
+```sql
+SELECT g.digest, f.path FROM duplicate_groups g
+JOIN group_files m ON m.group_id = g.id
+JOIN files f ON f.id = m.file_id
+WHERE g.scan = (SELECT MAX(id) FROM scans);
+```
+
+Ex.: the history of a group across the scans:
+
+```sql
+SELECT scan, num_files, wasted_size FROM duplicate_groups
+WHERE group_key = '0123456789abcdef' ORDER BY scan;
+```
+*/
+pub fn export_to_sqlite(
+    all_files: &[FileInfo],
+    identical_hash: &[GroupInfo],
+    arguments: &Arguments,
+    path: &Path,
+) -> MyResult<()> {
+    let mut connection = Connection::open(path).inspect_err(|error| {
+        eprintln!("fn export_to_sqlite()");
+        eprintln!("Couldn't open {path:?}");
+        eprintln!("Error: {error}");
+    })?;
+
+    connection.execute_batch(SCHEMA)?;
+    add_group_key(&connection)?;
+
+    let transaction = connection.transaction()?;
+
+    if !arguments.sqlite_upsert {
+        transaction.execute_batch(CLEAR)?;
+    }
+
+    let scan: i64 = insert_scan(&transaction, all_files, identical_hash, arguments)?;
+
+    // Digest of each file in the groups of identical files.
+    // Self-describing digests: `blake3:<hex>`.
+    let group_digests: Vec<String> = identical_hash
+        .iter()
+        .map(|group_info| group_info.get_digest().unwrap_or_default())
+        .collect();
+    let digests: BTreeMap<&Path, &str> = identical_hash
+        .iter()
+        .zip(&group_digests)
+        .flat_map(|(group_info, digest)| {
+            group_info
+                .paths
+                .iter()
+                .map(move |path| (path.as_path(), digest.as_str()))
+        })
+        .collect();
+
+    {
+        let mut upsert = transaction.prepare(
+            "INSERT INTO files (path, size, modified, digest, first_scan, last_scan)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+             ON CONFLICT(path) DO UPDATE SET
+                size = excluded.size,
+                modified = excluded.modified,
+                digest = CASE
+                    WHEN excluded.digest IS NOT NULL THEN excluded.digest
+                    -- The previous digest is kept only if the file was not modified.
+                    WHEN files.size = excluded.size AND files.modified IS excluded.modified
+                    THEN files.digest
+                END,
+                last_scan = excluded.last_scan",
+        )?;
+
+        for file_info in all_files {
+            let digest: Option<&str> = digests.get(file_info.path.as_path()).copied();
+            upsert.execute(params![
+                file_info.path.to_string_lossy(),
+                file_info.key.size as i64,
+                get_secs(file_info.modified),
+                digest,
+                scan,
+            ])?;
+        }
+
+        let mut insert_group = transaction.prepare(
+            "INSERT INTO duplicate_groups (scan, group_key, size, digest, num_files, wasted_size)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        let mut insert_member = transaction.prepare(
+            "INSERT OR IGNORE INTO group_files (group_id, file_id)
+             SELECT ?1, id FROM files WHERE path = ?2",
+        )?;
+
+        for (group_info, digest) in identical_hash.iter().zip(&group_digests) {
+            insert_group.execute(params![
+                scan,
+                get_group_id(digest, group_info.key.size),
+                group_info.key.size as i64,
+                digest,
+                group_info.num_file as i64,
+                group_info.wasted_size as i64,
+            ])?;
+            let group_id: i64 = transaction.last_insert_rowid();
+
+            for path in &group_info.paths {
+                insert_member.execute(params![group_id, path.to_string_lossy()])?;
+            }
+        }
+    }
+
+    transaction.commit()?;
+
+    Ok(())
+}
+
+/// Add the column `group_key` to the databases created by older versions.
+fn add_group_key(connection: &Connection) -> MyResult<()> {
+    let exists: bool = connection
+        .prepare("SELECT 1 FROM pragma_table_info('duplicate_groups') WHERE name = 'group_key'")?
+        .exists([])?;
+
+    if !exists {
+        connection.execute_batch("ALTER TABLE duplicate_groups ADD COLUMN group_key TEXT;")?;
+    }
+    connection.execute_batch(
+        "CREATE INDEX IF NOT EXISTS duplicate_groups_key ON duplicate_groups(group_key);",
+    )?;
+
+    Ok(())
+}
+
+/// Insert the scan (time, options and totals) and get its id.
+fn insert_scan(
+    transaction: &Transaction,
+    all_files: &[FileInfo],
+    identical_hash: &[GroupInfo],
+    arguments: &Arguments,
+) -> MyResult<i64> {
+    let options: BTreeMap<&str, String> = arguments.get_scan_options().into_iter().collect();
+    let total_wasted: usize = identical_hash
+        .iter()
+        .map(|group_info| group_info.wasted_size)
+        .sum();
+
+    transaction.execute(
+        "INSERT INTO scans (time, options, total_files, total_groups, total_wasted)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            get_secs(Some(SystemTime::now())),
+            serde_json::to_string(&options)?,
+            all_files.len() as i64,
+            identical_hash.len() as i64,
+            total_wasted as i64,
+        ],
+    )?;
+
+    Ok(transaction.last_insert_rowid())
+}
+
+/// Get the time in seconds since the Unix epoch.
+fn get_secs(time: Option<SystemTime>) -> Option<i64> {
+    time.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod test_sqlite {
+    use super::*;
+    use crate::Key;
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    /// cargo test --features sqlite -- --show-output test_export_to_sqlite
+    #[test]
+    fn test_export_to_sqlite() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let db = temp_dir.path().join("sqlite.db");
+
+        let file = |path: &str| FileInfo {
+            key: Key::new(10, None),
+            path: PathBuf::from(path),
+            inode: None,
+            modified: None,
+            allocated: None,
+        };
+        let all_files = vec![file("a.txt"), file("b.txt"), file("c.txt")];
+        let groups = vec![GroupInfo {
+            key: Key::new(10, Some("abc".to_string())),
+            algorithm: None,
+            paths: vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")],
+            num_file: 2,
+            sum_size: 20,
+            wasted_size: 10,
+        }];
+
+        let db_path: &str = &db.to_string_lossy();
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--export_sqlite",
+            db_path,
+            "--sqlite_upsert",
+        ]);
+        export_to_sqlite(&all_files, &groups, &arguments, &db)?;
+        export_to_sqlite(&all_files, &groups, &arguments, &db)?;
+
+        let connection = Connection::open(&db)?;
+        let count =
+            |sql: &str| -> MyResult<i64> { Ok(connection.query_row(sql, [], |row| row.get(0))?) };
+        assert_eq!(count("SELECT COUNT(*) FROM scans")?, 2);
+        assert_eq!(count("SELECT COUNT(*) FROM files")?, 3);
+        assert_eq!(count("SELECT COUNT(*) FROM duplicate_groups")?, 2);
+        assert_eq!(
+            count("SELECT COUNT(DISTINCT group_key) FROM duplicate_groups")?,
+            1
+        );
+        assert_eq!(
+            count("SELECT COUNT(*) FROM files WHERE digest = 'abc' AND last_scan = 2")?,
+            2
+        );
+        drop(connection);
+
+        // Without upsert, only the last scan is kept.
+        let arguments = Arguments::parse_from(["find-identical-files", "--export_sqlite", db_path]);
+        export_to_sqlite(&all_files, &groups, &arguments, &db)?;
+        let connection = Connection::open(&db)?;
+        let scans: i64 =
+            connection.query_row("SELECT COUNT(*) FROM scans", [], |row| row.get(0))?;
+        assert_eq!(scans, 1);
+        drop(connection);
+
+        Ok(())
+    }
+}
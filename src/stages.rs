@@ -0,0 +1,97 @@
+use crate::{human_readable_size, split_and_insert, tr, SEPARATOR};
+use std::{
+    io::Write,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Stages of the search, in the order they finished.
+static STAGES: Mutex<Vec<StageInfo>> = Mutex::new(Vec::new());
+
+/// Duration of a stage of the search and the files it handled (`--time`).
+#[derive(Debug, Clone)]
+pub struct StageInfo {
+    /// Stage name (ex.: walk, full hash)
+    pub name: &'static str,
+    /// Time spent in the stage
+    pub duration: Duration,
+    /// Number of files handled
+    pub files: usize,
+    /// Total size of the files handled (in bytes)
+    pub bytes: u64,
+}
+
+/**
+Stage of the search being measured.
+
+The stage is also a span of the log (`--log_level`): it closes with the stage.
+*/
+pub struct Stage {
+    name: &'static str,
+    start: Instant,
+    _span: tracing::span::EnteredSpan,
+}
+
+impl Stage {
+    /// Start measuring a stage.
+    pub fn start(name: &'static str) -> Self {
+        Stage {
+            name,
+            start: Instant::now(),
+            _span: tracing::info_span!("stage", name).entered(),
+        }
+    }
+
+    /// Stop measuring the stage, with the number and the total size of the files handled.
+    pub fn finish(self, files: usize, bytes: u64) {
+        let stage_info = StageInfo {
+            name: self.name,
+            duration: self.start.elapsed(),
+            files,
+            bytes,
+        };
+
+        tracing::info!(
+            files,
+            bytes,
+            duration = ?stage_info.duration,
+            "{} finished",
+            self.name
+        );
+
+        if let Ok(mut stages) = STAGES.lock() {
+            stages.push(stage_info);
+        }
+    }
+}
+
+/// Get the stages measured so far.
+pub fn get_stages() -> Vec<StageInfo> {
+    STAGES
+        .lock()
+        .map(|stages| stages.clone())
+        .unwrap_or_default()
+}
+
+/// Write the time spent in each stage and the files it handled (`--time`).
+pub fn write_stages(write: &mut dyn Write) -> std::io::Result<()> {
+    let stages: Vec<StageInfo> = get_stages();
+    if stages.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(write, "{}", tr("Time per stage:"))?;
+    for stage in stages {
+        writeln!(
+            write,
+            "  {:<16} {:>14} {} {:>12} {:>12.3?}",
+            stage.name,
+            split_and_insert(stage.files, SEPARATOR),
+            tr("files"),
+            human_readable_size(stage.bytes as usize),
+            stage.duration
+        )?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::{
+    add_thousands_separator,
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    split_and_insert, tr, MyResult, SEPARATOR,
+};
+use serde::Serialize;
+use std::{io, path::PathBuf};
+
+/// Summarize the actions applied to identical files
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ActionInfo {
+    /// Number of files removed or replaced
+    #[serde(rename = "Number of processed files")]
+    pub num_files: usize,
+    /// Number of files that could not be processed
+    #[serde(rename = "Number of failed actions")]
+    pub num_failed: usize,
+    /// Files changed since the search, skipped (`--verify_before_action`)
+    #[serde(
+        rename = "Changed files (skipped)",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub changed: Vec<PathBuf>,
+    /// Total size of removed or replaced files
+    #[serde(
+        rename = "Total size of reclaimed space",
+        serialize_with = "add_thousands_separator"
+    )]
+    pub reclaimed_size: usize,
+    /// Actions were only simulated (`--dry_run`)
+    #[serde(rename = "Dry run")]
+    pub dry_run: bool,
+}
+
+impl ActionInfo {
+    /// Print the actions information.
+    pub fn print_summary(&self, arguments: &Arguments) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize ActionInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(&self)?;
+                println!("{serialized}\n");
+            }
+            Yaml => {
+                // Serialize ActionInfo to a YAML string.
+                let serialized = serde_yaml::to_string(&self)?;
+                println!("{serialized}");
+            }
+            Cbor | MsgPack => {
+                write_binary(self, &arguments.result_format, &mut io::stdout().lock())?
+            }
+            Personal | Html | Markdown | Xml => {
+                if self.dry_run {
+                    println!("{}", tr("Dry run: no file was changed."));
+                }
+                println!("{} {}", tr("Number of processed files:"), self.num_files);
+                println!("{} {}", tr("Number of failed actions:"), self.num_failed);
+                if !self.changed.is_empty() {
+                    println!("{} {}", tr("Changed files (skipped):"), self.changed.len());
+                    for path in &self.changed {
+                        println!("{path:?}");
+                    }
+                }
+                println!(
+                    "{} {} bytes\n",
+                    tr("Total size of reclaimed space:"),
+                    split_and_insert(self.reclaimed_size, SEPARATOR)
+                );
+            }
+        }
+        Ok(())
+    }
+}
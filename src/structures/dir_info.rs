@@ -0,0 +1,141 @@
+use crate::{add_thousands_separator, get_paths, Arguments, GroupInfo, KeepRules};
+use hashbrown::HashMap;
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+/// Wasted size of the identical files under a directory (`--stats_by_dir`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DirInfo {
+    /// Directory at the chosen depth
+    #[serde(rename = "Directory")]
+    pub directory: PathBuf,
+    /// Number of identical files that would be removed
+    #[serde(rename = "Number of identical files")]
+    pub num_file: usize,
+    /// Space that would be reclaimed under the directory
+    #[serde(rename = "Wasted size", serialize_with = "add_thousands_separator")]
+    pub wasted_size: usize,
+    /// Percentage of the total reclaimable space
+    #[serde(rename = "Percentage")]
+    pub percentage: f64,
+}
+
+/**
+Aggregate the wasted size of the identical files by directory.
+
+In each group, the file kept by `--keep`, `--prefer` and `--avoid` is not wasted:
+the wasted size of the group is shared by the other files.
+
+Directories are taken at `depth` levels below the input directory
+(depth 1: the top-level directories).
+
+Sorted by wasted size in descending order.
+*/
+pub fn get_dir_stats(groups: &[GroupInfo], arguments: &Arguments, depth: usize) -> Vec<DirInfo> {
+    let roots: Vec<PathBuf> = get_paths(arguments).unwrap_or_default();
+    let keep_rules: Option<KeepRules> = KeepRules::new(arguments).ok();
+
+    let mut stats: HashMap<PathBuf, (usize, usize)> = HashMap::new();
+
+    for group_info in groups {
+        let num_wasted: usize = group_info.paths.len().saturating_sub(1);
+        if num_wasted == 0 {
+            continue;
+        }
+
+        let kept: usize = keep_rules
+            .as_ref()
+            .and_then(|rules| rules.select(&group_info.paths))
+            .unwrap_or(0);
+
+        for (index, path) in group_info.paths.iter().enumerate() {
+            if index == kept {
+                continue;
+            }
+            let directory: PathBuf = get_directory(path, &roots, depth);
+            let (num_file, wasted_size) = stats.entry(directory).or_default();
+            *num_file += 1;
+            *wasted_size += group_info.wasted_size / num_wasted;
+        }
+    }
+
+    let total_wasted: usize = stats.values().map(|(_, wasted_size)| wasted_size).sum();
+
+    let mut dir_stats: Vec<DirInfo> = stats
+        .into_iter()
+        .map(|(directory, (num_file, wasted_size))| DirInfo {
+            directory,
+            num_file,
+            wasted_size,
+            percentage: if total_wasted == 0 {
+                0.0
+            } else {
+                (10000.0 * wasted_size as f64 / total_wasted as f64).round() / 100.0
+            },
+        })
+        .collect();
+
+    dir_stats.sort_by(|a, b| {
+        b.wasted_size
+            .cmp(&a.wasted_size)
+            .then_with(|| a.directory.cmp(&b.directory))
+    });
+
+    dir_stats
+}
+
+/**
+Get the directory of the file at `depth` levels below its input directory.
+
+Files directly in the input directory (or shallower than `depth`)
+are attributed to their parent directory.
+*/
+fn get_directory(path: &Path, roots: &[PathBuf], depth: usize) -> PathBuf {
+    let parent: &Path = path.parent().unwrap_or(Path::new(""));
+
+    // The longest input directory containing the file.
+    let root: PathBuf = roots
+        .iter()
+        .filter(|root| parent.starts_with(root))
+        .max_by_key(|root| root.components().count())
+        .cloned()
+        .unwrap_or_else(|| {
+            // Ex.: files of imported indexes: the root of the filesystem.
+            parent
+                .components()
+                .take_while(|component| {
+                    matches!(component, Component::Prefix(_) | Component::RootDir)
+                })
+                .collect()
+        });
+
+    let relative: &Path = parent.strip_prefix(&root).unwrap_or(parent);
+    root.join(relative.components().take(depth).collect::<PathBuf>())
+}
+
+#[cfg(test)]
+mod test_dir_info {
+    use super::*;
+
+    /// cargo test -- --show-output test_get_directory
+    #[test]
+    fn test_get_directory() {
+        let roots = vec![PathBuf::from("/home/user")];
+
+        let path = Path::new("/home/user/Downloads/isos/debian.iso");
+        assert_eq!(
+            get_directory(path, &roots, 1),
+            PathBuf::from("/home/user/Downloads")
+        );
+        assert_eq!(
+            get_directory(path, &roots, 2),
+            PathBuf::from("/home/user/Downloads/isos")
+        );
+
+        let path = Path::new("/home/user/notes.txt");
+        assert_eq!(get_directory(path, &roots, 1), PathBuf::from("/home/user"));
+
+        let path = Path::new("/mnt/backup/notes.txt");
+        assert_eq!(get_directory(path, &roots, 1), PathBuf::from("/mnt"));
+    }
+}
@@ -1,7 +1,16 @@
 use crate::{Arguments, GroupInfo, Key};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use rayon::prelude::*;
-use std::path::PathBuf;
+use std::{
+    fs::Metadata,
+    path::{Path, PathBuf},
+    sync::{LazyLock, RwLock},
+    time::SystemTime,
+};
+
+/// Sizes allocated on disk of the files of the reported groups, by path (unix only).
+static ALLOCATED_SIZES: LazyLock<RwLock<HashMap<PathBuf, u64>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
 
 /// Individual file information
 #[derive(Debug, Clone)]
@@ -10,6 +19,63 @@ pub struct FileInfo {
     pub key: Key,
     /// File Path
     pub path: PathBuf,
+    /// Device and inode numbers, shared by hard links (unix only)
+    pub inode: Option<(u64, u64)>,
+    /// Last modification time
+    pub modified: Option<SystemTime>,
+    /// Size allocated on disk (in bytes), unix only
+    pub allocated: Option<u64>,
+}
+
+/// Get the (device, inode) of the file.
+#[cfg(unix)]
+pub fn get_inode(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Get the (device, inode) of the file.
+#[cfg(not(unix))]
+pub fn get_inode(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Get the size allocated on disk (in bytes), which is smaller than
+/// the apparent size for sparse or compressed files.
+#[cfg(unix)]
+pub fn get_allocated_size(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.blocks() * 512)
+}
+
+/// Get the size allocated on disk (in bytes).
+#[cfg(not(unix))]
+pub fn get_allocated_size(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Keep the sizes allocated on disk of the files of the groups, found by the walk:
+/// the wasted disk space is reported without reading the metadata again.
+pub fn insert_allocated_sizes(all_files: &[FileInfo], groups: &[GroupInfo]) {
+    let paths: HashSet<&Path> = groups
+        .iter()
+        .flat_map(|group_info| group_info.paths.iter().map(PathBuf::as_path))
+        .collect();
+
+    if let Ok(mut allocated_sizes) = ALLOCATED_SIZES.write() {
+        for file_info in all_files {
+            if let Some(allocated) = file_info.allocated {
+                if paths.contains(file_info.path.as_path()) {
+                    allocated_sizes.insert(file_info.path.clone(), allocated);
+                }
+            }
+        }
+    }
+}
+
+/// Get the size allocated on disk of a file of the groups, found by the walk.
+pub fn get_known_allocated_size(path: &Path) -> Option<u64> {
+    ALLOCATED_SIZES.read().ok()?.get(path).copied()
 }
 
 pub trait FileExtension {
@@ -67,25 +133,36 @@ impl FileExtension for [FileInfo] {
         // Converting group_by to vector
         let grouped_files: Vec<GroupInfo> = group_by
             .into_par_iter() // rayon parallel iterator
-            .filter(|(_key, paths)| {
+            .filter(|(key, paths)| {
                 // Filter identical files with same key
                 // procedure 1: filter only by size
                 // procedure 2: filter by size and by hash of the first bytes
                 // procedure 3: filter by size and by hash of the entire file
+                // with `--compare`: filter groups with reference and target files
+                // with `--min_wasted`: the wasted size can only decrease
+                let wasted_size = key.size * paths.len().saturating_sub(1);
                 if procedure <= 2 {
                     paths.len() >= min_frequency
+                        && arguments.wasted_is_included(wasted_size)
+                        && arguments.compare_is_included(paths)
                 } else {
-                    paths.len() >= min_frequency && paths.len() <= max_frequency
+                    paths.len() >= min_frequency
+                        && paths.len() <= max_frequency
+                        && arguments.wasted_is_included(wasted_size)
+                        && arguments.compare_is_included(paths)
                 }
             })
             .map(|(key, paths)| {
                 let num_file = paths.len();
                 let sum_size = key.size * num_file;
+                let wasted_size = key.size * (num_file - 1);
                 GroupInfo {
                     key,
+                    algorithm: (procedure == 3).then_some(arguments.algorithm),
                     paths,
                     num_file,
                     sum_size,
+                    wasted_size,
                 }
             })
             .collect();
@@ -1,25 +1,61 @@
+use crate::binary::write_binary;
+use crate::busy::is_busy;
+use crate::enumerations::{color::Theme, sort::compare_groups};
+use crate::html::{write_html_group, write_html_header};
+use crate::limits::{get_incomplete_reason, hash_is_stopped};
+use crate::markdown::{write_markdown_group, write_markdown_header};
+use crate::output::Writer;
+use crate::skipped::{add_error, get_skipped_files};
+use crate::structures::dir_info::get_dir_stats;
+use crate::structures::file_info::{get_allocated_size, get_known_allocated_size};
+use crate::structures::metadata_info::{get_metadata_diffs, FileMetadata};
+use crate::xml::{write_xml_group, write_xml_header};
 use crate::{
     add_thousands_separator,
     args::{Arguments, ResultFormat::*},
-    my_print, split_and_insert, write_xlsx, FileExtension, FileInfo, Key, MyResult,
-    PathBufExtension, PathInfo, TotalInfo, CSV_FILENAME, SEPARATOR, XLSX_FILENAME,
+    display_path, get_file_kind, human_readable_size, serialize_paths, split_and_insert, tr,
+    write_xlsx, Algorithm, Compression, FileExtension, FileInfo, Key, MyResult, PathBufExtension,
+    PathInfo, TotalInfo, CSV_FILENAME, SEPARATOR, XLSX_FILENAME,
 };
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::{
-    fs::{File, OpenOptions},
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::{self, File, OpenOptions},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
+/// Number of chunks of groups (one chunk per thread) formatted before being written.
+const CHUNKS_PER_BATCH: usize = 64;
+
+/// Groups of `--size_only`: the contents of the files are not compared.
+pub const UNVERIFIED: &str = "same size only, the contents were not compared";
+
+/// Labels of the Personal format, aligned to the longest one in the chosen language.
+const LABELS: [&str; 8] = [
+    "size:",
+    "hash:",
+    "algorithm:",
+    "group id:",
+    "Number of identical files:",
+    "Sum of file sizes:",
+    "Wasted size:",
+    "Wasted disk space:",
+];
+
 /// Grouped file information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GroupInfo {
     /// Key Information
     #[serde(rename = "File information")]
     pub key: Key,
+    /// Algorithm that produced the hash of the entire file
+    #[serde(rename = "Algorithm", default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<Algorithm>,
     /// File Paths
-    #[serde(rename = "Paths")]
+    #[serde(rename = "Paths", serialize_with = "serialize_paths")]
     pub paths: Vec<PathBuf>, // Vec<PathBuf> ; Arc<[PathBuf]> for immutable data
     /// Number of identical files with the same size and blake3 hash
     #[serde(rename = "Number of identical files")]
@@ -30,6 +66,33 @@ pub struct GroupInfo {
         serialize_with = "add_thousands_separator"
     )]
     pub sum_size: usize,
+    /// Space that would be reclaimed by keeping only one file: size × (count − 1)
+    #[serde(rename = "Wasted size", serialize_with = "add_thousands_separator")]
+    pub wasted_size: usize,
+}
+
+/// Group of identical files as written in the JSON, YAML, CBOR and MessagePack reports.
+#[derive(Serialize)]
+struct GroupReport<'a> {
+    /// Stable identifier of the group
+    #[serde(rename = "Group id")]
+    id: String,
+    #[serde(flatten)]
+    group_info: Cow<'a, GroupInfo>,
+}
+
+/**
+Get the stable identifier of a group of identical files: the first
+16 hexadecimal digits of the blake3 hash of `<digest>:<size>`, with the
+self-describing digest (`blake3:<hex>`).
+
+The identifier depends only on (algorithm, digest, size), not on the paths:
+the same group has the same identifier in repeated searches, even if its
+files were moved. It is the key that joins the groups of two reports (`diff`).
+*/
+pub fn get_group_id(digest: &str, size: usize) -> String {
+    let hash = blake3::hash(format!("{digest}:{size}").as_bytes());
+    hash.to_hex()[..16].to_string()
 }
 
 impl GroupInfo {
@@ -42,40 +105,208 @@ impl GroupInfo {
         match &arguments.result_format {
             Json => {
                 // Serialize GroupInfo to a JSON string.
-                let serialized = serde_json::to_string_pretty(self)?;
+                let serialized = serde_json::to_string_pretty(&self.to_report())?;
                 writeln!(write, "{serialized}\n")?;
             }
             Yaml => {
                 // Serialize GroupInfo to a YAML string.
-                let serialized = serde_yaml::to_string(self)?;
+                let serialized = serde_yaml::to_string(&self.to_report())?;
                 writeln!(*write, "{serialized}")?;
             }
-            Personal => {
-                writeln!(
-                    write,
-                    "size: {} bytes",
-                    split_and_insert(self.key.size, SEPARATOR)
-                )?;
-                writeln!(write, "hash: {}", self.key.hash.clone().unwrap_or_default())?;
-                writeln!(write, "Paths: {:#?}", self.paths)?;
-                writeln!(write, "Number of identical files: {}", self.num_file)?;
+            Cbor | MsgPack => {
+                write_binary(&self.to_report(), &arguments.result_format, &mut **write)?
+            }
+            Html => write_html_group(self, self.wasted_size, &mut **write)?,
+            Markdown => write_markdown_group(self, &mut **write)?,
+            Xml => write_xml_group(self, &mut **write)?,
+            Personal if arguments.print0 => {
+                for path in &self.paths {
+                    write.write_all(path.as_os_str().as_encoded_bytes())?;
+                    write.write_all(b"\0")?;
+                }
+                // Empty path: end of the group.
+                write.write_all(b"\0")?;
+            }
+            Personal => self.write_personal(arguments, &Theme::new(arguments), 0, &mut **write)?,
+        }
+
+        Ok(())
+    }
+
+    /**
+    Get the digest in self-describing form (`blake3:<hex>`), as written in the reports.
+
+    Groups without the hash of the entire file (ex.: `--dirs`) keep their digest unchanged.
+    */
+    pub fn get_digest(&self) -> Option<String> {
+        let hash: &str = self.key.hash.as_deref()?;
+
+        Some(match self.algorithm {
+            Some(algorithm) => algorithm.tag_digest(hash),
+            None => hash.to_string(),
+        })
+    }
+
+    /// Get the stable identifier of the group (see [`get_group_id`]).
+    pub fn get_id(&self) -> String {
+        get_group_id(&self.get_digest().unwrap_or_default(), self.key.size)
+    }
+
+    /// Get the group with its identifier and self-describing digest, to be serialized.
+    fn to_report(&self) -> GroupReport<'_> {
+        let group_info: Cow<'_, GroupInfo> = match self.algorithm {
+            Some(_) => {
+                let mut group_info: GroupInfo = self.clone();
+                group_info.key.hash = self.get_digest();
+                Cow::Owned(group_info)
+            }
+            None => Cow::Borrowed(self),
+        };
+
+        GroupReport {
+            id: self.get_id(),
+            group_info,
+        }
+    }
+
+    /**
+    Write the group in the Personal format, with aligned labels.
+
+    With colors, the paths of consecutive groups alternate
+    between two colors (`index`: position of the group).
+    */
+    pub fn write_personal(
+        &self,
+        arguments: &Arguments,
+        theme: &Theme,
+        index: usize,
+        write: &mut dyn Write,
+    ) -> MyResult<()> {
+        let (label, band, wasted) = (theme.label, theme.band(index), theme.wasted);
+        let width: usize = get_label_width();
+        let size = |size: usize| {
+            format!(
+                "{} bytes ({})",
+                split_and_insert(size, SEPARATOR),
+                human_readable_size(size)
+            )
+        };
+
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {}",
+            tr("size:"),
+            size(self.key.size)
+        )?;
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {}",
+            tr("hash:"),
+            match self.get_digest() {
+                Some(digest) => digest,
+                None if arguments.size_only => tr("unverified (same size only)").to_string(),
+                None => String::new(),
+            }
+        )?;
+        if let Some(algorithm) = self.algorithm {
+            writeln!(
+                write,
+                "{label}{:<width$}{label:#} {algorithm}",
+                tr("algorithm:")
+            )?;
+        }
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {}",
+            tr("group id:"),
+            self.get_id()
+        )?;
+        writeln!(write, "{label}{}{label:#} [", tr("Paths:"))?;
+        for path in &self.paths {
+            let path = display_path(path);
+            writeln!(write, "    {band}{path:?}{band:#},")?;
+        }
+        writeln!(write, "]")?;
+        if arguments.by_content || arguments.content_only {
+            let format = |size: Option<u64>| match size {
+                Some(size) => {
+                    format!("{} bytes", split_and_insert(size as usize, SEPARATOR))
+                }
+                None => tr("unknown").to_string(),
+            };
+            for path in &self.paths {
+                let (apparent, allocated) = get_disk_sizes(path);
                 writeln!(
                     write,
-                    "Sum of file sizes: {} bytes\n",
-                    split_and_insert(self.sum_size, SEPARATOR)
+                    "{:?}: {}: {}, {}: {}",
+                    display_path(path),
+                    tr("apparent size"),
+                    format(apparent),
+                    tr("allocated size"),
+                    format(allocated)
                 )?;
             }
         }
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {}",
+            tr("Number of identical files:"),
+            self.num_file
+        )?;
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {}",
+            tr("Sum of file sizes:"),
+            size(self.sum_size)
+        )?;
+        writeln!(
+            write,
+            "{label}{:<width$}{label:#} {wasted}{}{wasted:#}",
+            tr("Wasted size:"),
+            size(self.wasted_size)
+        )?;
+        if let Some(wasted_allocated) = self.get_wasted_allocated() {
+            writeln!(
+                write,
+                "{label}{:<width$}{label:#} {}",
+                tr("Wasted disk space:"),
+                size(wasted_allocated)
+            )?;
+        }
+        writeln!(write)?;
 
         Ok(())
     }
 
+    /**
+    Get the disk space that would be reclaimed by keeping only one file,
+    from the sizes allocated on disk found by the walk (unix only).
+
+    On filesystems with compression or sparse files, this is the real reclaimable space.
+    The largest allocation is assumed to be kept (conservative estimate).
+    */
+    pub fn get_wasted_allocated(&self) -> Option<usize> {
+        let allocated: Vec<u64> = self
+            .paths
+            .iter()
+            .map(|path| get_known_allocated_size(path))
+            .collect::<Option<Vec<u64>>>()?;
+
+        let total: u64 = allocated.iter().sum();
+        let largest: u64 = allocated.iter().max().copied().unwrap_or(0);
+
+        usize::try_from(total - largest).ok()
+    }
+
     /// Update hash
+    ///
+    /// Files that cannot be read (ex.: removed during the search) are skipped,
+    /// or the search is aborted with `--strict`.
     pub fn update_hash(&self, arguments: &Arguments, procedure: u8) -> Vec<FileInfo> {
         self.paths
             .clone()
             .into_par_iter() // rayon parallel iterator
-            .map(|path| {
+            .filter_map(|path| {
                 let key = match path.get_hash(arguments, procedure) {
                     Ok(hash) => Key {
                         size: self.key.size,
@@ -84,31 +315,245 @@ impl GroupInfo {
                     Err(why) => {
                         eprintln!("fn update_hash()");
                         eprintln!("path: {:#?}", path.display());
-                        panic!("Error getting path hash: {why}")
+                        eprintln!("Error getting path hash: {why}");
+                        add_error(&path, &why);
+                        return None;
                     }
                 };
 
-                FileInfo { key, path }
+                Some(FileInfo {
+                    key,
+                    path,
+                    inode: None,
+                    modified: None,
+                    allocated: None,
+                })
+            })
+            .collect()
+    }
+
+    /**
+    Split the group into subgroups of files with identical contents,
+    compared byte by byte.
+
+    The files are compared in parallel with a reference file.
+    */
+    pub fn split_by_content(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        let mut subgroups: Vec<GroupInfo> = Vec::new();
+        let mut remaining: Vec<PathBuf> = self.paths.clone();
+
+        while !remaining.is_empty() {
+            let reference: PathBuf = remaining.remove(0);
+
+            let (mut paths, different): (Vec<PathBuf>, Vec<PathBuf>) = remaining
+                .into_par_iter() // rayon parallel iterator
+                .partition(
+                    |path| match reference.is_identical_to(path, arguments.buffer_size) {
+                        Ok(identical) => identical,
+                        Err(why) => {
+                            eprintln!("fn split_by_content()");
+                            eprintln!("Failed to compare {reference:?} with {path:?}");
+                            eprintln!("Error: {why}");
+                            false
+                        }
+                    },
+                );
+
+            paths.insert(0, reference);
+
+            let num_file = paths.len();
+            let wasted_size = self.key.size * (num_file - 1);
+
+            if arguments.frequency_is_included(num_file)
+                && arguments.wasted_is_included(wasted_size)
+                && arguments.compare_is_included(&paths)
+            {
+                subgroups.push(GroupInfo {
+                    key: self.key.clone(),
+                    algorithm: self.algorithm,
+                    paths,
+                    num_file,
+                    sum_size: self.key.size * num_file,
+                    wasted_size,
+                });
+            }
+
+            remaining = different;
+        }
+
+        subgroups
+    }
+
+    /**
+    Keep only the files whose content is of the types of `--file_type`.
+
+    Called after grouping by size (procedure 1): the group may still contain
+    files with different contents.
+    */
+    pub fn retain_file_types(&self, arguments: &Arguments) -> Option<GroupInfo> {
+        let paths: Vec<PathBuf> = self
+            .paths
+            .iter()
+            .filter(|path| {
+                get_file_kind(path).is_some_and(|kind| arguments.file_type.contains(&kind))
+            })
+            .cloned()
+            .collect();
+
+        self.with_paths(paths, arguments)
+    }
+
+    /**
+    Remove the files that appear to be actively written (`--skip_busy`).
+
+    Called after grouping by size (procedure 1), before the files are hashed.
+    */
+    pub fn retain_idle_files(&self, arguments: &Arguments) -> Option<GroupInfo> {
+        let paths: Vec<PathBuf> = self
+            .paths
+            .iter()
+            .filter(|path| !is_busy(path))
+            .cloned()
+            .collect();
+
+        self.with_paths(paths, arguments)
+    }
+
+    /// Get the group with the remaining paths, if it still passes the group filters.
+    fn with_paths(&self, paths: Vec<PathBuf>, arguments: &Arguments) -> Option<GroupInfo> {
+        let num_file = paths.len();
+        let wasted_size = self.key.size * num_file.saturating_sub(1);
+
+        (num_file >= arguments.min_frequency as usize
+            && arguments.wasted_is_included(wasted_size)
+            && arguments.compare_is_included(&paths))
+        .then(|| GroupInfo {
+            key: self.key.clone(),
+            algorithm: self.algorithm,
+            paths,
+            num_file,
+            sum_size: self.key.size * num_file,
+            wasted_size,
+        })
+    }
+
+    /**
+    Split the group into subgroups of files with the same file name (`--same_name_only`).
+
+    With `--ignore_case`, file names are compared case-insensitively.
+    */
+    pub fn split_by_name(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        let mut names: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+        for path in &self.paths {
+            let name: String = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let name = if arguments.ignore_case {
+                name.to_lowercase()
+            } else {
+                name
+            };
+            names.entry(name).or_default().push(path.clone());
+        }
+
+        names
+            .into_values()
+            .filter_map(|paths| {
+                let num_file = paths.len();
+                let wasted_size = self.key.size * num_file.saturating_sub(1);
+
+                (num_file > 1
+                    && arguments.frequency_is_included(num_file)
+                    && arguments.wasted_is_included(wasted_size)
+                    && arguments.compare_is_included(&paths))
+                .then(|| GroupInfo {
+                    key: self.key.clone(),
+                    algorithm: self.algorithm,
+                    paths,
+                    num_file,
+                    sum_size: self.key.size * num_file,
+                    wasted_size,
+                })
+            })
+            .collect()
+    }
+
+    /// Split the group into subgroups of files with the same metadata (`--compare_metadata`).
+    pub fn split_by_metadata(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        let mut subgroups: BTreeMap<FileMetadata, Vec<PathBuf>> = BTreeMap::new();
+
+        for path in &self.paths {
+            let metadata = FileMetadata::new(path, &arguments.compare_metadata);
+            subgroups.entry(metadata).or_default().push(path.clone());
+        }
+
+        subgroups
+            .into_values()
+            .filter_map(|paths| {
+                let num_file = paths.len();
+                let wasted_size = self.key.size * num_file.saturating_sub(1);
+
+                (num_file > 1
+                    && arguments.frequency_is_included(num_file)
+                    && arguments.wasted_is_included(wasted_size)
+                    && arguments.compare_is_included(&paths))
+                .then(|| GroupInfo {
+                    key: self.key.clone(),
+                    algorithm: self.algorithm,
+                    paths,
+                    num_file,
+                    sum_size: self.key.size * num_file,
+                    wasted_size,
+                })
             })
             .collect()
     }
 
     /// Convert [`GroupInfo`] to Vec<[`PathInfo`]>
     pub fn flatten(&self) -> Vec<PathInfo> {
+        let group_id: String = self.get_id();
+
         self.paths
             .clone()
             .into_par_iter() // rayon parallel iterator
-            .map(|path| PathInfo {
-                size: self.key.size,
-                hash: self.key.hash.clone(),
-                path: path.to_owned(),
-                num_file: self.num_file,
-                sum_size: self.sum_size,
+            .map(|path| {
+                let (apparent_size, allocated_size) = get_disk_sizes(&path);
+                PathInfo {
+                    group_id: group_id.clone(),
+                    size: self.key.size,
+                    hash: self.get_digest(),
+                    algorithm: self.algorithm.map(|algorithm| algorithm.to_string()),
+                    path: display_path(&path).into_owned(),
+                    num_file: self.num_file,
+                    sum_size: self.sum_size,
+                    wasted_size: self.wasted_size,
+                    apparent_size,
+                    allocated_size,
+                }
             })
             .collect()
     }
 }
 
+/// Get the width of the labels of the Personal format (ex.: "Number of identical files:").
+fn get_label_width() -> usize {
+    LABELS
+        .iter()
+        .map(|label| tr(label).chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Get the apparent size and the size allocated on disk (in bytes) of the file.
+fn get_disk_sizes(path: &Path) -> (Option<u64>, Option<u64>) {
+    match fs::metadata(path) {
+        Ok(metadata) => (Some(metadata.len()), get_allocated_size(&metadata)),
+        Err(_) => (None, None),
+    }
+}
+
 pub trait GroupExtension {
     /**
     Get identical files from the hash of the first bytes or the entire file.
@@ -120,17 +565,35 @@ pub trait GroupExtension {
     fn get_identical_files(&self, arguments: &Arguments, procedure: u8) -> Vec<GroupInfo>;
 
     /**
-    Sort the list of identical files.
+    Verify identical files by comparing their contents byte by byte.
+
+    Protects against (unlikely) hash collisions.
+    */
+    fn verify_identical_files(&self, arguments: &Arguments) -> Vec<GroupInfo>;
+
+    /// Keep only identical files with the same file name (`--same_name_only`).
+    fn filter_by_name(&self, arguments: &Arguments) -> Vec<GroupInfo>;
+
+    /// Split groups by metadata (`--compare_metadata`).
+    fn filter_by_metadata(&self, arguments: &Arguments) -> Vec<GroupInfo>;
+
+    /// Keep only files of the chosen types (`--file_type`).
+    fn filter_by_file_type(&self, arguments: &Arguments) -> Vec<GroupInfo>;
+
+    /// Remove the files that appear to be actively written (`--skip_busy`).
+    fn filter_busy_files(&self, arguments: &Arguments) -> Vec<GroupInfo>;
+
+    /**
+    Sort the list of identical files by `--sort_by` keys, in `--order` direction.
 
-    Two options:
+    By default, sort by (file size, hash).
 
-    1. Sort by number of identical files and then by (file size, hash);
-    2. Sort by (file size, hash). `default`
+    With `--top`, sort by wasted size in descending order.
     */
     fn sort_identical_files(&mut self, arguments: &Arguments);
 
-    /// Print identical files
-    fn print_identical_files(&self, arguments: &Arguments) -> MyResult<()>;
+    /// Write identical files in the chosen format
+    fn write_identical_files(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()>;
 
     /// Get Total Info
     fn get_total_info(&self, arguments: &Arguments, total_num_files: usize) -> TotalInfo;
@@ -138,8 +601,8 @@ pub trait GroupExtension {
     /// Convert Vec<[`GroupInfo`]> to Vec<[`PathInfo`]>
     fn get_path_info(&self) -> Vec<PathInfo>;
 
-    /// Export to CSV format
-    fn export_to_csv(&self, dir_path: PathBuf) -> MyResult<()>;
+    /// Export to CSV format, compressed with `--compress`
+    fn export_to_csv(&self, dir_path: PathBuf, compression: Option<Compression>) -> MyResult<()>;
 
     /// Export to XLSX format
     fn export_to_xlsx(&self, dir_path: PathBuf) -> MyResult<()>;
@@ -150,6 +613,20 @@ impl GroupExtension for [GroupInfo] {
         let identical_hash: Vec<GroupInfo> = self
             .par_iter() // rayon parallel iterator
             .flat_map(|group_info| {
+                // With `--timeout`, the groups not yet hashed are skipped.
+                if hash_is_stopped() {
+                    return Vec::new();
+                }
+
+                // Small files are entirely read by the prefilter:
+                // their full hash is computed in procedure 3.
+                if procedure == 2
+                    && !arguments.prefilter_tail
+                    && group_info.key.size <= arguments.prefilter_bytes
+                {
+                    return vec![group_info.clone()];
+                }
+
                 group_info
                     .update_hash(arguments, procedure)
                     .get_grouped_files(arguments, procedure)
@@ -159,41 +636,104 @@ impl GroupExtension for [GroupInfo] {
         identical_hash
     }
 
+    fn verify_identical_files(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        self.par_iter() // rayon parallel iterator
+            .filter(|_| !hash_is_stopped())
+            .flat_map(|group_info| group_info.split_by_content(arguments))
+            .collect()
+    }
+
+    fn filter_by_name(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        self.par_iter() // rayon parallel iterator
+            .flat_map(|group_info| group_info.split_by_name(arguments))
+            .collect()
+    }
+
+    fn filter_by_metadata(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        self.par_iter() // rayon parallel iterator
+            .flat_map(|group_info| group_info.split_by_metadata(arguments))
+            .collect()
+    }
+
+    fn filter_by_file_type(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        self.par_iter() // rayon parallel iterator
+            .filter_map(|group_info| group_info.retain_file_types(arguments))
+            .collect()
+    }
+
+    fn filter_busy_files(&self, arguments: &Arguments) -> Vec<GroupInfo> {
+        self.par_iter() // rayon parallel iterator
+            .filter_map(|group_info| group_info.retain_idle_files(arguments))
+            .collect()
+    }
+
     fn sort_identical_files(&mut self, arguments: &Arguments) {
-        if arguments.sort {
-            // Sort by number of identical files and then by (file size, hash).
-            self.par_sort_unstable_by_key(|group_info| {
-                (
-                    group_info.num_file,
-                    group_info.key.size,
-                    group_info.key.hash.clone(),
-                )
+        if arguments.top.is_some() {
+            // Sort by wasted size (descending) and then by (file size, hash, paths).
+            self.par_sort_unstable_by(|a, b| {
+                b.wasted_size
+                    .cmp(&a.wasted_size)
+                    .then_with(|| a.key.size.cmp(&b.key.size))
+                    .then_with(|| a.key.hash.cmp(&b.key.hash))
+                    .then_with(|| a.paths.cmp(&b.paths))
             });
         } else {
-            // Sort by (file size, hash) and then by number of identical files.
-            self.par_sort_unstable_by_key(|group_info| {
-                (group_info.key.size, group_info.key.hash.clone())
+            self.par_sort_unstable_by(|a, b| {
+                compare_groups(a, b, &arguments.sort_by, arguments.order)
             });
         }
     }
 
-    fn print_identical_files(&self, arguments: &Arguments) -> MyResult<()> {
-        let all_buffer: Vec<u8> = self
-            .par_chunks(rayon::current_num_threads())
-            .flat_map(|groups_info| -> MyResult<Vec<u8>> {
-                let mut buffer: Vec<u8> = Vec::new();
-                let mut write: Box<&mut dyn Write> = Box::new(&mut buffer);
-                groups_info
-                    .iter()
-                    .try_for_each(|group_info| -> MyResult<()> {
-                        group_info.print_formatted(arguments, &mut write)
-                    })?;
-                Ok(buffer)
-            })
-            .flatten()
-            .collect();
+    fn write_identical_files(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        // The size bars of the HTML report are relative to the largest wasted size.
+        let max_wasted: usize = self
+            .iter()
+            .map(|group_info| group_info.wasted_size)
+            .max()
+            .unwrap_or_default();
+
+        match arguments.result_format {
+            Html => write_html_header(write)?,
+            Markdown => write_markdown_header(arguments, write)?,
+            Xml => write_xml_header(arguments, write)?,
+            _ => (),
+        }
+
+        let theme = Theme::new(arguments);
+        let chunk_size: usize = rayon::current_num_threads();
+        let batch_size: usize = chunk_size * CHUNKS_PER_BATCH;
+
+        // The groups are formatted in parallel, one batch at a time: each batch
+        // is written (and compressed with `--compress`) while the result grows.
+        for (batch, groups_batch) in self.chunks(batch_size).enumerate() {
+            let batch_buffer: Vec<u8> = groups_batch
+                .par_chunks(chunk_size)
+                .enumerate()
+                .flat_map(|(chunk, groups_info)| -> MyResult<Vec<u8>> {
+                    let mut buffer: Vec<u8> = Vec::new();
+                    let mut write: Box<&mut dyn Write> = Box::new(&mut buffer);
+                    groups_info.iter().enumerate().try_for_each(
+                        |(index, group_info)| -> MyResult<()> {
+                            match arguments.result_format {
+                                Html => write_html_group(group_info, max_wasted, &mut **write),
+                                Personal if !arguments.print0 => group_info.write_personal(
+                                    arguments,
+                                    &theme,
+                                    batch * batch_size + chunk * chunk_size + index,
+                                    &mut **write,
+                                ),
+                                _ => group_info.print_formatted(arguments, &mut write),
+                            }
+                        },
+                    )?;
+                    Ok(buffer)
+                })
+                .flatten()
+                .collect();
+
+            write.write_all(&batch_buffer)?;
+        }
 
-        my_print(&all_buffer)?;
         Ok(())
     }
 
@@ -203,6 +743,16 @@ impl GroupExtension for [GroupInfo] {
             || self.par_iter().map(|group_info| group_info.num_file).sum(),
             || self.par_iter().map(|group_info| group_info.sum_size).sum(),
         );
+        let total_wasted: usize = self
+            .par_iter()
+            .map(|group_info| group_info.wasted_size)
+            .sum();
+        // The sizes allocated on disk are not available on other systems.
+        let total_wasted_allocated: Option<usize> = cfg!(unix).then(|| {
+            self.par_iter()
+                .filter_map(|group_info| group_info.get_wasted_allocated())
+                .sum()
+        });
 
         /*
         let (result_a, result_b) = thread::scope(|s| {
@@ -227,6 +777,20 @@ impl GroupExtension for [GroupInfo] {
             total_num_identical,
             total_num_hashes: self.len(),
             total_size,
+            total_wasted,
+            total_wasted_allocated,
+            incomplete: get_incomplete_reason(),
+            unverified: arguments.size_only.then(|| UNVERIFIED.to_string()),
+            skipped_files: get_skipped_files(),
+            stats_by_dir: arguments
+                .stats_by_dir
+                .map(|depth| get_dir_stats(self, arguments, depth))
+                .unwrap_or_default(),
+            metadata_diffs: if arguments.metadata_diff {
+                get_metadata_diffs(self, &arguments.compare_metadata)
+            } else {
+                Vec::new()
+            },
         }
     }
 
@@ -236,8 +800,15 @@ impl GroupExtension for [GroupInfo] {
             .collect()
     }
 
-    fn export_to_csv(&self, mut dir_path: PathBuf) -> MyResult<()> {
+    fn export_to_csv(
+        &self,
+        mut dir_path: PathBuf,
+        compression: Option<Compression>,
+    ) -> MyResult<()> {
         dir_path.push(CSV_FILENAME); // dir_path + filename
+        if let Some(compression) = compression {
+            dir_path = compression.add_extension(dir_path);
+        }
         eprintln!("Write CSV File: {:?}", dir_path);
 
         // Open a file in write-only mode
@@ -260,13 +831,17 @@ impl GroupExtension for [GroupInfo] {
             .delimiter(b';')
             .has_headers(true)
             .quote_style(csv::QuoteStyle::Necessary) // NonNumeric
-            .from_writer(file);
+            .from_writer(Writer::new(file, compression)?);
 
         for path_info in self.get_path_info() {
             writer.serialize(path_info)?;
         }
 
-        writer.flush()?;
+        // Finish the compressed stream.
+        writer
+            .into_inner()
+            .map_err(|error| error.into_error())?
+            .finish()?;
 
         Ok(())
     }
@@ -280,3 +855,145 @@ impl GroupExtension for [GroupInfo] {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test_group_info {
+    use super::*;
+    use clap::Parser;
+
+    /// cargo test -- --show-output test_split_by_name
+    #[test]
+    fn test_split_by_name() {
+        let group_info = GroupInfo {
+            key: Key::new(100, Some("abc".to_string())),
+            algorithm: None,
+            paths: [
+                "a/IMG_0001.JPG",
+                "b/img_0001.jpg",
+                "c/IMG_0001.JPG",
+                "d/other.jpg",
+            ]
+            .iter()
+            .map(PathBuf::from)
+            .collect(),
+            num_file: 4,
+            sum_size: 400,
+            wasted_size: 300,
+        };
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--same_name_only"]);
+        let groups = group_info.split_by_name(&arguments);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].paths,
+            [
+                PathBuf::from("a/IMG_0001.JPG"),
+                PathBuf::from("c/IMG_0001.JPG")
+            ]
+        );
+        assert_eq!(groups[0].wasted_size, 100);
+
+        let arguments =
+            Arguments::parse_from(["find-identical-files", "--same_name_only", "--ignore_case"]);
+        let groups = group_info.split_by_name(&arguments);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].num_file, 3);
+        assert_eq!(groups[0].sum_size, 300);
+    }
+
+    /// cargo test -- --show-output test_print0
+    #[test]
+    fn test_print0() -> MyResult<()> {
+        let group_info = GroupInfo {
+            key: Key::new(10, Some("abc".to_string())),
+            algorithm: None,
+            paths: vec![PathBuf::from("a.txt"), PathBuf::from("new\nline.txt")],
+            num_file: 2,
+            sum_size: 20,
+            wasted_size: 10,
+        };
+
+        let arguments = Arguments::parse_from(["find-identical-files", "--print0"]);
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut write: Box<&mut dyn Write> = Box::new(&mut buffer);
+        group_info.print_formatted(&arguments, &mut write)?;
+        assert_eq!(buffer, b"a.txt\0new\nline.txt\0\0");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_group_id
+    #[test]
+    fn test_group_id() -> MyResult<()> {
+        let group_info = GroupInfo {
+            key: Key::new(10, Some("abc".to_string())),
+            algorithm: Some(Algorithm::Blake3),
+            paths: vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")],
+            num_file: 2,
+            sum_size: 20,
+            wasted_size: 10,
+        };
+
+        // The id depends on (algorithm, digest, size), not on the paths.
+        let id: String = group_info.get_id();
+        assert_eq!(id.len(), 16);
+        assert_eq!(id, get_group_id("blake3:abc", 10));
+        assert_ne!(id, get_group_id("sha256:abc", 10));
+        assert_ne!(id, get_group_id("blake3:abc", 11));
+
+        let moved = GroupInfo {
+            paths: vec![PathBuf::from("c.txt"), PathBuf::from("d.txt")],
+            ..group_info.clone()
+        };
+        assert_eq!(moved.get_id(), id);
+
+        let arguments = Arguments::parse_from(["find-identical-files", "-r", "json"]);
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut write: Box<&mut dyn Write> = Box::new(&mut buffer);
+        group_info.print_formatted(&arguments, &mut write)?;
+        let value: serde_json::Value = serde_json::from_slice(&buffer)?;
+        assert_eq!(value["Group id"], id.as_str());
+        assert_eq!(value["File information"]["hash"], "blake3:abc");
+
+        Ok(())
+    }
+
+    /// cargo test -- --show-output test_wasted_allocated
+    #[test]
+    fn test_wasted_allocated() {
+        // The files do not exist: the sizes found by the walk are used.
+        let file = |path: &str, allocated: Option<u64>| FileInfo {
+            key: Key::new(10_000, None),
+            path: PathBuf::from(path),
+            inode: None,
+            modified: None,
+            allocated,
+        };
+        let all_files = [
+            file("/allocated/a.bin", Some(4096)),
+            file("/allocated/b.bin", Some(12288)),
+            file("/allocated/c.bin", Some(4096)),
+            file("/allocated/d.bin", None),
+        ];
+
+        let group = |paths: &[&str]| GroupInfo {
+            key: Key::new(10_000, Some("abc".to_string())),
+            algorithm: None,
+            paths: paths.iter().map(PathBuf::from).collect(),
+            num_file: paths.len(),
+            sum_size: 10_000 * paths.len(),
+            wasted_size: 10_000 * (paths.len() - 1),
+        };
+        let groups = [
+            group(&["/allocated/a.bin", "/allocated/b.bin", "/allocated/c.bin"]),
+            group(&["/allocated/a.bin", "/allocated/d.bin"]),
+        ];
+
+        crate::structures::file_info::insert_allocated_sizes(&all_files, &groups);
+
+        // The largest allocation is kept.
+        assert_eq!(groups[0].get_wasted_allocated(), Some(8192));
+        // Unknown allocated size.
+        assert_eq!(groups[1].get_wasted_allocated(), None);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{serialize_paths, GroupInfo, MetadataKey};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+/// Metadata of a file, restricted to the keys of `--compare_metadata`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FileMetadata {
+    /// Last modification time (seconds since the Unix epoch)
+    pub mtime: Option<u64>,
+    /// User and group ids
+    pub owner: Option<(u32, u32)>,
+    /// Permission bits
+    pub perms: Option<u32>,
+    /// Extended attributes: name -> value
+    pub xattr: Option<BTreeMap<String, Vec<u8>>>,
+}
+
+impl FileMetadata {
+    /// Read the metadata of the file: only the chosen keys are read.
+    pub fn new(path: &Path, keys: &[MetadataKey]) -> Self {
+        let Ok(metadata) = fs::metadata(path) else {
+            return FileMetadata::default();
+        };
+
+        let mut file_metadata = FileMetadata::default();
+
+        for key in keys {
+            match key {
+                MetadataKey::Mtime => {
+                    file_metadata.mtime = metadata
+                        .modified()
+                        .ok()
+                        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                        .map(|duration| duration.as_secs());
+                }
+                MetadataKey::Owner => file_metadata.owner = get_owner(&metadata),
+                MetadataKey::Perms => file_metadata.perms = get_perms(&metadata),
+                MetadataKey::Xattr => file_metadata.xattr = get_xattr(path),
+            }
+        }
+
+        file_metadata
+    }
+
+    /// Get the keys whose values differ between the two files.
+    pub fn differences(&self, other: &FileMetadata) -> Vec<MetadataKey> {
+        [
+            (MetadataKey::Mtime, self.mtime != other.mtime),
+            (MetadataKey::Owner, self.owner != other.owner),
+            (MetadataKey::Perms, self.perms != other.perms),
+            (MetadataKey::Xattr, self.xattr != other.xattr),
+        ]
+        .into_iter()
+        .filter_map(|(key, differ)| differ.then_some(key))
+        .collect()
+    }
+}
+
+/// Get the (user id, group id) of the file.
+#[cfg(unix)]
+fn get_owner(metadata: &Metadata) -> Option<(u32, u32)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.uid(), metadata.gid()))
+}
+
+/// Get the (user id, group id) of the file.
+#[cfg(not(unix))]
+fn get_owner(_metadata: &Metadata) -> Option<(u32, u32)> {
+    None
+}
+
+/// Get the permission bits of the file.
+#[cfg(unix)]
+fn get_perms(metadata: &Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o7777)
+}
+
+/// Get the permission bits of the file: only the read-only attribute.
+#[cfg(not(unix))]
+fn get_perms(metadata: &Metadata) -> Option<u32> {
+    Some(metadata.permissions().readonly() as u32)
+}
+
+/// Get the extended attributes of the file.
+#[cfg(unix)]
+fn get_xattr(path: &Path) -> Option<BTreeMap<String, Vec<u8>>> {
+    let names = xattr::list(path).ok()?;
+
+    Some(
+        names
+            .filter_map(|name| {
+                let value = xattr::get(path, &name).ok().flatten()?;
+                Some((name.to_string_lossy().into_owned(), value))
+            })
+            .collect(),
+    )
+}
+
+/// Get the extended attributes of the file.
+#[cfg(not(unix))]
+fn get_xattr(_path: &Path) -> Option<BTreeMap<String, Vec<u8>>> {
+    None
+}
+
+/// Metadata that differ between identical files (`--metadata_diff`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MetadataDiff {
+    /// Paths of the identical files
+    #[serde(rename = "Paths", serialize_with = "serialize_paths")]
+    pub paths: Vec<PathBuf>,
+    /// Metadata that differ from the first file
+    #[serde(rename = "Differences")]
+    pub differences: Vec<MetadataKey>,
+}
+
+/**
+Get the metadata that differ within each group of identical files.
+
+Each file is compared with the first file of its group.
+Groups whose files have the same metadata are not reported.
+*/
+pub fn get_metadata_diffs(groups: &[GroupInfo], keys: &[MetadataKey]) -> Vec<MetadataDiff> {
+    groups
+        .par_iter() // rayon parallel iterator
+        .filter_map(|group_info| {
+            let metadata: Vec<FileMetadata> = group_info
+                .paths
+                .iter()
+                .map(|path| FileMetadata::new(path, keys))
+                .collect();
+
+            let mut differences: Vec<MetadataKey> = metadata
+                .iter()
+                .skip(1)
+                .flat_map(|other| metadata[0].differences(other))
+                .collect();
+            differences.sort();
+            differences.dedup();
+
+            (!differences.is_empty()).then(|| MetadataDiff {
+                paths: group_info.paths.clone(),
+                differences,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test_metadata_info {
+    use super::*;
+
+    /// cargo test -- --show-output test_metadata_differences
+    #[test]
+    fn test_metadata_differences() {
+        let a = FileMetadata {
+            mtime: Some(10),
+            perms: Some(0o644),
+            ..Default::default()
+        };
+        let b = FileMetadata {
+            mtime: Some(20),
+            perms: Some(0o644),
+            ..Default::default()
+        };
+
+        assert_eq!(a.differences(&b), [MetadataKey::Mtime]);
+        assert!(a.differences(&a).is_empty());
+    }
+}
@@ -1,14 +1,22 @@
+pub mod action_info;
+pub mod dir_info;
 pub mod file_info;
 pub mod group_info;
 pub mod key_info;
+pub mod metadata_info;
 pub mod path_info;
+pub mod summary_info;
 pub mod total_info;
 
 /*
 Structures defined in this directory:
+    ActionInfo,
+    DirInfo,
     Key,
     FileInfo,
     GroupInfo,
+    MetadataDiff,
     PathInfo,
+    SummaryInfo,
     TotalInfo,
 */
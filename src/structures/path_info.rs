@@ -18,6 +18,11 @@ use crate::excel::get_xlsx_format;
     table_default
 )]
 pub struct PathInfo {
+    /// Stable identifier of the group of identical files
+    #[serde(rename = "Group id")]
+    #[xlsx(value_format = get_xlsx_format("center"))]
+    pub group_id: String,
+
     /// File size (in bytes)
     #[serde(rename = "File size (bytes)")]
     #[xlsx(value_format = get_xlsx_format("integer"))]
@@ -28,6 +33,11 @@ pub struct PathInfo {
     #[xlsx(value_format = get_xlsx_format("center"))]
     pub hash: Option<String>,
 
+    /// Algorithm that produced the hash
+    #[serde(rename = "Algorithm")]
+    #[xlsx(value_format = get_xlsx_format("center"))]
+    pub algorithm: Option<String>,
+
     /// File Paths
     #[serde(rename = "Path")]
     #[xlsx(value_format = get_xlsx_format("default"))]
@@ -42,6 +52,21 @@ pub struct PathInfo {
     #[serde(rename = "Sum of file sizes (bytes)")]
     #[xlsx(value_format = get_xlsx_format("integer"))]
     pub sum_size: usize,
+
+    /// Space that would be reclaimed by keeping only one file of the group
+    #[serde(rename = "Wasted size (bytes)")]
+    #[xlsx(value_format = get_xlsx_format("integer"))]
+    pub wasted_size: usize,
+
+    /// Size reported by the filesystem
+    #[serde(rename = "Apparent size (bytes)")]
+    #[xlsx(value_format = get_xlsx_format("integer"))]
+    pub apparent_size: Option<u64>,
+
+    /// Size allocated on disk, smaller for sparse or compressed files (unix only)
+    #[serde(rename = "Allocated size (bytes)")]
+    #[xlsx(value_format = get_xlsx_format("integer"))]
+    pub allocated_size: Option<u64>,
 }
 
 /*
@@ -0,0 +1,62 @@
+use crate::{limits::get_incomplete_reason, skipped::get_skipped_files, GroupInfo, MyResult};
+use serde::Serialize;
+use std::{fs, io::Write, path::Path, time::Duration};
+
+/// Process exit code used with `--summary` when identical files are found.
+pub const DUPLICATES_FOUND_EXIT_CODE: i32 = 2;
+
+/// Machine-readable summary of the search
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SummaryInfo {
+    /// Total number of files found in the directories
+    pub files_scanned: usize,
+    /// Number of groups of identical files
+    pub groups_found: usize,
+    /// Number of identical files beyond the first one of each group
+    pub duplicate_files: usize,
+    /// Size of identical files beyond the first one of each group (in bytes)
+    pub duplicate_bytes: usize,
+    /// Total execution time (in seconds)
+    pub elapsed_seconds: f64,
+    /// Reason why the scan is incomplete (`--max_files` or `--timeout`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incomplete: Option<String>,
+    /// Number of files and directories that could not be read
+    pub skipped_files: usize,
+}
+
+impl SummaryInfo {
+    /// Get the summary from the groups of identical files.
+    pub fn new(identical_hash: &[GroupInfo], files_scanned: usize, elapsed: Duration) -> Self {
+        let groups = identical_hash
+            .iter()
+            .filter(|group_info| group_info.num_file >= 2);
+
+        SummaryInfo {
+            files_scanned,
+            groups_found: groups.clone().count(),
+            duplicate_files: groups.clone().map(|g| g.num_file - 1).sum(),
+            duplicate_bytes: groups.map(|g| g.wasted_size).sum(),
+            elapsed_seconds: elapsed.as_secs_f64(),
+            incomplete: get_incomplete_reason(),
+            skipped_files: get_skipped_files().len(),
+        }
+    }
+
+    /// Write the summary in JSON format to the file or, by default, to stderr.
+    pub fn write(&self, path: Option<&Path>) -> MyResult<()> {
+        let serialized = serde_json::to_string_pretty(self)?;
+
+        match path {
+            Some(path) => fs::write(path, format!("{serialized}\n"))?,
+            None => writeln!(std::io::stderr(), "{serialized}")?,
+        }
+
+        Ok(())
+    }
+
+    /// Check if identical files were found.
+    pub fn has_duplicates(&self) -> bool {
+        self.duplicate_files > 0
+    }
+}
@@ -1,12 +1,22 @@
 use crate::{
     add_thousands_separator,
     args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    display_path,
+    html::write_html_summary,
+    human_readable_size,
+    markdown::write_markdown_summary,
     split_and_insert,
     structures::group_info::GroupExtension,
-    Algorithm, GroupInfo, MyResult, SEPARATOR,
+    tr,
+    xml::write_xml_summary,
+    Algorithm, DirInfo, GroupInfo, MetadataDiff, MyResult, SkipReason, SkippedFile, SEPARATOR,
+};
+use serde::{Serialize, Serializer};
+use std::{
+    io::{self, Write},
+    thread,
 };
-use serde::Serialize;
-use std::thread;
 
 /// Summarize information for all files found in the directory
 #[derive(Debug, Default, Clone, Serialize)]
@@ -29,18 +39,65 @@ pub struct TotalInfo {
         serialize_with = "add_thousands_separator"
     )]
     pub total_size: usize,
+    /// Total space that would be reclaimed by keeping only one file per group
+    #[serde(
+        rename = "Total reclaimable space",
+        serialize_with = "add_thousands_separator"
+    )]
+    pub total_wasted: usize,
+    /// Total disk space that would be reclaimed, from the sizes allocated on disk (unix only)
+    #[serde(
+        rename = "Total reclaimable disk space",
+        serialize_with = "add_thousands_separator_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub total_wasted_allocated: Option<usize>,
+    /// Reason why the scan is incomplete (`--max_files` or `--timeout`)
+    #[serde(rename = "Incomplete scan", skip_serializing_if = "Option::is_none")]
+    pub incomplete: Option<String>,
+    /// Reason why the groups are not verified (`--size_only`)
+    #[serde(
+        rename = "Unverified candidates",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub unverified: Option<String>,
+    /// Files and directories that could not be read
+    #[serde(rename = "Skipped files", skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFile>,
+    /// Wasted size aggregated by directory (`--stats_by_dir`)
+    #[serde(
+        rename = "Wasted size by directory",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub stats_by_dir: Vec<DirInfo>,
+    /// Metadata that differ within the groups of identical files (`--metadata_diff`)
+    #[serde(rename = "Metadata differences", skip_serializing_if = "Vec::is_empty")]
+    pub metadata_diffs: Vec<MetadataDiff>,
+}
+
+/// Serialize an optional size with thousands separators.
+fn add_thousands_separator_option<S>(size: &Option<usize>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match size {
+        Some(size) => add_thousands_separator(size, serializer),
+        None => serializer.serialize_none(),
+    }
 }
 
 impl TotalInfo {
-    /// Get the summary information.
+    /// Get the summary information while writing the identical files.
     pub fn get_summary(
         identical_hash: &[GroupInfo],
         arguments: &Arguments,
         total_num_files: usize,
-    ) -> Self {
+        write: &mut (dyn Write + Send),
+    ) -> MyResult<Self> {
         let (result_display, result_total_info) = thread::scope(|s| {
-            let thread_a =
-                s.spawn(|| -> MyResult<()> { identical_hash.print_identical_files(arguments) });
+            let thread_a = s.spawn(|| -> MyResult<()> {
+                identical_hash.write_identical_files(arguments, write)
+            });
             let thread_b = s.spawn(|| -> TotalInfo {
                 identical_hash.get_total_info(arguments, total_num_files)
             });
@@ -51,42 +108,159 @@ impl TotalInfo {
             (thread_a.join(), thread_b.join())
         });
 
-        let (_display, total_info) = match (result_display, result_total_info) {
+        let (display, total_info) = match (result_display, result_total_info) {
             (Ok(display), Ok(total_info)) => (display, total_info),
             _ => panic!("thread::scope failed!"),
         };
 
-        total_info
+        display?;
+
+        Ok(total_info)
     }
 
-    /// Print the identicald files information.
+    /// Print the identical files information to stdout.
+    ///
+    /// The HTML, XML, CBOR and MessagePack results are written to a file:
+    /// the terminal gets the Personal format.
     pub fn print_summary(&self, arguments: &Arguments) -> MyResult<()> {
+        if let Html | Xml | Cbor | MsgPack = arguments.result_format {
+            let mut arguments = arguments.clone();
+            arguments.result_format = Personal;
+            return self.write_summary(&arguments, &mut io::stdout());
+        }
+        self.write_summary(arguments, &mut io::stdout())
+    }
+
+    /// Write the identical files information.
+    pub fn write_summary(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
         match &arguments.result_format {
             Json => {
                 // Serialize TotalInfo to a JSON string.
                 let serialized = serde_json::to_string_pretty(&self)?;
-                println!("{serialized}\n");
+                writeln!(write, "{serialized}\n")?;
             }
             Yaml => {
                 // Serialize GroupInfo to a YAML string.
                 let serialized = serde_yaml::to_string(&self)?;
-                println!("{serialized}");
+                writeln!(write, "{serialized}")?;
             }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            Html => write_html_summary(self, write)?,
+            Markdown => write_markdown_summary(self, write)?,
+            Xml => write_xml_summary(self, write)?,
+            // Only the paths are printed: the output is parsed by scripts.
+            Personal if arguments.print0 => {}
             Personal => {
-                println!("Hashing algorithm: {}", arguments.algorithm); // or self.algorithm
-                println!("Total number of files: {}", self.total_num_files);
-                println!(
-                    "Total number of identical files: {}",
+                if let Some(reason) = &self.incomplete {
+                    writeln!(write, "{} {reason}", tr("Incomplete scan:"))?;
+                }
+                if let Some(reason) = &self.unverified {
+                    writeln!(write, "{} {reason}", tr("Unverified candidates:"))?;
+                }
+                writeln!(
+                    write,
+                    "{} {}",
+                    tr("Hashing algorithm:"),
+                    arguments.algorithm
+                )?; // or self.algorithm
+                writeln!(
+                    write,
+                    "{} {}",
+                    tr("Total number of files:"),
+                    self.total_num_files
+                )?;
+                writeln!(
+                    write,
+                    "{} {}",
+                    tr("Total number of identical files:"),
                     self.total_num_identical
-                );
-                println!(
-                    "Total number of different hashes: {}",
+                )?;
+                writeln!(
+                    write,
+                    "{} {}",
+                    tr("Total number of different hashes:"),
                     self.total_num_hashes
-                );
-                println!(
-                    "Total size of identical files: {} bytes\n",
-                    split_and_insert(self.total_size, SEPARATOR)
-                );
+                )?;
+                writeln!(
+                    write,
+                    "{} {} bytes ({})",
+                    tr("Total size of identical files:"),
+                    split_and_insert(self.total_size, SEPARATOR),
+                    human_readable_size(self.total_size)
+                )?;
+                writeln!(
+                    write,
+                    "{} {} bytes ({})",
+                    tr("Total reclaimable space:"),
+                    split_and_insert(self.total_wasted, SEPARATOR),
+                    human_readable_size(self.total_wasted)
+                )?;
+                if let Some(total_wasted_allocated) = self.total_wasted_allocated {
+                    writeln!(
+                        write,
+                        "{} {} bytes ({})",
+                        tr("Total reclaimable disk space:"),
+                        split_and_insert(total_wasted_allocated, SEPARATOR),
+                        human_readable_size(total_wasted_allocated)
+                    )?;
+                }
+                if !self.stats_by_dir.is_empty() {
+                    writeln!(write, "{}", tr("Wasted size by directory:"))?;
+                    for dir_info in &self.stats_by_dir {
+                        writeln!(
+                            write,
+                            "{:>6.2}% {:>12} {:?} ({} {})",
+                            dir_info.percentage,
+                            human_readable_size(dir_info.wasted_size),
+                            dir_info.directory,
+                            dir_info.num_file,
+                            tr("files")
+                        )?;
+                    }
+                }
+                if !self.metadata_diffs.is_empty() {
+                    writeln!(write, "{}", tr("Metadata differences:"))?;
+                    for metadata_diff in &self.metadata_diffs {
+                        let keys: Vec<String> = metadata_diff
+                            .differences
+                            .iter()
+                            .map(|key| format!("{key:?}").to_lowercase())
+                            .collect();
+                        let paths: Vec<_> = metadata_diff
+                            .paths
+                            .iter()
+                            .map(|path| display_path(path))
+                            .collect();
+                        writeln!(write, "{}: {paths:?}", keys.join(","))?;
+                    }
+                }
+                let (busy_files, skipped_files): (Vec<&SkippedFile>, Vec<&SkippedFile>) = self
+                    .skipped_files
+                    .iter()
+                    .partition(|skipped| skipped.reason == SkipReason::Busy);
+                if !skipped_files.is_empty() {
+                    writeln!(write, "{} {}", tr("Skipped files:"), skipped_files.len())?;
+                    if arguments.verbose {
+                        for skipped in &skipped_files {
+                            writeln!(write, "{:?}: {}", skipped.path, skipped.reason)?;
+                        }
+                    } else {
+                        writeln!(write, "{}", tr("Use --verbose to list the skipped files."))?;
+                    }
+                }
+                // Files being written are always listed (`--skip_busy`).
+                if !busy_files.is_empty() {
+                    writeln!(
+                        write,
+                        "{} {}",
+                        tr("Skipped files (busy):"),
+                        busy_files.len()
+                    )?;
+                    for skipped in &busy_files {
+                        writeln!(write, "{:?}", skipped.path)?;
+                    }
+                }
+                writeln!(write)?;
             }
         }
         Ok(())
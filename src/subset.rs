@@ -0,0 +1,255 @@
+use crate::{
+    add_thousands_separator,
+    args::{Arguments, ResultFormat::*},
+    binary::write_binary,
+    clear_progress, human_readable_size, run_io,
+    skipped::add_error,
+    split_and_insert, start_hashing, DirSource, FileFilter, FileInfo, FileSource, MyResult,
+    PathBufExtension, SEPARATOR,
+};
+use hashbrown::HashSet;
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Process exit code of `--verify_subset` when files of SOURCE are missing in BACKUP.
+pub const MISSING_FILES_EXIT_CODE: i32 = 4;
+
+/// File of SOURCE whose content was not found in BACKUP.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct MissingFile {
+    /// File path
+    #[serde(rename = "Path")]
+    pub path: PathBuf,
+    /// File size
+    #[serde(rename = "Size", serialize_with = "add_thousands_separator")]
+    pub size: usize,
+}
+
+/// Result of `--verify_subset`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubsetInfo {
+    /// Source directory
+    #[serde(rename = "Source")]
+    pub source: PathBuf,
+    /// Backup directory
+    #[serde(rename = "Backup")]
+    pub backup: PathBuf,
+    /// Number of files of the source directory
+    #[serde(rename = "Number of source files")]
+    pub num_source: usize,
+    /// Files of the source directory missing in the backup directory
+    #[serde(rename = "Missing files")]
+    pub missing_files: Vec<MissingFile>,
+    /// Sum of the sizes of the missing files
+    #[serde(
+        rename = "Size of missing files",
+        serialize_with = "add_thousands_separator"
+    )]
+    pub missing_size: usize,
+}
+
+impl SubsetInfo {
+    /// Write the missing files in the chosen format.
+    pub fn write(&self, arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+        match &arguments.result_format {
+            Json => {
+                // Serialize SubsetInfo to a JSON string.
+                let serialized = serde_json::to_string_pretty(self)?;
+                writeln!(write, "{serialized}\n")?;
+            }
+            Yaml => {
+                // Serialize SubsetInfo to a YAML string.
+                let serialized = serde_yaml::to_string(self)?;
+                writeln!(write, "{serialized}")?;
+            }
+            Cbor | MsgPack => write_binary(self, &arguments.result_format, write)?,
+            _ => {
+                writeln!(
+                    write,
+                    "Files of {:?} missing in {:?}:",
+                    self.source, self.backup
+                )?;
+                for missing in &self.missing_files {
+                    writeln!(
+                        write,
+                        "{:>12} {:?}",
+                        human_readable_size(missing.size),
+                        missing.path
+                    )?;
+                }
+                writeln!(write)?;
+                writeln!(write, "Number of source files: {}", self.num_source)?;
+                writeln!(
+                    write,
+                    "Number of missing files: {}",
+                    self.missing_files.len()
+                )?;
+                writeln!(
+                    write,
+                    "Size of missing files: {} bytes ({})",
+                    split_and_insert(self.missing_size, SEPARATOR),
+                    human_readable_size(self.missing_size)
+                )?;
+                writeln!(write)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+Verify that the BACKUP directory contains the content of every file
+of the SOURCE directory (`--verify_subset`).
+
+Names and locations are not compared: a file is found if any file
+of BACKUP has the same size and the same hash.
+
+Files of SOURCE whose size is not in BACKUP are missing without being read.
+*/
+pub fn verify_subset(source: &Path, backup: &Path, arguments: &Arguments) -> MyResult<SubsetInfo> {
+    for dir_path in [source, backup] {
+        if !dir_path.is_dir() {
+            eprintln!("fn verify_subset()");
+            eprintln!("{dir_path:?} is not a directory!");
+            return Err(format!("{dir_path:?} is not a directory").into());
+        }
+    }
+
+    let filter = FileFilter::new(arguments)?;
+    let source_files: Vec<FileInfo> =
+        DirSource::new(source).list_files(arguments, &filter, None)?;
+    let backup_files: Vec<FileInfo> =
+        DirSource::new(backup).list_files(arguments, &filter, None)?;
+    clear_progress();
+
+    let source_sizes: HashSet<usize> = source_files
+        .iter()
+        .map(|file_info| file_info.key.size)
+        .collect();
+    let backup_sizes: HashSet<usize> = backup_files
+        .iter()
+        .map(|file_info| file_info.key.size)
+        .collect();
+
+    // Only files of the same size can be identical.
+    let candidates = |files: &[FileInfo], sizes: &HashSet<usize>| -> Vec<FileInfo> {
+        files
+            .iter()
+            .filter(|file_info| sizes.contains(&file_info.key.size))
+            .cloned()
+            .collect()
+    };
+    let source_candidates: Vec<FileInfo> = candidates(&source_files, &backup_sizes);
+    let backup_candidates: Vec<FileInfo> = candidates(&backup_files, &source_sizes);
+
+    start_hashing(
+        source_candidates
+            .iter()
+            .chain(&backup_candidates)
+            .map(|file_info| file_info.key.size as u64)
+            .sum(),
+    );
+
+    let backup_digests: HashSet<(usize, String)> = run_io(arguments, || {
+        backup_candidates
+            .par_iter() // rayon parallel iterator
+            .filter_map(|file_info| get_digest(file_info, arguments))
+            .collect()
+    });
+
+    let found: HashSet<&Path> = run_io(arguments, || {
+        source_candidates
+            .par_iter() // rayon parallel iterator
+            .filter(|file_info| {
+                get_digest(file_info, arguments)
+                    .is_some_and(|digest| backup_digests.contains(&digest))
+            })
+            .map(|file_info| file_info.path.as_path())
+            .collect()
+    });
+    clear_progress();
+
+    let mut missing_files: Vec<MissingFile> = source_files
+        .iter()
+        .filter(|file_info| !found.contains(file_info.path.as_path()))
+        .map(|file_info| MissingFile {
+            path: file_info.path.clone(),
+            size: file_info.key.size,
+        })
+        .collect();
+    missing_files.sort();
+
+    Ok(SubsetInfo {
+        source: source.to_path_buf(),
+        backup: backup.to_path_buf(),
+        num_source: source_files.len(),
+        missing_size: missing_files.iter().map(|missing| missing.size).sum(),
+        missing_files,
+    })
+}
+
+/// Get the (size, hash of the entire file) of the file.
+///
+/// Unreadable files are reported as skipped files.
+fn get_digest(file_info: &FileInfo, arguments: &Arguments) -> Option<(usize, String)> {
+    match file_info.path.get_hash(arguments, 3) {
+        Ok(hash) => hash.map(|hash| (file_info.key.size, hash)),
+        Err(error) => {
+            eprintln!("fn verify_subset()");
+            eprintln!("path: {:?}", file_info.path);
+            eprintln!("Error: {error}");
+            add_error(&file_info.path, &error);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_subset {
+    use super::*;
+    use clap::Parser;
+    use std::fs;
+
+    /// cargo test -- --show-output test_verify_subset
+    #[test]
+    fn test_verify_subset() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+        let source = dir.join("source");
+        let backup = dir.join("backup");
+        fs::create_dir_all(source.join("photos"))?;
+        fs::create_dir_all(&backup)?;
+
+        fs::write(source.join("photos/a.jpg"), "image a")?;
+        fs::write(source.join("b.txt"), "text b")?;
+        fs::write(source.join("c.txt"), "text c")?; // same size as b.txt
+        fs::write(source.join("d.txt"), "other size")?;
+        fs::write(backup.join("renamed.jpg"), "image a")?;
+        fs::write(backup.join("b.txt"), "text b")?;
+
+        let source_str: &str = &source.to_string_lossy();
+        let backup_str: &str = &backup.to_string_lossy();
+        let arguments = Arguments::parse_from([
+            "find-identical-files",
+            "--verify_subset",
+            source_str,
+            backup_str,
+        ]);
+        let subset_info = verify_subset(&source, &backup, &arguments)?;
+
+        let missing: Vec<PathBuf> = subset_info
+            .missing_files
+            .iter()
+            .map(|missing| missing.path.clone())
+            .collect();
+        assert_eq!(subset_info.num_source, 4);
+        assert_eq!(missing, [source.join("c.txt"), source.join("d.txt")]);
+        assert_eq!(subset_info.missing_size, 16);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::Arguments;
+use std::{
+    process::{self, Command, Stdio},
+    sync::{Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Maximum read bandwidth (`--throttle`), shared by all threads.
+static THROTTLE: OnceLock<Throttle> = OnceLock::new();
+
+/// Bytes per megabyte of `--throttle`.
+const MEGABYTE: f64 = 1024.0 * 1024.0;
+
+/// Rate limiter: the bytes read since the start may not exceed the elapsed time × rate.
+#[derive(Debug)]
+struct Throttle {
+    /// Bytes per second
+    rate: f64,
+    /// Start time and bytes read since then
+    state: Mutex<(Instant, u64)>,
+}
+
+impl Throttle {
+    /// Count the bytes read and get how long the reader must wait.
+    fn consume(&self, bytes: usize) -> Duration {
+        let Ok(mut state) = self.state.lock() else {
+            return Duration::ZERO;
+        };
+        let (start, total) = &mut *state;
+        *total += bytes as u64;
+
+        let expected = Duration::from_secs_f64(*total as f64 / self.rate);
+        expected.saturating_sub(start.elapsed())
+    }
+}
+
+/**
+Reduce the impact of the search on interactive use (`--idle` and `--throttle`).
+
+With `--idle`, the CPU and I/O priorities of the process are lowered
+(`renice` and `ionice` on Linux, `renice` and `taskpolicy` on macOS).
+
+Must be called before any thread is created: threads inherit the priorities.
+*/
+pub fn init_throttle(arguments: &Arguments) {
+    if let Some(rate) = arguments.throttle {
+        let _ = THROTTLE.set(Throttle {
+            rate: rate * MEGABYTE,
+            state: Mutex::new((Instant::now(), 0)),
+        });
+    }
+
+    if arguments.idle {
+        set_idle_priority();
+    }
+}
+
+/// Wait, if needed, so that the bytes read do not exceed `--throttle`.
+pub fn throttle(bytes: usize) {
+    if let Some(throttle) = THROTTLE.get() {
+        let wait: Duration = throttle.consume(bytes);
+        if !wait.is_zero() {
+            thread::sleep(wait);
+        }
+    }
+}
+
+/// Commands that lower the CPU and I/O priorities of a process (followed by its pid).
+#[cfg(target_os = "linux")]
+const IDLE_COMMANDS: &[(&str, &[&str])] = &[
+    ("renice", &["-n", "19", "-p"]),
+    ("ionice", &["-c", "3", "-p"]),
+];
+
+/// Commands that lower the CPU and I/O priorities of a process (followed by its pid).
+#[cfg(target_os = "macos")]
+const IDLE_COMMANDS: &[(&str, &[&str])] = &[
+    ("renice", &["-n", "19", "-p"]),
+    ("taskpolicy", &["-b", "-p"]),
+];
+
+/// Commands that lower the CPU and I/O priorities of a process (followed by its pid).
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "macos"))))]
+const IDLE_COMMANDS: &[(&str, &[&str])] = &[("renice", &["-n", "19", "-p"])];
+
+/// Commands that lower the CPU and I/O priorities of a process (followed by its pid).
+#[cfg(not(unix))]
+const IDLE_COMMANDS: &[(&str, &[&str])] = &[];
+
+/// Lower the CPU and I/O priorities of the process.
+fn set_idle_priority() {
+    let pid: String = process::id().to_string();
+
+    #[cfg(not(unix))]
+    eprintln!("--idle is not supported on this platform: priorities unchanged.");
+
+    for (program, args) in IDLE_COMMANDS {
+        let status = Command::new(program)
+            .args(*args)
+            .arg(&pid)
+            .stdout(Stdio::null())
+            .status();
+
+        if !status.is_ok_and(|status| status.success()) {
+            eprintln!("fn set_idle_priority()");
+            eprintln!("Couldn't run {program:?}: priority unchanged.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_throttle {
+    use super::*;
+
+    /// cargo test -- --show-output test_throttle_consume
+    #[test]
+    fn test_throttle_consume() {
+        let throttle = Throttle {
+            rate: MEGABYTE,
+            state: Mutex::new((Instant::now(), 0)),
+        };
+
+        // 2 MB at 1 MB/s: about 2 seconds ahead of the elapsed time.
+        let wait = throttle.consume(2 * MEGABYTE as usize);
+        assert!(wait > Duration::from_millis(1900));
+        assert!(wait <= Duration::from_secs(2));
+    }
+}
@@ -0,0 +1,379 @@
+use crate::limits::is_interrupted;
+use crate::output::get_output_path;
+use crate::{
+    get_paths, Arguments, DigestIndex, FileFilter, FileInfo, GroupExtension, GroupInfo, Key,
+    MyResult, PathBufExtension,
+};
+use hashbrown::{HashMap, HashSet};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+/// Files are processed only after this time without new events (writes in progress).
+const DEBOUNCE: Duration = Duration::from_millis(1000);
+
+/**
+Known files of the watched directories.
+
+Files are grouped by size and hashed only when another file of the same size appears.
+*/
+pub struct WatchState {
+    /// Paths by file size
+    files: HashMap<usize, HashSet<PathBuf>>,
+    /// Hash of the entire file, by path
+    digests: HashMap<PathBuf, String>,
+    /// Filters of directories and files
+    filter: FileFilter,
+    /// Input directories
+    roots: Vec<PathBuf>,
+    /// Digest index updated incrementally (`--export_index`)
+    index: Option<DigestIndex>,
+    /// Files written by this program (`--export_index` and `--output`)
+    ignored: Vec<PathBuf>,
+}
+
+impl WatchState {
+    /// Build the state from the result of the initial scan.
+    pub fn new(
+        all_files: &[FileInfo],
+        identical_hash: &[GroupInfo],
+        arguments: &Arguments,
+    ) -> MyResult<Self> {
+        let mut files: HashMap<usize, HashSet<PathBuf>> = HashMap::new();
+        for file_info in all_files {
+            files
+                .entry(file_info.key.size)
+                .or_default()
+                .insert(file_info.path.clone());
+        }
+
+        // Reuse the hashes of the entire file (procedure 3).
+        let digests: HashMap<PathBuf, String> = identical_hash
+            .iter()
+            .filter(|group_info| group_info.algorithm.is_some())
+            .flat_map(|group_info| {
+                let hash = group_info.key.hash.clone();
+                group_info
+                    .paths
+                    .iter()
+                    .filter_map(move |path| Some((path.clone(), hash.clone()?)))
+            })
+            .collect();
+
+        let index: Option<DigestIndex> = match &arguments.export_index {
+            Some(path) => Some(DigestIndex::read(path)?),
+            None => None,
+        };
+
+        let ignored: Vec<PathBuf> = arguments
+            .export_index
+            .iter()
+            .cloned()
+            .chain(
+                arguments
+                    .output
+                    .iter()
+                    .map(|path| get_output_path(path, arguments)),
+            )
+            .chain(arguments.report_dir.iter().cloned())
+            .filter_map(|path| fs::canonicalize(path).ok())
+            .collect();
+
+        Ok(WatchState {
+            files,
+            digests,
+            filter: FileFilter::new(arguments)?,
+            roots: get_paths(arguments)?,
+            index,
+            ignored,
+        })
+    }
+
+    /// Check if the file must be searched, as in the initial scan.
+    fn is_searched(&self, path: &Path, arguments: &Arguments) -> bool {
+        // Symbolic links to files are ignored, unless `--symlink_files` is chosen.
+        let Ok(link_metadata) = fs::symlink_metadata(path) else {
+            return false;
+        };
+
+        if !self
+            .filter
+            .is_file(path, link_metadata.file_type(), link_metadata.is_symlink())
+        {
+            return false;
+        }
+
+        let Ok(metadata) = fs::metadata(path) else {
+            return false;
+        };
+
+        if fs::canonicalize(path)
+            .is_ok_and(|path| self.ignored.iter().any(|ignored| path.starts_with(ignored)))
+        {
+            return false;
+        }
+
+        // Excluded or hidden directories between the input directory and the file.
+        let is_skipped = path
+            .ancestors()
+            .take_while(|ancestor| !self.roots.iter().any(|root| root == ancestor))
+            .any(|ancestor| self.filter.is_excluded(ancestor) || self.filter.is_hidden(ancestor));
+
+        !is_skipped
+            && self.filter.is_included(path)
+            && arguments.size_is_included(metadata.len())
+            && arguments.time_is_included(metadata.modified().ok())
+            && self.filter.is_permitted(&metadata)
+    }
+
+    /// Remove the file from the known files.
+    fn remove(&mut self, path: &Path) {
+        for paths in self.files.values_mut() {
+            paths.remove(path);
+        }
+        self.digests.remove(path);
+    }
+
+    /// Get the hash of the entire file.
+    fn get_digest(&mut self, path: &Path, arguments: &Arguments) -> Option<String> {
+        if let Some(digest) = self.digests.get(path) {
+            return Some(digest.clone());
+        }
+
+        match path.to_path_buf().get_hash(arguments, 3) {
+            Ok(digest) => {
+                let digest = digest?;
+                self.digests.insert(path.to_path_buf(), digest.clone());
+                Some(digest)
+            }
+            Err(error) => {
+                eprintln!("fn get_digest()");
+                eprintln!("path: {path:?}");
+                eprintln!("Error: {error}");
+                None
+            }
+        }
+    }
+
+    /**
+    Update the state with a created, modified or removed file.
+
+    Returns the group of identical files if the file duplicates known files.
+    */
+    pub fn update(&mut self, path: &Path, arguments: &Arguments) -> Option<GroupInfo> {
+        // The content of the file may have changed.
+        self.remove(path);
+
+        if !self.is_searched(path, arguments) {
+            self.update_index(path, None);
+            return None;
+        }
+
+        let size: usize = Key::new(fs::metadata(path).ok()?.len(), None).size;
+        let candidates: Vec<PathBuf> = self
+            .files
+            .get(&size)
+            .map(|paths| paths.iter().cloned().collect())
+            .unwrap_or_default();
+
+        self.files
+            .entry(size)
+            .or_default()
+            .insert(path.to_path_buf());
+
+        // Files with a unique size are not hashed (unless indexed).
+        if candidates.is_empty() && self.index.is_none() {
+            return None;
+        }
+
+        let digest: String = self.get_digest(path, arguments)?;
+        self.update_index(path, Some(digest.clone()));
+
+        let mut paths: Vec<PathBuf> = candidates
+            .into_iter()
+            .filter(|candidate| self.get_digest(candidate, arguments).as_ref() == Some(&digest))
+            .collect();
+
+        if paths.is_empty() {
+            return None;
+        }
+
+        paths.sort();
+        paths.push(path.to_path_buf());
+
+        let num_file = paths.len();
+        let wasted_size = size * (num_file - 1);
+
+        if !arguments.frequency_is_included(num_file) || !arguments.wasted_is_included(wasted_size)
+        {
+            return None;
+        }
+
+        Some(GroupInfo {
+            key: Key::new(size as u64, Some(digest)),
+            algorithm: Some(arguments.algorithm),
+            paths,
+            num_file,
+            sum_size: size * num_file,
+            wasted_size,
+        })
+    }
+
+    /// Update the digest index (`--export_index`).
+    fn update_index(&mut self, path: &Path, digest: Option<String>) {
+        if let Some(index) = self.index.as_mut() {
+            index.update(path, digest);
+        }
+    }
+
+    /// Write the digest index (`--export_index`).
+    fn write_index(&self, arguments: &Arguments) -> MyResult<()> {
+        if let (Some(index), Some(path)) = (&self.index, &arguments.export_index) {
+            index.write(path)?;
+        }
+        Ok(())
+    }
+}
+
+/**
+Watch the input directories and report new identical files (`--watch`).
+
+After the initial scan, files created or modified are compared with
+the known files until the process is interrupted (Ctrl+C).
+*/
+pub fn watch(
+    all_files: &[FileInfo],
+    identical_hash: &[GroupInfo],
+    arguments: &Arguments,
+) -> MyResult<()> {
+    let mut state = WatchState::new(all_files, identical_hash, arguments)?;
+
+    let (sender, receiver) = channel::<notify::Result<Event>>();
+    let mut watcher: RecommendedWatcher = notify::recommended_watcher(sender)?;
+
+    for root in &state.roots {
+        watcher
+            .watch(root, RecursiveMode::Recursive)
+            .inspect_err(|error| {
+                eprintln!("fn watch()");
+                eprintln!("Couldn't watch {root:?}");
+                eprintln!("Error: {error}");
+            })?;
+    }
+
+    eprintln!(
+        "Watching {:?} for new identical files (Ctrl+C to stop)...",
+        state.roots
+    );
+
+    // Paths with events, by time of the last event.
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+
+    loop {
+        match receiver.recv_timeout(DEBOUNCE / 2) {
+            Ok(Ok(event)) => {
+                if matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                ) {
+                    let now = Instant::now();
+                    for path in event.paths {
+                        pending.insert(path, now);
+                    }
+                }
+            }
+            Ok(Err(error)) => {
+                eprintln!("fn watch()");
+                eprintln!("Error: {error}");
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        // Ctrl+C: stop watching (the digest index is already written).
+        if is_interrupted() {
+            break;
+        }
+
+        let ready: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_path, time)| time.elapsed() >= DEBOUNCE)
+            .map(|(path, _time)| path.clone())
+            .collect();
+
+        if ready.is_empty() {
+            continue;
+        }
+
+        let mut groups: Vec<GroupInfo> = Vec::new();
+        for path in ready {
+            pending.remove(&path);
+            groups.extend(state.update(&path, arguments));
+        }
+
+        if arguments.same_name_only {
+            groups = groups.filter_by_name(arguments);
+        }
+
+        groups.write_identical_files(arguments, &mut io::stdout())?;
+        state.write_index(arguments)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_watch {
+    use super::*;
+    use clap::Parser;
+
+    /// cargo test -- --show-output test_watch_state
+    #[test]
+    fn test_watch_state() -> MyResult<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let dir = temp_dir.path();
+
+        let original = dir.join("original.txt");
+        let other = dir.join("other.txt");
+        let copy = dir.join("copy.txt");
+        fs::write(&original, "same content")?;
+        fs::write(&other, "different!!!")?;
+
+        let arguments =
+            Arguments::parse_from(["find-identical-files", "-i", &dir.to_string_lossy()]);
+
+        let all_files: Vec<FileInfo> = [&original, &other]
+            .into_iter()
+            .map(|path| FileInfo {
+                key: Key::new(12, None),
+                path: path.clone(),
+                inode: None,
+                modified: None,
+                allocated: None,
+            })
+            .collect();
+
+        let mut state = WatchState::new(&all_files, &[], &arguments)?;
+
+        // A new file identical to a known file.
+        fs::write(&copy, "same content")?;
+        let group = state.update(&copy, &arguments).expect("identical files");
+        assert_eq!(group.paths, [original.clone(), copy.clone()]);
+        assert_eq!(group.wasted_size, 12);
+
+        // The known file was modified: no longer identical.
+        fs::write(&original, "new content!")?;
+        assert!(state.update(&original, &arguments).is_none());
+
+        // The file was removed.
+        fs::remove_file(&copy)?;
+        assert!(state.update(&copy, &arguments).is_none());
+        assert!(!state.files[&12].contains(&copy));
+
+        Ok(())
+    }
+}
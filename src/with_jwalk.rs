@@ -1,29 +1,57 @@
-use crate::{get_path, Arguments, FileInfo, Key, MyResult};
+use crate::{
+    limits::{add_file, walk_is_stopped},
+    progress::inc_files,
+    skipped::{add_skipped, add_walk_error},
+    structures::file_info::{get_allocated_size, get_inode},
+    Arguments, FileFilter, FileInfo, HashPipeline, Key, MyResult,
+};
 use jwalk::{DirEntry, Parallelism, WalkDirGeneric};
-use std::path::PathBuf;
+use std::{fs, path::Path};
 
-/// Get all files into one vector.
+/// Get all files from the directory path into one vector.
 ///
 /// Use jwalk.
-pub fn get_all_files(arguments: &Arguments) -> MyResult<Vec<FileInfo>> {
-    let path: PathBuf = get_path(arguments)?;
+pub fn get_files(
+    path: &Path,
+    arguments: &Arguments,
+    filter: &FileFilter,
+    pipeline: Option<&HashPipeline>,
+) -> MyResult<Vec<FileInfo>> {
+    let arguments: Arguments = arguments.clone();
+    let filter: FileFilter = filter.clone();
+    let root_device: Option<u64> = filter.get_root_device(path);
 
-    let min_size: u64 = arguments.min_size;
-    let max_size: u64 = arguments.max_size;
+    // The root directory may have already been traversed by following a link.
+    if filter.is_visited_dir(path) {
+        return Ok(Vec::new());
+    }
 
     let jwalk = WalkDirGeneric::<((), Option<FileInfo>)>::new(path)
-        .skip_hidden(arguments.omit_hidden)
+        // Hidden entries are skipped by the filter (also Windows attributes).
+        .skip_hidden(false)
         .min_depth(arguments.min_depth)
         .max_depth(arguments.max_depth)
+        .follow_links(arguments.follow_links)
         .parallelism(Parallelism::RayonNewPool(rayon::current_num_threads()))
         .process_read_dir(move |_depth, _path, _read_dir_state, dir_entry_results| {
-            process_dir_entries(dir_entry_results, min_size, max_size);
+            process_dir_entries(dir_entry_results, &arguments, &filter, root_device);
         });
 
     let all_files: Vec<FileInfo> = jwalk
         .into_iter()
-        .flatten() // Result<DirEntry, Error> to DirEntry
+        // Unreadable directories and files are reported as skipped files.
+        .filter_map(|result| {
+            result
+                .inspect_err(|error| add_walk_error(error.path(), error.io_error(), error))
+                .ok()
+        })
         .flat_map(|dir_entry| dir_entry.client_state)
+        .inspect(|file_info| {
+            // Start hashing while the walk is still ongoing.
+            if let Some(pipeline) = pipeline {
+                pipeline.push(file_info);
+            }
+        })
         .collect();
 
     Ok(all_files)
@@ -33,30 +61,78 @@ type JwalkResults = Vec<Result<DirEntry<((), Option<FileInfo>)>, jwalk::Error>>;
 
 // https://docs.rs/jwalk
 // https://github.com/Byron/jwalk/blob/main/examples/du.rs
-fn process_dir_entries(dir_entry_results: &mut JwalkResults, min_size: u64, max_size: u64) {
+fn process_dir_entries(
+    dir_entry_results: &mut JwalkResults,
+    arguments: &Arguments,
+    filter: &FileFilter,
+    root_device: Option<u64>,
+) {
     // inode: “index nodes”
     // https://doc.rust-lang.org/std/os/unix/fs/trait.MetadataExt.html#tymethod.ino
 
     // cargo bench
 
+    // Stop walking with `--max_files` or `--timeout`: subdirectories are not traversed.
+    if walk_is_stopped() {
+        dir_entry_results.clear();
+        return;
+    }
+
+    // Skip excluded directories (they will not be traversed) and files.
+    // Skip hidden directories and files with `--omit_hidden`.
+    // Skip directories already visited by following symbolic links.
+    // Skip directories on other filesystems with `--one_file_system`.
+    dir_entry_results.retain(|dir_entry_result| match dir_entry_result {
+        Ok(dir_entry) => {
+            let path = dir_entry.path();
+            let is_dir = dir_entry.file_type().is_dir();
+            !(filter.is_excluded(&path)
+                || filter.is_hidden(&path)
+                || is_dir
+                    && (filter.is_other_filesystem(&path, root_device)
+                        || filter.is_visited_dir(&path)))
+        }
+        Err(_) => true,
+    });
+
     // 3. Custom skip
     dir_entry_results
         .iter_mut()
         .flatten() // Result<DirEntry, Error> to DirEntry
         .filter_map(|dir_entry| {
-            if dir_entry.file_type().is_file() {
-                dir_entry
-                    .metadata()
+            let path = dir_entry.path();
+            let is_symlink = dir_entry.path_is_symlink();
+            if filter.is_file(&path, dir_entry.file_type(), is_symlink) && filter.is_included(&path)
+            {
+                // Follow symbolic links to get the size of the target file.
+                fs::metadata(&path)
+                    .inspect_err(|error| add_skipped(&path, error))
                     .ok()
-                    .map(|metadata| (dir_entry, metadata.len()))
+                    .map(|metadata| (dir_entry, metadata))
             } else {
                 None
             }
         })
-        .filter(|(_dir_entry, file_size)| *file_size >= min_size && *file_size <= max_size)
-        .for_each(|(dir_entry, file_size)| {
-            let key = Key::new(file_size, None);
+        .filter(|(_dir_entry, metadata)| {
+            arguments.size_is_included(metadata.len())
+                && arguments.time_is_included(metadata.modified().ok())
+                && arguments.allocated_is_included(get_allocated_size(metadata))
+                && filter.is_permitted(metadata)
+                && add_file()
+        })
+        .for_each(|(dir_entry, metadata)| {
+            let key = Key::new(metadata.len(), None);
             let path = dir_entry.path();
-            dir_entry.client_state = Some(FileInfo { key, path });
+            let inode = get_inode(&metadata);
+            let modified = metadata.modified().ok();
+            let allocated = get_allocated_size(&metadata);
+            dir_entry.client_state = Some(FileInfo {
+                key,
+                path,
+                inode,
+                modified,
+                allocated,
+            });
+            inc_files(1);
         });
 }
@@ -1,56 +1,95 @@
-use crate::{get_path, Arguments, FileInfo, Key, MyResult};
+use crate::{
+    limits::{add_file, walk_is_stopped},
+    progress::inc_files,
+    skipped::{add_skipped, add_walk_error},
+    structures::file_info::{get_allocated_size, get_inode},
+    Arguments, FileFilter, FileInfo, HashPipeline, Key, MyResult,
+};
 use rayon::prelude::*;
-use std::path::PathBuf;
+use std::{fs, path::Path};
 use walkdir::{DirEntry, WalkDir};
 
-/// Get all files into one vector.
+/// Get all files from the directory path into one vector.
 ///
 /// Use walkdir.
-pub fn get_all_files(arguments: &Arguments) -> MyResult<Vec<FileInfo>> {
-    let entries: Vec<DirEntry> = get_entries(arguments)?;
+pub fn get_files(
+    path: &Path,
+    arguments: &Arguments,
+    filter: &FileFilter,
+    pipeline: Option<&HashPipeline>,
+) -> MyResult<Vec<FileInfo>> {
+    let entries: Vec<DirEntry> = get_entries(path, arguments, filter)?;
 
     let all_files: Vec<FileInfo> = entries
         .into_par_iter() // rayon parallel iterator
         .filter_map(|entry| {
-            let metadata = entry.metadata().ok()?;
+            // Follow symbolic links to get the size of the target file.
+            let metadata = fs::metadata(entry.path())
+                .inspect_err(|error| add_skipped(entry.path(), error))
+                .ok()?;
             let file_size: u64 = metadata.len();
-            //let inode_number: u64 = metadata.ino();
+            let modified = metadata.modified().ok();
+            let allocated = get_allocated_size(&metadata);
 
-            if arguments.size_is_included(file_size) {
+            if arguments.size_is_included(file_size)
+                && arguments.time_is_included(modified)
+                && arguments.allocated_is_included(allocated)
+                && filter.is_included(entry.path())
+                && filter.is_permitted(&metadata)
+                && add_file()
+            {
                 let key = Key::new(file_size, None);
                 let path = entry.into_path();
-                Some(FileInfo { key, path })
+                let inode = get_inode(&metadata);
+                inc_files(1);
+                Some(FileInfo {
+                    key,
+                    path,
+                    inode,
+                    modified,
+                    allocated,
+                })
             } else {
                 None
             }
         })
+        .inspect(|file_info| {
+            // Start hashing while the other files are processed.
+            if let Some(pipeline) = pipeline {
+                pipeline.push(file_info);
+            }
+        })
         .collect();
 
     Ok(all_files)
 }
 
 /// Get result: Vec<DirEntry>.
-fn get_entries(arguments: &Arguments) -> MyResult<Vec<DirEntry>> {
-    let dir_path: PathBuf = get_path(arguments)?;
-
+fn get_entries(
+    dir_path: &Path,
+    arguments: &Arguments,
+    filter: &FileFilter,
+) -> MyResult<Vec<DirEntry>> {
     let entries: Vec<DirEntry> = WalkDir::new(dir_path)
         .min_depth(arguments.min_depth)
         .max_depth(arguments.max_depth)
+        .follow_links(arguments.follow_links)
+        .same_file_system(arguments.one_file_system)
         .into_iter()
-        .filter_entry(|e| !arguments.omit_hidden || !is_hidden(e))
-        .flatten() // Result<DirEntry, Error> to DirEntry
-        .filter(|entry| entry.file_type().is_file())
+        .filter_entry(|e| {
+            (e.depth() == 0 || (!filter.is_hidden(e.path()) && !filter.is_excluded(e.path())))
+                && !(e.file_type().is_dir() && filter.is_visited_dir(e.path()))
+        })
+        // Stop walking with `--max_files` or `--timeout`.
+        .take_while(|_| !walk_is_stopped())
+        // Unreadable directories and files are reported as skipped files.
+        .filter_map(|result| {
+            result
+                .inspect_err(|error| add_walk_error(error.path(), error.io_error(), error))
+                .ok()
+        })
+        .filter(|entry| filter.is_file(entry.path(), entry.file_type(), entry.path_is_symlink()))
         .collect();
 
     Ok(entries)
 }
-
-// https://github.com/BurntSushi/walkdir
-// https://rust-lang-nursery.github.io/rust-cookbook/file/dir.html
-/// Identify hidden files efficiently on unix.
-fn is_hidden(entry: &DirEntry) -> bool {
-    entry
-        .file_name()
-        .to_str()
-        .is_some_and(|s| entry.depth() != 0 && s.starts_with('.'))
-}
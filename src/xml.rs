@@ -0,0 +1,131 @@
+use crate::{display_path, Arguments, GroupInfo, MyResult, TotalInfo};
+use std::io::Write;
+
+/// Version of the XML schema (`schema/identical_files.xsd`).
+pub const XML_SCHEMA_VERSION: u32 = 1;
+
+/**
+Escape the characters with special meaning in XML (text and attribute values).
+
+Control characters are not allowed in XML 1.0: they are replaced by U+FFFD.
+*/
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Otherwise, newlines and tabs are normalized to spaces in attribute values.
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\t' => escaped.push_str("&#9;"),
+            character if character.is_control() => escaped.push('\u{FFFD}'),
+            _ => escaped.push(character),
+        }
+    }
+    escaped
+}
+
+/// Write the XML declaration, the root element and the scan parameters.
+pub fn write_xml_header(arguments: &Arguments, write: &mut dyn Write) -> MyResult<()> {
+    writeln!(write, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        write,
+        r#"<identical_files version="{XML_SCHEMA_VERSION}" generator="find-identical-files {}">"#,
+        env!("CARGO_PKG_VERSION")
+    )?;
+    writeln!(write, "  <scan>")?;
+    for (name, value) in arguments.get_scan_options() {
+        writeln!(
+            write,
+            r#"    <option name="{}">{}</option>"#,
+            escape_xml(name),
+            escape_xml(&value)
+        )?;
+    }
+    writeln!(write, "  </scan>")?;
+    writeln!(write, "  <groups>")?;
+    Ok(())
+}
+
+/// Write one group of identical files: its identifier, its digest and the paths of the files.
+pub fn write_xml_group(group_info: &GroupInfo, write: &mut dyn Write) -> MyResult<()> {
+    let algorithm: String = group_info
+        .algorithm
+        .map(|algorithm| format!(r#" algorithm="{algorithm}""#))
+        .unwrap_or_default();
+
+    writeln!(
+        write,
+        r#"    <group id="{}" size="{}" digest="{}"{algorithm} files="{}" sum_size="{}" wasted_size="{}">"#,
+        group_info.get_id(),
+        group_info.key.size,
+        escape_xml(&group_info.get_digest().unwrap_or_default()),
+        group_info.num_file,
+        group_info.sum_size,
+        group_info.wasted_size,
+    )?;
+    for path in &group_info.paths {
+        writeln!(
+            write,
+            r#"      <file path="{}"/>"#,
+            escape_xml(&display_path(path).to_string_lossy())
+        )?;
+    }
+    writeln!(write, "    </group>")?;
+    Ok(())
+}
+
+/// Write the summary information and close the root element.
+pub fn write_xml_summary(total_info: &TotalInfo, write: &mut dyn Write) -> MyResult<()> {
+    writeln!(write, "  </groups>")?;
+    writeln!(
+        write,
+        r#"  <summary algorithm="{}" total_files="{}" identical_files="{}" different_hashes="{}" total_size="{}" reclaimable_space="{}"{}>"#,
+        total_info.algorithm,
+        total_info.total_num_files,
+        total_info.total_num_identical,
+        total_info.total_num_hashes,
+        total_info.total_size,
+        total_info.total_wasted,
+        total_info
+            .total_wasted_allocated
+            .map(|size| format!(r#" reclaimable_disk_space="{size}""#))
+            .unwrap_or_default(),
+    )?;
+    if let Some(reason) = &total_info.incomplete {
+        writeln!(write, "    <incomplete>{}</incomplete>", escape_xml(reason))?;
+    }
+    if let Some(reason) = &total_info.unverified {
+        writeln!(write, "    <unverified>{}</unverified>", escape_xml(reason))?;
+    }
+    for skipped in &total_info.skipped_files {
+        writeln!(
+            write,
+            r#"    <skipped path="{}" reason="{}"/>"#,
+            escape_xml(&skipped.path.to_string_lossy()),
+            escape_xml(&skipped.reason.to_string())
+        )?;
+    }
+    writeln!(write, "  </summary>")?;
+    writeln!(write, "</identical_files>")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_xml {
+    use super::*;
+
+    /// cargo test -- --show-output test_escape_xml
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml(r#"<a b="c">Tom & Jerry's</a>"#),
+            "&lt;a b=&quot;c&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;"
+        );
+        assert_eq!(escape_xml("new\nline\u{1}"), "new&#10;line\u{FFFD}");
+    }
+}